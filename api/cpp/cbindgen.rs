@@ -39,6 +39,7 @@ fn default_config() -> cbindgen::Config {
                 ("KeyEventArg".into(), "KeyEvent".into()),
                 ("PointerEventArg".into(), "PointerEvent".into()),
                 ("PointArg".into(), "Point".into()),
+                ("DropEventArg".into(), "DropEvent".into()),
             ]
             .iter()
             .cloned()
@@ -92,6 +93,9 @@ fn gen_corelib(
         "ImageItem",
         "ClippedImage",
         "TouchArea",
+        "PinchArea",
+        "DragArea",
+        "DropArea",
         "FocusScope",
         "Flickable",
         "Text",
@@ -101,6 +105,7 @@ fn gen_corelib(
         "Clip",
         "BoxShadow",
         "Rotate",
+        "Scale",
         "Opacity",
     ];
 
@@ -122,6 +127,9 @@ fn gen_corelib(
         "PointerEventKind",
         "PointerEventButton",
         "PointerEvent",
+        "DropEvent",
+        "AccessibleRole",
+        "AccessibleStringProperty",
     ]
     .iter()
     .chain(items.iter())
@@ -148,13 +156,16 @@ fn gen_corelib(
         "Callback",
         "slint_property_listener_scope_evaluate",
         "slint_property_listener_scope_is_dirty",
+        "PropertyTracker",
         "PropertyTrackerOpaque",
+        "ChangeTrackerOpaque",
         "CallbackOpaque",
         "WindowRc",
         "VoidArg",
         "KeyEventArg",
         "PointerEventArg",
         "PointArg",
+        "DropEventArg",
         "Point",
         "slint_color_brighter",
         "slint_color_darker",
@@ -371,6 +382,10 @@ fn gen_corelib(
         "StandardListViewItem".to_owned(),
         "friend bool operator==(const StandardListViewItem&, const StandardListViewItem&) = default;".into(),
     );
+    config.export.body.insert(
+        "DropEvent".to_owned(),
+        "friend bool operator==(const DropEvent&, const DropEvent&) = default;".into(),
+    );
     config
         .export
         .body
@@ -398,8 +413,9 @@ fn gen_corelib(
     namespace cbindgen_private {
         using slint::private_api::WindowRc;
         using namespace vtable;
-        struct KeyEvent; struct PointerEvent;
+        struct KeyEvent; struct PointerEvent; struct DropEvent;
         using private_api::Property;
+        using private_api::PropertyTracker;
         using private_api::PathData;
         using private_api::Point;
     }
@@ -11,6 +11,7 @@
 #include <vector>
 #include <memory>
 #include <algorithm>
+#include <functional>
 #include <iostream> // FIXME: remove: iostream always bring it lots of code so we should not have it in this header
 #include <chrono>
 #include <optional>
@@ -54,6 +55,7 @@ using ItemTreeNode = cbindgen_private::ItemTreeNode<uint8_t>;
 using cbindgen_private::KeyboardModifiers;
 using cbindgen_private::KeyEvent;
 using cbindgen_private::PointerEvent;
+using cbindgen_private::DropEvent;
 using cbindgen_private::StandardListViewItem;
 
 /// Internal function that checks that the API that must be called from the main
@@ -119,6 +121,16 @@ public:
         cbindgen_private::slint_windowrc_set_focus_item(&inner, &item_rc);
     }
 
+    void text_input_undo(const cbindgen_private::TextInput *text_input) const
+    {
+        cbindgen_private::slint_textinput_undo(text_input, &inner);
+    }
+
+    void text_input_redo(const cbindgen_private::TextInput *text_input) const
+    {
+        cbindgen_private::slint_textinput_redo(text_input, &inner);
+    }
+
     template<typename Component, typename ItemTree>
     void init_items(Component *c, ItemTree items) const
     {
@@ -493,6 +505,8 @@ struct AbstractRepeaterView
     virtual void row_added(int index, int count) = 0;
     virtual void row_removed(int index, int count) = 0;
     virtual void row_changed(int index) = 0;
+    virtual void row_moved(int from, int count, int to) = 0;
+    virtual void reset() = 0;
 };
 using ModelPeer = std::weak_ptr<AbstractRepeaterView>;
 
@@ -507,6 +521,18 @@ auto access_array_index(const M &model, int index)
     }
 }
 
+/// Returns the new index of \a row after \a count rows starting at \a from were moved to \a to
+inline int moved_row(int row, int from, int count, int to)
+{
+    if (row >= from && row < from + count) {
+        return row - from + to;
+    }
+    if (row >= from + count) {
+        row -= count;
+    }
+    return row >= to ? row + count : row;
+}
+
 } // namespace private_api
 
 /// \rst
@@ -586,6 +612,23 @@ protected:
         model_row_data_dirty_property.mark_dirty();
         for_each_peers([=](auto peer) { peer->row_removed(index, count); });
     }
+    /// Notify the views that \a count rows starting at \a from were moved, so that they now
+    /// start at row \a to. Views can reuse the elements they created for the moved rows.
+    void row_moved(int from, int count, int to)
+    {
+        tracked_rows.clear();
+        model_row_data_dirty_property.mark_dirty();
+        for_each_peers([=](auto peer) { peer->row_moved(from, count, to); });
+    }
+    /// Notify the views that the whole content of the model changed, including the row count.
+    /// This is cheaper than removing and adding all rows, as views can reuse their elements.
+    void reset()
+    {
+        model_row_count_dirty_property.mark_dirty();
+        tracked_rows.clear();
+        model_row_data_dirty_property.mark_dirty();
+        for_each_peers([=](auto peer) { peer->reset(); });
+    }
 
 private:
     template<typename F>
@@ -702,6 +745,444 @@ public:
         data.insert(data.begin() + index, value);
         this->row_added(int(index), 1);
     }
+
+    /// Replaces the underlying vector with \a array and notifies the views that the model was
+    /// reset.
+    void set_vector(std::vector<ModelData> array)
+    {
+        data = std::move(array);
+        this->reset();
+    }
+
+    /// Swaps the rows at the indices \a a and \a b, which must be smaller than row_count().
+    void swap(int a, int b)
+    {
+        if (a > b) {
+            std::swap(a, b);
+        }
+        if (a == b) {
+            return;
+        }
+        std::swap(data[a], data[b]);
+        // Move the row at `a` after the one at `b`, then the row at `b` in place of `a`
+        this->row_moved(a, 1, b);
+        if (b - 1 != a) {
+            this->row_moved(b - 1, 1, a);
+        }
+    }
+};
+
+template<typename ModelData>
+class FilterModel;
+
+namespace private_api {
+template<typename ModelData>
+struct FilterModelInner : private_api::AbstractRepeaterView
+{
+    FilterModelInner(std::shared_ptr<slint::Model<ModelData>> source_model,
+                     std::function<bool(const ModelData &)> filter_fn,
+                     slint::FilterModel<ModelData> &target_model)
+        : source_model(std::move(source_model)),
+          filter_fn(std::move(filter_fn)),
+          target_model(target_model)
+    {
+        update_mapping();
+    }
+
+    bool accepts(int row) const
+    {
+        if (auto data = source_model->row_data(row)) {
+            return filter_fn(*data);
+        }
+        return false;
+    }
+
+    void row_added(int index, int count) override
+    {
+        if (count == 0) {
+            return;
+        }
+        std::vector<int> added_accepted_rows;
+        for (int row = index; row < index + count; ++row) {
+            if (accepts(row)) {
+                added_accepted_rows.push_back(row);
+            }
+        }
+
+        auto insertion_point = std::lower_bound(accepted_rows.begin(), accepted_rows.end(), index);
+        for (auto it = insertion_point; it != accepted_rows.end(); ++it) {
+            (*it) += count;
+        }
+
+        if (added_accepted_rows.empty()) {
+            return;
+        }
+        int insertion_index = int(insertion_point - accepted_rows.begin());
+        accepted_rows.insert(insertion_point, added_accepted_rows.begin(),
+                             added_accepted_rows.end());
+        target_model.row_added(insertion_index, int(added_accepted_rows.size()));
+    }
+    void row_changed(int index) override
+    {
+        auto existing_row = std::lower_bound(accepted_rows.begin(), accepted_rows.end(), index);
+        int existing_row_index = int(existing_row - accepted_rows.begin());
+        bool is_contained = existing_row != accepted_rows.end() && *existing_row == index;
+        bool should_be_contained = accepts(index);
+
+        if (is_contained && should_be_contained) {
+            target_model.row_changed(existing_row_index);
+        } else if (!is_contained && should_be_contained) {
+            accepted_rows.insert(existing_row, index);
+            target_model.row_added(existing_row_index, 1);
+        } else if (is_contained && !should_be_contained) {
+            accepted_rows.erase(existing_row);
+            target_model.row_removed(existing_row_index, 1);
+        }
+    }
+    void row_removed(int index, int count) override
+    {
+        if (count == 0) {
+            return;
+        }
+        auto first = std::lower_bound(accepted_rows.begin(), accepted_rows.end(), index);
+        auto last = std::lower_bound(first, accepted_rows.end(), index + count);
+        int first_index = int(first - accepted_rows.begin());
+        int removed_count = int(last - first);
+        for (auto it = last; it != accepted_rows.end(); ++it) {
+            (*it) -= count;
+        }
+        accepted_rows.erase(first, last);
+
+        if (removed_count > 0) {
+            target_model.row_removed(first_index, removed_count);
+        }
+    }
+
+    void row_moved(int from, int count, int to) override
+    {
+        auto first = std::lower_bound(accepted_rows.begin(), accepted_rows.end(), from);
+        auto last = std::lower_bound(first, accepted_rows.end(), from + count);
+        int filtered_from = int(first - accepted_rows.begin());
+        int filtered_count = int(last - first);
+        for (auto &row : accepted_rows) {
+            row = moved_row(row, from, count, to);
+        }
+        std::sort(accepted_rows.begin(), accepted_rows.end());
+        int filtered_to = int(std::lower_bound(accepted_rows.begin(), accepted_rows.end(), to)
+                              - accepted_rows.begin());
+
+        if (filtered_count > 0 && filtered_from != filtered_to) {
+            target_model.row_moved(filtered_from, filtered_count, filtered_to);
+        }
+    }
+
+    void update_mapping()
+    {
+        accepted_rows.clear();
+        for (int row = 0, count = source_model->row_count(); row < count; ++row) {
+            if (accepts(row)) {
+                accepted_rows.push_back(row);
+            }
+        }
+    }
+
+    void reset() override
+    {
+        update_mapping();
+        // Not FilterModel::reset, which re-applies the filter by calling this function
+        target_model.Model<ModelData>::reset();
+    }
+
+    std::shared_ptr<slint::Model<ModelData>> source_model;
+    std::function<bool(const ModelData &)> filter_fn;
+    // The rows of the source model that are accepted by the filter, in increasing order
+    std::vector<int> accepted_rows;
+    slint::FilterModel<ModelData> &target_model;
+};
+} // namespace private_api
+
+/// The FilterModel acts as an adapter model for a given source model by applying a filter
+/// function. The filter function is called for each row on the source model and if the
+/// filter accepts the row (i.e. returns true), the row is also visible in the FilterModel.
+/// Changes of the source model are forwarded as changes of the corresponding rows.
+template<typename ModelData>
+class FilterModel : public Model<ModelData>
+{
+    friend struct private_api::FilterModelInner<ModelData>;
+
+public:
+    /// Constructs a new FilterModel that provides a limited view on the \a source_model by
+    /// applying \a filter_fn on each row. If the provided function returns true, the row is
+    /// exposed by the FilterModel.
+    FilterModel(std::shared_ptr<Model<ModelData>> source_model,
+                std::function<bool(const ModelData &)> filter_fn)
+        : inner(std::make_shared<private_api::FilterModelInner<ModelData>>(
+                std::move(source_model), std::move(filter_fn), *this))
+    {
+        inner->source_model->attach_peer(inner);
+    }
+
+    int row_count() const override { return int(inner->accepted_rows.size()); }
+
+    std::optional<ModelData> row_data(int i) const override
+    {
+        if (i < 0 || i >= row_count())
+            return {};
+        return inner->source_model->row_data(inner->accepted_rows[i]);
+    }
+
+    void set_row_data(int i, const ModelData &value) override
+    {
+        inner->source_model->set_row_data(inner->accepted_rows[i], value);
+    }
+
+    /// Re-applies the model's filter function on each row of the source model. Use this if state
+    /// external to the filter function has changed.
+    void reset() { inner->reset(); }
+
+    /// Given the \a filtered_row index, this function returns the corresponding row index in the
+    /// source model.
+    int unfiltered_row(int filtered_row) const { return inner->accepted_rows[filtered_row]; }
+
+    /// Returns the source model of this filter model.
+    std::shared_ptr<Model<ModelData>> source_model() const { return inner->source_model; }
+
+private:
+    std::shared_ptr<private_api::FilterModelInner<ModelData>> inner;
+};
+
+template<typename SourceModelData, typename MappedModelData>
+class MapModel;
+
+namespace private_api {
+template<typename SourceModelData, typename MappedModelData>
+struct MapModelInner : private_api::AbstractRepeaterView
+{
+    MapModelInner(slint::MapModel<SourceModelData, MappedModelData> &target_model)
+        : target_model(target_model)
+    {
+    }
+
+    void row_added(int index, int count) override { target_model.row_added(index, count); }
+    void row_changed(int index) override { target_model.row_changed(index); }
+    void row_removed(int index, int count) override { target_model.row_removed(index, count); }
+    void row_moved(int from, int count, int to) override
+    {
+        target_model.row_moved(from, count, to);
+    }
+    void reset() override { target_model.reset(); }
+
+    slint::MapModel<SourceModelData, MappedModelData> &target_model;
+};
+} // namespace private_api
+
+/// The MapModel acts as an adapter model for a given source model by applying a mapping
+/// function. The mapping function is called for each row on the source model and allows
+/// transforming the values on the fly. The MapModel has two template parameters: The
+/// SourceModelData specifies the data type of the underlying source model, and the
+/// MappedModelData the data type of this MapModel. This permits not only changing the
+/// values of the underlying source model, but also changing the data type itself.
+template<typename SourceModelData, typename MappedModelData = SourceModelData>
+class MapModel : public Model<MappedModelData>
+{
+    friend struct private_api::MapModelInner<SourceModelData, MappedModelData>;
+
+public:
+    /// Constructs a new MapModel that provides an altered view on the \a source_model by
+    /// applying \a map_fn on the data in each row.
+    MapModel(std::shared_ptr<Model<SourceModelData>> source_model,
+             std::function<MappedModelData(const SourceModelData &)> map_fn)
+        : inner(std::make_shared<private_api::MapModelInner<SourceModelData, MappedModelData>>(
+                *this)),
+          model(std::move(source_model)),
+          map_fn(std::move(map_fn))
+    {
+        model->attach_peer(inner);
+    }
+
+    int row_count() const override { return model->row_count(); }
+
+    std::optional<MappedModelData> row_data(int i) const override
+    {
+        if (auto source_data = model->row_data(i))
+            return map_fn(*source_data);
+        else
+            return {};
+    }
+
+    /// Returns the source model of this map model.
+    std::shared_ptr<Model<SourceModelData>> source_model() const { return model; }
+
+private:
+    std::shared_ptr<private_api::MapModelInner<SourceModelData, MappedModelData>> inner;
+    std::shared_ptr<slint::Model<SourceModelData>> model;
+    std::function<MappedModelData(const SourceModelData &)> map_fn;
+};
+
+template<typename ModelData>
+class SortModel;
+
+namespace private_api {
+template<typename ModelData>
+struct SortModelInner : private_api::AbstractRepeaterView
+{
+    SortModelInner(std::shared_ptr<slint::Model<ModelData>> source_model,
+                   std::function<bool(const ModelData &, const ModelData &)> comp,
+                   slint::SortModel<ModelData> &target_model)
+        : source_model(std::move(source_model)), comp(std::move(comp)), target_model(target_model)
+    {
+        update_mapping();
+    }
+
+    // Returns the position of the first row that sorts after \a data, so that the sort is stable
+    std::vector<int>::iterator insertion_point(const ModelData &data)
+    {
+        return std::upper_bound(sorted_rows.begin(), sorted_rows.end(), data,
+                                [this](const ModelData &data, int row) {
+                                    auto other = source_model->row_data(row);
+                                    return other && comp(data, *other);
+                                });
+    }
+
+    void row_added(int index, int count) override
+    {
+        for (auto &row : sorted_rows) {
+            if (row >= index) {
+                row += count;
+            }
+        }
+        for (int row = index; row < index + count; ++row) {
+            if (auto data = source_model->row_data(row)) {
+                auto it = sorted_rows.insert(insertion_point(*data), row);
+                target_model.row_added(int(it - sorted_rows.begin()), 1);
+            }
+        }
+    }
+    void row_changed(int index) override
+    {
+        auto data = source_model->row_data(index);
+        auto existing_row = std::find(sorted_rows.begin(), sorted_rows.end(), index);
+        if (!data || existing_row == sorted_rows.end()) {
+            return;
+        }
+        int old_index = int(existing_row - sorted_rows.begin());
+        sorted_rows.erase(existing_row);
+        int new_index =
+                int(sorted_rows.insert(insertion_point(*data), index) - sorted_rows.begin());
+
+        if (old_index == new_index) {
+            target_model.row_changed(new_index);
+        } else {
+            target_model.row_removed(old_index, 1);
+            target_model.row_added(new_index, 1);
+        }
+    }
+    void row_removed(int index, int count) override
+    {
+        std::vector<int> removed;
+        for (auto it = sorted_rows.begin(); it != sorted_rows.end();) {
+            if (*it >= index && *it < index + count) {
+                // The indices are the ones at the time of the removal, so they must be
+                // notified in that order
+                removed.push_back(int(it - sorted_rows.begin()));
+                it = sorted_rows.erase(it);
+            } else {
+                if (*it >= index + count) {
+                    *it -= count;
+                }
+                ++it;
+            }
+        }
+        for (int i : removed) {
+            target_model.row_removed(i, 1);
+        }
+    }
+    void row_moved(int from, int count, int to) override
+    {
+        // The data didn't change, so the sorted rows stay in place
+        for (auto &row : sorted_rows) {
+            row = moved_row(row, from, count, to);
+        }
+    }
+
+    void update_mapping()
+    {
+        std::vector<std::pair<int, ModelData>> rows;
+        for (int row = 0, count = source_model->row_count(); row < count; ++row) {
+            if (auto data = source_model->row_data(row)) {
+                rows.emplace_back(row, *data);
+            }
+        }
+        std::stable_sort(rows.begin(), rows.end(),
+                         [this](const auto &a, const auto &b) { return comp(a.second, b.second); });
+        sorted_rows.clear();
+        for (const auto &row : rows) {
+            sorted_rows.push_back(row.first);
+        }
+    }
+
+    void reset() override
+    {
+        update_mapping();
+        // Not SortModel::reset, which re-sorts the rows by calling this function
+        target_model.Model<ModelData>::reset();
+    }
+
+    std::shared_ptr<slint::Model<ModelData>> source_model;
+    std::function<bool(const ModelData &, const ModelData &)> comp;
+    // For each row of the sorted model, the row of the source model
+    std::vector<int> sorted_rows;
+    slint::SortModel<ModelData> &target_model;
+};
+} // namespace private_api
+
+/// The SortModel acts as an adapter model for a given source model by sorting all rows
+/// with by order provided by the given sorting function. The sorting function is called for
+/// pairs of elements of the source model and returns true if the first element is less
+/// than the second one. The sort is stable: rows that are equivalent keep their order.
+template<typename ModelData>
+class SortModel : public Model<ModelData>
+{
+    friend struct private_api::SortModelInner<ModelData>;
+
+public:
+    /// Constructs a new SortModel that provides a sorted view on the \a source_model by
+    /// applying the order given by the specified \a comp.
+    SortModel(std::shared_ptr<Model<ModelData>> source_model,
+              std::function<bool(const ModelData &, const ModelData &)> comp)
+        : inner(std::make_shared<private_api::SortModelInner<ModelData>>(std::move(source_model),
+                                                                         std::move(comp), *this))
+    {
+        inner->source_model->attach_peer(inner);
+    }
+
+    int row_count() const override { return int(inner->sorted_rows.size()); }
+
+    std::optional<ModelData> row_data(int i) const override
+    {
+        if (i < 0 || i >= row_count())
+            return {};
+        return inner->source_model->row_data(inner->sorted_rows[i]);
+    }
+
+    void set_row_data(int i, const ModelData &value) override
+    {
+        inner->source_model->set_row_data(inner->sorted_rows[i], value);
+    }
+
+    /// Re-applies the model's sort function on each row of the source model. Use this if state
+    /// external to the sort function has changed.
+    void reset() { inner->reset(); }
+
+    /// Given the \a sorted_row index, this function returns the corresponding row index in the
+    /// source model.
+    int unsorted_row(int sorted_row) const { return inner->sorted_rows[sorted_row]; }
+
+    /// Returns the source model of this sort model.
+    std::shared_ptr<Model<ModelData>> source_model() const { return inner->source_model; }
+
+private:
+    std::shared_ptr<private_api::SortModelInner<ModelData>> inner;
 };
 
 namespace private_api {
@@ -742,6 +1223,38 @@ class Repeater
                 data[i].state = State::Dirty;
             }
         }
+        void row_moved(int from, int count, int to) override
+        {
+            if (count == 0 || from == to) {
+                return;
+            }
+            is_dirty.set(true);
+            // The components are moved along with their rows and only get their index and data
+            // updated. All the rows between the old and the new position change their index.
+            int begin = std::min(from, to);
+            int end = std::max(from, to) + count;
+            if (end > int(data.size())) {
+                return;
+            }
+            if (from < to) {
+                std::rotate(data.begin() + begin, data.begin() + begin + count,
+                            data.begin() + end);
+            } else {
+                std::rotate(data.begin() + begin, data.begin() + end - count, data.begin() + end);
+            }
+            for (int i = begin; i < end; ++i) {
+                data[i].state = State::Dirty;
+            }
+        }
+        void reset() override
+        {
+            // The existing components are kept and updated with the new data, ensure_updated
+            // drops the ones that are not needed anymore.
+            is_dirty.set(true);
+            for (auto &c : data) {
+                c.state = State::Dirty;
+            }
+        }
     };
 
 public:
@@ -802,6 +1315,44 @@ public:
         viewport_height->set(h);
     }
 
+    /// Scroll the ListView the least possible so that the given row becomes visible.
+    /// The position of rows that are not instantiated is estimated from the average item height,
+    /// like in the Rust implementation.
+    void ensure_visible_listview(int row, const private_api::Property<float> *viewport_y,
+                                 float listview_height) const
+    {
+        if (!inner || row < 0)
+            return;
+        float y = 0, height = 0;
+        if (std::size_t(row) < inner->data.size() && inner->data[row].ptr) {
+            auto component = item_at(row);
+            auto item = component.vtable->get_item_ref(component, 0);
+            auto geometry = item.vtable->geometry(item);
+            y = geometry.y;
+            height = geometry.height;
+        } else {
+            float total_height = 0;
+            int count = 0;
+            for (std::size_t i = 0; i < inner->data.size(); ++i) {
+                if (!inner->data[i].ptr)
+                    continue;
+                auto component = item_at(int(i));
+                auto item = component.vtable->get_item_ref(component, 0);
+                total_height += item.vtable->geometry(item).height;
+                ++count;
+            }
+            if (count > 0)
+                height = total_height / count;
+            y = row * height;
+        }
+        float offset_y = -viewport_y->get();
+        if (y < offset_y) {
+            viewport_y->set(-y);
+        } else if (y + height > offset_y + listview_height) {
+            viewport_y->set(-std::min(y + height - listview_height, y));
+        }
+    }
+
     uintptr_t visit(TraversalOrder order, private_api::ItemVisitorRefMut visitor) const
     {
         for (std::size_t i = 0; i < inner->data.size(); ++i) {
@@ -1019,8 +1570,35 @@ auto blocking_invoke_from_event_loop(Functor f) -> void
     cv.wait(lock, [&] { return ok; });
 }
 
+/// Sets the translations of the `@tr(...)` strings from the content of a gettext `.mo` or `.po`
+/// file. The bindings that use translated strings are re-evaluated.
+/// \returns false if the data is not a valid catalog, in which case the translations are unchanged
+inline bool set_translation_catalog(std::span<const uint8_t> data)
+{
+    return cbindgen_private::slint_set_translation_catalog(
+            { const_cast<uint8_t *>(data.data()), data.size() });
+}
+
+/// Removes the translations set with set_translation_catalog(), so that the `@tr(...)` strings
+/// show the original strings.
+inline void reset_translation_catalog()
+{
+    cbindgen_private::slint_reset_translation_catalog();
+}
+
 namespace private_api {
 
+inline SharedString translate(const SharedString &original, const SharedString &context,
+                              std::span<const SharedString> arguments, int n,
+                              const SharedString &plural)
+{
+    SharedString result = original;
+    cbindgen_private::slint_translate(
+            &result, &context,
+            { const_cast<SharedString *>(arguments.data()), arguments.size() }, n, &plural);
+    return result;
+}
+
 /// Registers a font by the specified path. The path must refer to an existing
 /// TrueType font.
 /// \returns an empty optional on success, otherwise an error string
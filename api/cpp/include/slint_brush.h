@@ -60,10 +60,89 @@ private:
     }
 };
 
+/// \private
+/// RadialGradientBrush represents a circular gradient centered in the shape, for a brush that is a
+/// sequence of color stops.
+class RadialGradientBrush
+{
+public:
+    /// Constructs an empty radial gradient with no color stops.
+    RadialGradientBrush() = default;
+    /// Constructs a new circular radial gradient. The color stops will be
+    /// constructed from the stops array pointed to be \a firstStop, with the length \a stopCount.
+    RadialGradientBrush(const GradientStop *firstStop, int stopCount)
+        : inner(make_gradient(firstStop, stopCount))
+    {
+    }
+
+    /// Returns the number of gradient stops.
+    int stopCount() const { return int(inner.size()); }
+
+    /// Returns a pointer to the first gradient stop; undefined if the gradient has not stops.
+    const GradientStop *stopsBegin() const { return inner.begin(); }
+    /// Returns a pointer past the last gradient stop. The returned pointer cannot be dereferenced,
+    /// it can only be used for comparison.
+    const GradientStop *stopsEnd() const { return inner.end(); }
+
+private:
+    cbindgen_private::types::RadialGradientBrush inner;
+
+    friend class slint::Brush;
+
+    static SharedVector<private_api::GradientStop> make_gradient(const GradientStop *firstStop,
+                                                                 int stopCount)
+    {
+        SharedVector<private_api::GradientStop> gradient;
+        for (int i = 0; i < stopCount; ++i, ++firstStop)
+            gradient.push_back(*firstStop);
+        return gradient;
+    }
+};
+
+/// \private
+/// ConicGradientBrush represents a gradient for a brush that is a sequence of color stops that
+/// rotate around the center of the shape. The position of the stops is the fraction of a full turn,
+/// starting at the top and going clockwise.
+class ConicGradientBrush
+{
+public:
+    /// Constructs an empty conic gradient with no color stops.
+    ConicGradientBrush() = default;
+    /// Constructs a new conic gradient. The color stops will be
+    /// constructed from the stops array pointed to be \a firstStop, with the length \a stopCount.
+    ConicGradientBrush(const GradientStop *firstStop, int stopCount)
+        : inner(make_gradient(firstStop, stopCount))
+    {
+    }
+
+    /// Returns the number of gradient stops.
+    int stopCount() const { return int(inner.size()); }
+
+    /// Returns a pointer to the first gradient stop; undefined if the gradient has not stops.
+    const GradientStop *stopsBegin() const { return inner.begin(); }
+    /// Returns a pointer past the last gradient stop. The returned pointer cannot be dereferenced,
+    /// it can only be used for comparison.
+    const GradientStop *stopsEnd() const { return inner.end(); }
+
+private:
+    cbindgen_private::types::ConicGradientBrush inner;
+
+    friend class slint::Brush;
+
+    static SharedVector<private_api::GradientStop> make_gradient(const GradientStop *firstStop,
+                                                                 int stopCount)
+    {
+        SharedVector<private_api::GradientStop> gradient;
+        for (int i = 0; i < stopCount; ++i, ++firstStop)
+            gradient.push_back(*firstStop);
+        return gradient;
+    }
+};
+
 }
 
 /// Brush is used to declare how to fill or outline shapes, such as rectangles, paths or text. A
-/// brush is either a solid color or a linear gradient.
+/// brush is either a solid color or a gradient.
 class Brush
 {
 public:
@@ -77,6 +156,18 @@ public:
         : data(Inner::LinearGradient(gradient.inner))
     {
     }
+    /// \private
+    /// Constructs a new brush that is the gradient \a gradient.
+    Brush(const private_api::RadialGradientBrush &gradient)
+        : data(Inner::RadialGradient(gradient.inner))
+    {
+    }
+    /// \private
+    /// Constructs a new brush that is the gradient \a gradient.
+    Brush(const private_api::ConicGradientBrush &gradient)
+        : data(Inner::ConicGradient(gradient.inner))
+    {
+    }
 
     /// Returns the color of the brush. If the brush is a gradient, this function returns the color
     /// of the first stop.
@@ -109,6 +200,16 @@ Color Brush::color() const
             result.inner = data.linear_gradient._0[1].color;
         }
         break;
+    case Tag::RadialGradient:
+        if (data.radial_gradient._0.size() > 0) {
+            result.inner = data.radial_gradient._0[0].color;
+        }
+        break;
+    case Tag::ConicGradient:
+        if (data.conic_gradient._0.size() > 0) {
+            result.inner = data.conic_gradient._0[0].color;
+        }
+        break;
     }
     return result;
 }
@@ -451,6 +451,11 @@ inline Value::Value(const std::shared_ptr<slint::Model<Value>> &model)
         {
             cbindgen_private::slint_interpreter_model_notify_row_removed(&notify, index, count);
         }
+        void row_moved(int from, int count, int to) override
+        {
+            cbindgen_private::slint_interpreter_model_notify_row_moved(&notify, from, count, to);
+        }
+        void reset() override { cbindgen_private::slint_interpreter_model_notify_reset(&notify); }
     };
 
     auto wrapper = std::make_shared<ModelWrapper>();
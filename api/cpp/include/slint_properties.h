@@ -4,6 +4,8 @@
 #pragma once
 #include <string_view>
 #include <memory>
+#include <optional>
+#include <type_traits>
 
 namespace slint::cbindgen_private {
 struct PropertyAnimation;
@@ -292,4 +294,47 @@ private:
     cbindgen_private::PropertyTrackerOpaque inner;
 };
 
+/// ChangeTracker calls a handler when the value returned by a functor changes.
+/// The handler is not called right away, but from the event loop after the value was
+/// re-evaluated.
+struct ChangeTracker
+{
+    /// Constructs a new change tracker instance.
+    ChangeTracker() { cbindgen_private::slint_change_tracker_construct(&inner); }
+    /// Destroys the change tracker.
+    ~ChangeTracker() { cbindgen_private::slint_change_tracker_drop(&inner); }
+    /// The copy constructor is intentionally deleted, change trackers cannot be copied.
+    ChangeTracker(const ChangeTracker &) = delete;
+    /// The assignment operator is intentionally deleted, change trackers cannot be copied.
+    ChangeTracker &operator=(const ChangeTracker &) = delete;
+
+    /// Sets the functor \a eval that computes the value, and the functor \a notify that is
+    /// called when this value changes.
+    template<typename FEval, typename FNotify>
+    void init(FEval eval, FNotify notify) const
+    {
+        using T = std::invoke_result_t<FEval>;
+        struct Data
+        {
+            FEval eval;
+            FNotify notify;
+            std::optional<T> value;
+        };
+        cbindgen_private::slint_change_tracker_init(
+                &inner, new Data { std::move(eval), std::move(notify), {} },
+                [](void *data) { delete reinterpret_cast<Data *>(data); },
+                [](void *d) {
+                    auto data = reinterpret_cast<Data *>(d);
+                    T value = data->eval();
+                    bool changed = data->value.has_value() && !(*data->value == value);
+                    data->value = std::move(value);
+                    return changed;
+                },
+                [](void *d) { reinterpret_cast<Data *>(d)->notify(); });
+    }
+
+private:
+    cbindgen_private::ChangeTrackerOpaque inner;
+};
+
 } // namespace slint::private_api
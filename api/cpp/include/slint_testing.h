@@ -32,6 +32,17 @@ inline void send_keyboard_string_sequence(const Component *component,
                                                     &component->m_window.window_handle());
 }
 
+/// Returns a textual representation of the accessibility tree of the component, with one line
+/// per node, for example `button label="Ok"`. Children are indented below their parent.
+template<typename Component>
+inline SharedString dump_accessibility_tree(const Component *component)
+{
+    auto crc = *component->self_weak.into_dyn().lock();
+    SharedString result;
+    cbindgen_private::slint_testing_dump_accessibility_tree(&crc, &result);
+    return result;
+}
+
 #define assert_eq(A, B)                                                                            \
     slint::testing::private_api::assert_eq_impl(A, B, #A, #B, __FILE__, __LINE__)
 
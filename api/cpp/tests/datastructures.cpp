@@ -147,6 +147,68 @@ TEST_CASE("Track model row data changes")
     REQUIRE(tracker.is_dirty());
 }
 
+TEST_CASE("Filter model")
+{
+    auto vec_model =
+            std::make_shared<slint::VectorModel<int>>(std::vector<int> { 1, 2, 3, 4, 5, 6 });
+    slint::FilterModel<int> filter_model(vec_model, [](int value) { return value % 2 == 0; });
+
+    auto rows = [&]() {
+        std::vector<int> result;
+        for (int i = 0; i < filter_model.row_count(); ++i)
+            result.push_back(*filter_model.row_data(i));
+        return result;
+    };
+
+    REQUIRE(rows() == std::vector<int> { 2, 4, 6 });
+    vec_model->insert(0, 10);
+    REQUIRE(rows() == std::vector<int> { 10, 2, 4, 6 });
+    vec_model->insert(2, 11);
+    REQUIRE(rows() == std::vector<int> { 10, 2, 4, 6 });
+    vec_model->set_row_data(3, 13);
+    REQUIRE(rows() == std::vector<int> { 10, 4, 6 });
+    vec_model->set_row_data(1, 8);
+    REQUIRE(rows() == std::vector<int> { 10, 8, 4, 6 });
+    vec_model->erase(5);
+    REQUIRE(rows() == std::vector<int> { 10, 8, 6 });
+    REQUIRE(filter_model.unfiltered_row(2) == 6);
+}
+
+TEST_CASE("Sort model")
+{
+    auto vec_model = std::make_shared<slint::VectorModel<int>>(std::vector<int> { 3, 1, 2 });
+    slint::SortModel<int> sort_model(vec_model, [](int a, int b) { return a < b; });
+
+    auto rows = [&]() {
+        std::vector<int> result;
+        for (int i = 0; i < sort_model.row_count(); ++i)
+            result.push_back(*sort_model.row_data(i));
+        return result;
+    };
+
+    REQUIRE(rows() == std::vector<int> { 1, 2, 3 });
+    vec_model->push_back(0);
+    REQUIRE(rows() == std::vector<int> { 0, 1, 2, 3 });
+    REQUIRE(sort_model.unsorted_row(0) == 3);
+    vec_model->set_row_data(1, 4);
+    REQUIRE(rows() == std::vector<int> { 0, 2, 3, 4 });
+    vec_model->erase(0);
+    REQUIRE(rows() == std::vector<int> { 0, 2, 4 });
+}
+
+TEST_CASE("Map model")
+{
+    auto vec_model = std::make_shared<slint::VectorModel<int>>(std::vector<int> { 1, 2, 3 });
+    slint::MapModel<int, slint::SharedString> map_model(
+            vec_model, [](int value) { return slint::SharedString::from_number(value * 2); });
+
+    REQUIRE(map_model.row_count() == 3);
+    REQUIRE(*map_model.row_data(2) == "6");
+    vec_model->push_back(4);
+    REQUIRE(map_model.row_count() == 4);
+    REQUIRE(*map_model.row_data(3) == "8");
+}
+
 TEST_CASE("Image")
 {
     using namespace slint;
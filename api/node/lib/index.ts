@@ -90,6 +90,12 @@ require.extensions['.60'] = require.extensions['.slint'] =
                     enumerable: true,
                 })
             });
+            c.functions().forEach((x: string) => {
+                Object.defineProperty(ret, x.replace(/-/g, '_'), {
+                    value: function () { return comp.invoke_callback(x, [...arguments]); },
+                    enumerable: true,
+                })
+            });
             return ret;
         }
     }
@@ -223,7 +223,13 @@ fn to_eval_value<'cx>(
                     .collect::<Result<_, _>>()?,
             ))
         }
-        Type::Enumeration(_) => todo!(),
+        Type::Enumeration(e) => {
+            let value = val.to_string(cx)?.value();
+            if !e.values.contains(&value) {
+                return cx.throw_error(format!("'{}' is not a value of enum {}", value, e.name));
+            }
+            Ok(Value::EnumerationValue(e.name.clone(), value))
+        }
         Type::Invalid
         | Type::Void
         | Type::InferredProperty
@@ -289,6 +295,7 @@ fn to_js_value<'cx>(
             &format!("#{:02x}{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue(), c.alpha()),
         )
         .as_value(cx),
+        Value::EnumerationValue(_, value) => JsString::new(cx, value.as_str()).as_value(cx),
         _ => todo!("converting {:?} to js has not been implemented", val),
     })
 }
@@ -334,6 +341,18 @@ fn to_js_value<'cx>(
             }
             Ok(array.as_value(&mut cx))
         }
+        method functions(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let functions = ct.properties_and_callbacks().filter(|(_, prop_type)| matches!(prop_type, Type::Function{..}));
+            let array = JsArray::new(&mut cx, 0);
+            for (len , (p, _)) in functions.enumerate() {
+                let prop_name = JsString::new(&mut cx, p);
+                array.set(&mut cx, len as u32, prop_name)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
     }
 
     class SlintComponent for WrappedComponentRc {
@@ -409,7 +428,7 @@ fn to_js_value<'cx>(
                 })?;
             let persistent_context =
                 persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
-            let args = if let Type::Callback {args, ..} = ty {
+            let args = if let Type::Callback {args, ..} | Type::Function {args, ..} = ty {
                 let count = args.len();
                 let args = arguments.into_iter()
                     .zip(args.into_iter())
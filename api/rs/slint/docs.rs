@@ -80,6 +80,12 @@ pub fn get_counter(&self) -> i32 {
         /// In this case, this is the setter that sets the value of the `counter` property
         /// declared in the `.slint` design markup.
         pub fn set_counter(&self, value: i32) {}
+        /// For each property declared at the root of the component, a function to be notified
+        /// of its changes is generated. The handler `f` is called with the new value of the
+        /// `counter` property, from the event loop, after the property changed. If the property
+        /// changed several times in between, the handler is only called once. Calling this
+        /// function again replaces the previous handler.
+        pub fn on_counter_changed(&self, f: impl FnMut(i32) + 'static) {}
         /// Returns the value of the `user_name` property declared in the `.slint` design markup.
         pub fn get_user_name(&self) -> re_exports::SharedString {
             unimplemented!()
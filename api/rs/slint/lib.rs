@@ -136,6 +136,9 @@ struct MyComponent{ /*...*/ }
 For each top-level property
   - A setter [`fn set_<property_name>(&self, value: <PropertyType>)`](docs::generated_code::SampleComponent::set_counter)
   - A getter [`fn get_<property_name>(&self) -> <PropertyType>`](docs::generated_code::SampleComponent::get_counter)
+  - [`fn on_<property_name>_changed(&self, callback: impl FnMut(<PropertyType>) + 'static)`](docs::generated_code::SampleComponent::on_counter_changed):
+    to set a handler called from the event loop with the new value when the property changes.
+    It is not generated if there is a `<property_name>-changed` callback, since its `on_` function has the same name.
 
 For each top-level callback
   - [`fn invoke_<callback_name>(&self)`](docs::generated_code::SampleComponent::invoke_hello): to invoke the callback
@@ -211,6 +214,7 @@ struct MyStruct {
 For each property
   - A setter: `fn set_<property_name>(&self, value: <PropertyType>)`
   - A getter: `fn get_<property_name>(&self) -> <PropertyType>`
+  - `fn on_<property_name>_changed(&self, callback: impl FnMut(<PropertyType>) + 'static)` to set a handler called when the property changes.
 
 For each callback
   - `fn invoke_<callback_name>(&self, <CallbackArgs>) -> <ReturnValue>` to invoke the callback
@@ -242,7 +246,8 @@ struct MyStruct {
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
 pub use i_slint_core::model::{
-    Model, ModelNotify, ModelPeer, ModelRc, ModelTracker, StandardListViewItem, VecModel,
+    FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
+    SortModel, StandardListViewItem, VecModel,
 };
 pub use i_slint_core::sharedvector::SharedVector;
 pub use i_slint_core::string::SharedString;
@@ -279,6 +284,7 @@ pub mod re_exports {
     pub use const_field_offset::{self, FieldOffsets, PinnedDrop};
     pub use core::iter::FromIterator;
     pub use i_slint_backend_selector::native_widgets::*;
+    pub use i_slint_core::accessibility::AccessibleStringProperty;
     pub use i_slint_core::animations::EasingCurve;
     pub use i_slint_core::callbacks::Callback;
     pub use i_slint_core::component::{
@@ -287,7 +293,8 @@ pub mod re_exports {
     };
     pub use i_slint_core::graphics::*;
     pub use i_slint_core::input::{
-        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers, MouseEvent,
+        DropEvent, FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers,
+        MouseEvent,
     };
     pub use i_slint_core::item_tree::{
         visit_item_tree, ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, TraversalOrder,
@@ -296,8 +303,11 @@ pub mod re_exports {
     pub use i_slint_core::items::*;
     pub use i_slint_core::layout::*;
     pub use i_slint_core::model::*;
-    pub use i_slint_core::properties::{set_state_binding, Property, PropertyTracker, StateInfo};
+    pub use i_slint_core::properties::{
+        set_state_binding, ChangeTracker, Property, PropertyTracker, StateInfo,
+    };
     pub use i_slint_core::slice::Slice;
+    pub use i_slint_core::translations::translate;
     pub use i_slint_core::window::{Window, WindowHandleAccess, WindowRc};
     pub use i_slint_core::Color;
     pub use i_slint_core::ComponentVTable_static;
@@ -488,15 +498,42 @@ pub fn send_mouse_click<
         i_slint_core::tests::slint_send_mouse_click(&dyn_rc, x, y, &rc.window_handle().clone());
     }
 
+    pub use i_slint_core::input::TouchPhase;
+
+    /// Simulate a touch event of the touch point identified by `id`, at the position `x` and `y`
+    /// of the window.
+    pub fn send_touch_event<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        phase: TouchPhase,
+        x: f32,
+        y: f32,
+    ) {
+        let component = component.clone_strong().into();
+        component.window_handle().clone().process_mouse_input(
+            crate::re_exports::MouseEvent::Touch {
+                pos: crate::re_exports::Point::new(x, y),
+                id,
+                phase,
+            },
+        )
+    }
+
     /// Simulate a change in keyboard modifiers being pressed
     pub fn set_current_keyboard_modifiers<
         X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
             + crate::re_exports::WindowHandleAccess,
         Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
     >(
-        _component: &Component,
+        component: &Component,
         modifiers: crate::re_exports::KeyboardModifiers,
     ) {
+        let component = component.clone_strong().into();
+        component.window_handle().set_current_keyboard_modifiers(modifiers);
         KEYBOARD_MODIFIERS.with(|x| x.set(modifiers))
     }
 
@@ -517,6 +554,32 @@ pub fn send_keyboard_string_sequence<
         )
     }
 
+    /// Simulate the input method updating the text it is composing.
+    pub fn set_preedit_text<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        text: &str,
+    ) {
+        let component = component.clone_strong().into();
+        component.window_handle().clone().set_preedit_text(text.into())
+    }
+
+    /// Simulate the input method committing the given text.
+    pub fn commit_preedit_text<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        text: &str,
+    ) {
+        let component = component.clone_strong().into();
+        component.window_handle().clone().commit_preedit_text(text.into())
+    }
+
     /// Applies the specified scale factor to the window that's associated with the given component.
     /// This overrides the value provided by the windowing system.
     pub fn set_window_scale_factor<
@@ -530,6 +593,53 @@ pub fn set_window_scale_factor<
         let component = component.clone_strong().into();
         component.window_handle().set_scale_factor(factor)
     }
+
+    pub use i_slint_core::tests::{ElementHandle, ElementQuery};
+
+    /// Returns the elements of the component that match the query, in the order of the item tree.
+    ///
+    /// ```ignore
+    /// let buttons = slint::testing::find_elements(&app, &ElementQuery::new().element_id("ok-button"));
+    /// buttons[0].click();
+    /// ```
+    pub fn find_elements<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess
+            + 'static,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        query: &ElementQuery,
+    ) -> Vec<ElementHandle> {
+        let rc = component.clone_strong().into();
+        let dyn_rc = vtable::VRc::into_dyn(rc.clone());
+        i_slint_core::tests::find_elements(&dyn_rc, &rc.window_handle().clone(), query)
+    }
+
+    pub use i_slint_core::accessibility::AccessibleNode;
+
+    /// Returns the accessibility tree of the component. It contains a node for each element with an
+    /// `accessible-role`, nested like the elements.
+    pub fn accessibility_tree<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable> + 'static,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> Vec<AccessibleNode> {
+        let dyn_rc = vtable::VRc::into_dyn(component.clone_strong().into());
+        i_slint_core::accessibility::accessibility_tree(&dyn_rc)
+    }
+
+    /// Returns a textual representation of the accessibility tree of the component, with one line
+    /// per node, for example `button label="Ok"`. Children are indented below their parent.
+    pub fn dump_accessibility_tree<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable> + 'static,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> String {
+        i_slint_core::accessibility::dump_accessibility_tree(&accessibility_tree(component))
+    }
 }
 
 /// Include the code generated with the slint-build crate from the build script. After calling `slint_build::compile`
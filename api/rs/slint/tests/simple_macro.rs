@@ -13,3 +13,17 @@ fn empty_stuff() {
     slint!();
     slint!(struct Hei := { abcd: bool });
 }
+
+#[test]
+fn tr_plural() {
+    slint!(X := Rectangle {
+        property <int> count: 1;
+        property <string> files: @tr("{n} file" | "{n} files" % count);
+        property <float> ratio: 50%;
+    });
+    let x = X::new();
+    assert_eq!(x.get_files(), "1 file");
+    assert_eq!(x.get_ratio(), 0.5);
+    x.set_count(3);
+    assert_eq!(x.get_files(), "3 files");
+}
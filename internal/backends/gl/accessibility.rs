@@ -0,0 +1,972 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module bridges the accessibility tree of the windows to AT-SPI, the accessibility API of the
+Linux and BSD desktops.
+
+When the accessibility of the desktop is enabled, the application registers itself on the
+accessibility bus and answers the D-Bus requests of the assistive technologies from a separate
+thread. As the item tree can only be accessed from the thread running the event loop, every window
+publishes a snapshot of its accessibility tree. The properties read to build the snapshot are
+tracked, and a new snapshot is only built when one of them changed. The changes between two
+snapshots are sent to the assistive technologies as AT-SPI events, from a thread of their own.
+*/
+
+// cspell:ignore atspi zbus zvariant
+
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+use corelib::accessibility::{accessibility_tree, AccessibleNode};
+use corelib::items::{AccessibleRole, ItemRef};
+use corelib::properties::{PropertyChangeHandler, PropertyTracker};
+use i_slint_core as corelib;
+use zbus::blocking::{Connection, ConnectionBuilder, MessageIterator};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, StructureBuilder, Value};
+use zbus::{Message, MessageType};
+
+/// The path of the object representing the application
+const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+/// The path of the windows and of the accessible items is this prefix followed by their id
+const NODE_PATH_PREFIX: &str = "/org/slint/accessible/";
+const NULL_PATH: &str = "/org/a11y/atspi/null";
+
+const ACCESSIBLE_INTERFACE: &str = "org.a11y.atspi.Accessible";
+const APPLICATION_INTERFACE: &str = "org.a11y.atspi.Application";
+const COMPONENT_INTERFACE: &str = "org.a11y.atspi.Component";
+const TEXT_INTERFACE: &str = "org.a11y.atspi.Text";
+const VALUE_INTERFACE: &str = "org.a11y.atspi.Value";
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+const EVENT_INTERFACE: &str = "org.a11y.atspi.Event.Object";
+
+// Values of AtspiRole
+const ROLE_CHECK_BOX: u32 = 7;
+const ROLE_COMBO_BOX: u32 = 11;
+const ROLE_FRAME: u32 = 23;
+const ROLE_LABEL: u32 = 29;
+const ROLE_LIST: u32 = 31;
+const ROLE_LIST_ITEM: u32 = 32;
+const ROLE_PAGE_TAB: u32 = 37;
+const ROLE_PROGRESS_BAR: u32 = 42;
+const ROLE_PUSH_BUTTON: u32 = 43;
+const ROLE_SLIDER: u32 = 51;
+const ROLE_SPIN_BUTTON: u32 = 52;
+const ROLE_UNKNOWN: u32 = 67;
+const ROLE_APPLICATION: u32 = 75;
+const ROLE_ENTRY: u32 = 79;
+
+// Values of AtspiStateType
+const STATE_CHECKED: u32 = 4;
+const STATE_EDITABLE: u32 = 7;
+const STATE_ENABLED: u32 = 8;
+const STATE_SENSITIVE: u32 = 24;
+const STATE_SHOWING: u32 = 25;
+const STATE_VISIBLE: u32 = 30;
+const STATE_CHECKABLE: u32 = 41;
+
+// Values of AtspiCoordType
+const COORD_TYPE_SCREEN: u32 = 0;
+const COORD_TYPE_WINDOW: u32 = 1;
+const COORD_TYPE_PARENT: u32 = 2;
+
+// Values of AtspiComponentLayer
+const LAYER_WIDGET: u32 = 3;
+const LAYER_WINDOW: u32 = 7;
+
+/// A reference to an accessible object: the bus name of the application and the path of the object
+type ObjectRef = (String, OwnedObjectPath);
+
+fn object_path(path: String) -> OwnedObjectPath {
+    OwnedObjectPath::try_from(path).unwrap()
+}
+
+fn object_ref(bus_name: &str, object: Object) -> ObjectRef {
+    let path = match object {
+        Object::Application => ROOT_PATH.into(),
+        Object::Node(id) => format!("{}{}", NODE_PATH_PREFIX, id),
+    };
+    (bus_name.into(), object_path(path))
+}
+
+fn ref_value(object: &ObjectRef) -> Value<'static> {
+    StructureBuilder::new()
+        .add_field(object.0.clone())
+        .add_field(object.1.clone().into_inner())
+        .build()
+        .into()
+}
+
+/// The snapshot of a window or of an accessible item
+#[derive(Clone, PartialEq)]
+struct Node {
+    /// The parent of the node, `None` for the windows, whose parent is the application
+    parent: Option<u32>,
+    children: Vec<u32>,
+    /// The AtspiRole
+    role: u32,
+    name: String,
+    description: String,
+    value: String,
+    checked: bool,
+    /// The geometry of the node in physical pixels, relative to the window
+    extents: (i32, i32, i32, i32),
+}
+
+impl Node {
+    fn new(parent: u32, node: &AccessibleNode, scale_factor: f32) -> Self {
+        let geometry = node.geometry.scale(scale_factor, scale_factor).round();
+        Self {
+            parent: Some(parent),
+            children: Vec::new(),
+            role: match node.role {
+                AccessibleRole::none => ROLE_UNKNOWN,
+                AccessibleRole::button => ROLE_PUSH_BUTTON,
+                AccessibleRole::checkbox => ROLE_CHECK_BOX,
+                AccessibleRole::combobox => ROLE_COMBO_BOX,
+                AccessibleRole::list => ROLE_LIST,
+                AccessibleRole::list_item => ROLE_LIST_ITEM,
+                AccessibleRole::progress_indicator => ROLE_PROGRESS_BAR,
+                AccessibleRole::slider => ROLE_SLIDER,
+                AccessibleRole::spinbox => ROLE_SPIN_BUTTON,
+                AccessibleRole::tab => ROLE_PAGE_TAB,
+                AccessibleRole::text => ROLE_LABEL,
+                AccessibleRole::text_input => ROLE_ENTRY,
+            },
+            name: node.label.to_string(),
+            description: node.description.to_string(),
+            value: node.value.to_string(),
+            checked: node.checked,
+            extents: (
+                geometry.origin.x as i32,
+                geometry.origin.y as i32,
+                geometry.size.width as i32,
+                geometry.size.height as i32,
+            ),
+        }
+    }
+
+    fn role_name(&self) -> &'static str {
+        match self.role {
+            ROLE_CHECK_BOX => "check box",
+            ROLE_COMBO_BOX => "combo box",
+            ROLE_FRAME => "frame",
+            ROLE_LABEL => "label",
+            ROLE_LIST => "list",
+            ROLE_LIST_ITEM => "list item",
+            ROLE_PAGE_TAB => "page tab",
+            ROLE_PROGRESS_BAR => "progress bar",
+            ROLE_PUSH_BUTTON => "push button",
+            ROLE_SLIDER => "slider",
+            ROLE_SPIN_BUTTON => "spin button",
+            ROLE_ENTRY => "entry",
+            _ => "unknown",
+        }
+    }
+
+    fn states(&self) -> Vec<u32> {
+        let mut states = [STATE_ENABLED, STATE_SENSITIVE, STATE_SHOWING, STATE_VISIBLE]
+            .iter()
+            .fold(0u64, |states, state| states | 1 << state);
+        if self.role == ROLE_CHECK_BOX {
+            states |= 1 << STATE_CHECKABLE;
+        }
+        if self.checked {
+            states |= 1 << STATE_CHECKED;
+        }
+        if self.role == ROLE_ENTRY {
+            states |= 1 << STATE_EDITABLE;
+        }
+        vec![states as u32, (states >> 32) as u32]
+    }
+
+    /// The numeric value exposed through the Value interface
+    fn numeric_value(&self) -> Option<f64> {
+        if matches!(self.role, ROLE_PROGRESS_BAR | ROLE_SLIDER | ROLE_SPIN_BUTTON) {
+            self.value.parse().ok()
+        } else {
+            None
+        }
+    }
+
+    /// The content exposed through the Text interface
+    fn text(&self) -> Option<&str> {
+        match self.role {
+            ROLE_LABEL => Some(&self.name),
+            ROLE_ENTRY => Some(&self.value),
+            _ => None,
+        }
+    }
+
+    fn interfaces(&self) -> Vec<&'static str> {
+        let mut interfaces = vec![ACCESSIBLE_INTERFACE, COMPONENT_INTERFACE];
+        if self.numeric_value().is_some() {
+            interfaces.push(VALUE_INTERFACE);
+        }
+        if self.text().is_some() {
+            interfaces.push(TEXT_INTERFACE);
+        }
+        interfaces
+    }
+}
+
+/// The objects that can be addressed on the accessibility bus
+#[derive(Clone, Copy)]
+enum Object {
+    Application,
+    Node(u32),
+}
+
+/// A change of the tree, sent as a signal to the assistive technologies
+enum Event {
+    PropertyChange { id: u32, property: &'static str, value: String },
+    StateChange { id: u32, state: &'static str, enabled: bool },
+    ChildrenChanged { parent: Option<u32>, added: bool, index: usize, child: u32 },
+}
+
+/// The accessibility tree of all the windows, shared with the thread that answers the requests
+#[derive(Default)]
+struct Tree {
+    /// The unique name of the application on the accessibility bus
+    bus_name: String,
+    application_name: String,
+    /// The desktop, which is the parent of the application
+    desktop: Option<ObjectRef>,
+    /// The windows, which are the children of the application
+    windows: Vec<u32>,
+    nodes: HashMap<u32, Node>,
+    window_ids: HashMap<winit::window::WindowId, u32>,
+    /// The position of the windows on the screen in physical pixels, by window node
+    window_positions: HashMap<u32, (i32, i32)>,
+    /// The id of the items, by window and `AccessibleNode::item_key`
+    item_ids: HashMap<(u32, (usize, usize)), u32>,
+    next_id: u32,
+}
+
+impl Tree {
+    fn object_ref(&self, object: Object) -> ObjectRef {
+        object_ref(&self.bus_name, object)
+    }
+
+    fn null_ref(&self) -> ObjectRef {
+        (String::new(), object_path(NULL_PATH.into()))
+    }
+
+    fn children(&self, object: Object) -> &[u32] {
+        match object {
+            Object::Application => &self.windows,
+            Object::Node(id) => &self.nodes[&id].children,
+        }
+    }
+
+    fn parent_ref(&self, object: Object) -> ObjectRef {
+        match object {
+            Object::Application => self.desktop.clone().unwrap_or_else(|| self.null_ref()),
+            Object::Node(id) => match self.nodes[&id].parent {
+                Some(parent) => self.object_ref(Object::Node(parent)),
+                None => self.object_ref(Object::Application),
+            },
+        }
+    }
+
+    fn index_in_parent(&self, object: Object) -> i32 {
+        let id = match object {
+            Object::Application => return -1,
+            Object::Node(id) => id,
+        };
+        let siblings = match self.nodes[&id].parent {
+            Some(parent) => &self.nodes[&parent].children,
+            None => &self.windows,
+        };
+        siblings.iter().position(|x| *x == id).map_or(-1, |index| index as i32)
+    }
+
+    /// Returns the extents of the node in the given AtspiCoordType
+    fn extents(&self, id: u32, coord_type: u32) -> (i32, i32, i32, i32) {
+        let node = &self.nodes[&id];
+        let (mut x, mut y, width, height) = node.extents;
+        match coord_type {
+            COORD_TYPE_SCREEN => {
+                let mut window = id;
+                while let Some(parent) = self.nodes[&window].parent {
+                    window = parent;
+                }
+                let position = self.window_positions.get(&window).copied().unwrap_or_default();
+                x += position.0;
+                y += position.1;
+            }
+            COORD_TYPE_PARENT => {
+                if let Some(parent) = node.parent {
+                    let parent_extents = self.nodes[&parent].extents;
+                    x -= parent_extents.0;
+                    y -= parent_extents.1;
+                }
+            }
+            _ => {}
+        }
+        (x, y, width, height)
+    }
+
+    fn contains(&self, id: u32, x: i32, y: i32, coord_type: u32) -> bool {
+        let (left, top, width, height) = self.extents(id, coord_type);
+        x >= left && y >= top && x < left + width && y < top + height
+    }
+
+    /// Returns the deepest descendant of the node at the given position
+    fn accessible_at_point(&self, id: u32, x: i32, y: i32, coord_type: u32) -> Option<u32> {
+        // Convert the position to window coordinates, which are the same for all the nodes
+        let (left, top, _, _) = self.extents(id, coord_type);
+        let (window_x, window_y, _, _) = self.nodes[&id].extents;
+        let (x, y) = (x - left + window_x, y - top + window_y);
+        let mut result = None;
+        let mut children = &self.nodes[&id].children;
+        // The children are in the order of the item tree: the last ones are drawn on top
+        while let Some(child) =
+            children.iter().rev().find(|c| self.contains(**c, x, y, COORD_TYPE_WINDOW))
+        {
+            result = Some(*child);
+            children = &self.nodes[child].children;
+        }
+        result
+    }
+
+    fn property(&self, object: Object, interface: &str, property: &str) -> Option<Value<'static>> {
+        let node = match object {
+            Object::Application => None,
+            Object::Node(id) => Some(&self.nodes[&id]),
+        };
+        Some(match (interface, property) {
+            (ACCESSIBLE_INTERFACE, "Name") => match node {
+                Some(node) => node.name.clone().into(),
+                None => self.application_name.clone().into(),
+            },
+            (ACCESSIBLE_INTERFACE, "Description") => {
+                node.map_or(String::new(), |node| node.description.clone()).into()
+            }
+            (ACCESSIBLE_INTERFACE, "Parent") => ref_value(&self.parent_ref(object)),
+            (ACCESSIBLE_INTERFACE, "ChildCount") => (self.children(object).len() as i32).into(),
+            (ACCESSIBLE_INTERFACE, "Locale" | "AccessibleId") => String::new().into(),
+            (APPLICATION_INTERFACE, "ToolkitName") if node.is_none() => "Slint".into(),
+            (APPLICATION_INTERFACE, "Version") if node.is_none() => {
+                env!("CARGO_PKG_VERSION").into()
+            }
+            (APPLICATION_INTERFACE, "AtspiVersion") if node.is_none() => "2.1".into(),
+            (APPLICATION_INTERFACE, "Id") if node.is_none() => 0i32.into(),
+            // The range of the value is not known: report the value as its own range
+            (VALUE_INTERFACE, "CurrentValue" | "MinimumValue" | "MaximumValue") => {
+                node?.numeric_value()?.into()
+            }
+            (VALUE_INTERFACE, "MinimumIncrement") => {
+                node?.numeric_value()?;
+                0f64.into()
+            }
+            (VALUE_INTERFACE, "Text") => {
+                node?.numeric_value()?;
+                node?.value.clone().into()
+            }
+            (TEXT_INTERFACE, "CharacterCount") => (node?.text()?.chars().count() as i32).into(),
+            (TEXT_INTERFACE, "CaretOffset") => {
+                node?.text()?;
+                (-1i32).into()
+            }
+            _ => return None,
+        })
+    }
+
+    fn property_names(interface: &str) -> &'static [&'static str] {
+        match interface {
+            ACCESSIBLE_INTERFACE => {
+                &["Name", "Description", "Parent", "ChildCount", "Locale", "AccessibleId"]
+            }
+            APPLICATION_INTERFACE => &["ToolkitName", "Version", "AtspiVersion", "Id"],
+            VALUE_INTERFACE => {
+                &["CurrentValue", "MinimumValue", "MaximumValue", "MinimumIncrement", "Text"]
+            }
+            TEXT_INTERFACE => &["CharacterCount", "CaretOffset"],
+            _ => &[],
+        }
+    }
+
+    /// Answers a method call of an assistive technology
+    fn handle_method_call(&self, connection: &Connection, message: &Message) -> zbus::Result<()> {
+        let path = message.path();
+        let object = match path.as_ref().map(|path| path.as_str()) {
+            Some(ROOT_PATH) => Object::Application,
+            Some(path) => match path
+                .strip_prefix(NODE_PATH_PREFIX)
+                .and_then(|id| id.parse().ok())
+                .filter(|id| self.nodes.contains_key(id))
+            {
+                Some(id) => Object::Node(id),
+                None => return reply_error(connection, message, "UnknownObject"),
+            },
+            None => return reply_error(connection, message, "UnknownObject"),
+        };
+        let interface = message.interface();
+        let interface = interface.as_ref().map_or("", |interface| interface.as_str());
+        let member = message.member();
+        let member = member.as_ref().map_or("", |member| member.as_str());
+
+        match (interface, member, object) {
+            (PROPERTIES_INTERFACE, "Get", _) => {
+                let (interface, property): (String, String) = message.body()?;
+                match self.property(object, &interface, &property) {
+                    Some(value) => connection.reply(message, &value)?,
+                    None => return reply_error(connection, message, "UnknownProperty"),
+                };
+            }
+            (PROPERTIES_INTERFACE, "GetAll", _) => {
+                let interface: String = message.body()?;
+                let properties: HashMap<&str, Value> = Self::property_names(&interface)
+                    .iter()
+                    .filter_map(|name| Some((*name, self.property(object, &interface, name)?)))
+                    .collect();
+                connection.reply(message, &properties)?;
+            }
+            // The registry sets the id of the application, which is not needed
+            (PROPERTIES_INTERFACE, "Set", _) => {
+                let _: (String, String, OwnedValue) = message.body()?;
+                connection.reply(message, &())?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetChildAtIndex", _) => {
+                let index: i32 = message.body()?;
+                let child = match self.children(object).get(index as usize) {
+                    Some(child) => self.object_ref(Object::Node(*child)),
+                    None => self.null_ref(),
+                };
+                connection.reply(message, &(child,))?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetChildren", _) => {
+                let children: Vec<ObjectRef> = self
+                    .children(object)
+                    .iter()
+                    .map(|child| self.object_ref(Object::Node(*child)))
+                    .collect();
+                connection.reply(message, &children)?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetIndexInParent", _) => {
+                connection.reply(message, &self.index_in_parent(object))?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetRelationSet", _) => {
+                connection.reply(message, &Vec::<(u32, Vec<ObjectRef>)>::new())?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetRole", Object::Application) => {
+                connection.reply(message, &ROLE_APPLICATION)?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetRole", Object::Node(id)) => {
+                connection.reply(message, &self.nodes[&id].role)?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetRoleName" | "GetLocalizedRoleName", Object::Application) => {
+                connection.reply(message, &"application")?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetRoleName" | "GetLocalizedRoleName", Object::Node(id)) => {
+                connection.reply(message, &self.nodes[&id].role_name())?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetState", Object::Application) => {
+                connection.reply(message, &vec![0u32, 0u32])?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetState", Object::Node(id)) => {
+                connection.reply(message, &self.nodes[&id].states())?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetAttributes", _) => {
+                connection.reply(message, &HashMap::<String, String>::new())?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetApplication", _) => {
+                connection.reply(message, &(self.object_ref(Object::Application),))?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetInterfaces", Object::Application) => {
+                connection.reply(message, &vec![ACCESSIBLE_INTERFACE, APPLICATION_INTERFACE])?;
+            }
+            (ACCESSIBLE_INTERFACE, "GetInterfaces", Object::Node(id)) => {
+                connection.reply(message, &self.nodes[&id].interfaces())?;
+            }
+            (APPLICATION_INTERFACE, "GetLocale", Object::Application) => {
+                connection.reply(message, &"")?;
+            }
+            (COMPONENT_INTERFACE, "GetExtents", Object::Node(id)) => {
+                let coord_type: u32 = message.body()?;
+                connection.reply(message, &(self.extents(id, coord_type),))?;
+            }
+            (COMPONENT_INTERFACE, "GetPosition", Object::Node(id)) => {
+                let coord_type: u32 = message.body()?;
+                let (x, y, _, _) = self.extents(id, coord_type);
+                connection.reply(message, &(x, y))?;
+            }
+            (COMPONENT_INTERFACE, "GetSize", Object::Node(id)) => {
+                let (_, _, width, height) = self.nodes[&id].extents;
+                connection.reply(message, &(width, height))?;
+            }
+            (COMPONENT_INTERFACE, "Contains", Object::Node(id)) => {
+                let (x, y, coord_type): (i32, i32, u32) = message.body()?;
+                connection.reply(message, &self.contains(id, x, y, coord_type))?;
+            }
+            (COMPONENT_INTERFACE, "GetAccessibleAtPoint", Object::Node(id)) => {
+                let (x, y, coord_type): (i32, i32, u32) = message.body()?;
+                let child = match self.accessible_at_point(id, x, y, coord_type) {
+                    Some(child) => self.object_ref(Object::Node(child)),
+                    None => self.null_ref(),
+                };
+                connection.reply(message, &(child,))?;
+            }
+            (COMPONENT_INTERFACE, "GetLayer", Object::Node(id)) => {
+                let layer =
+                    if self.nodes[&id].parent.is_none() { LAYER_WINDOW } else { LAYER_WIDGET };
+                connection.reply(message, &layer)?;
+            }
+            (COMPONENT_INTERFACE, "GetMDIZOrder", Object::Node(_)) => {
+                connection.reply(message, &0i16)?;
+            }
+            (COMPONENT_INTERFACE, "GetAlpha", Object::Node(_)) => {
+                connection.reply(message, &1f64)?;
+            }
+            (COMPONENT_INTERFACE, "GrabFocus", Object::Node(_)) => {
+                connection.reply(message, &false)?;
+            }
+            (TEXT_INTERFACE, "GetText", Object::Node(id)) => {
+                let (start, end): (i32, i32) = message.body()?;
+                let text = self.nodes[&id].text().unwrap_or_default();
+                // An end offset of -1 means the end of the text
+                let end = if end < 0 { usize::MAX } else { end as usize };
+                let start = start.max(0) as usize;
+                let text: String =
+                    text.chars().skip(start).take(end.saturating_sub(start)).collect();
+                connection.reply(message, &text)?;
+            }
+            _ => return reply_error(connection, message, "UnknownMethod"),
+        }
+        Ok(())
+    }
+
+    fn new_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Returns the ids of the nodes of the window, including the window itself
+    fn window_node_ids(&self, window: u32) -> HashSet<u32> {
+        let mut result = HashSet::new();
+        let mut stack = vec![window];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes.get(&id) {
+                stack.extend_from_slice(&node.children);
+            }
+            result.insert(id);
+        }
+        result
+    }
+
+    /// Creates the nodes for the accessible nodes and their descendants. Returns their ids.
+    fn add_nodes(
+        &mut self,
+        window: u32,
+        parent: u32,
+        nodes: &[AccessibleNode],
+        scale_factor: f32,
+        new_nodes: &mut HashMap<u32, Node>,
+    ) -> Vec<u32> {
+        let mut ids = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            let key = (window, node.item_key());
+            let id = match self.item_ids.get(&key) {
+                Some(id) => *id,
+                None => {
+                    let id = self.new_id();
+                    self.item_ids.insert(key, id);
+                    id
+                }
+            };
+            let mut new_node = Node::new(parent, node, scale_factor);
+            new_node.children = self.add_nodes(window, id, &node.children, scale_factor, new_nodes);
+            new_nodes.insert(id, new_node);
+            ids.push(id);
+        }
+        ids
+    }
+
+    /// Replaces the nodes of the window, and returns the changes to report
+    fn update_window(
+        &mut self,
+        window_id: winit::window::WindowId,
+        mut window_node: Node,
+        nodes: &[AccessibleNode],
+        scale_factor: f32,
+    ) -> Vec<Event> {
+        let mut events = Vec::new();
+        let window = match self.window_ids.get(&window_id) {
+            Some(window) => *window,
+            None => {
+                let window = self.new_id();
+                self.window_ids.insert(window_id, window);
+                events.push(Event::ChildrenChanged {
+                    parent: None,
+                    added: true,
+                    index: self.windows.len(),
+                    child: window,
+                });
+                self.windows.push(window);
+                window
+            }
+        };
+
+        let mut new_nodes = HashMap::new();
+        window_node.children = self.add_nodes(window, window, nodes, scale_factor, &mut new_nodes);
+        new_nodes.insert(window, window_node);
+
+        for (id, new_node) in &new_nodes {
+            let old_node = match self.nodes.get(id) {
+                Some(old_node) if old_node != new_node => old_node,
+                _ => continue,
+            };
+            for (property, old_value, new_value) in [
+                ("accessible-name", &old_node.name, &new_node.name),
+                ("accessible-description", &old_node.description, &new_node.description),
+                ("accessible-value", &old_node.value, &new_node.value),
+            ] {
+                if old_value != new_value {
+                    events.push(Event::PropertyChange {
+                        id: *id,
+                        property,
+                        value: new_value.clone(),
+                    });
+                }
+            }
+            if old_node.checked != new_node.checked {
+                events.push(Event::StateChange {
+                    id: *id,
+                    state: "checked",
+                    enabled: new_node.checked,
+                });
+            }
+            for (index, child) in old_node.children.iter().enumerate() {
+                if !new_node.children.contains(child) {
+                    events.push(Event::ChildrenChanged {
+                        parent: Some(*id),
+                        added: false,
+                        index,
+                        child: *child,
+                    });
+                }
+            }
+            for (index, child) in new_node.children.iter().enumerate() {
+                if !old_node.children.contains(child) {
+                    events.push(Event::ChildrenChanged {
+                        parent: Some(*id),
+                        added: true,
+                        index,
+                        child: *child,
+                    });
+                }
+            }
+        }
+
+        let removed: HashSet<u32> = self
+            .window_node_ids(window)
+            .into_iter()
+            .filter(|id| !new_nodes.contains_key(id))
+            .collect();
+        self.nodes.retain(|id, _| !removed.contains(id));
+        self.item_ids.retain(|_, id| !removed.contains(id));
+        self.nodes.extend(new_nodes);
+        events
+    }
+
+    /// Sets the position of the window on the screen, used for the extents in screen coordinates
+    fn set_window_position(&mut self, window_id: winit::window::WindowId, position: (i32, i32)) {
+        if let Some(window) = self.window_ids.get(&window_id) {
+            self.window_positions.insert(*window, position);
+        }
+    }
+
+    fn remove_window(&mut self, window_id: winit::window::WindowId) -> Vec<Event> {
+        let window = match self.window_ids.remove(&window_id) {
+            Some(window) => window,
+            None => return Vec::new(),
+        };
+        self.window_positions.remove(&window);
+        let removed = self.window_node_ids(window);
+        self.nodes.retain(|id, _| !removed.contains(id));
+        self.item_ids.retain(|(w, _), _| *w != window);
+        let index = self.windows.iter().position(|w| *w == window).unwrap_or_default();
+        self.windows.retain(|w| *w != window);
+        vec![Event::ChildrenChanged { parent: None, added: false, index, child: window }]
+    }
+}
+
+fn reply_error(connection: &Connection, message: &Message, error: &str) -> zbus::Result<()> {
+    connection.reply_error(
+        message,
+        format!("org.freedesktop.DBus.Error.{}", error).as_str(),
+        &format!("{} on {:?}", error, message.path()),
+    )?;
+    Ok(())
+}
+
+struct Bridge {
+    tree: Arc<Mutex<Tree>>,
+    /// Sends the events to the thread that emits them on the bus, so that the event loop never
+    /// waits for D-Bus
+    events: Mutex<Sender<Vec<Event>>>,
+}
+
+impl Bridge {
+    /// Connects to the accessibility bus and registers the application. Returns None if the
+    /// accessibility of the desktop is not enabled.
+    fn connect() -> zbus::Result<Option<Self>> {
+        let session = Connection::session()?;
+        let enabled: OwnedValue = session
+            .call_method(
+                Some("org.a11y.Bus"),
+                "/org/a11y/bus",
+                Some(PROPERTIES_INTERFACE),
+                "Get",
+                &("org.a11y.Status", "IsEnabled"),
+            )?
+            .body()?;
+        if !matches!(*enabled, Value::Bool(true)) {
+            return Ok(None);
+        }
+
+        let address: String = session
+            .call_method(
+                Some("org.a11y.Bus"),
+                "/org/a11y/bus",
+                Some("org.a11y.Bus"),
+                "GetAddress",
+                &(),
+            )?
+            .body()?;
+        let connection = ConnectionBuilder::address(address.as_str())?.build()?;
+
+        let bus_name = connection.unique_name().map(|name| name.to_string()).unwrap_or_default();
+        let application_name = std::env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        let tree = Arc::new(Mutex::new(Tree {
+            bus_name: bus_name.clone(),
+            application_name,
+            ..Default::default()
+        }));
+
+        // The registry queries the application while it is being embedded, so the requests must
+        // already be answered.
+        let messages = MessageIterator::from(&connection);
+        std::thread::spawn({
+            let connection = connection.clone();
+            let tree = tree.clone();
+            move || {
+                for message in messages.flatten() {
+                    if message.message_type() != MessageType::MethodCall {
+                        continue;
+                    }
+                    let result = tree.lock().unwrap().handle_method_call(&connection, &message);
+                    if let Err(err) = result {
+                        let _ = connection.reply_error(
+                            &message,
+                            "org.freedesktop.DBus.Error.Failed",
+                            &err.to_string(),
+                        );
+                    }
+                }
+            }
+        });
+
+        let root = tree.lock().unwrap().object_ref(Object::Application);
+        let desktop: ObjectRef = connection
+            .call_method(
+                Some("org.a11y.atspi.Registry"),
+                ROOT_PATH,
+                Some("org.a11y.atspi.Socket"),
+                "Embed",
+                &(root,),
+            )?
+            .body()?;
+        tree.lock().unwrap().desktop = Some(desktop);
+
+        let (sender, receiver) = std::sync::mpsc::channel::<Vec<Event>>();
+        std::thread::spawn(move || {
+            for event in receiver.into_iter().flatten() {
+                emit_event(&connection, &bus_name, event);
+            }
+        });
+
+        Ok(Some(Self { tree, events: Mutex::new(sender) }))
+    }
+
+    fn emit(&self, events: Vec<Event>) {
+        if !events.is_empty() {
+            // The thread only stops with the process
+            let _ = self.events.lock().unwrap().send(events);
+        }
+    }
+}
+
+/// Sends the signal of an event on the accessibility bus
+fn emit_event(connection: &Connection, bus_name: &str, event: Event) {
+    let no_properties = HashMap::<&str, Value>::new();
+    let path = |object| object_ref(bus_name, object).1;
+    let result = match event {
+        Event::PropertyChange { id, property, value } => connection.emit_signal(
+            None::<&str>,
+            path(Object::Node(id)).as_str(),
+            EVENT_INTERFACE,
+            "PropertyChange",
+            &(property, 0i32, 0i32, Value::from(value), no_properties),
+        ),
+        Event::StateChange { id, state, enabled } => connection.emit_signal(
+            None::<&str>,
+            path(Object::Node(id)).as_str(),
+            EVENT_INTERFACE,
+            "StateChanged",
+            &(state, enabled as i32, 0i32, Value::from(0i32), no_properties),
+        ),
+        Event::ChildrenChanged { parent, added, index, child } => connection.emit_signal(
+            None::<&str>,
+            path(parent.map_or(Object::Application, Object::Node)).as_str(),
+            EVENT_INTERFACE,
+            "ChildrenChanged",
+            &(
+                if added { "add" } else { "remove" },
+                index as i32,
+                0i32,
+                ref_value(&object_ref(bus_name, Object::Node(child))),
+                no_properties,
+            ),
+        ),
+    };
+    // The assistive technologies will query the tree again if they missed an event
+    let _ = result;
+}
+
+static BRIDGE: once_cell::sync::OnceCell<Option<Bridge>> = once_cell::sync::OnceCell::new();
+
+/// Returns the bridge, or None if the application is not connected to the accessibility bus (yet)
+fn bridge() -> Option<&'static Bridge> {
+    BRIDGE.get().and_then(Option::as_ref)
+}
+
+/// Connects to the accessibility bus from a separate thread the first time it is called, as the
+/// D-Bus calls would block the event loop. The windows are updated once it is connected.
+fn start_connecting() {
+    static START: std::sync::Once = std::sync::Once::new();
+    START.call_once(|| {
+        std::thread::spawn(|| {
+            let bridge = Bridge::connect().ok().flatten();
+            let connected = bridge.is_some();
+            BRIDGE.set(bridge).ok();
+            if connected {
+                request_update();
+            }
+        });
+    });
+}
+
+/// Asks the event loop to publish the accessibility trees that changed
+fn request_update() {
+    crate::event_loop::GLOBAL_PROXY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .send_event(crate::event_loop::CustomEvent::UpdateAccessibility);
+}
+
+struct TreeChangeHandler;
+
+impl PropertyChangeHandler for TreeChangeHandler {
+    fn notify(&self) {
+        request_update();
+    }
+}
+
+/// The accessibility tree of a mapped window, as published to AT-SPI
+pub(crate) struct WindowAccessibility {
+    window_id: winit::window::WindowId,
+    /// Tracks the properties read when building the accessibility tree. It is separate from the
+    /// redraw tracker, so that changing an accessible property doesn't repaint the window.
+    tracker: Pin<Box<PropertyTracker<TreeChangeHandler>>>,
+}
+
+impl WindowAccessibility {
+    pub(crate) fn new(window_id: winit::window::WindowId) -> Self {
+        start_connecting();
+        // The tracker starts dirty, so the tree is published on the next update
+        if bridge().is_some() {
+            request_update();
+        }
+        Self {
+            window_id,
+            tracker: Box::pin(PropertyTracker::new_with_change_handler(TreeChangeHandler)),
+        }
+    }
+
+    /// Publishes the accessibility tree of the window if it changed since the last update
+    pub(crate) fn update(
+        &self,
+        window: &winit::window::Window,
+        runtime_window: &corelib::window::Window,
+    ) {
+        let bridge = match bridge() {
+            Some(bridge) => bridge,
+            // The tracker stays dirty, the tree is published once connected
+            None => return,
+        };
+        if !self.tracker.is_dirty() {
+            return;
+        }
+
+        let component_rc = runtime_window.component();
+        let (title, nodes, scale_factor) =
+            self.tracker.as_ref().evaluate_as_dependency_root(|| {
+                let component = corelib::component::ComponentRc::borrow_pin(&component_rc);
+                let title = ItemRef::downcast_pin::<corelib::items::WindowItem>(
+                    component.as_ref().get_item_ref(0),
+                )
+                .map_or(String::new(), |window_item| window_item.title().to_string());
+                (title, accessibility_tree(&component_rc), runtime_window.scale_factor())
+            });
+        let size = window.inner_size();
+        let window_node = Node {
+            parent: None,
+            children: Vec::new(),
+            role: ROLE_FRAME,
+            name: title,
+            description: String::new(),
+            value: String::new(),
+            checked: false,
+            extents: (0, 0, size.width as i32, size.height as i32),
+        };
+
+        let position = window_position(window);
+        let events = {
+            let mut tree = bridge.tree.lock().unwrap();
+            let events = tree.update_window(self.window_id, window_node, &nodes, scale_factor);
+            tree.set_window_position(self.window_id, position);
+            events
+        };
+        bridge.emit(events);
+    }
+
+    /// Updates the position of the window on the screen, after it moved
+    pub(crate) fn update_position(&self, window: &winit::window::Window) {
+        if let Some(bridge) = bridge() {
+            let position = window_position(window);
+            bridge.tree.lock().unwrap().set_window_position(self.window_id, position);
+        }
+    }
+}
+
+fn window_position(window: &winit::window::Window) -> (i32, i32) {
+    window.inner_position().map_or((0, 0), |position| (position.x, position.y))
+}
+
+impl Drop for WindowAccessibility {
+    /// Removes the window from the accessibility tree
+    fn drop(&mut self) {
+        if let Some(bridge) = bridge() {
+            let events = bridge.tree.lock().unwrap().remove_window(self.window_id);
+            bridge.emit(events);
+        }
+    }
+}
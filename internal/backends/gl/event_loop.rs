@@ -13,7 +13,9 @@
 use i_slint_core as corelib;
 
 use corelib::graphics::Point;
-use corelib::input::{KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
+use corelib::input::{
+    DropEvent, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent, TouchPhase,
+};
 use corelib::SharedString;
 use corelib::{window::*, Color};
 use std::cell::{Cell, RefCell};
@@ -36,6 +38,10 @@ fn set_constraints(
     );
     fn set_background_color(&self, color: Color);
     fn set_icon(&self, icon: corelib::graphics::Image);
+    /// Publishes the accessibility tree of the window, if it changed since the last update
+    fn update_accessibility(&self) {}
+    /// Updates the position of the window in the accessibility tree, after the window moved
+    fn update_accessibility_position(&self) {}
 
     fn apply_constraints(
         &self,
@@ -301,6 +307,10 @@ pub enum CustomEvent {
     #[cfg(target_arch = "wasm32")]
     RedrawAllWindows,
     UpdateWindowProperties(winit::window::WindowId),
+    /// Publish the accessibility tree of the windows whose accessible items changed
+    UpdateAccessibility,
+    /// Run the handlers of the change trackers whose value changed
+    RunChangeHandlers,
     UserEvent(Box<dyn FnOnce() + Send>),
     Exit,
 }
@@ -311,6 +321,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             #[cfg(target_arch = "wasm32")]
             Self::RedrawAllWindows => write!(f, "RedrawAllWindows"),
             Self::UpdateWindowProperties(e) => write!(f, "UpdateWindowProperties({:?})", e),
+            Self::UpdateAccessibility => write!(f, "UpdateAccessibility"),
+            Self::RunChangeHandlers => write!(f, "RunChangeHandlers"),
             Self::UserEvent(_) => write!(f, "UserEvent"),
             Self::Exit => write!(f, "Exit"),
         }
@@ -343,6 +355,31 @@ pub fn winit_key_to_string(virtual_keycode: winit::event::VirtualKeyCode) -> Opt
     i_slint_common::for_each_special_keys!(winit_key_to_string_fn);
 }
 
+/// The files dragged over a window from another application
+#[derive(Default)]
+struct FileDrop {
+    /// The files reported by `WindowEvent::HoveredFile` since the drag entered the window
+    hovered: Vec<std::path::PathBuf>,
+    /// The files reported by `WindowEvent::DroppedFile` so far
+    dropped: Vec<std::path::PathBuf>,
+}
+
+/// Returns the `file://` URL of the path, with the characters that are not allowed in URLs
+/// percent-encoded.
+fn file_url(path: &std::path::Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from(if path.starts_with('/') { "file://" } else { "file:///" });
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    url
+}
+
 fn process_window_event(
     window: Rc<dyn WinitWindow>,
     event: WindowEvent,
@@ -350,6 +387,7 @@ fn process_window_event(
     control_flow: &mut winit::event_loop::ControlFlow,
     cursor_pos: &mut Point,
     pressed: &mut bool,
+    file_drop: &mut FileDrop,
 ) {
     fn key_event(
         event_type: KeyEventType,
@@ -368,6 +406,14 @@ fn key_event(
         event
     }
 
+    // Files from the windowing system are dropped as a `text/uri-list` with the URLs of the
+    // files, separated by line breaks.
+    // winit doesn't report the position of the drag, so use the last known cursor position.
+    fn file_drop_event(paths: &[std::path::PathBuf], position: Point) -> DropEvent {
+        let urls: Vec<String> = paths.iter().map(|path| file_url(path)).collect();
+        DropEvent { mime_type: "text/uri-list".into(), data: urls.join("\r\n").into(), position }
+    }
+
     let runtime_window = window.runtime_window();
     match event {
         WindowEvent::Resized(size) => {
@@ -443,6 +489,7 @@ fn key_event(
             let modifiers =
                 KeyboardModifiers { shift: state.shift(), alt: state.alt(), control, meta };
             window.current_keyboard_modifiers().set(modifiers);
+            runtime_window.set_current_keyboard_modifiers(modifiers);
         }
         WindowEvent::CursorMoved { position, .. } => {
             corelib::animations::update_animations();
@@ -496,18 +543,47 @@ fn key_event(
             corelib::animations::update_animations();
             let location = touch.location.to_logical(runtime_window.scale_factor() as f64);
             let pos = euclid::point2(location.x, location.y);
-            let ev = match touch.phase {
+            let phase = match touch.phase {
                 winit::event::TouchPhase::Started => {
                     *pressed = true;
-                    MouseEvent::MousePressed { pos, button: PointerEventButton::left }
+                    TouchPhase::Pressed
                 }
                 winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
                     *pressed = false;
-                    MouseEvent::MouseReleased { pos, button: PointerEventButton::left }
+                    TouchPhase::Released
                 }
-                winit::event::TouchPhase::Moved => MouseEvent::MouseMoved { pos },
+                winit::event::TouchPhase::Moved => TouchPhase::Moved,
             };
-            runtime_window.process_mouse_input(ev);
+            runtime_window.process_mouse_input(MouseEvent::Touch { pos, id: touch.id, phase });
+        }
+        // winit reports one event per file: all the HoveredFile events come first, then as many
+        // DroppedFile events. The files are collected so that they are dropped at once.
+        WindowEvent::HoveredFile(path) => {
+            corelib::animations::update_animations();
+            if !file_drop.dropped.is_empty() {
+                // A previous drop was not completed
+                file_drop.hovered.clear();
+                file_drop.dropped.clear();
+            }
+            file_drop.hovered.push(path);
+            runtime_window.process_drag_move(file_drop_event(&file_drop.hovered, *cursor_pos));
+        }
+        WindowEvent::HoveredFileCancelled => {
+            file_drop.hovered.clear();
+            file_drop.dropped.clear();
+            runtime_window.cancel_drag();
+        }
+        WindowEvent::DroppedFile(path) => {
+            corelib::animations::update_animations();
+            file_drop.dropped.push(path);
+            if file_drop.dropped.len() >= file_drop.hovered.len() {
+                runtime_window.process_drop(file_drop_event(&file_drop.dropped, *cursor_pos));
+                file_drop.hovered.clear();
+                file_drop.dropped.clear();
+            }
+        }
+        WindowEvent::Moved(_) => {
+            window.update_accessibility_position();
         }
         WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size: size } => {
             if std::env::var("SLINT_SCALE_FACTOR").is_err() {
@@ -547,6 +623,7 @@ pub fn run(quit_behavior: i_slint_core::backend::EventLoopQuitBehavior) {
     // last seen cursor position, (physical coordinate)
     let mut cursor_pos = Point::default();
     let mut pressed = false;
+    let mut file_drop = FileDrop::default();
     let mut run_fn = move |event: Event<CustomEvent>,
                            event_loop_target: &EventLoopWindowTarget<CustomEvent>,
                            control_flow: &mut ControlFlow| {
@@ -565,6 +642,7 @@ pub fn run(quit_behavior: i_slint_core::backend::EventLoopQuitBehavior) {
                             control_flow,
                             &mut cursor_pos,
                             &mut pressed,
+                            &mut file_drop,
                         );
                     };
                 }
@@ -582,6 +660,18 @@ pub fn run(quit_behavior: i_slint_core::backend::EventLoopQuitBehavior) {
                     }
                 }
 
+                winit::event::Event::UserEvent(CustomEvent::UpdateAccessibility) => {
+                    let all_windows_weak = ALL_WINDOWS
+                        .with(|windows| windows.borrow().values().cloned().collect::<Vec<_>>());
+                    for window in all_windows_weak.iter().filter_map(Weak::upgrade) {
+                        window.update_accessibility();
+                    }
+                }
+
+                winit::event::Event::UserEvent(CustomEvent::RunChangeHandlers) => {
+                    corelib::properties::ChangeTracker::run_change_handlers();
+                }
+
                 winit::event::Event::UserEvent(CustomEvent::Exit) => {
                     *control_flow = winit::event_loop::ControlFlow::Exit;
                 }
@@ -238,6 +238,19 @@ fn draw(self: Rc<Self>) {
             window.opengl_context.swap_buffers();
             window.opengl_context.make_not_current();
         });
+
+        // winit doesn't report the pre-edit text of input methods (the composition is shown by
+        // the platform), but it lets us place the candidate window next to the text cursor.
+        if let Some(cursor_rect) = runtime_window.text_input_cursor_rect() {
+            if let Some(window) = self.borrow_mapped_window() {
+                let position =
+                    (cursor_rect.origin + euclid::vec2(0., cursor_rect.height())) * scale_factor;
+                window
+                    .opengl_context
+                    .window()
+                    .set_ime_position(winit::dpi::PhysicalPosition::new(position.x, position.y));
+            }
+        }
     }
 
     fn with_window_handle(&self, callback: &mut dyn FnMut(&winit::window::Window)) {
@@ -278,6 +291,18 @@ fn set_icon(&self, icon: corelib::graphics::Image) {
             }
         };
     }
+
+    fn update_accessibility(&self) {
+        if let Some(window) = self.borrow_mapped_window() {
+            window.accessibility.update(&window.opengl_context.window(), &self.runtime_window());
+        }
+    }
+
+    fn update_accessibility_position(&self) {
+        if let Some(window) = self.borrow_mapped_window() {
+            window.accessibility.update_position(&window.opengl_context.window());
+        }
+    }
 }
 
 impl PlatformWindow for GLWindow {
@@ -503,6 +528,7 @@ fn show(self: Rc<Self>) {
             opengl_context,
             clear_color: RgbaColor { red: 255_u8, green: 255, blue: 255, alpha: 255 }.into(),
             constraints: Default::default(),
+            accessibility: crate::accessibility::WindowAccessibility::new(id),
         }));
 
         crate::event_loop::register_window(id, self);
@@ -601,6 +627,7 @@ fn text_input_byte_offset_for_position(
         let text_context =
             crate::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
         let font_height = text_context.measure_font(paint).unwrap().height();
+        let mut found_line = false;
         crate::fonts::layout_text_lines(
             text.as_str(),
             &font,
@@ -611,17 +638,21 @@ fn text_input_byte_offset_for_position(
             text_input.single_line(),
             paint,
             |line_text, line_pos, start, metrics| {
-                if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
-                    let mut current_x = 0.;
-                    for glyph in &metrics.glyphs {
-                        if line_pos.x + current_x + glyph.advance_x / 2. >= pos.x {
-                            result = start + glyph.byte_index;
-                            return;
-                        }
-                        current_x += glyph.advance_x;
+                // Positions above the first line or below the last line are in that line
+                if found_line {
+                    return;
+                }
+                found_line = pos.y < line_pos.y + font_height;
+                let mut current_x = 0.;
+                for glyph in &metrics.glyphs {
+                    if line_pos.x + current_x + glyph.advance_x / 2. >= pos.x {
+                        result = start + glyph.byte_index;
+                        return;
                     }
-                    result = start + line_text.trim_end().len();
+                    current_x += glyph.advance_x;
                 }
+                // Positions past the end of a line are before its line break
+                result = start + line_text.strip_suffix('\n').unwrap_or(line_text).len();
             },
         );
 
@@ -696,6 +727,7 @@ struct MappedWindow {
     opengl_context: crate::OpenGLContext,
     clear_color: Color,
     constraints: Cell<(corelib::layout::LayoutInfo, corelib::layout::LayoutInfo)>,
+    accessibility: crate::accessibility::WindowAccessibility,
 }
 
 impl Drop for MappedWindow {
@@ -11,7 +11,8 @@
 use euclid::approxeq::ApproxEq;
 use event_loop::WinitWindow;
 use i_slint_core::graphics::{
-    Brush, Color, Image, ImageInner, IntRect, IntSize, Point, Rect, RenderingCache, Size,
+    Brush, Color, ConicGradientBrush, Image, ImageInner, IntRect, IntSize, Point, Rect,
+    RenderingCache, Size,
 };
 use i_slint_core::item_rendering::{CachedRenderingData, ItemRenderer};
 use i_slint_core::items::{FillRule, ImageFit, ImageRendering};
@@ -29,6 +30,37 @@
 
 mod fonts;
 
+cfg_if::cfg_if! {
+    if #[cfg(all(
+        feature = "accessibility",
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    ))] {
+        mod accessibility;
+    } else {
+        mod accessibility {
+            pub(crate) struct WindowAccessibility;
+            impl WindowAccessibility {
+                pub(crate) fn new(_: winit::window::WindowId) -> Self {
+                    Self
+                }
+                pub(crate) fn update(
+                    &self,
+                    _: &winit::window::Window,
+                    _: &i_slint_core::window::Window,
+                ) {
+                }
+                pub(crate) fn update_position(&self, _: &winit::window::Window) {}
+            }
+        }
+    }
+}
+
 type Canvas = femtovg::Canvas<femtovg::renderer::OpenGl>;
 type CanvasRc = Rc<RefCell<Canvas>>;
 
@@ -43,6 +75,9 @@ enum ItemGraphicsCacheEntry {
         _original_image: Rc<CachedImage>,
         colorized_image: Rc<CachedImage>,
     },
+    // femtovg has no conic gradient paint, so conic gradient brushes of an item are rendered into
+    // textures that are kept here until the brush or the size of the item changes.
+    ConicGradients(Rc<RefCell<Vec<ConicGradientTexture>>>),
 }
 
 impl ItemGraphicsCacheEntry {
@@ -50,6 +85,7 @@ fn as_image(&self) -> &Rc<CachedImage> {
         match self {
             ItemGraphicsCacheEntry::Image(image) => image,
             ItemGraphicsCacheEntry::ColorizedImage { colorized_image, .. } => colorized_image,
+            ItemGraphicsCacheEntry::ConicGradients(_) => unreachable!(),
         }
     }
     fn is_colorized_image(&self) -> bool {
@@ -59,6 +95,18 @@ fn is_colorized_image(&self) -> bool {
 
 type ItemGraphicsCache = RenderingCache<Option<ItemGraphicsCacheEntry>>;
 
+struct ConicGradientTexture {
+    gradient: ConicGradientBrush,
+    // Size of the texture in physical pixels
+    width: u32,
+    height: u32,
+    image: Rc<CachedImage>,
+}
+
+// An item uses at most two brushes (fill and stroke), so that's how many conic gradient
+// textures are kept per item.
+const MAX_CONIC_GRADIENTS_PER_ITEM: usize = 2;
+
 // Layers are stored in the renderers State and flushed to the screen (or current rendering target)
 // in restore_state() by filling the target_path.
 struct Layer {
@@ -75,10 +123,10 @@ struct State {
 
 pub struct GLItemRenderer {
     canvas: CanvasRc,
-    // Layers that were scheduled for rendering where we can't delete the femtovg::ImageId yet
-    // because that can only happen after calling `flush`. Otherwise femtovg ends up processing
-    // `set_render_target` commands with image ids that have been deleted.
-    layer_images_to_delete_after_flush: Vec<CachedImage>,
+    // Layers and gradient textures that were scheduled for rendering where we can't delete the
+    // femtovg::ImageId yet because that can only happen after calling `flush`. Otherwise femtovg
+    // ends up processing `set_render_target` commands with image ids that have been deleted.
+    layer_images_to_delete_after_flush: RefCell<Vec<CachedImage>>,
     graphics_window: Rc<GLWindow>,
     scale_factor: f32,
     /// track the state manually since femtovg don't have accessor for its state
@@ -129,7 +177,11 @@ fn draw_rectangle(&mut self, rect: std::pin::Pin<&i_slint_core::items::Rectangle
         }
         // TODO: cache path in item to avoid re-tesselation
         let mut path = rect_to_path(geometry);
-        let paint = match self.brush_to_paint(rect.background(), &mut path) {
+        let paint = match self.brush_to_paint(
+            rect.background(),
+            &mut path,
+            Some(&rect.cached_rendering_data),
+        ) {
             Some(paint) => paint,
             None => return,
         };
@@ -154,12 +206,15 @@ fn draw_border_rectangle(
 
         let mut path = rect_with_radius_to_path(geometry, rect.border_radius() * self.scale_factor);
 
-        let fill_paint = self.brush_to_paint(rect.background(), &mut path);
+        let fill_paint =
+            self.brush_to_paint(rect.background(), &mut path, Some(&rect.cached_rendering_data));
 
-        let border_paint = self.brush_to_paint(rect.border_color(), &mut path).map(|mut paint| {
-            paint.set_line_width(border_width);
-            paint
-        });
+        let border_paint = self
+            .brush_to_paint(rect.border_color(), &mut path, Some(&rect.cached_rendering_data))
+            .map(|mut paint| {
+                paint.set_line_width(border_width);
+                paint
+            });
 
         let mut canvas = self.canvas.borrow_mut();
         if let Some(paint) = fill_paint {
@@ -225,9 +280,11 @@ fn draw_text(&mut self, text: std::pin::Pin<&i_slint_core::items::Text>) {
             )
         });
 
-        let paint = match self
-            .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
-        {
+        let paint = match self.brush_to_paint(
+            text.color(),
+            &mut rect_to_path(item_rect(text, self.scale_factor)),
+            Some(&text.cached_rendering_data),
+        ) {
             Some(paint) => font.init_paint(text.letter_spacing() * self.scale_factor, paint),
             None => return,
         };
@@ -255,35 +312,39 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&i_slint_core::items::Te
             return;
         }
 
+        let visual_representation = text_input.visual_representation();
+        let text = visual_representation.text.as_str();
+
         let font = fonts::FONT_CACHE.with(|cache| {
             cache.borrow_mut().font(
                 text_input
                     .unresolved_font_request()
                     .merge(&self.graphics_window.default_font_properties()),
                 self.scale_factor,
-                &text_input.text(),
+                text,
             )
         });
 
         let paint = match self.brush_to_paint(
             text_input.color(),
             &mut rect_to_path(item_rect(text_input, self.scale_factor)),
+            Some(&text_input.cached_rendering_data),
         ) {
             Some(paint) => font.init_paint(text_input.letter_spacing() * self.scale_factor, paint),
             None => return,
         };
 
-        let (min_select, max_select) = text_input.selection_anchor_and_cursor();
-        let cursor_pos = text_input.cursor_position();
-        let cursor_visible = cursor_pos >= 0 && text_input.cursor_visible() && text_input.enabled();
+        let min_select = visual_representation.selection_range.start;
+        let max_select = visual_representation.selection_range.end;
+        let preedit = visual_representation.preedit_range;
+        let cursor_visible = visual_representation.cursor_position.is_some();
         let mut canvas = self.canvas.borrow_mut();
         let font_height = canvas.measure_font(paint).unwrap().height();
-        let text = text_input.text();
 
         let mut cursor_point: Option<Point> = None;
 
         let baseline_y = fonts::layout_text_lines(
-            text.as_str(),
+            text,
             &font,
             Size::new(width, height),
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
@@ -365,7 +426,30 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&i_slint_core::items::Te
                     // no selection on this line
                     canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
                 };
-                let cursor_pos = cursor_pos as usize;
+                // Underline the part of the text being composed by the input method
+                if !preedit.is_empty()
+                    && (range.contains(&preedit.start)
+                        || (preedit.start..preedit.end).contains(&start))
+                {
+                    let x_for_byte_offset = |byte_offset: usize| {
+                        metrics
+                            .glyphs
+                            .iter()
+                            .find(|glyph| glyph.byte_index + start >= byte_offset)
+                            .map_or_else(|| metrics.width(), |glyph| glyph.x - glyph.bearing_x)
+                    };
+                    let underline_start_x = x_for_byte_offset(preedit.start.max(start));
+                    let underline_end_x = x_for_byte_offset(preedit.end.min(range.end));
+                    let mut underline = femtovg::Path::new();
+                    underline.rect(
+                        pos.x + underline_start_x,
+                        pos.y + font_height - self.scale_factor,
+                        underline_end_x - underline_start_x,
+                        self.scale_factor,
+                    );
+                    canvas.fill_path(&mut underline, paint);
+                }
+                let cursor_pos = visual_representation.cursor_position.unwrap_or_default();
                 if cursor_visible
                     && (range.contains(&cursor_pos)
                         || (cursor_pos == range.end && cursor_pos == text.len()))
@@ -480,8 +564,9 @@ fn add_point(&mut self, p: Point) {
             }
         }
 
-        let fill_paint =
-            self.brush_to_paint(path.fill(), &mut femtovg_path).map(|mut fill_paint| {
+        let fill_paint = self
+            .brush_to_paint(path.fill(), &mut femtovg_path, Some(&path.cached_rendering_data))
+            .map(|mut fill_paint| {
                 fill_paint.set_fill_rule(match path.fill_rule() {
                     FillRule::nonzero => femtovg::FillRule::NonZero,
                     FillRule::evenodd => femtovg::FillRule::EvenOdd,
@@ -489,8 +574,9 @@ fn add_point(&mut self, p: Point) {
                 fill_paint
             });
 
-        let border_paint =
-            self.brush_to_paint(path.stroke(), &mut femtovg_path).map(|mut paint| {
+        let border_paint = self
+            .brush_to_paint(path.stroke(), &mut femtovg_path, Some(&path.cached_rendering_data))
+            .map(|mut paint| {
                 paint.set_line_width(path.stroke_width() * self.scale_factor);
                 paint
             });
@@ -578,7 +664,7 @@ fn draw_box_shadow(&mut self, box_shadow: std::pin::Pin<&i_slint_core::items::Bo
                             .borrow_mut()
                             .set_render_target(blurred_image.as_render_target());
 
-                        self.layer_images_to_delete_after_flush.push(shadow_image);
+                        self.layer_images_to_delete_after_flush.borrow_mut().push(shadow_image);
 
                         blurred_image
                     } else {
@@ -694,7 +780,7 @@ fn restore_state(&mut self) {
         {
             let paint = layer_to_restore.image.as_paint();
 
-            self.layer_images_to_delete_after_flush.push(layer_to_restore.image);
+            self.layer_images_to_delete_after_flush.borrow_mut().push(layer_to_restore.image);
 
             let mut canvas = self.canvas.borrow_mut();
 
@@ -735,7 +821,8 @@ fn draw_cached_pixmap(
         });
         let image_id = match cache_entry {
             Some(ItemGraphicsCacheEntry::Image(image)) => image.ensure_uploaded_to_gpu(self, None),
-            Some(ItemGraphicsCacheEntry::ColorizedImage { .. }) => unreachable!(),
+            Some(ItemGraphicsCacheEntry::ColorizedImage { .. })
+            | Some(ItemGraphicsCacheEntry::ConicGradients(_)) => unreachable!(),
             None => return,
         };
         let mut canvas = self.canvas.borrow_mut();
@@ -801,6 +888,17 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         *clip = Rect::new(origin, (end - origin).into());
     }
 
+    fn scale(&mut self, x: f32, y: f32) {
+        self.canvas.borrow_mut().scale(x, y);
+        let clip = &mut self.state.last_mut().unwrap().scissor;
+        *clip = if x == 0. || y == 0. {
+            Rect::default()
+        } else {
+            let unscale_point = |p: Point| Point::new(p.x / x, p.y / y);
+            Rect::from_points([unscale_point(clip.min()), unscale_point(clip.max())])
+        };
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let state = &mut self.state.last_mut().unwrap().global_alpha;
         *state *= opacity;
@@ -849,7 +947,7 @@ fn colorize_image(
 
         let mut image_rect = femtovg::Path::new();
         image_rect.rect(0., 0., image_size.width, image_size.height);
-        let brush_paint = self.brush_to_paint(colorize_brush, &mut image_rect).unwrap();
+        let brush_paint = self.brush_to_paint(colorize_brush, &mut image_rect, None).unwrap();
 
         self.canvas.borrow_mut().save_with(|canvas| {
             canvas.reset();
@@ -1038,24 +1136,34 @@ fn draw_image_impl(
         })
     }
 
-    fn brush_to_paint(&self, brush: Brush, path: &mut femtovg::Path) -> Option<femtovg::Paint> {
+    /// Returns the paint for filling the given path with the brush. Textures needed for the
+    /// brush are cached in `item_cache`, if provided.
+    fn brush_to_paint(
+        &self,
+        brush: Brush,
+        path: &mut femtovg::Path,
+        item_cache: Option<&CachedRenderingData>,
+    ) -> Option<femtovg::Paint> {
         if brush.is_transparent() {
             return None;
         }
+
+        // `canvas.path_bbox()` applies the current transform. However we're not interested in that, since
+        // we operate in item local coordinates with the `path` parameter as well as the resulting
+        // paint.
+        let path_bounds = |path: &mut femtovg::Path| {
+            let mut canvas = self.canvas.borrow_mut();
+            canvas.save();
+            canvas.reset_transform();
+            let bounding_box = canvas.path_bbox(path);
+            canvas.restore();
+            bounding_box
+        };
+
         Some(match brush {
             Brush::SolidColor(color) => femtovg::Paint::color(to_femtovg_color(&color)),
             Brush::LinearGradient(gradient) => {
-                // `canvas.path_bbox()` applies the current transform. However we're not interested in that, since
-                // we operate in item local coordinates with the `path` parameter as well as the resulting
-                // paint.
-                let path_bounds = {
-                    let mut canvas = self.canvas.borrow_mut();
-                    canvas.save();
-                    canvas.reset_transform();
-                    let bounding_box = canvas.path_bbox(path);
-                    canvas.restore();
-                    bounding_box
-                };
+                let path_bounds = path_bounds(path);
 
                 let path_width = path_bounds.maxx - path_bounds.minx;
                 let path_height = path_bounds.maxy - path_bounds.miny;
@@ -1074,10 +1182,132 @@ fn brush_to_paint(&self, brush: Brush, path: &mut femtovg::Path) -> Option<femto
                     .collect::<Vec<_>>();
                 femtovg::Paint::linear_gradient_stops(start.x, start.y, end.x, end.y, &stops)
             }
+            Brush::RadialGradient(gradient) => {
+                let path_bounds = path_bounds(path);
+
+                let path_width = path_bounds.maxx - path_bounds.minx;
+                let path_height = path_bounds.maxy - path_bounds.miny;
+
+                let stops = gradient
+                    .stops()
+                    .map(|stop| (stop.position, to_femtovg_color(&stop.color)))
+                    .collect::<Vec<_>>();
+                femtovg::Paint::radial_gradient_stops(
+                    path_bounds.minx + path_width / 2.,
+                    path_bounds.miny + path_height / 2.,
+                    0.,
+                    (path_width * path_width + path_height * path_height).sqrt() / 2.,
+                    &stops,
+                )
+            }
+            Brush::ConicGradient(gradient) => {
+                let path_bounds = path_bounds(path);
+                let width = path_bounds.maxx - path_bounds.minx;
+                let height = path_bounds.maxy - path_bounds.miny;
+                let image_id = self.conic_gradient_texture(gradient, width, height, item_cache)?;
+                femtovg::Paint::image(
+                    image_id,
+                    path_bounds.minx,
+                    path_bounds.miny,
+                    width,
+                    height,
+                    0.,
+                    1.,
+                )
+            }
             _ => return None,
         })
     }
 
+    /// Returns the id of a texture of `width` x `height` physical pixels filled with the conic
+    /// gradient. The texture is kept in the item cache and only re-rendered when the gradient
+    /// or the size changes.
+    fn conic_gradient_texture(
+        &self,
+        gradient: ConicGradientBrush,
+        width: f32,
+        height: f32,
+        item_cache: Option<&CachedRenderingData>,
+    ) -> Option<femtovg::ImageId> {
+        // The path coordinates are in physical pixels already, so the texture maps one texel
+        // to one pixel on the screen.
+        let texture_width = width.ceil().max(1.) as u32;
+        let texture_height = height.ceil().max(1.) as u32;
+
+        let create_texture = || {
+            let pixels = (0..texture_height)
+                .flat_map(|y| {
+                    let gradient = &gradient;
+                    (0..texture_width).map(move |x| {
+                        let color = gradient.color_at(
+                            x as f32 + 0.5 - texture_width as f32 / 2.,
+                            y as f32 + 0.5 - texture_height as f32 / 2.,
+                        );
+                        rgb::RGBA8::new(color.red(), color.green(), color.blue(), color.alpha())
+                    })
+                })
+                .collect::<Vec<_>>();
+            let image = imgref::Img::new(pixels, texture_width as usize, texture_height as usize);
+            let image_id = self
+                .canvas
+                .borrow_mut()
+                .create_image(image.as_ref(), femtovg::ImageFlags::empty())
+                .ok()?;
+            Some(CachedImage::new_on_gpu(&self.canvas, image_id))
+        };
+
+        let textures = item_cache.and_then(|item_cache| {
+            match item_cache.get_or_update(&self.graphics_window.graphics_cache, || {
+                // get the scale factor as a property, to ensure the cache is invalidated when the scale factor changes
+                self.window().scale_factor();
+                Some(ItemGraphicsCacheEntry::ConicGradients(Default::default()))
+            }) {
+                Some(ItemGraphicsCacheEntry::ConicGradients(textures)) => Some(textures),
+                _ => None,
+            }
+        });
+
+        let textures = match textures {
+            Some(textures) => textures,
+            None => {
+                // Without a cache, the texture can only be deleted after the next flush.
+                let image = create_texture()?;
+                let image_id = image.ensure_uploaded_to_gpu(self, None);
+                self.layer_images_to_delete_after_flush.borrow_mut().push(image);
+                return Some(image_id);
+            }
+        };
+        let mut textures = textures.borrow_mut();
+
+        if let Some(index) = textures.iter().position(|texture| {
+            texture.width == texture_width
+                && texture.height == texture_height
+                && texture.gradient == gradient
+        }) {
+            // Keep the most recently used texture at the end
+            let texture = textures.remove(index);
+            let image_id = texture.image.ensure_uploaded_to_gpu(self, None);
+            textures.push(texture);
+            return Some(image_id);
+        }
+
+        let image = Rc::new(create_texture()?);
+        if textures.len() >= MAX_CONIC_GRADIENTS_PER_ITEM {
+            let evicted = textures.remove(0);
+            if let Ok(image) = Rc::try_unwrap(evicted.image) {
+                self.layer_images_to_delete_after_flush.borrow_mut().push(image);
+            }
+        }
+        let image_id = image.ensure_uploaded_to_gpu(self, None);
+        textures.push(ConicGradientTexture {
+            gradient,
+            width: texture_width,
+            height: texture_height,
+            image,
+        });
+        Some(image_id)
+    }
+
     // Set the specified path for clipping. This is done by redirecting rendering into
     // an intermediate image and using that to fill the clip path on the next restore_state()
     // call. Therefore this can only be called once per save_state()!
@@ -1257,6 +1487,16 @@ fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
         }
     }
 
+    fn request_change_handlers_run(&'static self) {
+        let e = crate::event_loop::CustomEvent::RunChangeHandlers;
+        #[cfg(not(target_arch = "wasm32"))]
+        crate::event_loop::GLOBAL_PROXY.get_or_init(Default::default).lock().unwrap().send_event(e);
+        #[cfg(target_arch = "wasm32")]
+        crate::event_loop::GLOBAL_PROXY.with(|global_proxy| {
+            global_proxy.borrow_mut().get_or_insert_with(Default::default).send_event(e)
+        });
+    }
+
     fn image_size(&'static self, image: &Image) -> IntSize {
         IMAGE_CACHE.with(|image_cache| {
             image_cache
@@ -52,30 +52,66 @@ pub fn register_bitmap_font(font_data: &'static BitmapFont) {
     FONTS.with(|fonts| fonts.borrow_mut().push(font_data))
 }
 
-pub fn glyphs_for_text<'a>(
+/// Returns an iterator over the characters of the text, with their byte offset, their x position,
+/// their advance, and their glyph if the font has one.
+pub fn layout_chars<'a>(
     font: &'static BitmapFont,
     glyphs: &'static BitmapGlyphs,
     text: &'a str,
-) -> impl Iterator<Item = (f32, &'static BitmapGlyph)> + 'a {
+) -> impl Iterator<Item = (usize, f32, f32, Option<&'static BitmapGlyph>)> + 'a {
     let mut x: f32 = 0.;
-    text.chars().filter_map(move |char| {
-        if let Some(glyph_index) = font
+    text.char_indices().map(move |(byte_offset, char)| {
+        let glyph = font
             .character_map
             .binary_search_by_key(&char, |char_map_entry| char_map_entry.code_point)
             .ok()
             .map(|char_map_index| font.character_map[char_map_index].glyph_index)
-        {
-            let glyph = &glyphs.glyph_data[glyph_index as usize];
-            let glyph_x = x;
-            x += glyph.x_advance as f32;
-            Some((glyph_x, glyph))
-        } else {
-            x += glyphs.pixel_size as f32;
-            None
-        }
+            .map(|glyph_index| &glyphs.glyph_data[glyph_index as usize]);
+        let advance = glyph.map_or(glyphs.pixel_size as f32, |glyph| glyph.x_advance as f32);
+        let glyph_x = x;
+        x += advance;
+        (byte_offset, glyph_x, advance, glyph)
     })
 }
 
+pub fn glyphs_for_text<'a>(
+    font: &'static BitmapFont,
+    glyphs: &'static BitmapGlyphs,
+    text: &'a str,
+) -> impl Iterator<Item = (f32, &'static BitmapGlyph)> + 'a {
+    layout_chars(font, glyphs, text).filter_map(|(_, x, _, glyph)| Some((x, glyph?)))
+}
+
+/// Returns the x position of the caret placed before the character at the given byte offset
+pub fn x_for_byte_offset(
+    font: &'static BitmapFont,
+    glyphs: &'static BitmapGlyphs,
+    text: &str,
+    byte_offset: usize,
+) -> f32 {
+    layout_chars(font, glyphs, text)
+        .take_while(|(offset, ..)| *offset < byte_offset)
+        .last()
+        .map_or(0., |(_, x, advance, _)| x + advance)
+}
+
+/// Returns the byte offset of the caret position closest to the given x position
+pub fn byte_offset_for_x(
+    font: &'static BitmapFont,
+    glyphs: &'static BitmapGlyphs,
+    text: &str,
+    x: f32,
+) -> usize {
+    layout_chars(font, glyphs, text)
+        .find(|(_, glyph_x, advance, _)| x < glyph_x + advance / 2.)
+        .map_or(text.len(), |(offset, ..)| offset)
+}
+
+/// Returns the height of a line of text, from the ascent to the descent
+pub fn line_height(font: &'static BitmapFont, glyphs: &'static BitmapGlyphs) -> f32 {
+    (font.ascent - font.descent) * (glyphs.pixel_size as f32) / font.units_per_em
+}
+
 pub fn text_size(
     font_request: FontRequest,
     text: &str,
@@ -27,6 +27,7 @@
 use simulator::event_loop;
 
 mod fonts;
+mod rasterizer;
 mod renderer;
 
 pub trait Devices {
@@ -85,6 +86,7 @@ mod the_backend {
     use core::pin::Pin;
     use i_slint_core::component::ComponentRc;
     use i_slint_core::graphics::{Color, Point, Size};
+    use i_slint_core::item_rendering::{DirtyRegion, PartialRenderingCache};
     use i_slint_core::window::PlatformWindow;
     use i_slint_core::window::Window;
     use i_slint_core::ImageInner;
@@ -95,6 +97,16 @@ pub struct McuWindow {
         backend: &'static MCUBackend,
         self_weak: Weak<Window>,
         background_color: Cell<Color>,
+        /// Used to only redraw the parts of the window that changed
+        partial_rendering_cache: RefCell<PartialRenderingCache>,
+        /// The region to redraw in the next frame in addition to the items that changed: the
+        /// location of the removed items, or the whole window
+        initial_dirty_region: Cell<DirtyRegion>,
+    }
+
+    /// A dirty region that covers the whole window
+    fn whole_window() -> DirtyRegion {
+        DirtyRegion::new(Default::default(), Size::new(f32::MAX, f32::MAX))
     }
 
     impl PlatformWindow for McuWindow {
@@ -102,6 +114,7 @@ fn show(self: Rc<Self>) {
             self.self_weak.upgrade().unwrap().set_scale_factor(
                 option_env!("SLINT_SCALE_FACTOR").and_then(|x| x.parse().ok()).unwrap_or(1.),
             );
+            self.initial_dirty_region.set(whole_window());
             WINDOWS.with(|x| *x.borrow_mut() = Some(self))
         }
         fn hide(self: Rc<Self>) {
@@ -112,8 +125,14 @@ fn request_redraw(&self) {
         }
         fn free_graphics_resources<'a>(
             &self,
-            _items: &mut dyn Iterator<Item = Pin<i_slint_core::items::ItemRef<'a>>>,
+            items: &mut dyn Iterator<Item = Pin<i_slint_core::items::ItemRef<'a>>>,
         ) {
+            let mut cache = self.partial_rendering_cache.borrow_mut();
+            // The location of the removed items needs to be redrawn
+            let dirty_region = items
+                .filter_map(|item| item.cached_rendering_data_offset().release(&mut cache))
+                .fold(self.initial_dirty_region.get(), |region, rect| region.union(&rect));
+            self.initial_dirty_region.set(dirty_region);
         }
 
         fn show_popup(&self, _popup: &ComponentRc, _position: i_slint_core::graphics::Point) {
@@ -121,7 +140,11 @@ fn show_popup(&self, _popup: &ComponentRc, _position: i_slint_core::graphics::Po
         }
         fn request_window_properties_update(&self) {}
         fn apply_window_properties(&self, window_item: Pin<&i_slint_core::items::WindowItem>) {
-            self.background_color.set(window_item.background());
+            let background = window_item.background();
+            if background != self.background_color.get() {
+                self.initial_dirty_region.set(whole_window());
+            }
+            self.background_color.set(background);
         }
         fn apply_geometry_constraint(
             &self,
@@ -147,17 +170,34 @@ fn text_size(
 
         fn text_input_byte_offset_for_position(
             &self,
-            _text_input: Pin<&i_slint_core::items::TextInput>,
-            _pos: Point,
+            text_input: Pin<&i_slint_core::items::TextInput>,
+            pos: Point,
         ) -> usize {
-            0
+            let runtime_window = self.self_weak.upgrade().unwrap();
+            let (font, glyphs) = crate::fonts::match_font(
+                &text_input
+                    .unresolved_font_request()
+                    .merge(&runtime_window.default_font_properties()),
+                renderer::ScaleFactor(runtime_window.scale_factor()),
+            );
+            crate::fonts::byte_offset_for_x(font, glyphs, &text_input.text(), pos.x)
         }
         fn text_input_position_for_byte_offset(
             &self,
-            _text_input: Pin<&i_slint_core::items::TextInput>,
-            _byte_offset: usize,
+            text_input: Pin<&i_slint_core::items::TextInput>,
+            byte_offset: usize,
         ) -> Point {
-            Default::default()
+            let runtime_window = self.self_weak.upgrade().unwrap();
+            let (font, glyphs) = crate::fonts::match_font(
+                &text_input
+                    .unresolved_font_request()
+                    .merge(&runtime_window.default_font_properties()),
+                renderer::ScaleFactor(runtime_window.scale_factor()),
+            );
+            Point::new(
+                crate::fonts::x_for_byte_offset(font, glyphs, &text_input.text(), byte_offset),
+                0.,
+            )
         }
         fn as_any(&self) -> &dyn core::any::Any {
             self
@@ -168,6 +208,7 @@ enum McuEvent {
         Custom(Box<dyn FnOnce() + Send>),
         Quit,
         Repaint,
+        RunChangeHandlers,
     }
 
     #[derive(Default)]
@@ -217,7 +258,13 @@ fn draw(&self, window: Rc<McuWindow>) {
                 runtime_window.set_window_item_geometry(size.width as _, size.height as _);
                 let background =
                     crate::renderer::to_rgb888_color_discard_alpha(window.background_color.get());
-                crate::renderer::render_window_frame(runtime_window, background, &mut **devices);
+                crate::renderer::render_window_frame(
+                    runtime_window,
+                    background,
+                    &mut **devices,
+                    &window.partial_rendering_cache,
+                    window.initial_dirty_region.take(),
+                );
             });
         }
     }
@@ -229,6 +276,8 @@ fn create_window(&'static self) -> Rc<i_slint_core::window::Window> {
                     backend: self,
                     self_weak: window.clone(),
                     background_color: Color::from_rgb_u8(0, 0, 0).into(),
+                    partial_rendering_cache: Default::default(),
+                    initial_dirty_region: Default::default(),
                 })
             })
         }
@@ -244,6 +293,9 @@ fn run_event_loop(&'static self, behavior: i_slint_core::backend::EventLoopQuitB
                             self.draw(window)
                         }
                     }
+                    Some(McuEvent::RunChangeHandlers) => {
+                        i_slint_core::properties::ChangeTracker::run_change_handlers()
+                    }
                     None => {
                         // TODO: sleep();
                     }
@@ -296,6 +348,10 @@ fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
             self.with_inner(|inner| inner.post_event(McuEvent::Custom(event)));
         }
 
+        fn request_change_handlers_run(&'static self) {
+            self.with_inner(|inner| inner.post_event(McuEvent::RunChangeHandlers));
+        }
+
         fn image_size(
             &'static self,
             image: &i_slint_core::graphics::Image,
@@ -0,0 +1,132 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Rasterization of shapes into coverage maps (one alpha byte per pixel), used for the items
+that cannot be drawn as plain rectangles or textures.
+*/
+
+use alloc::vec::Vec;
+use i_slint_core::graphics::{IntRect, Point as PointF};
+
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
+
+/// Number of scanlines sampled per row of pixels, for the anti-aliasing in the vertical direction
+const SUBSAMPLES: usize = 4;
+
+/// A line segment of the outline of a polygon
+pub type Edge = (PointF, PointF);
+
+/// Computes the coverage of the pixels of `area` by the polygons made of the given edges.
+/// With `even_odd`, the even-odd fill rule is used, otherwise the non-zero rule.
+pub fn fill_polygons(edges: &[Edge], area: IntRect, even_odd: bool) -> Vec<u8> {
+    let width = area.width().max(0) as usize;
+    let height = area.height().max(0) as usize;
+    let mut coverage = Vec::with_capacity(width * height);
+    let mut row = alloc::vec![0f32; width];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    for line in 0..height {
+        row.fill(0.);
+        for sample in 0..SUBSAMPLES {
+            let y = (area.min_y() + line as i32) as f32 + (sample as f32 + 0.5) / SUBSAMPLES as f32;
+            crossings.clear();
+            crossings.extend(edges.iter().filter_map(|(from, to)| {
+                let winding = if from.y <= y && y < to.y {
+                    1
+                } else if to.y <= y && y < from.y {
+                    -1
+                } else {
+                    return None;
+                };
+                let x = from.x + (y - from.y) * (to.x - from.x) / (to.y - from.y);
+                Some((x - area.min_x() as f32, winding))
+            }));
+            crossings.sort_unstable_by(|a, b| {
+                a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal)
+            });
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                let inside = if even_odd { winding % 2 != 0 } else { winding != 0 };
+                if inside {
+                    add_span(&mut row, pair[0].0, pair[1].0, 1. / SUBSAMPLES as f32);
+                }
+            }
+        }
+        coverage.extend(row.iter().map(|c| (c.min(1.) * 255.) as u8));
+    }
+    coverage
+}
+
+/// Adds the coverage of the span from `begin` to `end` to the pixels of the row
+fn add_span(row: &mut [f32], begin: f32, end: f32, weight: f32) {
+    let begin = begin.max(0.);
+    let end = end.min(row.len() as f32);
+    if begin >= end {
+        return;
+    }
+    let first = begin as usize;
+    let last = end as usize;
+    if first == last {
+        row[first] += (end - begin) * weight;
+        return;
+    }
+    row[first] += (first as f32 + 1. - begin) * weight;
+    for pixel in &mut row[first + 1..last] {
+        *pixel += weight;
+    }
+    if last < row.len() {
+        row[last] += (end - last as f32) * weight;
+    }
+}
+
+/// Returns the edges of the polygons covering the stroke of the given segments. Each segment
+/// becomes a rectangle extended by half the width on both ends, which approximates the joins.
+/// The rectangles all have the same orientation so they can be filled with the non-zero rule.
+pub fn stroke_edges(segments: &[Edge], width: f32) -> Vec<Edge> {
+    let half_width = width / 2.;
+    let mut edges = Vec::with_capacity(segments.len() * 4);
+    for (from, to) in segments {
+        let direction = (*to - *from).try_normalize().unwrap_or_default() * half_width;
+        let normal = euclid::vec2(-direction.y, direction.x);
+        let (from, to) = (*from - direction, *to + direction);
+        let corners = [from + normal, to + normal, to - normal, from - normal];
+        for i in 0..4 {
+            edges.push((corners[i], corners[(i + 1) % 4]));
+        }
+    }
+    edges
+}
+
+/// Computes the coverage of a shadow of a rounded rectangle of the given size, blurred by `blur`
+/// pixels. The shadow map has a margin of `blur` pixels around the rectangle. The blur is
+/// approximated by a smooth ramp of the alpha over the distance to the edge of the rectangle.
+pub fn box_shadow(width: f32, height: f32, radius: f32, blur: f32) -> (Vec<u8>, u16, u16) {
+    let map_width = (width + 2. * blur).ceil().max(0.) as u16;
+    let map_height = (height + 2. * blur).ceil().max(0.) as u16;
+    let radius = radius.min(width / 2.).min(height / 2.).max(0.);
+    let center = euclid::point2(blur + width / 2., blur + height / 2.);
+    let inner_half_size = euclid::vec2(width / 2. - radius, height / 2. - radius);
+
+    let mut coverage = Vec::with_capacity(map_width as usize * map_height as usize);
+    for y in 0..map_height {
+        for x in 0..map_width {
+            let point: PointF = euclid::point2(x as f32 + 0.5, y as f32 + 0.5);
+            let q = (point - center).abs() - inner_half_size;
+            // signed distance from the edge of the rounded rectangle, negative inside
+            let distance = euclid::default::Vector2D::new(q.x.max(0.), q.y.max(0.)).length()
+                + q.x.max(q.y).min(0.)
+                - radius;
+            let t = if blur > 0. {
+                (0.5 - distance / (2. * blur)).max(0.).min(1.)
+            } else {
+                (0.5 - distance).max(0.).min(1.)
+            };
+            // smoothstep, to approximate the gaussian profile
+            coverage.push((t * t * (3. - 2. * t) * 255.) as u8);
+        }
+    }
+    (coverage, map_width, map_height)
+}
@@ -1,28 +1,52 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
+use alloc::borrow::Cow;
 use alloc::collections::VecDeque;
 use alloc::rc::Rc;
 use alloc::{vec, vec::Vec};
+use core::cell::RefCell;
 use core::pin::Pin;
 
 use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::prelude::*;
 use i_slint_core::graphics::{
-    FontRequest, IntRect, PixelFormat, Point as PointF, Rect as RectF, Size as SizeF,
+    BitmapFont, BitmapGlyphs, FontRequest, IntRect, PixelFormat, Point as PointF, Rect as RectF,
+    Size as SizeF,
+};
+use i_slint_core::item_rendering::{
+    DirtyRegion, ItemRenderer, PartialRenderer, PartialRenderingCache,
 };
 use i_slint_core::items::Item;
-use i_slint_core::{Color, ImageInner};
+use i_slint_core::{Brush, Color, ImageInner};
+
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
+use crate::rasterizer::Edge;
 use crate::Devices;
 
+/// Renders the parts of the window that changed since the last frame, as well as the
+/// `initial_dirty_region`, to the devices. `cache` must be kept between the frames.
 pub fn render_window_frame(
     runtime_window: Rc<i_slint_core::window::Window>,
     background: Rgb888,
     devices: &mut dyn Devices,
+    cache: &RefCell<PartialRenderingCache>,
+    initial_dirty_region: DirtyRegion,
 ) {
     let size = devices.screen_size();
-    let mut scene = prepare_scene(runtime_window, SizeF::new(size.width as _, size.height as _));
+    let (mut scene, dirty_region) = prepare_scene(
+        runtime_window,
+        SizeF::new(size.width as _, size.height as _),
+        cache,
+        initial_dirty_region,
+    );
+    if dirty_region.is_empty() {
+        return;
+    }
+    let dirty_begin = dirty_region.min_x() as u16;
+    let dirty_end = dirty_region.max_x() as u16;
 
     /*for item in scene.future_items {
         match item.command {
@@ -65,88 +89,251 @@ pub fn render_window_frame(
     }*/
 
     let mut line_buffer = vec![background; size.width as usize];
-    while scene.current_line < size.height as u16 {
-        line_buffer.fill(background);
+    // The opacity layers open on the current line, from the outermost to the innermost, with
+    // the buffer in which their items are drawn
+    let mut layer_stack: Vec<(usize, Vec<LayerPixel>)> = Vec::new();
+    // The buffers of the closed layers, kept to be reused
+    let mut free_layer_buffers: Vec<Vec<LayerPixel>> = Vec::new();
+    while scene.current_line < dirty_region.max_y() as u16 {
         let line = scene.process_line();
+        if line.line < dirty_region.min_y() as u16 {
+            continue;
+        }
+        let dirty_range = dirty_begin as usize..dirty_end as usize;
+        line_buffer[dirty_range.clone()].fill(background);
         for span in line.spans.iter().rev() {
-            match span.command {
-                SceneCommand::Rectangle => {
-                    let color = scene.rectangles[span.data_index];
-                    let alpha = color.alpha();
-                    if alpha == u8::MAX {
-                        line_buffer[(span.x) as usize..(span.x + span.width) as usize]
-                            .fill(to_rgb888_color_discard_alpha(color))
-                    } else {
-                        for pix in
-                            &mut line_buffer[(span.x) as usize..(span.x + span.width) as usize]
-                        {
-                            let a = (u8::MAX - alpha) as u16;
-                            let b = alpha as u16;
-                            *pix = Rgb888::new(
-                                ((pix.r() as u16 * a + color.red() as u16 * b) >> 8) as u8,
-                                ((pix.g() as u16 * a + color.green() as u16 * b) >> 8) as u8,
-                                ((pix.b() as u16 * a + color.blue() as u16 * b) >> 8) as u8,
-                            );
-                        }
+            let (begin, end) = match scene.span_range(span, line.line) {
+                Some((begin, end)) if begin < dirty_end && end > dirty_begin => {
+                    (begin.max(dirty_begin), end.min(dirty_end))
+                }
+                _ => continue,
+            };
+            // The spans are drawn in z order, so all the spans of a layer are drawn one after
+            // the other: close the layers that the span is not in, and open the ones it is in.
+            while let Some((layer, _)) = layer_stack.last() {
+                if scene.is_in_layer(span.layer, *layer) {
+                    break;
+                }
+                close_layer(
+                    &scene.layers,
+                    &mut layer_stack,
+                    &mut free_layer_buffers,
+                    &mut line_buffer,
+                    dirty_range.clone(),
+                );
+            }
+            let mut layers_to_open = Vec::new();
+            let mut layer = span.layer;
+            while let Some(index) = layer {
+                if layer_stack.last().map_or(false, |(open, _)| *open == index) {
+                    break;
+                }
+                layers_to_open.push(index);
+                layer = scene.layers[index].parent;
+            }
+            for index in layers_to_open.into_iter().rev() {
+                let buffer = free_layer_buffers
+                    .pop()
+                    .unwrap_or_else(|| vec![LayerPixel::default(); size.width as usize]);
+                layer_stack.push((index, buffer));
+            }
+
+            let range = begin as usize..end as usize;
+            match layer_stack.last_mut() {
+                Some((_, buffer)) => draw_span(&scene, span, line.line, begin, &mut buffer[range]),
+                None => draw_span(&scene, span, line.line, begin, &mut line_buffer[range]),
+            }
+        }
+        while !layer_stack.is_empty() {
+            close_layer(
+                &scene.layers,
+                &mut layer_stack,
+                &mut free_layer_buffers,
+                &mut line_buffer,
+                dirty_range.clone(),
+            );
+        }
+        devices.fill_region(
+            euclid::rect(dirty_begin as i32, line.line as i32, dirty_region.width(), 1),
+            &line_buffer[dirty_begin as usize..dirty_end as usize],
+        )
+    }
+}
+
+/// Draws the part of the span that is on the given line in `line_pixels`, which are the pixels
+/// of the line starting at the x coordinate `begin`
+fn draw_span<P: TargetPixel>(
+    scene: &Scene,
+    span: &SceneItem,
+    line: u16,
+    begin: u16,
+    line_pixels: &mut [P],
+) {
+    // The position, relative to the span, of the center of the first pixel of line_pixels
+    let x = (begin - span.x) as f32 + 0.5;
+    let y = (line - span.y) as f32 + 0.5;
+    match span.command {
+        SceneCommand::Rectangle => {
+            let color = scene.rectangles[span.data_index];
+            if color.alpha() == u8::MAX {
+                line_pixels.fill(P::from_opaque(color))
+            } else {
+                for pix in line_pixels {
+                    pix.blend(color);
+                }
+            }
+        }
+        SceneCommand::Gradient => {
+            let gradient = &scene.gradients[span.data_index];
+            let size = gradient.rotation.unrotated_size(span.width, span.height);
+            let color_at = |x: f32| {
+                let (x, y) = gradient.rotation.unrotate_point(x, y, size);
+                gradient.color_at(x + gradient.offset.x, y + gradient.offset.y)
+            };
+            if let Some(coverage) = &gradient.coverage {
+                let sx = size.width / coverage.width as f32;
+                let sy = size.height / coverage.height as f32;
+                for (i, pix) in line_pixels.iter_mut().enumerate() {
+                    let (x, y) = gradient.rotation.unrotate_point(x + i as f32, y, size);
+                    let coverage_x = ((x / sx) as usize).min(coverage.width as usize - 1);
+                    let coverage_y = ((y / sy) as usize).min(coverage.height as usize - 1);
+                    let alpha = coverage.data[coverage_y * coverage.stride as usize + coverage_x];
+                    if alpha == 0 {
+                        continue;
                     }
+                    let color = gradient.color_at(x + gradient.offset.x, y + gradient.offset.y);
+                    pix.blend(Color::from_argb_u8(
+                        ((color.alpha() as u16 * alpha as u16) / 255) as u8,
+                        color.red(),
+                        color.green(),
+                        color.blue(),
+                    ));
                 }
-                SceneCommand::Texture => {
-                    let SceneTexture { data, format, stride, source_width, source_height, color } =
-                        scene.textures[span.data_index];
-
-                    let sx = span.width as f32 / source_width as f32;
-                    let sy = span.height as f32 / source_height as f32;
-                    let bpp = bpp(format) as usize;
-                    let y = line.line - span.y;
-
-                    for (x, pix) in line_buffer[(span.x) as usize..(span.x + span.width) as usize]
-                        .iter_mut()
-                        .enumerate()
-                    {
-                        let pos = ((y as f32 / sy) as usize * stride as usize)
-                            + (x as f32 / sx) as usize * bpp;
-                        *pix = match format {
-                            PixelFormat::Rgb => {
-                                Rgb888::new(data[pos + 0], data[pos + 1], data[pos + 2])
-                            }
-                            PixelFormat::Rgba => {
-                                if color.alpha() == 0 {
-                                    let a = (u8::MAX - data[pos + 3]) as u16;
-                                    let b = data[pos + 3] as u16;
-                                    Rgb888::new(
-                                        ((pix.r() as u16 * a + data[pos + 0] as u16 * b) >> 8)
-                                            as u8,
-                                        ((pix.g() as u16 * a + data[pos + 1] as u16 * b) >> 8)
-                                            as u8,
-                                        ((pix.b() as u16 * a + data[pos + 2] as u16 * b) >> 8)
-                                            as u8,
-                                    )
-                                } else {
-                                    let a = (u8::MAX - data[pos + 3]) as u16;
-                                    let b = data[pos + 3] as u16;
-                                    Rgb888::new(
-                                        ((pix.r() as u16 * a + color.red() as u16 * b) >> 8) as u8,
-                                        ((pix.g() as u16 * a + color.green() as u16 * b) >> 8)
-                                            as u8,
-                                        ((pix.b() as u16 * a + color.blue() as u16 * b) >> 8) as u8,
-                                    )
-                                }
-                            }
-                            PixelFormat::AlphaMap => {
-                                let a = (u8::MAX - data[pos]) as u16;
-                                let b = data[pos] as u16;
-                                Rgb888::new(
-                                    ((pix.r() as u16 * a + color.red() as u16 * b) >> 8) as u8,
-                                    ((pix.g() as u16 * a + color.green() as u16 * b) >> 8) as u8,
-                                    ((pix.b() as u16 * a + color.blue() as u16 * b) >> 8) as u8,
-                                )
-                            }
-                        }
+            } else if gradient.is_constant_on_line() {
+                let color = color_at(x);
+                if color.alpha() == u8::MAX {
+                    line_pixels.fill(P::from_opaque(color));
+                } else {
+                    for pix in line_pixels {
+                        pix.blend(color);
                     }
                 }
+            } else {
+                for (i, pix) in line_pixels.iter_mut().enumerate() {
+                    pix.blend(color_at(x + i as f32));
+                }
+            }
+        }
+        SceneCommand::Texture => {
+            let texture = &scene.textures[span.data_index];
+            let data = &texture.data;
+            let color = texture.color;
+            let rotation = texture.rotation;
+            let source_width = texture.source_width as usize;
+            let source_height = texture.source_height as usize;
+
+            let size = rotation.unrotated_size(span.width, span.height);
+            let sx = size.width / source_width as f32;
+            let sy = size.height / source_height as f32;
+            let bpp = bpp(texture.format) as usize;
+
+            for (i, pix) in line_pixels.iter_mut().enumerate() {
+                let (source_x, source_y) = rotation.unrotate_point(x + i as f32, y, size);
+                let source_x = ((source_x / sx) as usize).min(source_width - 1);
+                let source_y = ((source_y / sy) as usize).min(source_height - 1);
+                let pos = source_y * texture.stride as usize + source_x * bpp;
+                let (red, green, blue, coverage) = match texture.format {
+                    PixelFormat::Rgb => (data[pos + 0], data[pos + 1], data[pos + 2], u8::MAX),
+                    PixelFormat::Rgba => {
+                        if color.alpha() == 0 {
+                            (data[pos + 0], data[pos + 1], data[pos + 2], data[pos + 3])
+                        } else {
+                            (color.red(), color.green(), color.blue(), data[pos + 3])
+                        }
+                    }
+                    PixelFormat::AlphaMap => (color.red(), color.green(), color.blue(), data[pos]),
+                };
+                pix.blend(Color::from_argb_u8(coverage, red, green, blue));
             }
         }
-        devices.fill_region(euclid::rect(0, line.line as i32, size.width as i32, 1), &line_buffer)
+    }
+}
+
+/// A pixel of a buffer in which the scene items are drawn
+trait TargetPixel: Copy {
+    /// The pixel entirely covered by the opaque color
+    fn from_opaque(color: Color) -> Self;
+    /// Blends the color over the pixel, according to the color's alpha
+    fn blend(&mut self, color: Color);
+}
+
+impl TargetPixel for Rgb888 {
+    fn from_opaque(color: Color) -> Self {
+        to_rgb888_color_discard_alpha(color)
+    }
+
+    fn blend(&mut self, color: Color) {
+        *self = blend_pixel(*self, color);
+    }
+}
+
+/// A pixel of an opacity layer, with premultiplied alpha. It is transparent by default.
+#[derive(Clone, Copy, Default)]
+struct LayerPixel {
+    red: u8,
+    green: u8,
+    blue: u8,
+    alpha: u8,
+}
+
+impl TargetPixel for LayerPixel {
+    fn from_opaque(color: Color) -> Self {
+        Self { red: color.red(), green: color.green(), blue: color.blue(), alpha: u8::MAX }
+    }
+
+    fn blend(&mut self, color: Color) {
+        let a = (u8::MAX - color.alpha()) as u16;
+        let b = color.alpha() as u16;
+        let over = |dst: u8, src: u8| ((dst as u16 * a + src as u16 * b) / 255) as u8;
+        self.red = over(self.red, color.red());
+        self.green = over(self.green, color.green());
+        self.blue = over(self.blue, color.blue());
+        self.alpha = over(self.alpha, u8::MAX);
+    }
+}
+
+/// Blends the innermost open layer, in the given range of pixels, over the layer or the line
+/// below it, and keeps its buffer to be reused
+fn close_layer(
+    layers: &[Layer],
+    layer_stack: &mut Vec<(usize, Vec<LayerPixel>)>,
+    free_layer_buffers: &mut Vec<Vec<LayerPixel>>,
+    line_buffer: &mut [Rgb888],
+    range: core::ops::Range<usize>,
+) {
+    let (layer, mut buffer) = layer_stack.pop().unwrap();
+    let alpha = layers[layer].alpha;
+    match layer_stack.last_mut() {
+        Some((_, parent)) => blend_layer(&mut parent[range.clone()], &buffer[range.clone()], alpha),
+        None => blend_layer(&mut line_buffer[range.clone()], &buffer[range.clone()], alpha),
+    }
+    buffer[range].fill(LayerPixel::default());
+    free_layer_buffers.push(buffer);
+}
+
+/// Blends the pixels of a layer over the target pixels, with the opacity of the layer
+fn blend_layer<P: TargetPixel>(target: &mut [P], layer: &[LayerPixel], opacity: u8) {
+    for (pix, layer_pix) in target.iter_mut().zip(layer) {
+        if layer_pix.alpha == 0 {
+            continue;
+        }
+        let unpremultiply = |c: u8| ((c as u16 * 255) / layer_pix.alpha as u16).min(255) as u8;
+        pix.blend(Color::from_argb_u8(
+            ((layer_pix.alpha as u16 * opacity as u16) / 255) as u8,
+            unpremultiply(layer_pix.red),
+            unpremultiply(layer_pix.green),
+            unpremultiply(layer_pix.blue),
+        ));
     }
 }
 
@@ -167,10 +354,20 @@ struct Scene {
 
     rectangles: Vec<Color>,
     textures: Vec<SceneTexture>,
+    gradients: Vec<SceneGradient>,
+    rounded_clips: Vec<RoundedClip>,
+    layers: Vec<Layer>,
 }
 
 impl Scene {
-    fn new(mut items: Vec<SceneItem>, rectangles: Vec<Color>, textures: Vec<SceneTexture>) -> Self {
+    fn new(
+        mut items: Vec<SceneItem>,
+        rectangles: Vec<Color>,
+        textures: Vec<SceneTexture>,
+        gradients: Vec<SceneGradient>,
+        rounded_clips: Vec<RoundedClip>,
+        layers: Vec<Layer>,
+    ) -> Self {
         items.sort_unstable_by(|a, b| compare_scene_item(a, b).reverse());
         Self {
             future_items: items,
@@ -179,7 +376,37 @@ fn new(mut items: Vec<SceneItem>, rectangles: Vec<Color>, textures: Vec<SceneTex
             next_items: Default::default(),
             rectangles,
             textures,
+            gradients,
+            rounded_clips,
+            layers,
+        }
+    }
+
+    /// Returns true if `layer` is the layer `ancestor` or one of the layers within it
+    fn is_in_layer(&self, mut layer: Option<usize>, ancestor: usize) -> bool {
+        while let Some(index) = layer {
+            if index == ancestor {
+                return true;
+            }
+            layer = self.layers[index].parent;
+        }
+        false
+    }
+
+    /// Returns the range of x coordinates of the span that are to be drawn on the given line,
+    /// taking the rounded clips into account. Returns None if nothing is to be drawn.
+    fn span_range(&self, span: &SceneItem, line: u16) -> Option<(u16, u16)> {
+        let mut begin = span.x;
+        let mut end = span.x + span.width;
+        let mut clip = span.rounded_clip;
+        while let Some(index) = clip {
+            let rounded_clip = &self.rounded_clips[index];
+            let (clip_begin, clip_end) = rounded_clip.horizontal_range(line, begin, end)?;
+            begin = clip_begin;
+            end = clip_end;
+            clip = rounded_clip.parent;
         }
+        (begin < end).then(|| (begin, end))
     }
 
     /// Will generate a LineCommand for the current_line, remove all items that are done from the items
@@ -234,6 +461,129 @@ struct SceneItem {
     z: u16,
     command: SceneCommand,
     data_index: usize, // SceneCommand specific index in textures, etc. arrays for additional data
+    /// Index in Scene::rounded_clips of the innermost rounded clip that applies to this item
+    rounded_clip: Option<usize>,
+    /// Index in Scene::layers of the innermost opacity layer in which this item is drawn
+    layer: Option<usize>,
+}
+
+/// A rotation by a multiple of 90 degrees, stored as the number of quarter turns clockwise
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Rotation(u8);
+
+impl Rotation {
+    /// The rotation closest to the given angle
+    fn from_degrees(angle: f32) -> Self {
+        Self(((angle / 90.).round() as i32).rem_euclid(4) as u8)
+    }
+
+    /// The rotation that is this rotation followed by `other`
+    fn then(self, other: Self) -> Self {
+        Self((self.0 + other.0) % 4)
+    }
+
+    fn inverse(self) -> Self {
+        Self((4 - self.0) % 4)
+    }
+
+    fn apply_to_vector(self, v: euclid::default::Vector2D<f32>) -> euclid::default::Vector2D<f32> {
+        match self.0 {
+            0 => v,
+            1 => euclid::vec2(-v.y, v.x),
+            2 => euclid::vec2(-v.x, -v.y),
+            _ => euclid::vec2(v.y, -v.x),
+        }
+    }
+
+    /// The rectangle covered by the rotation of `rect` around the origin
+    fn apply_to_rect(self, rect: RectF) -> RectF {
+        let a = self.apply_to_vector(rect.min().to_vector()).to_point();
+        let b = self.apply_to_vector(rect.max().to_vector()).to_point();
+        RectF::from_points([a, b])
+    }
+
+    /// The size, before the rotation, of an item that has the given size in the scene
+    fn unrotated_size(self, width: u16, height: u16) -> SizeF {
+        if self.0 % 2 == 0 {
+            SizeF::new(width as f32, height as f32)
+        } else {
+            SizeF::new(height as f32, width as f32)
+        }
+    }
+
+    /// Maps a point relative to the top left corner of an item in the scene to the point
+    /// relative to the top left corner of the item before the rotation. `size` is the unrotated
+    /// size of the item.
+    fn unrotate_point(self, x: f32, y: f32, size: SizeF) -> (f32, f32) {
+        match self.0 {
+            0 => (x, y),
+            1 => (y, size.height - x),
+            2 => (size.width - x, size.height - y),
+            _ => (size.width - y, x),
+        }
+    }
+}
+
+/// The items of an element with an opacity are drawn in a layer, which is then blended with
+/// the opacity, so that the overlapping items of the layer don't show through each other.
+struct Layer {
+    /// The opacity of the layer, as an alpha value
+    alpha: u8,
+    /// The index of the enclosing layer, in which this one is blended
+    parent: Option<usize>,
+}
+
+/// A rounded rectangle clipping the items, in physical pixels in the scene coordinates
+struct RoundedClip {
+    rect: RectF,
+    radius: f32,
+    /// A rounded rectangle, with its radius, that is cut out of the clip. The items clipped by
+    /// it must be entirely on one side of its center, so that only one range of each line is
+    /// within the clip.
+    hole: Option<(RectF, f32)>,
+    /// The index of the enclosing rounded clip that also applies
+    parent: Option<usize>,
+}
+
+impl RoundedClip {
+    /// Returns the range of x coordinates that are within the clip on the given line for a
+    /// span that begins at `begin` and ends at `end`, or None if nothing is within the clip
+    fn horizontal_range(&self, line: u16, begin: u16, end: u16) -> Option<(u16, u16)> {
+        let (clip_begin, clip_end) = rounded_rect_range(self.rect, self.radius, line)?;
+        let (mut new_begin, mut new_end) = (begin.max(clip_begin), end.min(clip_end));
+        if let Some((hole, hole_radius)) = self.hole {
+            if let Some((hole_begin, hole_end)) = rounded_rect_range(hole, hole_radius, line) {
+                if (begin as f32 + end as f32) / 2. < hole.center().x {
+                    new_end = new_end.min(hole_begin);
+                } else {
+                    new_begin = new_begin.max(hole_end);
+                }
+            }
+        }
+        (new_begin < new_end).then(|| (new_begin, new_end))
+    }
+}
+
+/// Returns the range of x coordinates that are within the rounded rectangle on the given line,
+/// or None if the line is not within the rectangle
+fn rounded_rect_range(rect: RectF, radius: f32, line: u16) -> Option<(u16, u16)> {
+    let y = line as f32 + 0.5;
+    if y < rect.min_y() || y >= rect.max_y() {
+        return None;
+    }
+    let radius = radius.min(rect.width() / 2.).min(rect.height() / 2.);
+    let dy = if y < rect.min_y() + radius {
+        rect.min_y() + radius - y
+    } else if y > rect.max_y() - radius {
+        y - (rect.max_y() - radius)
+    } else {
+        0.
+    };
+    let inset = radius - (radius * radius - dy * dy).max(0.).sqrt();
+    Some((
+        (rect.min_x() + inset).round().max(0.) as u16,
+        (rect.max_x() - inset).round().max(0.) as u16,
+    ))
 }
 
 struct LineCommand {
@@ -263,40 +613,159 @@ fn compare_scene_item(a: &SceneItem, b: &SceneItem) -> core::cmp::Ordering {
 enum SceneCommand {
     Rectangle,
     Texture,
+    Gradient,
 }
 
 struct SceneTexture {
-    data: &'static [u8],
+    /// The pixels, borrowed from the image or glyph data, or rendered by the renderer
+    data: Cow<'static, [u8]>,
     format: PixelFormat,
     /// bytes between two lines in the source
     stride: u16,
     source_width: u16,
     source_height: u16,
     color: Color,
+    rotation: Rotation,
 }
 
-fn prepare_scene(runtime_window: Rc<i_slint_core::window::Window>, size: SizeF) -> Scene {
-    let mut prepare_scene = PrepareScene::new(
-        size,
-        ScaleFactor(runtime_window.scale_factor()),
-        runtime_window.default_font_properties(),
-    );
+/// A rectangle filled with a gradient, the color is computed for each pixel
+struct SceneGradient {
+    brush: Brush,
+    /// The size of the whole shape filled by the gradient, in physical pixels
+    size: SizeF,
+    /// The position of the scene item within the shape, in physical pixels. It is not zero
+    /// when the shape is clipped.
+    offset: PointF,
+    /// For linear gradients, the start point of the gradient line, and the direction of the line
+    /// divided by its squared length, so that the dot product with it gives the position of a
+    /// point on the gradient line.
+    line: (PointF, euclid::default::Vector2D<f32>),
+    rotation: Rotation,
+    /// The coverage of the scene item by the shape, when the shape is not a rectangle
+    coverage: Option<SceneCoverage>,
+}
+
+/// An alpha map of the pixels of a scene item that are covered by a shape, before the rotation
+struct SceneCoverage {
+    data: Vec<u8>,
+    /// bytes between two lines
+    stride: u16,
+    width: u16,
+    height: u16,
+}
+
+impl SceneGradient {
+    fn new(
+        brush: Brush,
+        size: SizeF,
+        offset: PointF,
+        rotation: Rotation,
+        coverage: Option<SceneCoverage>,
+    ) -> Self {
+        let line = match &brush {
+            Brush::LinearGradient(gradient) => {
+                let (start, end) = i_slint_core::graphics::line_for_angle(gradient.angle());
+                let start: PointF = euclid::point2(start.x * size.width, start.y * size.height);
+                let end: PointF = euclid::point2(end.x * size.width, end.y * size.height);
+                let direction = end - start;
+                let length_square = direction.square_length();
+                let direction =
+                    if length_square > 0. { direction / length_square } else { direction };
+                (start, direction)
+            }
+            _ => Default::default(),
+        };
+        Self { brush, size, offset, line, rotation, coverage }
+    }
+
+    /// Returns true if all the pixels of an horizontal line have the same color
+    fn is_constant_on_line(&self) -> bool {
+        // With a rotation of 90 or 270 degrees, the horizontal lines of the scene are vertical
+        // lines of the shape
+        self.rotation.0 % 2 == 0
+            && matches!(self.brush, Brush::LinearGradient(_))
+            && self.line.1.x.abs() < f32::EPSILON
+    }
+
+    /// Returns the color of the point at the given position relative to the shape
+    fn color_at(&self, x: f32, y: f32) -> Color {
+        let dx = x - self.size.width / 2.;
+        let dy = y - self.size.height / 2.;
+        match &self.brush {
+            Brush::LinearGradient(gradient) => {
+                let (start, direction) = self.line;
+                let position = (euclid::point2(x, y) - start).dot(direction);
+                i_slint_core::graphics::gradient_color_at(gradient.stops(), position)
+            }
+            Brush::RadialGradient(gradient) => {
+                let radius = self.size.to_vector().length() / 2.;
+                i_slint_core::graphics::gradient_color_at(
+                    gradient.stops(),
+                    (dx * dx + dy * dy).sqrt() / radius,
+                )
+            }
+            Brush::ConicGradient(gradient) => gradient.color_at(dx, dy),
+            brush => brush.color(),
+        }
+    }
+}
+
+/// Prepares the scene for the parts of the window that need to be redrawn, and returns it with
+/// the region to redraw, in physical pixels
+fn prepare_scene(
+    runtime_window: Rc<i_slint_core::window::Window>,
+    size: SizeF,
+    cache: &RefCell<PartialRenderingCache>,
+    initial_dirty_region: DirtyRegion,
+) -> (Scene, IntRect) {
+    let scale_factor = ScaleFactor(runtime_window.scale_factor());
+    let prepare_scene =
+        PrepareScene::new(size, scale_factor, runtime_window.default_font_properties());
+    let mut renderer = PartialRenderer::new(cache, initial_dirty_region, prepare_scene);
+    let mut dirty_region = IntRect::default();
     runtime_window.draw_contents(|components| {
         for (component, origin) in components {
-            i_slint_core::item_rendering::render_component_items(
-                component,
-                &mut prepare_scene,
-                origin.clone(),
-            );
+            renderer.compute_dirty_regions(component, *origin);
+        }
+
+        // Only whole pixels can be redrawn
+        dirty_region = match renderer
+            .dirty_region
+            .scale(scale_factor.0, scale_factor.0)
+            .intersection(&RectF::from_size(size))
+        {
+            Some(region) => region.round_out().cast(),
+            None => return,
+        };
+        renderer.combine_clip(
+            dirty_region.cast::<f32>().scale(1. / scale_factor.0, 1. / scale_factor.0),
+            0.,
+            0.,
+        );
+
+        for (component, origin) in components {
+            renderer.render_component_items(component, *origin);
         }
     });
-    Scene::new(prepare_scene.items, prepare_scene.rectangles, prepare_scene.textures)
+    let prepare_scene = renderer.actual_renderer;
+    let scene = Scene::new(
+        prepare_scene.items,
+        prepare_scene.rectangles,
+        prepare_scene.textures,
+        prepare_scene.gradients,
+        prepare_scene.rounded_clips,
+        prepare_scene.layers,
+    );
+    (scene, dirty_region)
 }
 
 struct PrepareScene {
     items: Vec<SceneItem>,
     rectangles: Vec<Color>,
     textures: Vec<SceneTexture>,
+    gradients: Vec<SceneGradient>,
+    rounded_clips: Vec<RoundedClip>,
+    layers: Vec<Layer>,
     state_stack: Vec<RenderState>,
     current_state: RenderState,
     scale_factor: ScaleFactor,
@@ -309,11 +778,18 @@ fn new(size: SizeF, scale_factor: ScaleFactor, default_font: FontRequest) -> Sel
             items: vec![],
             rectangles: vec![],
             textures: vec![],
+            gradients: vec![],
+            rounded_clips: vec![],
+            layers: vec![],
             state_stack: vec![],
             current_state: RenderState {
                 alpha: 1.,
                 offset: PointF::default(),
+                rotation: Rotation::default(),
+                scale: euclid::vec2(1., 1.),
                 clip: RectF::new(PointF::default(), size / scale_factor.0),
+                rounded_clip: None,
+                layer: None,
             },
             scale_factor,
             default_font,
@@ -336,19 +812,184 @@ fn new_scene_texture(&mut self, geometry: RectF, texture: SceneTexture) {
         self.new_scene_item(geometry, SceneCommand::Texture, self.textures.len() - 1);
     }
 
+    /// Adds the part of the shape of the given geometry that is within the clip, filled with the brush
+    fn new_scene_brush_rectangle(&mut self, geometry: RectF, brush: Brush) {
+        self.new_scene_brush_rectangle_part(geometry, geometry, brush)
+    }
+
+    /// Adds the part of a shape that is within the clip, filled with the brush. `shape` is the
+    /// geometry of the whole shape, used to position the gradients, and `part` is the rectangle
+    /// of the shape to draw.
+    fn new_scene_brush_rectangle_part(&mut self, shape: RectF, part: RectF, brush: Brush) {
+        let clipped = match part.intersection(&self.current_state.clip) {
+            Some(clipped) => clipped,
+            None => return,
+        };
+        match brush {
+            Brush::SolidColor(color) => {
+                if color.alpha() > 0 {
+                    self.new_scene_rectangle(clipped, color);
+                }
+            }
+            brush => {
+                let scale = self.current_state.scale.abs() * self.scale_factor.0;
+                let offset = (clipped.origin - shape.origin).component_mul(scale);
+                self.gradients.push(SceneGradient::new(
+                    brush,
+                    euclid::size2(shape.width() * scale.x, shape.height() * scale.y),
+                    offset.to_point(),
+                    self.current_state.rotation,
+                    None,
+                ));
+                self.new_scene_item(clipped, SceneCommand::Gradient, self.gradients.len() - 1);
+            }
+        }
+    }
+
+    /// Maps a rectangle in the coordinates of the current item to the scene, in logical pixels
+    fn to_scene_rect(&self, rect: RectF) -> RectF {
+        let scale = self.current_state.scale;
+        let scale_point = |p: PointF| euclid::point2(p.x * scale.x, p.y * scale.y);
+        self.current_state
+            .rotation
+            .apply_to_rect(RectF::from_points([scale_point(rect.min()), scale_point(rect.max())]))
+            .translate(self.current_state.offset.to_vector())
+    }
+
+    /// Makes the rounded rectangle, in the coordinates of the current item, clip the next scene
+    /// items. `hole` is a rounded rectangle cut out of it, in the same coordinates.
+    fn push_rounded_clip(&mut self, rect: RectF, radius: f32, hole: Option<(RectF, f32)>) {
+        let scale = self.current_state.scale;
+        let radius_scale = self.scale_factor.0 * scale.x.abs().min(scale.y.abs());
+        let to_scene = |(rect, radius): (RectF, f32)| {
+            (
+                self.to_scene_rect(rect).scale(self.scale_factor.0, self.scale_factor.0),
+                radius * radius_scale,
+            )
+        };
+        let (rect, radius) = to_scene((rect, radius));
+        let hole = hole.map(to_scene);
+        let parent = self.current_state.rounded_clip;
+        self.rounded_clips.push(RoundedClip { rect, radius, hole, parent });
+        self.current_state.rounded_clip = Some(self.rounded_clips.len() - 1);
+    }
+
     fn new_scene_item(&mut self, geometry: RectF, command: SceneCommand, data_index: usize) {
         let z = self.items.len() as u16;
+        let geometry = self.to_scene_rect(geometry);
         self.items.push(SceneItem {
-            x: (self.current_state.offset.x + geometry.origin.x) * self.scale_factor,
-            y: (self.current_state.offset.y + geometry.origin.y) * self.scale_factor,
+            x: geometry.origin.x * self.scale_factor,
+            y: geometry.origin.y * self.scale_factor,
             width: geometry.size.width * self.scale_factor,
             height: geometry.size.height * self.scale_factor,
             z,
             command,
             data_index,
+            rounded_clip: self.current_state.rounded_clip,
+            layer: self.current_state.layer,
         });
     }
 
+    /// Adds a texture for the glyphs of a line of text, with the color given by `color_for`
+    /// for the byte offset of each character
+    fn draw_glyphs(
+        &mut self,
+        font: &'static BitmapFont,
+        glyphs: &'static BitmapGlyphs,
+        text: &str,
+        color_for: impl Fn(usize) -> Color,
+    ) {
+        let baseline_y = font.ascent * (glyphs.pixel_size as f32) / font.units_per_em;
+
+        for (byte_offset, glyph_baseline_x, _, glyph) in
+            crate::fonts::layout_chars(font, glyphs, text)
+        {
+            let glyph = match glyph {
+                Some(glyph) => glyph,
+                None => continue,
+            };
+            if let Some(dest_rect) = euclid::rect(
+                glyph_baseline_x + glyph.x as f32,
+                baseline_y - glyph.y as f32 - glyph.height as f32,
+                glyph.width as f32,
+                glyph.height as f32,
+            )
+            .intersection(&self.current_state.clip)
+            {
+                let stride = glyph.width;
+
+                self.new_scene_texture(
+                    dest_rect,
+                    SceneTexture {
+                        data: Cow::Borrowed(glyph.data.as_slice()),
+                        stride,
+                        source_width: glyph.width,
+                        source_height: glyph.height,
+                        format: PixelFormat::AlphaMap,
+                        color: color_for(byte_offset),
+                        rotation: self.current_state.rotation,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Adds a texture of the given coverage map, filled with the brush. `area` is the region
+    /// covered by the map, in physical pixels in the coordinates of the current item. `shape` is
+    /// the geometry of the whole shape, used to position the gradients.
+    fn new_scene_coverage(&mut self, area: IntRect, coverage: Vec<u8>, brush: Brush, shape: RectF) {
+        if area.is_empty() || brush.is_transparent() {
+            return;
+        }
+        // Only keep the part of the map that is within the clip
+        let clip = self.current_state.clip.scale(self.scale_factor.0, self.scale_factor.0);
+        let visible = match area.cast::<f32>().intersection(&clip) {
+            Some(visible) => visible.round_in().cast::<i32>(),
+            None => return,
+        };
+        if visible.is_empty() {
+            return;
+        }
+        let stride = area.width() as usize;
+        let offset = (visible.min_y() - area.min_y()) as usize * stride
+            + (visible.min_x() - area.min_x()) as usize;
+        let mut coverage = coverage;
+        coverage.drain(..offset);
+        let geometry =
+            visible.cast::<f32>().scale(1. / self.scale_factor.0, 1. / self.scale_factor.0);
+        match brush {
+            Brush::SolidColor(color) => self.new_scene_texture(
+                geometry,
+                SceneTexture {
+                    data: Cow::Owned(coverage),
+                    stride: stride as u16,
+                    source_width: visible.width() as u16,
+                    source_height: visible.height() as u16,
+                    format: PixelFormat::AlphaMap,
+                    color,
+                    rotation: self.current_state.rotation,
+                },
+            ),
+            brush => {
+                let scale = self.current_state.scale.abs() * self.scale_factor.0;
+                let offset = (geometry.origin - shape.origin).component_mul(scale);
+                self.gradients.push(SceneGradient::new(
+                    brush,
+                    euclid::size2(shape.width() * scale.x, shape.height() * scale.y),
+                    offset.to_point(),
+                    self.current_state.rotation,
+                    Some(SceneCoverage {
+                        data: coverage,
+                        stride: stride as u16,
+                        width: visible.width() as u16,
+                        height: visible.height() as u16,
+                    }),
+                ));
+                self.new_scene_item(geometry, SceneCommand::Gradient, self.gradients.len() - 1);
+            }
+        }
+    }
+
     fn draw_image_impl(
         &mut self,
         geom: RectF,
@@ -378,14 +1019,17 @@ fn draw_image_impl(
                         self.new_scene_texture(
                             dest_rect.cast().scale(sx, sy),
                             SceneTexture {
-                                data: &data.as_slice()[(t.index
-                                    + (stride as usize) * (actual_y as usize)
-                                    + (bpp(t.format) as usize) * (actual_x as usize))..],
+                                data: Cow::Borrowed(
+                                    &data.as_slice()[(t.index
+                                        + (stride as usize) * (actual_y as usize)
+                                        + (bpp(t.format) as usize) * (actual_x as usize))..],
+                                ),
                                 stride,
                                 source_height: dest_rect.height() as u16,
                                 source_width: dest_rect.width() as u16,
                                 format: t.format,
                                 color: if colorize.alpha() > 0 { colorize } else { t.color },
+                                rotation: self.current_state.rotation,
                             },
                         );
                     }
@@ -395,63 +1039,110 @@ fn draw_image_impl(
     }
 }
 
+/// Set when a rotation that is not a multiple of 90 degrees was rounded, so that the warning
+/// is only logged once
+static UNSUPPORTED_ROTATION_WARNED: core::sync::atomic::AtomicBool =
+    core::sync::atomic::AtomicBool::new(false);
+
 #[derive(Clone, Copy)]
 struct RenderState {
+    /// The product of the opacities of the current item and its ancestors. The items are drawn
+    /// without it, in `layer`, and it is only used to skip the invisible items.
     alpha: f32,
+    /// The position of the origin of the current item in the scene, in logical pixels
     offset: PointF,
+    /// The rotation of the current item relative to the scene
+    rotation: Rotation,
+    /// The scale factors of the current item relative to the scene, applied before the rotation
+    scale: euclid::default::Vector2D<f32>,
+    /// The clip, in the coordinates of the current item
     clip: RectF,
+    /// Index in PrepareScene::rounded_clips of the innermost rounded clip
+    rounded_clip: Option<usize>,
+    /// Index in PrepareScene::layers of the innermost opacity layer
+    layer: Option<usize>,
 }
 
-impl i_slint_core::item_rendering::ItemRenderer for PrepareScene {
+impl ItemRenderer for PrepareScene {
     fn draw_rectangle(&mut self, rect: Pin<&i_slint_core::items::Rectangle>) {
         let geom = RectF::new(PointF::default(), rect.geometry().size);
         if self.should_draw(&geom) {
-            let geom = match geom.intersection(&self.current_state.clip) {
-                Some(geom) => geom,
-                None => return,
-            };
-
-            // FIXME: gradients
-            let color = rect.background().color();
-            if color.alpha() == 0 {
-                return;
-            }
-            self.new_scene_rectangle(geom, color);
+            self.new_scene_brush_rectangle(geom, rect.background());
         }
     }
 
     fn draw_border_rectangle(&mut self, rect: Pin<&i_slint_core::items::BorderRectangle>) {
         let geom = RectF::new(PointF::default(), rect.geometry().size);
         if self.should_draw(&geom) {
-            let border = rect.border_width();
-            // FIXME: gradients
-            let color = rect.background().color();
-            if color.alpha() > 0 {
-                if let Some(r) =
-                    geom.inflate(-border, -border).intersection(&self.current_state.clip)
-                {
-                    self.new_scene_rectangle(r, color);
+            let border = rect.border_width().max(0.).min(geom.width().min(geom.height()) / 2.);
+            let radius = rect.border_radius().max(0.);
+            let inner_geom = geom.inflate(-border, -border);
+            let inner_radius = (radius - border).max(0.);
+            // The rounded corners are drawn by clipping the items with rounded clips, which are
+            // restored when the border rectangle is done.
+            let rounded_clip = self.current_state.rounded_clip;
+            let background = rect.background();
+            if !background.is_transparent() {
+                if inner_radius > 0. {
+                    self.push_rounded_clip(inner_geom, inner_radius, None);
                 }
+                self.new_scene_brush_rectangle(inner_geom, background);
+                self.current_state.rounded_clip = rounded_clip;
             }
             if border > 0.01 {
-                // FIXME: radius
-                // FIXME: gradients
-                let border_color = rect.border_color().color();
-                if border_color.alpha() > 0 {
-                    let mut add_border = |r: RectF| {
-                        if let Some(r) = r.intersection(&self.current_state.clip) {
-                            self.new_scene_rectangle(r, border_color);
-                        }
-                    };
-                    add_border(euclid::rect(0., 0., geom.width(), border));
-                    add_border(euclid::rect(0., geom.height() - border, geom.width(), border));
-                    add_border(euclid::rect(0., border, border, geom.height() - border - border));
-                    add_border(euclid::rect(
-                        geom.width() - border,
-                        border,
-                        border,
-                        geom.height() - border - border,
-                    ));
+                let border_color = rect.border_color();
+                if !border_color.is_transparent() {
+                    if radius > 0. {
+                        // The inside of the border is cut out of the clip, which only works for
+                        // the items on one side of it, so the border is drawn in two halves that
+                        // are side by side in the scene.
+                        let hole = (!inner_geom.is_empty()).then(|| (inner_geom, inner_radius));
+                        self.push_rounded_clip(geom, radius, hole);
+                        let (first_half, second_half) = if self.current_state.rotation.0 % 2 == 0 {
+                            let half_width = geom.width() / 2.;
+                            (
+                                euclid::rect(0., 0., half_width, geom.height()),
+                                euclid::rect(
+                                    half_width,
+                                    0.,
+                                    geom.width() - half_width,
+                                    geom.height(),
+                                ),
+                            )
+                        } else {
+                            let half_height = geom.height() / 2.;
+                            (
+                                euclid::rect(0., 0., geom.width(), half_height),
+                                euclid::rect(
+                                    0.,
+                                    half_height,
+                                    geom.width(),
+                                    geom.height() - half_height,
+                                ),
+                            )
+                        };
+                        self.new_scene_brush_rectangle_part(geom, first_half, border_color.clone());
+                        self.new_scene_brush_rectangle_part(geom, second_half, border_color);
+                        self.current_state.rounded_clip = rounded_clip;
+                    } else {
+                        let mut add_border = |r: RectF| {
+                            self.new_scene_brush_rectangle_part(geom, r, border_color.clone());
+                        };
+                        add_border(euclid::rect(0., 0., geom.width(), border));
+                        add_border(euclid::rect(0., geom.height() - border, geom.width(), border));
+                        add_border(euclid::rect(
+                            0.,
+                            border,
+                            border,
+                            geom.height() - border - border,
+                        ));
+                        add_border(euclid::rect(
+                            geom.width() - border,
+                            border,
+                            border,
+                            geom.height() - border - border,
+                        ));
+                    }
                 }
             }
         }
@@ -494,58 +1185,173 @@ fn draw_text(&mut self, text: Pin<&i_slint_core::items::Text>) {
         let (font, glyphs) = crate::fonts::match_font(&font_request, self.scale_factor);
 
         let color = text.color().color();
+        self.draw_glyphs(font, glyphs, &text.text(), |_| color);
+    }
 
-        let baseline_y = font.ascent * (glyphs.pixel_size as f32) / font.units_per_em;
-
-        for (glyph_baseline_x, glyph) in crate::fonts::glyphs_for_text(font, glyphs, &text.text()) {
-            if let Some(dest_rect) = euclid::rect(
-                glyph_baseline_x + glyph.x as f32,
-                baseline_y - glyph.y as f32 - glyph.height as f32,
-                glyph.width as f32,
-                glyph.height as f32,
-            )
-            .intersection(&self.current_state.clip)
-            {
-                let stride = glyph.width;
+    fn draw_text_input(&mut self, text_input: Pin<&i_slint_core::items::TextInput>) {
+        let font_request = text_input.unresolved_font_request().merge(&self.default_font);
+        let (font, glyphs) = crate::fonts::match_font(&font_request, self.scale_factor);
+        let visual_representation = text_input.visual_representation();
+        let text = visual_representation.text.as_str();
+        let line_height = crate::fonts::line_height(font, glyphs);
+        let x_for_byte_offset =
+            |byte_offset| crate::fonts::x_for_byte_offset(font, glyphs, text, byte_offset);
 
-                self.new_scene_texture(
-                    dest_rect,
-                    SceneTexture {
-                        data: glyph.data.as_slice(),
-                        stride,
-                        source_width: glyph.width,
-                        source_height: glyph.height,
-                        format: PixelFormat::AlphaMap,
-                        color,
-                    },
+        let selection = visual_representation.selection_range;
+        if !selection.is_empty() {
+            let begin = x_for_byte_offset(selection.start);
+            let selection_rect =
+                euclid::rect(begin, 0., x_for_byte_offset(selection.end) - begin, line_height);
+            if self.should_draw(&selection_rect) {
+                self.new_scene_brush_rectangle(
+                    selection_rect,
+                    Brush::SolidColor(text_input.selection_background_color()),
                 );
             }
         }
-    }
 
-    fn draw_text_input(&mut self, _text_input: Pin<&i_slint_core::items::TextInput>) {
-        // TODO
+        let color = text_input.color().color();
+        let selection_color = text_input.selection_foreground_color();
+        self.draw_glyphs(font, glyphs, text, |byte_offset| {
+            if selection.contains(&byte_offset) {
+                selection_color
+            } else {
+                color
+            }
+        });
+
+        // Underline the text being composed by the input method
+        let preedit = visual_representation.preedit_range;
+        if !preedit.is_empty() {
+            let begin = x_for_byte_offset(preedit.start);
+            let underline_rect =
+                euclid::rect(begin, line_height - 1., x_for_byte_offset(preedit.end) - begin, 1.);
+            if self.should_draw(&underline_rect) {
+                self.new_scene_brush_rectangle(underline_rect, text_input.color());
+            }
+        }
+
+        if let Some(cursor_pos) = visual_representation.cursor_position {
+            let cursor_rect = euclid::rect(
+                x_for_byte_offset(cursor_pos),
+                0.,
+                text_input.text_cursor_width(),
+                line_height,
+            );
+            if self.should_draw(&cursor_rect) {
+                self.new_scene_brush_rectangle(cursor_rect, text_input.color());
+            }
+        }
     }
 
-    #[cfg(feature = "simulator")]
-    fn draw_path(&mut self, _path: Pin<&i_slint_core::items::Path>) {
-        // TODO
+    fn draw_path(&mut self, path: Pin<&i_slint_core::items::Path>) {
+        if matches!(path.elements(), i_slint_core::PathData::None) {
+            return;
+        }
+        let geom = RectF::new(PointF::default(), path.geometry().size);
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let (offset, sub_paths) = path.fitted_flattened_path();
+        let scale = self.scale_factor.0;
+        let to_physical = |p: PointF| (p + offset) * scale;
+        // The lines of the stroke, and the lines closing the sub-paths that are not closed,
+        // which are only part of the fill
+        let mut segments: Vec<Edge> = Vec::new();
+        let mut closing_segments: Vec<Edge> = Vec::new();
+        for sub_path in &sub_paths {
+            segments.extend(
+                sub_path.points.windows(2).map(|line| (to_physical(line[0]), to_physical(line[1]))),
+            );
+            if let (Some(first), Some(last)) = (sub_path.points.first(), sub_path.points.last()) {
+                let closing_segment = (to_physical(*last), to_physical(*first));
+                if sub_path.closed {
+                    segments.push(closing_segment);
+                } else {
+                    closing_segments.push(closing_segment);
+                }
+            }
+        }
+
+        let stroke_width = path.stroke_width() * scale;
+        let bounding_rect = RectF::from_points(segments.iter().flat_map(|(from, to)| [*from, *to]))
+            .inflate(stroke_width, stroke_width);
+        let area = match bounding_rect.intersection(&self.current_state.clip.scale(scale, scale)) {
+            Some(area) => area.round_out().cast::<i32>(),
+            None => return,
+        };
+
+        let fill = path.fill();
+        if !fill.is_transparent() {
+            // The fill implicitly closes the open sub-paths
+            let mut edges = segments.clone();
+            edges.extend(closing_segments);
+            let even_odd = path.fill_rule() == i_slint_core::items::FillRule::evenodd;
+            let coverage = crate::rasterizer::fill_polygons(&edges, area, even_odd);
+            self.new_scene_coverage(area, coverage, fill, geom);
+        }
+
+        let stroke = path.stroke();
+        if !stroke.is_transparent() && stroke_width > 0. {
+            let edges = crate::rasterizer::stroke_edges(&segments, stroke_width);
+            let coverage = crate::rasterizer::fill_polygons(&edges, area, false);
+            self.new_scene_coverage(area, coverage, stroke, geom);
+        }
     }
 
-    fn draw_box_shadow(&mut self, _box_shadow: Pin<&i_slint_core::items::BoxShadow>) {
-        // TODO
+    fn draw_box_shadow(&mut self, box_shadow: Pin<&i_slint_core::items::BoxShadow>) {
+        if box_shadow.color().alpha() == 0
+            || (box_shadow.blur() == 0.0
+                && box_shadow.offset_x() == 0.
+                && box_shadow.offset_y() == 0.)
+        {
+            return;
+        }
+
+        let scale = self.scale_factor.0;
+        let blur = box_shadow.blur();
+        let geom = euclid::rect(
+            box_shadow.offset_x() - blur,
+            box_shadow.offset_y() - blur,
+            box_shadow.width() + 2. * blur,
+            box_shadow.height() + 2. * blur,
+        );
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let (coverage, width, height) = crate::rasterizer::box_shadow(
+            box_shadow.width() * scale,
+            box_shadow.height() * scale,
+            box_shadow.border_radius() * scale,
+            blur * scale,
+        );
+        let area = euclid::rect(
+            (geom.origin.x * scale) as i32,
+            (geom.origin.y * scale) as i32,
+            width as i32,
+            height as i32,
+        );
+        self.new_scene_coverage(area, coverage, Brush::SolidColor(box_shadow.color()), geom);
     }
 
-    fn combine_clip(&mut self, other: RectF, _radius: f32, _border_width: f32) {
+    fn combine_clip(&mut self, other: RectF, radius: f32, border_width: f32) {
+        // The clip is the area inside the border
+        let other = other.inflate(-border_width, -border_width);
+        let radius = (radius - border_width).max(0.);
         match self.current_state.clip.intersection(&other) {
             Some(r) => {
                 self.current_state.clip = r;
             }
             None => {
                 self.current_state.clip = RectF::default();
+                return;
             }
         };
-        // TODO: handle radius and border
+        if radius > 0. {
+            self.push_rounded_clip(other, radius, None);
+        }
     }
 
     fn get_current_clip(&self) -> i_slint_core::graphics::Rect {
@@ -553,17 +1359,60 @@ fn get_current_clip(&self) -> i_slint_core::graphics::Rect {
     }
 
     fn translate(&mut self, x: f32, y: f32) {
-        self.current_state.offset.x += x;
-        self.current_state.offset.y += y;
+        let scale = self.current_state.scale;
+        self.current_state.offset +=
+            self.current_state.rotation.apply_to_vector(euclid::vec2(x * scale.x, y * scale.y));
         self.current_state.clip = self.current_state.clip.translate((-x, -y).into())
     }
 
-    fn rotate(&mut self, _angle_in_degrees: f32) {
-        todo!()
+    /// Only rotations by a multiple of 90 degrees are supported: the angle is rounded to the
+    /// closest one, and a warning is logged the first time that happens.
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        let rotation = Rotation::from_degrees(angle_in_degrees);
+        let quarter_turns = angle_in_degrees / 90.;
+        if (quarter_turns - quarter_turns.round()).abs() > 0.0001
+            && !UNSUPPORTED_ROTATION_WARNED.load(core::sync::atomic::Ordering::Relaxed)
+        {
+            UNSUPPORTED_ROTATION_WARNED.store(true, core::sync::atomic::Ordering::Relaxed);
+            #[cfg(feature = "std")]
+            i_slint_core::debug_log!(
+                "Warning: the MCU backend only supports rotations by a multiple of 90 degrees, {}deg is drawn as {}deg",
+                angle_in_degrees,
+                rotation.0 as u16 * 90
+            );
+        }
+        if rotation.0 % 2 == 1 {
+            // The scale factors are expressed along the axes of the rotated item
+            let scale = self.current_state.scale;
+            self.current_state.scale = euclid::vec2(scale.y, scale.x);
+        }
+        self.current_state.rotation = self.current_state.rotation.then(rotation);
+        self.current_state.clip = rotation.inverse().apply_to_rect(self.current_state.clip);
     }
 
+    /// Mirroring with negative factors is not supported: the content is drawn at its mirrored
+    /// location, but is not itself mirrored. Text and paths are rasterized before being scaled.
+    fn scale(&mut self, x: f32, y: f32) {
+        self.current_state.scale = self.current_state.scale.component_mul(euclid::vec2(x, y));
+        let clip = self.current_state.clip;
+        self.current_state.clip = if x == 0. || y == 0. {
+            RectF::default()
+        } else {
+            let unscale_point = |p: PointF| euclid::point2(p.x / x, p.y / y);
+            RectF::from_points([unscale_point(clip.min()), unscale_point(clip.max())])
+        };
+    }
+
+    /// The items are drawn in a new layer that is blended as a whole with the opacity
     fn apply_opacity(&mut self, opacity: f32) {
         self.current_state.alpha *= opacity;
+        if opacity < 1. {
+            self.layers.push(Layer {
+                alpha: (opacity.max(0.) * 255.) as u8,
+                parent: self.current_state.layer,
+            });
+            self.current_state.layer = Some(self.layers.len() - 1);
+        }
     }
 
     fn save_state(&mut self) {
@@ -625,6 +1474,20 @@ fn to_color(data: &[u8], format: PixelFormat, color: Color) -> Rgb888 {
     }
 }*/
 
+/// Blends the color over the pixel, according to the color's alpha
+fn blend_pixel(pix: Rgb888, color: Color) -> Rgb888 {
+    if color.alpha() == u8::MAX {
+        return to_rgb888_color_discard_alpha(color);
+    }
+    let a = (u8::MAX - color.alpha()) as u16;
+    let b = color.alpha() as u16;
+    Rgb888::new(
+        ((pix.r() as u16 * a + color.red() as u16 * b) >> 8) as u8,
+        ((pix.g() as u16 * a + color.green() as u16 * b) >> 8) as u8,
+        ((pix.b() as u16 * a + color.blue() as u16 * b) >> 8) as u8,
+    )
+}
+
 pub fn to_rgb888_color_discard_alpha(col: Color) -> Rgb888 {
     Rgb888::new(col.red(), col.green(), col.blue())
 }
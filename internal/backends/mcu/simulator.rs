@@ -10,6 +10,7 @@
 use i_slint_core::component::ComponentRc;
 use i_slint_core::graphics::{Image, ImageInner};
 use i_slint_core::input::KeyboardModifiers;
+use i_slint_core::item_rendering::PartialRenderingCache;
 use i_slint_core::items::ItemRef;
 use i_slint_core::layout::Orientation;
 use i_slint_core::window::{PlatformWindow, Window};
@@ -34,6 +35,7 @@ pub struct SimulatorWindow {
     constraints: Cell<(i_slint_core::layout::LayoutInfo, i_slint_core::layout::LayoutInfo)>,
     visible: Cell<bool>,
     background_color: Cell<Color>,
+    partial_rendering_cache: RefCell<PartialRenderingCache>,
 }
 
 impl SimulatorWindow {
@@ -61,6 +63,7 @@ pub(crate) fn new(window_weak: &Weak<i_slint_core::window::Window>) -> Rc<Self>
             constraints: Default::default(),
             visible: Default::default(),
             background_color: Color::from_rgb_u8(0, 0, 0).into(),
+            partial_rendering_cache: Default::default(),
         });
 
         let runtime_window = window_weak.upgrade().unwrap();
@@ -135,9 +138,12 @@ fn request_redraw(&self) {
 
     fn free_graphics_resources<'a>(
         &self,
-        _items: &mut dyn Iterator<Item = std::pin::Pin<i_slint_core::items::ItemRef<'a>>>,
+        items: &mut dyn Iterator<Item = std::pin::Pin<i_slint_core::items::ItemRef<'a>>>,
     ) {
-        // Nothing to do until we start caching stuff that needs freeing
+        let mut cache = self.partial_rendering_cache.borrow_mut();
+        for item in items {
+            item.cached_rendering_data_offset().release(&mut cache);
+        }
     }
 
     fn show_popup(
@@ -190,18 +196,31 @@ fn text_size(
 
     fn text_input_byte_offset_for_position(
         &self,
-        _text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
-        _pos: i_slint_core::graphics::Point,
+        text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
+        pos: i_slint_core::graphics::Point,
     ) -> usize {
-        todo!()
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        let (font, glyphs) = crate::fonts::match_font(
+            &text_input.unresolved_font_request().merge(&runtime_window.default_font_properties()),
+            crate::renderer::ScaleFactor(runtime_window.scale_factor()),
+        );
+        crate::fonts::byte_offset_for_x(font, glyphs, &text_input.text(), pos.x)
     }
 
     fn text_input_position_for_byte_offset(
         &self,
-        _text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
-        _byte_offset: usize,
+        text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
+        byte_offset: usize,
     ) -> i_slint_core::graphics::Point {
-        todo!()
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        let (font, glyphs) = crate::fonts::match_font(
+            &text_input.unresolved_font_request().merge(&runtime_window.default_font_properties()),
+            crate::renderer::ScaleFactor(runtime_window.scale_factor()),
+        );
+        i_slint_core::graphics::Point::new(
+            crate::fonts::x_for_byte_offset(font, glyphs, &text_input.text(), byte_offset),
+            0.,
+        )
     }
 
     fn as_any(&self) -> &dyn core::any::Any {
@@ -264,7 +283,16 @@ fn draw(self: Rc<Self>) {
                     .unwrap();
             }
 
-            crate::renderer::render_window_frame(runtime_window, background, &mut display);
+            // The display is created for every frame, so everything needs to be redrawn
+            let whole_window = euclid::rect(0., 0., size.width as f32, size.height as f32)
+                .scale(1. / runtime_window.scale_factor(), 1. / runtime_window.scale_factor());
+            crate::renderer::render_window_frame(
+                runtime_window,
+                background,
+                &mut display,
+                &self.partial_rendering_cache,
+                whole_window,
+            );
 
             let output_image = display
                 .to_rgb_output_image(&embedded_graphics_simulator::OutputSettings::default());
@@ -373,6 +401,14 @@ fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
             .send_event(self::event_loop::CustomEvent::UserEvent(event));
     }
 
+    fn request_change_handlers_run(&'static self) {
+        self::event_loop::GLOBAL_PROXY
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap()
+            .send_event(self::event_loop::CustomEvent::RunChangeHandlers);
+    }
+
     fn image_size(&'static self, image: &Image) -> i_slint_core::graphics::IntSize {
         let inner: &ImageInner = image.into();
         match inner {
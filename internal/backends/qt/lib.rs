@@ -14,6 +14,8 @@
 #[cfg(not(no_qt))]
 use i_slint_core::ImageInner;
 
+#[cfg(not(no_qt))]
+mod qt_accessible;
 #[cfg(not(no_qt))]
 mod qt_widgets;
 #[cfg(not(no_qt))]
@@ -258,6 +260,24 @@ struct EventHolder {
         };
     }
 
+    fn request_change_handlers_run(&'static self) {
+        #[cfg(not(no_qt))]
+        {
+            use cpp::cpp;
+            // If there is no application yet, the handlers are run once the event loop starts
+            cpp! {unsafe [] {
+                if (qApp) {
+                    QTimer::singleShot(0, qApp, [] {
+                        rust!(Slint_run_change_handlers [] {
+                            i_slint_core::properties::ChangeTracker::run_change_handlers();
+                            crate::qt_window::timer_event();
+                        });
+                    });
+                }
+            }}
+        };
+    }
+
     fn image_size(&'static self, _image: &Image) -> IntSize {
         #[cfg(not(no_qt))]
         {
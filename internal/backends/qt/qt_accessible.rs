@@ -0,0 +1,372 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module exposes the accessibility tree of the windows to Qt's accessibility framework.
+
+The widget of a window gets a `QAccessibleWidget` whose children are a `QAccessibleInterface` for
+each top level node of the accessibility tree. As the item tree cannot be queried from the
+interfaces, the interfaces hold a copy of the properties of their node. The properties read to
+build the accessibility tree are tracked, and the copies are only updated when one of them changed.
+The interfaces of the items are kept as long as their item exists, and the changes are notified to
+the assistive technologies.
+*/
+
+// cspell:ignore qrectf
+
+use cpp::*;
+use i_slint_core::accessibility::{accessibility_tree, AccessibleNode};
+use i_slint_core::items::AccessibleRole;
+use i_slint_core::properties::{PropertyChangeHandler, PropertyTracker};
+use i_slint_core::window::Window;
+use std::ffi::c_void;
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+cpp! {{
+    #include <QtGui/QAccessible>
+    #include <QtWidgets/QAccessibleWidget>
+    #include <map>
+
+    /// The accessible interface of an item. It is owned by the SlintAccessibleWidget of its window.
+    struct SlintAccessibleItem : QAccessibleInterface {
+        QPointer<QWidget> widget;
+        QAccessibleInterface *parent_interface = nullptr;
+        QList<SlintAccessibleItem *> children;
+        QAccessible::Role item_role = QAccessible::NoRole;
+        QString label;
+        QString description;
+        QString value;
+        /// The geometry of the item, relative to the widget
+        QRectF geometry;
+        bool checked = false;
+        QAccessible::Id id = 0;
+
+        // State of the update, see SlintAccessibleWidget::update_item
+        QList<SlintAccessibleItem *> new_children;
+        bool used = false;
+
+        bool isValid() const override { return widget; }
+        QObject *object() const override { return nullptr; }
+        QWindow *window() const override {
+            return widget ? widget->window()->windowHandle() : nullptr;
+        }
+
+        QAccessibleInterface *parent() const override { return parent_interface; }
+        int childCount() const override { return children.size(); }
+        QAccessibleInterface *child(int index) const override {
+            return children.value(index);
+        }
+        int indexOfChild(const QAccessibleInterface *child) const override {
+            for (int i = 0; i < children.size(); ++i) {
+                if (children[i] == child)
+                    return i;
+            }
+            return -1;
+        }
+        QAccessibleInterface *childAt(int x, int y) const override {
+            // The last children are drawn on top
+            for (int i = children.size() - 1; i >= 0; --i) {
+                if (children[i]->rect().contains(x, y))
+                    return children[i];
+            }
+            return nullptr;
+        }
+
+        QString text(QAccessible::Text t) const override {
+            switch (t) {
+            case QAccessible::Name:
+                return label;
+            case QAccessible::Description:
+                return description;
+            case QAccessible::Value:
+                return value;
+            default:
+                return {};
+            }
+        }
+        void setText(QAccessible::Text, const QString &) override { }
+
+        QRect rect() const override {
+            if (!widget)
+                return {};
+            auto r = geometry.toAlignedRect();
+            return QRect(widget->mapToGlobal(r.topLeft()), r.size());
+        }
+        QAccessible::Role role() const override { return item_role; }
+        QAccessible::State state() const override {
+            QAccessible::State state;
+            if (!widget || !widget->isVisible())
+                state.invisible = true;
+            if (item_role == QAccessible::CheckBox)
+                state.checkable = true;
+            if (item_role == QAccessible::EditableText)
+                state.editable = true;
+            state.checked = checked;
+            return state;
+        }
+    };
+
+    /// The accessible interface of the widget of a window. Its children are the interfaces of the
+    /// top level nodes of the accessibility tree, followed by the ones of the child widgets.
+    struct SlintAccessibleWidget : QAccessibleWidget {
+        QList<SlintAccessibleItem *> items;
+        std::map<std::pair<quintptr, quintptr>, SlintAccessibleItem *> items_by_key;
+        QList<SlintAccessibleItem *> new_items;
+
+        SlintAccessibleWidget(QWidget *widget) : QAccessibleWidget(widget, QAccessible::Client) {
+            // The items are only updated while an assistive technology is active
+            QCoreApplication::postEvent(widget, new QEvent(SlintAccessibilityUpdate));
+        }
+        ~SlintAccessibleWidget() {
+            for (const auto &entry : items_by_key)
+                QAccessible::deleteAccessibleInterface(entry.second->id);
+        }
+
+        int childCount() const override { return items.size() + QAccessibleWidget::childCount(); }
+        QAccessibleInterface *child(int index) const override {
+            if (index >= 0 && index < items.size())
+                return items[index];
+            return QAccessibleWidget::child(index - items.size());
+        }
+        int indexOfChild(const QAccessibleInterface *child) const override {
+            for (int i = 0; i < items.size(); ++i) {
+                if (items[i] == child)
+                    return i;
+            }
+            int index = QAccessibleWidget::indexOfChild(child);
+            return index < 0 ? index : index + items.size();
+        }
+        QAccessibleInterface *childAt(int x, int y) const override {
+            if (auto child = QAccessibleWidget::childAt(x, y))
+                return child;
+            for (int i = items.size() - 1; i >= 0; --i) {
+                if (items[i]->rect().contains(x, y))
+                    return items[i];
+            }
+            return nullptr;
+        }
+
+        void begin_update() {
+            new_items.clear();
+            for (const auto &entry : items_by_key)
+                entry.second->used = false;
+        }
+
+        /// Creates or updates the interface of an item, and appends it to the children of its
+        /// parent, or to the top level items if parent is null.
+        SlintAccessibleItem *update_item(SlintAccessibleItem *parent,
+                                         std::pair<quintptr, quintptr> key, QAccessible::Role role,
+                                         const QString &label, const QString &description,
+                                         const QString &value, const QRectF &geometry,
+                                         bool checked)
+        {
+            auto &item = items_by_key[key];
+            bool is_new = !item;
+            if (is_new) {
+                item = new SlintAccessibleItem;
+                item->widget = widget();
+                item->id = QAccessible::registerAccessibleInterface(item);
+            }
+            if (item->used) {
+                // The same item can't be twice in the tree
+                return item;
+            }
+            item->used = true;
+            item->new_children.clear();
+            item->parent_interface = parent ? static_cast<QAccessibleInterface *>(parent) : this;
+            (parent ? parent->new_children : new_items).append(item);
+
+            bool label_changed = item->label != label;
+            bool description_changed = item->description != description;
+            bool value_changed = item->value != value;
+            bool checked_changed = item->checked != checked;
+            item->item_role = role;
+            item->label = label;
+            item->description = description;
+            item->value = value;
+            item->geometry = geometry;
+            item->checked = checked;
+
+            if (!is_new) {
+                if (label_changed) {
+                    QAccessibleEvent event(item, QAccessible::NameChanged);
+                    QAccessible::updateAccessibility(&event);
+                }
+                if (description_changed) {
+                    QAccessibleEvent event(item, QAccessible::DescriptionChanged);
+                    QAccessible::updateAccessibility(&event);
+                }
+                if (value_changed) {
+                    QAccessibleValueChangeEvent event(item, value);
+                    QAccessible::updateAccessibility(&event);
+                }
+                if (checked_changed) {
+                    QAccessible::State changed;
+                    changed.checked = true;
+                    QAccessibleStateChangeEvent event(item, changed);
+                    QAccessible::updateAccessibility(&event);
+                }
+            }
+            return item;
+        }
+
+        /// Deletes the interfaces of the items that were not updated and notifies the new children
+        void end_update() {
+            // Replace all the children before sending any event, so that no interface refers
+            // to a deleted one when the assistive technologies query the tree.
+            QList<QAccessibleInterface *> reordered;
+            for (auto it = items_by_key.begin(); it != items_by_key.end();) {
+                auto item = it->second;
+                if (!item->used) {
+                    QAccessible::deleteAccessibleInterface(item->id);
+                    it = items_by_key.erase(it);
+                    continue;
+                }
+                if (item->children != item->new_children) {
+                    item->children = item->new_children;
+                    reordered.append(item);
+                }
+                ++it;
+            }
+            if (items != new_items) {
+                items = new_items;
+                reordered.append(this);
+            }
+            for (auto interface : reordered) {
+                QAccessibleEvent event(interface, QAccessible::ObjectReorder);
+                QAccessible::updateAccessibility(&event);
+            }
+        }
+    };
+
+    QAccessibleInterface *slint_accessible_factory(const QString &, QObject *object) {
+        if (auto widget = dynamic_cast<SlintWidget *>(object))
+            return new SlintAccessibleWidget(widget);
+        return nullptr;
+    }
+}}
+
+/// Installs the factory that creates the accessible interface of the widgets of the windows
+pub(crate) fn install_factory() {
+    cpp! {unsafe [] {
+        static bool installed = false;
+        if (!installed) {
+            QAccessible::installFactory(slint_accessible_factory);
+            installed = true;
+        }
+    }}
+}
+
+/// Posts an event to the widget of a window to update its accessible items
+pub(crate) struct UpdateRequest(NonNull<()>);
+
+impl PropertyChangeHandler for UpdateRequest {
+    fn notify(&self) {
+        let widget_ptr = self.0;
+        cpp! {unsafe [widget_ptr as "QWidget*"] {
+            QCoreApplication::postEvent(widget_ptr, new QEvent(SlintAccessibilityUpdate));
+        }}
+    }
+}
+
+/// Tracks the properties read when building the accessibility tree of a window. It is separate
+/// from the redraw tracker, so that changing an accessible property doesn't repaint the window.
+pub(crate) type AccessibleItemsTracker = PropertyTracker<UpdateRequest>;
+
+pub(crate) fn new_tracker(widget_ptr: NonNull<()>) -> Pin<Box<AccessibleItemsTracker>> {
+    Box::pin(PropertyTracker::new_with_change_handler(UpdateRequest(widget_ptr)))
+}
+
+/// Updates the accessible interfaces of the items of the window if the accessibility tree changed
+/// since the last update. Does nothing unless an assistive technology is active.
+pub(crate) fn update_accessible_items(
+    widget_ptr: NonNull<()>,
+    window: &Window,
+    tracker: Pin<&AccessibleItemsTracker>,
+) {
+    if !tracker.is_dirty() {
+        return;
+    }
+    let interface = cpp! {unsafe [widget_ptr as "QWidget*"] -> *mut c_void as "SlintAccessibleWidget*" {
+        if (!QAccessible::isActive())
+            return nullptr;
+        return dynamic_cast<SlintAccessibleWidget *>(
+                QAccessible::queryAccessibleInterface(widget_ptr));
+    }};
+    if interface.is_null() {
+        // The tracker stays dirty, so the items are updated once the interface is created
+        return;
+    }
+    let nodes = tracker.evaluate_as_dependency_root(|| accessibility_tree(&window.component()));
+    cpp! {unsafe [interface as "SlintAccessibleWidget*"] {
+        interface->begin_update();
+    }}
+    for node in &nodes {
+        update_item(interface, std::ptr::null_mut(), node);
+    }
+    cpp! {unsafe [interface as "SlintAccessibleWidget*"] {
+        interface->end_update();
+    }}
+}
+
+fn update_item(interface: *mut c_void, parent: *mut c_void, node: &AccessibleNode) {
+    let (component_key, index_key) = node.item_key();
+    // Index in the `roles` array below
+    let role: u32 = match node.role {
+        AccessibleRole::none => 0,
+        AccessibleRole::button => 1,
+        AccessibleRole::checkbox => 2,
+        AccessibleRole::combobox => 3,
+        AccessibleRole::list => 4,
+        AccessibleRole::list_item => 5,
+        AccessibleRole::progress_indicator => 6,
+        AccessibleRole::slider => 7,
+        AccessibleRole::spinbox => 8,
+        AccessibleRole::tab => 9,
+        AccessibleRole::text => 10,
+        AccessibleRole::text_input => 11,
+    };
+    let label: qttypes::QString = node.label.as_str().into();
+    let description: qttypes::QString = node.description.as_str().into();
+    let value: qttypes::QString = node.value.as_str().into();
+    let geometry = qttypes::QRectF {
+        x: node.geometry.origin.x as _,
+        y: node.geometry.origin.y as _,
+        width: node.geometry.width() as _,
+        height: node.geometry.height() as _,
+    };
+    let checked = node.checked;
+    let item = cpp! {unsafe [
+        interface as "SlintAccessibleWidget*",
+        parent as "SlintAccessibleItem*",
+        component_key as "quintptr",
+        index_key as "quintptr",
+        role as "uint",
+        label as "QString",
+        description as "QString",
+        value as "QString",
+        geometry as "QRectF",
+        checked as "bool"
+    ] -> *mut c_void as "SlintAccessibleItem*" {
+        static const QAccessible::Role roles[] = {
+            QAccessible::Client,
+            QAccessible::Button,
+            QAccessible::CheckBox,
+            QAccessible::ComboBox,
+            QAccessible::List,
+            QAccessible::ListItem,
+            QAccessible::ProgressBar,
+            QAccessible::Slider,
+            QAccessible::SpinBox,
+            QAccessible::PageTab,
+            QAccessible::StaticText,
+            QAccessible::EditableText,
+        };
+        return interface->update_item(parent, {component_key, index_key}, roles[role], label,
+                                      description, value, geometry, checked);
+    }};
+    for child in &node.children {
+        update_item(interface, item, child);
+    }
+}
@@ -208,6 +208,13 @@ fn input_event(
                     // TODO
                     InputEventResult::EventAccepted
                 }
+                MouseEvent::Touch { .. } => {
+                    if data.pressed != 0 {
+                        InputEventResult::GrabMouse
+                    } else {
+                        InputEventResult::EventIgnored
+                    }
+                }
             };
             self.data.set(data);
             result
@@ -182,6 +182,13 @@ fn input_event(
                 debug_assert_ne!(button, PointerEventButton::left);
                 InputEventResult::EventIgnored
             }
+            MouseEvent::Touch { .. } => {
+                if data.pressed != 0 {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
         };
         data.active_controls = new_control;
 
@@ -171,6 +171,7 @@ fn input_event(
                 }
                 MouseEvent::MouseMoved { .. } => false,
                 MouseEvent::MouseWheel { .. } => false, // TODO
+                MouseEvent::Touch { .. } => false,
             };
         data.active_controls = new_control;
         if changed {
@@ -393,7 +393,7 @@ fn input_event(
         Self::FIELD_OFFSETS.pressed.apply_pin(self).set(match event {
             MouseEvent::MousePressed { .. } => true,
             MouseEvent::MouseExit | MouseEvent::MouseReleased { .. } => false,
-            MouseEvent::MouseMoved { .. } => {
+            MouseEvent::MouseMoved { .. } | MouseEvent::Touch { .. } => {
                 return if self.pressed() {
                     InputEventResult::GrabMouse
                 } else {
@@ -9,7 +9,7 @@
     Brush, Color, FPSCounter, FontRequest, Image, Point, Rect, RenderingCache, SharedImageBuffer,
     Size,
 };
-use i_slint_core::input::{KeyEvent, KeyEventType, MouseEvent};
+use i_slint_core::input::{DropEvent, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
 use i_slint_core::item_rendering::{CachedRenderingData, ItemRenderer};
 use i_slint_core::items::{
     self, FillRule, ImageRendering, ItemRef, MouseCursor, PointerEventButton, TextOverflow,
@@ -69,12 +69,32 @@ struct TimerHandler : QObject {
 
     };
 
+    // Returns the mime type of a drag as passed to the DropArea: dropped files are a text/uri-list
+    static QString drag_mime_type(const QMimeData *mime_data) {
+        return mime_data->hasUrls() ? QStringLiteral("text/uri-list") : QStringLiteral("text/plain");
+    }
+
+    static QString drag_data(const QMimeData *mime_data) {
+        if (mime_data->hasUrls()) {
+            QStringList urls;
+            for (const auto &url : mime_data->urls())
+                urls << QString::fromUtf8(url.toEncoded());
+            return urls.join(QStringLiteral("\r\n"));
+        }
+        return mime_data->text();
+    }
+
+    /// Posted to the widget of a window to update its accessible items
+    static const QEvent::Type SlintAccessibilityUpdate = QEvent::Type(QEvent::User + 1);
+
     struct SlintWidget : QWidget {
         void *rust_window;
 
         SlintWidget() {
             setMouseTracking(true);
             setFocusPolicy(Qt::StrongFocus);
+            setAttribute(Qt::WA_InputMethodEnabled);
+            setAcceptDrops(true);
         }
 
         void paintEvent(QPaintEvent *) override {
@@ -85,6 +105,9 @@ struct SlintWidget : QWidget {
             rust!(Slint_paintEvent [rust_window: &QtWindow as "void*", painter_ptr: &mut QPainter as "QPainter*"] {
                 rust_window.paint_event(painter_ptr)
             });
+            // The text cursor may have moved, let the input method move its candidate window
+            if (hasFocus())
+                QGuiApplication::inputMethod()->update(Qt::ImCursorRectangle);
         }
 
         void resizeEvent(QResizeEvent *event) override {
@@ -97,9 +120,11 @@ struct SlintWidget : QWidget {
         void mousePressEvent(QMouseEvent *event) override {
             QPoint pos = event->pos();
             int button = event->button();
-            rust!(Slint_mousePressEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", button: u32 as "int" ] {
+            uint modifiers = uint(event->modifiers());
+            rust!(Slint_mousePressEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", button: u32 as "int", modifiers: u32 as "uint" ] {
                 let pos = Point::new(pos.x as _, pos.y as _);
                 let button = from_qt_button(button);
+                rust_window.self_weak.upgrade().unwrap().set_current_keyboard_modifiers(from_qt_modifiers(modifiers));
                 rust_window.mouse_event(MouseEvent::MousePressed{ pos, button })
             });
         }
@@ -139,6 +164,44 @@ struct SlintWidget : QWidget {
             });
         }
 
+        void dragEnterEvent(QDragEnterEvent *event) override {
+            if (!event->mimeData()->hasUrls() && !event->mimeData()->hasText())
+                return;
+            dragMoveEvent(event);
+            // Accept the enter event even if there is no DropArea at this position, otherwise
+            // we wouldn't get the following move events
+            event->accept();
+        }
+        void dragMoveEvent(QDragMoveEvent *event) override {
+            QPoint pos = event->pos();
+            QString mime_type = drag_mime_type(event->mimeData());
+            QString data = drag_data(event->mimeData());
+            bool accepted = rust!(Slint_dragMoveEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", mime_type: qttypes::QString as "QString", data: qttypes::QString as "QString"] -> bool as "bool" {
+                rust_window.drag_move_event(drop_event(pos, mime_type.clone(), data.clone()))
+            });
+            if (accepted)
+                event->acceptProposedAction();
+            else
+                event->ignore();
+        }
+        void dragLeaveEvent(QDragLeaveEvent *) override {
+            rust!(Slint_dragLeaveEvent [rust_window: &QtWindow as "void*"] {
+                rust_window.drag_leave_event()
+            });
+        }
+        void dropEvent(QDropEvent *event) override {
+            QPoint pos = event->pos();
+            QString mime_type = drag_mime_type(event->mimeData());
+            QString data = drag_data(event->mimeData());
+            bool accepted = rust!(Slint_dropEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", mime_type: qttypes::QString as "QString", data: qttypes::QString as "QString"] -> bool as "bool" {
+                rust_window.drop_event(drop_event(pos, mime_type.clone(), data.clone()))
+            });
+            if (accepted)
+                event->acceptProposedAction();
+            else
+                event->ignore();
+        }
+
         void keyPressEvent(QKeyEvent *event) override {
             uint modifiers = uint(event->modifiers());
             QString text =  event->text();
@@ -156,11 +219,33 @@ struct SlintWidget : QWidget {
             });
         }
 
+        void inputMethodEvent(QInputMethodEvent *event) override {
+            QString commit_string = event->commitString();
+            QString preedit_string = event->preeditString();
+            rust!(Slint_inputMethodEvent [rust_window: &QtWindow as "void*", commit_string: qttypes::QString as "QString", preedit_string: qttypes::QString as "QString"] {
+                rust_window.input_method_event(commit_string.clone(), preedit_string.clone());
+            });
+            event->accept();
+        }
+
+        QVariant inputMethodQuery(Qt::InputMethodQuery query) const override {
+            if (query == Qt::ImCursorRectangle) {
+                return rust!(Slint_inputMethodCursorRect [rust_window: &QtWindow as "void*"] -> qttypes::QRectF as "QRectF" {
+                    rust_window.input_method_cursor_rect()
+                });
+            }
+            return QWidget::inputMethodQuery(query);
+        }
+
         void customEvent(QEvent *event) override {
             if (event->type() == QEvent::User) {
                 rust!(Slint_updateWindowProps [rust_window: &QtWindow as "void*"]{
                    if let Some(window) = rust_window.self_weak.upgrade() { window.update_window_properties() }
                 });
+            } else if (event->type() == SlintAccessibilityUpdate) {
+                rust!(Slint_updateAccessibleItems [rust_window: &QtWindow as "void*"]{
+                    rust_window.update_accessible_items()
+                });
             } else {
                 QWidget::customEvent(event);
             }
@@ -196,9 +281,7 @@ struct SlintWidget : QWidget {
     };
 
     // Helper function used for the TextInput layouting
-    //
-    // if line_for_y_pos > 0, then the function will return the line at this y position
-    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, int line_for_y_pos = -1) {
+    static void do_text_layout(QTextLayout &layout, int flags, const QRectF &rect) {
         QTextOption options;
         options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
         layout.setTextOption(options);
@@ -207,7 +290,6 @@ struct SlintWidget : QWidget {
         int leading = fm.leading();
         qreal height = 0;
         layout.beginLayout();
-        int count = 0;
         while(1) {
             auto line = layout.createLine();
             if (!line.isValid())
@@ -216,10 +298,6 @@ struct SlintWidget : QWidget {
             height += leading;
             line.setPosition(QPointF(0, height));
             height += line.height();
-            if (line_for_y_pos >= 0 && height > line_for_y_pos) {
-                return count;
-            }
-            count++;
         }
         layout.endLayout();
         if (flags & Qt::AlignVCenter) {
@@ -227,7 +305,6 @@ struct SlintWidget : QWidget {
         } else if (flags & Qt::AlignBottom) {
             layout.setPosition(QPointF(0, rect.height() - height));
         }
-        return -1;
     }
 }}
 
@@ -280,7 +357,13 @@ pub fn set_fill_rule(&mut self, rule: key_generated::Qt_FillRule) {
     }
 }
 
-fn into_qbrush(brush: i_slint_core::Brush) -> qttypes::QBrush {
+/// Convert the brush to a QBrush. The width and height are the size of the shape to fill, in the
+/// painter's coordinate, which is needed for radial and conic gradients.
+fn into_qbrush(
+    brush: i_slint_core::Brush,
+    width: qttypes::qreal,
+    height: qttypes::qreal,
+) -> qttypes::QBrush {
     match brush {
         i_slint_core::Brush::SolidColor(color) => {
             let color: u32 = color.as_argb_encoded();
@@ -311,10 +394,63 @@ fn into_qbrush(brush: i_slint_core::Brush) -> qttypes::QBrush {
                 return QBrush(qlg);
             }}
         }
+        i_slint_core::Brush::RadialGradient(g) => {
+            let center = qttypes::QPointF { x: width / 2., y: height / 2. };
+            let radius: qttypes::qreal = (width * width + height * height).sqrt() / 2.;
+            cpp_class!(unsafe struct QRadialGradient as "QRadialGradient");
+            let mut qrg = cpp! {
+                unsafe [center as "QPointF", radius as "qreal"] -> QRadialGradient as "QRadialGradient" {
+                    return QRadialGradient(center, radius);
+                }
+            };
+            for s in g.stops() {
+                let pos: f32 = s.position;
+                let color: u32 = s.color.as_argb_encoded();
+                cpp! {unsafe [mut qrg as "QRadialGradient", pos as "float", color as "QRgb"] {
+                    qrg.setColorAt(pos, QColor::fromRgba(color));
+                }};
+            }
+            cpp! {unsafe [qrg as "QRadialGradient"] -> qttypes::QBrush as "QBrush" {
+                return QBrush(qrg);
+            }}
+        }
+        i_slint_core::Brush::ConicGradient(g) => {
+            let center = qttypes::QPointF { x: width / 2., y: height / 2. };
+            cpp_class!(unsafe struct QConicalGradient as "QConicalGradient");
+            // QConicalGradient starts at 3 o'clock and goes counter-clockwise, while our
+            // gradient starts at the top and goes clockwise.
+            let mut qcg = cpp! {
+                unsafe [center as "QPointF"] -> QConicalGradient as "QConicalGradient" {
+                    return QConicalGradient(center, 90);
+                }
+            };
+            for s in g.stops() {
+                let pos: f32 = 1. - s.position;
+                let color: u32 = s.color.as_argb_encoded();
+                cpp! {unsafe [mut qcg as "QConicalGradient", pos as "float", color as "QRgb"] {
+                    qcg.setColorAt(pos, QColor::fromRgba(color));
+                }};
+            }
+            cpp! {unsafe [qcg as "QConicalGradient"] -> qttypes::QBrush as "QBrush" {
+                return QBrush(qcg);
+            }}
+        }
         _ => qttypes::QBrush::default(),
     }
 }
 
+fn drop_event(
+    pos: qttypes::QPoint,
+    mime_type: qttypes::QString,
+    data: qttypes::QString,
+) -> DropEvent {
+    DropEvent {
+        mime_type: String::from(mime_type).into(),
+        data: String::from(data).into(),
+        position: Point::new(pos.x as _, pos.y as _),
+    }
+}
+
 fn from_qt_button(qt_button: u32) -> PointerEventButton {
     match qt_button {
         1 => PointerEventButton::left,
@@ -324,6 +460,15 @@ fn from_qt_button(qt_button: u32) -> PointerEventButton {
     }
 }
 
+fn from_qt_modifiers(qt_modifiers: u32) -> KeyboardModifiers {
+    KeyboardModifiers {
+        control: (qt_modifiers & key_generated::Qt_KeyboardModifier_ControlModifier) != 0,
+        alt: (qt_modifiers & key_generated::Qt_KeyboardModifier_AltModifier) != 0,
+        shift: (qt_modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier) != 0,
+        meta: (qt_modifiers & key_generated::Qt_KeyboardModifier_MetaModifier) != 0,
+    }
+}
+
 /// Given a position offset and an object of a given type that has x,y,width,height properties,
 /// create a QRectF that fits it.
 macro_rules! get_geometry {
@@ -371,11 +516,12 @@ struct QtItemRenderer<'a> {
 
 impl ItemRenderer for QtItemRenderer<'_> {
     fn draw_rectangle(&mut self, rect: Pin<&items::Rectangle>) {
-        let brush: qttypes::QBrush = into_qbrush(rect.background());
-        let rect: qttypes::QRectF = get_geometry!(items::Rectangle, rect);
+        let geometry: qttypes::QRectF = get_geometry!(items::Rectangle, rect);
+        let brush: qttypes::QBrush =
+            into_qbrush(rect.background(), geometry.width, geometry.height);
         let painter: &mut QPainter = &mut *self.painter;
-        cpp! { unsafe [painter as "QPainter*", brush as "QBrush", rect as "QRectF"] {
-            painter->fillRect(rect, brush);
+        cpp! { unsafe [painter as "QPainter*", brush as "QBrush", geometry as "QRectF"] {
+            painter->fillRect(geometry, brush);
         }}
     }
 
@@ -428,7 +574,7 @@ fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>) {
 
     fn draw_text(&mut self, text: std::pin::Pin<&items::Text>) {
         let rect: qttypes::QRectF = get_geometry!(items::Text, text);
-        let fill_brush: qttypes::QBrush = into_qbrush(text.color());
+        let fill_brush: qttypes::QBrush = into_qbrush(text.color(), rect.width, rect.height);
         let mut string: qttypes::QString = text.text().as_str().into();
         let font: QFont =
             get_font(text.unresolved_font_request().merge(&self.default_font_properties));
@@ -507,14 +653,15 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>) {
 
     fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
-        let fill_brush: qttypes::QBrush = into_qbrush(text_input.color());
+        let fill_brush: qttypes::QBrush = into_qbrush(text_input.color(), rect.width, rect.height);
         let selection_foreground_color: u32 =
             text_input.selection_foreground_color().as_argb_encoded();
         let selection_background_color: u32 =
             text_input.selection_background_color().as_argb_encoded();
 
-        let text = text_input.text();
-        let mut string: qttypes::QString = text.as_str().into();
+        let visual_representation = text_input.visual_representation();
+        let text = visual_representation.text.as_str();
+        let mut string: qttypes::QString = text.into();
         let font: QFont =
             get_font(text_input.unresolved_font_request().merge(&self.default_font_properties));
         let flags = match text_input.horizontal_alignment() {
@@ -532,22 +679,21 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
 
         // convert byte offsets to offsets in Qt UTF-16 encoded string, as that's
         // what QTextLayout expects.
-        let cursor_position_as_offset: i32 = text_input.cursor_position();
-        let anchor_position_as_offset: i32 = text_input.anchor_position();
-        let cursor_position: i32 = if cursor_position_as_offset > 0 {
-            utf8_byte_offset_to_utf16_units(text.as_str(), cursor_position_as_offset as usize)
-                as i32
-        } else {
-            0
-        };
-        let anchor_position: i32 = if anchor_position_as_offset > 0 {
-            utf8_byte_offset_to_utf16_units(text.as_str(), anchor_position_as_offset as usize)
-                as i32
-        } else {
-            0
-        };
-
-        let text_cursor_width: f32 = if text_input.cursor_visible() && text_input.enabled() {
+        let selection_start: i32 =
+            utf8_byte_offset_to_utf16_units(text, visual_representation.selection_range.start)
+                as i32;
+        let selection_end: i32 =
+            utf8_byte_offset_to_utf16_units(text, visual_representation.selection_range.end) as i32;
+        let preedit_start: i32 =
+            utf8_byte_offset_to_utf16_units(text, visual_representation.preedit_range.start) as i32;
+        let preedit_end: i32 =
+            utf8_byte_offset_to_utf16_units(text, visual_representation.preedit_range.end) as i32;
+        let cursor_position: i32 = visual_representation
+            .cursor_position
+            .map_or(0, |cursor_position| utf8_byte_offset_to_utf16_units(text, cursor_position))
+            as i32;
+
+        let text_cursor_width: f32 = if visual_representation.cursor_position.is_some() {
             text_input.text_cursor_width()
         } else {
             0.
@@ -567,7 +713,10 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
                 single_line as "bool",
                 font as "QFont",
                 cursor_position as "int",
-                anchor_position as "int",
+                selection_start as "int",
+                selection_end as "int",
+                preedit_start as "int",
+                preedit_end as "int",
                 text_cursor_width as "float"] {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
@@ -576,13 +725,22 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
             do_text_layout(layout, flags, rect);
             painter->setPen(QPen(fill_brush, 0));
             QVector<QTextLayout::FormatRange> selections;
-            if (anchor_position != cursor_position) {
+            if (selection_start != selection_end) {
                 QTextCharFormat fmt;
                 fmt.setBackground(QColor::fromRgba(selection_background_color));
                 fmt.setForeground(QColor::fromRgba(selection_foreground_color));
                 selections << QTextLayout::FormatRange{
-                    std::min(anchor_position, cursor_position),
-                    std::abs(anchor_position - cursor_position),
+                    selection_start,
+                    selection_end - selection_start,
+                    fmt
+                };
+            }
+            if (preedit_start != preedit_end) {
+                QTextCharFormat fmt;
+                fmt.setFontUnderline(true);
+                selections << QTextLayout::FormatRange{
+                    preedit_start,
+                    preedit_end - preedit_start,
                     fmt
                 };
             }
@@ -600,8 +758,10 @@ fn draw_path(&mut self, path: Pin<&items::Path>) {
         }
         // FIXME: handle width/height
         //let rect: qttypes::QRectF = get_geometry!(pos, items::Path, path);
-        let fill_brush: qttypes::QBrush = into_qbrush(path.fill());
-        let stroke_brush: qttypes::QBrush = into_qbrush(path.stroke());
+        let (width, height): (qttypes::qreal, qttypes::qreal) =
+            (path.width() as _, path.height() as _);
+        let fill_brush: qttypes::QBrush = into_qbrush(path.fill(), width, height);
+        let stroke_brush: qttypes::QBrush = into_qbrush(path.stroke(), width, height);
         let stroke_width: f32 = path.stroke_width();
         let (offset, path_events) = path.fitted_path_events();
         let pos = qttypes::QPoint { x: offset.x as _, y: offset.y as _ };
@@ -809,7 +969,7 @@ fn draw_cached_pixmap(
     }
 
     fn draw_string(&mut self, string: &str, color: Color) {
-        let fill_brush: qttypes::QBrush = into_qbrush(color.into());
+        let fill_brush: qttypes::QBrush = into_qbrush(color.into(), 0., 0.);
         let mut string: qttypes::QString = string.into();
         let font: QFont = get_font(self.default_font_properties.clone());
         let painter: &mut QPainter = &mut *self.painter;
@@ -843,6 +1003,13 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         }}
     }
 
+    fn scale(&mut self, x: f32, y: f32) {
+        let painter: &mut QPainter = &mut *self.painter;
+        cpp! { unsafe [painter as "QPainter*", x as "float", y as "float"] {
+            painter->scale(x, y);
+        }}
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let painter: &mut QPainter = &mut *self.painter;
         cpp! { unsafe [painter as "QPainter*", opacity as "float"] {
@@ -1029,7 +1196,9 @@ fn draw_image_impl(
                 .map_or(QtRenderingCacheItem::Invalid, |mut pixmap: qttypes::QPixmap| {
                     let colorize = colorize_property.map_or(Brush::default(), |c| c.get());
                     if !colorize.is_transparent() {
-                        let brush: qttypes::QBrush = into_qbrush(colorize);
+                        let size = pixmap.size();
+                        let brush: qttypes::QBrush =
+                            into_qbrush(colorize, size.width as _, size.height as _);
                         cpp!(unsafe [mut pixmap as "QPixmap", brush as "QBrush"] {
                             QPainter p(&pixmap);
                             p.setCompositionMode(QPainter::CompositionMode_SourceIn);
@@ -1075,8 +1244,8 @@ fn draw_rectangle_impl(
         mut border_width: f32,
         border_radius: f32,
     ) {
-        let brush: qttypes::QBrush = into_qbrush(brush);
-        let border_color: qttypes::QBrush = into_qbrush(border_color);
+        let brush: qttypes::QBrush = into_qbrush(brush, rect.width, rect.height);
+        let border_color: qttypes::QBrush = into_qbrush(border_color, rect.width, rect.height);
         adjust_rect_and_border_for_inner_drawing(&mut rect, &mut border_width);
         cpp! { unsafe [painter as "QPainter*", brush as "QBrush",  border_color as "QBrush", border_width as "float", border_radius as "float", rect as "QRectF"] {
             painter->setPen(border_width > 0 ? QPen(border_color, border_width) : Qt::NoPen);
@@ -1093,6 +1262,8 @@ fn draw_rectangle_impl(
 cpp_class!(unsafe struct QWidgetPtr as "std::unique_ptr<QWidget>");
 
 pub struct QtWindow {
+    /// Dropped before the widget, as its change handler posts events to the widget
+    accessible_items_tracker: Pin<Box<crate::qt_accessible::AccessibleItemsTracker>>,
     widget_ptr: QWidgetPtr,
     pub(crate) self_weak: Weak<i_slint_core::window::Window>,
 
@@ -1107,7 +1278,12 @@ pub fn new(window_weak: &Weak<i_slint_core::window::Window>) -> Rc<Self> {
             ensure_initialized(true);
             return std::make_unique<SlintWidget>();
         }};
+        crate::qt_accessible::install_factory();
+        let accessible_items_tracker = crate::qt_accessible::new_tracker(unsafe {
+            std::mem::transmute_copy::<QWidgetPtr, NonNull<()>>(&widget_ptr)
+        });
         let rc = Rc::new(QtWindow {
+            accessible_items_tracker,
             widget_ptr,
             self_weak: window_weak.clone(),
             fps_counter: FPSCounter::new(),
@@ -1128,6 +1304,16 @@ fn widget_ptr(&self) -> NonNull<()> {
         unsafe { std::mem::transmute_copy::<QWidgetPtr, NonNull<_>>(&self.widget_ptr) }
     }
 
+    fn update_accessible_items(&self) {
+        if let Some(window) = self.self_weak.upgrade() {
+            crate::qt_accessible::update_accessible_items(
+                self.widget_ptr(),
+                &window,
+                self.accessible_items_tracker.as_ref(),
+            );
+        }
+    }
+
     fn paint_event(&self, painter: &mut QPainter) {
         let runtime_window = self.self_weak.upgrade().unwrap();
         runtime_window.clone().draw_contents(|components| {
@@ -1181,15 +1367,28 @@ fn mouse_event(&self, event: MouseEvent) {
         timer_event();
     }
 
+    fn drag_move_event(&self, event: DropEvent) -> bool {
+        i_slint_core::animations::update_animations();
+        let accepted = self.self_weak.upgrade().unwrap().process_drag_move(event);
+        timer_event();
+        accepted
+    }
+
+    fn drag_leave_event(&self) {
+        self.self_weak.upgrade().unwrap().cancel_drag();
+    }
+
+    fn drop_event(&self, event: DropEvent) -> bool {
+        i_slint_core::animations::update_animations();
+        let accepted = self.self_weak.upgrade().unwrap().process_drop(event);
+        timer_event();
+        accepted
+    }
+
     fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, released: bool) {
         i_slint_core::animations::update_animations();
         let text: String = text.into();
-        let modifiers = i_slint_core::input::KeyboardModifiers {
-            control: (qt_modifiers & key_generated::Qt_KeyboardModifier_ControlModifier) != 0,
-            alt: (qt_modifiers & key_generated::Qt_KeyboardModifier_AltModifier) != 0,
-            shift: (qt_modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier) != 0,
-            meta: (qt_modifiers & key_generated::Qt_KeyboardModifier_MetaModifier) != 0,
-        };
+        let modifiers = from_qt_modifiers(qt_modifiers);
 
         let text = qt_key_to_string(key as key_generated::Qt_Key, text);
 
@@ -1198,11 +1397,43 @@ fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, release
             text,
             modifiers,
         };
-        self.self_weak.upgrade().unwrap().process_key_input(&event);
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        runtime_window.set_current_keyboard_modifiers(modifiers);
+        runtime_window.process_key_input(&event);
 
         timer_event();
     }
 
+    fn input_method_event(
+        &self,
+        commit_string: qttypes::QString,
+        preedit_string: qttypes::QString,
+    ) {
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        let commit_string: String = commit_string.into();
+        if !commit_string.is_empty() {
+            runtime_window.clone().commit_preedit_text(commit_string.into());
+        }
+        let preedit_string: String = preedit_string.into();
+        runtime_window.set_preedit_text(preedit_string.into());
+
+        timer_event();
+    }
+
+    fn input_method_cursor_rect(&self) -> qttypes::QRectF {
+        let rect = self
+            .self_weak
+            .upgrade()
+            .and_then(|runtime_window| runtime_window.text_input_cursor_rect())
+            .unwrap_or_default();
+        qttypes::QRectF {
+            x: rect.origin.x as _,
+            y: rect.origin.y as _,
+            width: rect.width() as _,
+            height: rect.height() as _,
+        }
+    }
+
     fn default_font_properties(&self) -> FontRequest {
         self.self_weak.upgrade().unwrap().default_font_properties()
     }
@@ -1450,18 +1681,20 @@ fn text_input_byte_offset_for_position(
                 copy.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(copy, font);
-            auto line = do_text_layout(layout, flags, rect, pos.y());
-            if (line < 0 || layout.lineCount() <= line)
+            do_text_layout(layout, flags, rect);
+            if (layout.lineCount() == 0)
                 return 0;
+            // Positions above the first line or below the last line are in that line
+            auto local_pos = pos - layout.position();
+            int line = 0;
+            while (line < layout.lineCount() - 1 && layout.lineAt(line).rect().bottom() < local_pos.y())
+                line++;
             QTextLine textLine = layout.lineAt(line);
-            int cur;
-            if (pos.x() > textLine.naturalTextWidth()) {
-                cur = textLine.textStart() + textLine.textLength();
-                if (cur > 0 && string[cur - 1] == '\n')
-                    cur--;
-            } else {
-                cur = textLine.xToCursor(pos.x());
-            }
+            int cur = textLine.xToCursor(local_pos.x());
+            // Positions past the end of a line are before its line break
+            if (cur > textLine.textStart() && cur == textLine.textStart() + textLine.textLength()
+                    && string[cur - 1] == '\n')
+                cur--;
             // convert to an utf8 pos;
             return QStringView(string).left(cur).toUtf8().size();
         }}
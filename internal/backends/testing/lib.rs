@@ -4,31 +4,127 @@
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
 
+use i_slint_core::animations::{Instant, CURRENT_ANIMATION_DRIVER};
 use i_slint_core::component::ComponentRc;
-use i_slint_core::graphics::{Image, IntSize, Point, Size};
-use i_slint_core::window::{PlatformWindow, Window};
+use i_slint_core::graphics::{Image, IntSize, Point, Rgba8Pixel, SharedPixelBuffer, Size};
+use i_slint_core::items::ItemRef;
+use i_slint_core::properties::ChangeTracker;
+use i_slint_core::timers::TimerList;
+use i_slint_core::window::{PlatformWindow, PopupWindow, PopupWindowLocation, Window, WindowRc};
 use i_slint_core::ImageInner;
 use image::GenericImageView;
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
 use std::path::Path;
 use std::pin::Pin;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::sync::Mutex;
+use std::thread::ThreadId;
+
+mod renderer;
+
+pub use renderer::SoftwareRenderer;
+
+enum TestingEvent {
+    Quit,
+    Custom(Box<dyn FnOnce() + Send>),
+}
+
+/// The events posted with `post_event`, keyed by the thread that owns the event loop which
+/// processes them. Like the virtual clock and the timers, each thread has its own event loop,
+/// so that tests running in parallel don't process each other's events.
+/// Only the threads that currently have an event loop have an entry.
+static EVENT_QUEUES: once_cell::sync::Lazy<Mutex<HashMap<ThreadId, VecDeque<TestingEvent>>>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// Removes the event queue of the thread when the thread exits
+struct EventQueueRegistration(ThreadId);
+
+impl Drop for EventQueueRegistration {
+    fn drop(&mut self) {
+        if let Ok(mut queues) = EVENT_QUEUES.lock() {
+            queues.remove(&self.0);
+        }
+    }
+}
+
+thread_local!(static EVENT_QUEUE_REGISTRATION: EventQueueRegistration =
+    EventQueueRegistration(std::thread::current().id()));
+
+/// Registers the current thread as the owner of an event loop
+fn register_event_loop_thread() {
+    EVENT_QUEUE_REGISTRATION.with(|_| ());
+    EVENT_QUEUES.lock().unwrap().entry(std::thread::current().id()).or_default();
+}
+
+/// Removes the event queue of the current thread, once its event loop has ended
+fn unregister_event_loop_thread() {
+    EVENT_QUEUES.lock().unwrap().remove(&std::thread::current().id());
+}
+
+/// Queues the event in the event loop of the given thread. The event is dropped if that
+/// thread has no event loop (anymore).
+fn push_event(thread: ThreadId, event: TestingEvent) {
+    if let Some(queue) = EVENT_QUEUES.lock().unwrap().get_mut(&thread) {
+        queue.push_back(event);
+    }
+}
+
+thread_local!(static VISIBLE_WINDOWS: Cell<usize> = Cell::new(0));
 
-#[derive(Default)]
 pub struct TestingBackend {
     clipboard: Mutex<Option<String>>,
+    /// The thread that created the backend. It runs the events posted with
+    /// `invoke_from_event_loop` from threads that have no event loop of their own.
+    owner_thread: ThreadId,
+}
+
+impl Default for TestingBackend {
+    fn default() -> Self {
+        register_event_loop_thread();
+        Self { clipboard: Default::default(), owner_thread: std::thread::current().id() }
+    }
 }
 
 impl i_slint_core::backend::Backend for TestingBackend {
     fn create_window(&'static self) -> Rc<Window> {
-        Window::new(|_| Rc::new(TestingWindow::default()))
+        register_event_loop_thread();
+        Window::new(|window| {
+            Rc::new(TestingWindow { self_weak: window.clone(), visible: Cell::new(false) })
+        })
     }
 
-    fn run_event_loop(&'static self, _behavior: i_slint_core::backend::EventLoopQuitBehavior) {
-        unimplemented!("running an event loop with the testing backend");
+    /// Runs the event loop against the virtual clock: instead of waiting, the time jumps to the
+    /// next timer or animation frame. The loop returns when it is quit, or when nothing is left
+    /// that could ever happen (no pending events, timers or animations).
+    fn run_event_loop(&'static self, behavior: i_slint_core::backend::EventLoopQuitBehavior) {
+        loop {
+            if process_pending_events() {
+                break;
+            }
+            match behavior {
+                i_slint_core::backend::EventLoopQuitBehavior::QuitOnLastWindowClosed => {
+                    if VISIBLE_WINDOWS.with(|count| count.get()) == 0 {
+                        break;
+                    }
+                }
+                i_slint_core::backend::EventLoopQuitBehavior::QuitOnlyExplicitly => (),
+            }
+            if let Some(timeout) = TimerList::next_timeout() {
+                set_current_tick(timeout);
+            } else if CURRENT_ANIMATION_DRIVER.with(|driver| driver.has_active_animations()) {
+                set_current_tick(i_slint_core::animations::current_tick() + ANIMATION_FRAME);
+            } else {
+                break;
+            }
+        }
+        unregister_event_loop_thread();
     }
 
-    fn quit_event_loop(&'static self) {}
+    fn quit_event_loop(&'static self) {
+        register_event_loop_thread();
+        push_event(std::thread::current().id(), TestingEvent::Quit);
+    }
 
     fn register_font_from_memory(
         &'static self,
@@ -52,8 +148,17 @@ fn clipboard_text(&'static self) -> Option<String> {
         self.clipboard.lock().unwrap().clone()
     }
 
-    fn post_event(&'static self, _event: Box<dyn FnOnce() + Send>) {
-        // The event will never be invoked
+    fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
+        let thread = std::thread::current().id();
+        let has_event_loop = EVENT_QUEUES.lock().unwrap().contains_key(&thread);
+        push_event(
+            if has_event_loop { thread } else { self.owner_thread },
+            TestingEvent::Custom(event),
+        );
+    }
+
+    fn post_event_to_thread(&'static self, thread: ThreadId, event: Box<dyn FnOnce() + Send>) {
+        push_event(thread, TestingEvent::Custom(event));
     }
 
     fn image_size(&'static self, image: &Image) -> IntSize {
@@ -81,15 +186,24 @@ fn duration_since_start(&'static self) -> core::time::Duration {
     }
 }
 
-#[derive(Default)]
-pub struct TestingWindow {}
+pub struct TestingWindow {
+    self_weak: Weak<Window>,
+    visible: Cell<bool>,
+}
 
 impl PlatformWindow for TestingWindow {
     fn show(self: Rc<Self>) {
-        unimplemented!("showing a testing window")
+        // There is nothing to show, the contents are only rendered with `screenshot()`
+        if !self.visible.replace(true) {
+            VISIBLE_WINDOWS.with(|count| count.set(count.get() + 1));
+        }
     }
 
-    fn hide(self: Rc<Self>) {}
+    fn hide(self: Rc<Self>) {
+        if self.visible.replace(false) {
+            VISIBLE_WINDOWS.with(|count| count.set(count.get() - 1));
+        }
+    }
 
     fn request_redraw(&self) {}
 
@@ -99,15 +213,28 @@ fn free_graphics_resources<'a>(
     ) {
     }
 
-    fn show_popup(&self, _popup: &ComponentRc, _position: i_slint_core::graphics::Point) {
-        todo!()
+    fn show_popup(&self, popup: &ComponentRc, position: i_slint_core::graphics::Point) {
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        let size = runtime_window.set_active_popup(PopupWindow {
+            location: PopupWindowLocation::ChildWindow(position),
+            component: popup.clone(),
+        });
+
+        let popup = ComponentRc::borrow_pin(popup);
+        let popup_root = popup.as_ref().get_item_ref(0);
+        if let Some(window_item) = ItemRef::downcast_pin(popup_root) {
+            let width_property =
+                i_slint_core::items::WindowItem::FIELD_OFFSETS.width.apply_pin(window_item);
+            let height_property =
+                i_slint_core::items::WindowItem::FIELD_OFFSETS.height.apply_pin(window_item);
+            width_property.set(size.width);
+            height_property.set(size.height);
+        }
     }
 
     fn request_window_properties_update(&self) {}
 
-    fn apply_window_properties(&self, _window_item: Pin<&i_slint_core::items::WindowItem>) {
-        todo!()
-    }
+    fn apply_window_properties(&self, _window_item: Pin<&i_slint_core::items::WindowItem>) {}
 
     fn apply_geometry_constraint(
         &self,
@@ -124,23 +251,23 @@ fn text_size(
         text: &str,
         _max_width: Option<f32>,
     ) -> Size {
-        Size::new(text.len() as f32 * 10., 10.)
+        Size::new(text.len() as f32 * renderer::GLYPH_SIZE, renderer::GLYPH_SIZE)
     }
 
     fn text_input_byte_offset_for_position(
         &self,
-        _text_input: Pin<&i_slint_core::items::TextInput>,
-        _pos: Point,
+        text_input: Pin<&i_slint_core::items::TextInput>,
+        pos: Point,
     ) -> usize {
-        0
+        renderer::text_input_byte_offset_for_position(text_input, pos)
     }
 
     fn text_input_position_for_byte_offset(
         &self,
-        _text_input: Pin<&i_slint_core::items::TextInput>,
-        _byte_offset: usize,
+        text_input: Pin<&i_slint_core::items::TextInput>,
+        byte_offset: usize,
     ) -> Point {
-        Default::default()
+        renderer::text_input_position_for_byte_offset(text_input, byte_offset)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -154,3 +281,101 @@ fn as_any(&self) -> &dyn std::any::Any {
 pub fn init() {
     i_slint_core::backend::instance_or_init(|| Box::new(TestingBackend::default()));
 }
+
+/// The interval at which the virtual clock advances while animations are running
+const ANIMATION_FRAME: core::time::Duration = core::time::Duration::from_millis(16);
+
+fn set_current_tick(tick: Instant) {
+    CURRENT_ANIMATION_DRIVER.with(|driver| driver.update_animations(tick));
+}
+
+/// Invokes the posted events and the expired timers until there is nothing left to do at
+/// the current time. Returns true if the event loop was asked to quit.
+fn process_pending_events() -> bool {
+    register_event_loop_thread();
+    let thread = std::thread::current().id();
+    loop {
+        // Don't hold the lock while invoking the event, since it may post more events
+        let event =
+            EVENT_QUEUES.lock().unwrap().get_mut(&thread).and_then(|queue| queue.pop_front());
+        match event {
+            Some(TestingEvent::Quit) => return true,
+            Some(TestingEvent::Custom(event)) => event(),
+            None => {
+                let had_change_handlers = ChangeTracker::has_pending_change_handlers();
+                ChangeTracker::run_change_handlers();
+                if !TimerList::maybe_activate_timers() && !had_change_handlers {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// Invokes all the callbacks posted with `invoke_from_event_loop` and all the timers that
+/// have expired, without advancing the time. Requests to quit the event loop are ignored,
+/// since no event loop is running.
+pub fn run_until_idle() {
+    while process_pending_events() {}
+}
+
+/// Advances the virtual clock used by the animations and the timers by the given duration,
+/// and processes the events posted in the meantime.
+///
+/// The time goes forward in steps, to the timeout of each timer that expires during that
+/// duration, so that timers fire in order and repeated timers fire once per interval.
+/// The clock has a resolution of one millisecond.
+pub fn advance_time(duration: core::time::Duration) {
+    let target = i_slint_core::animations::current_tick() + duration;
+    run_until_idle();
+    while let Some(timeout) = TimerList::next_timeout().filter(|timeout| *timeout <= target) {
+        set_current_tick(timeout);
+        run_until_idle();
+    }
+    set_current_tick(target);
+    run_until_idle();
+}
+
+/// Renders the contents of the window into a pixel buffer and returns it.
+///
+/// The size of the buffer is the size of the window in physical pixels. If the window
+/// has no size yet, the preferred size of its layout is used.
+/// Text is rendered as a sequence of boxes, one per glyph, so that the result does not
+/// depend on the fonts installed on the system.
+pub fn screenshot(window: &WindowRc) -> SharedPixelBuffer<Rgba8Pixel> {
+    let component_rc = window.component();
+    let component = ComponentRc::borrow_pin(&component_rc);
+    let root_item = component.as_ref().get_item_ref(0);
+    let window_item = ItemRef::downcast_pin::<i_slint_core::items::WindowItem>(root_item);
+
+    let (mut width, mut height) =
+        window_item.map_or((0., 0.), |window_item| (window_item.width(), window_item.height()));
+    if width <= 0. || height <= 0. {
+        let preferred_size =
+            |orientation| component.as_ref().layout_info(orientation).preferred_bounded();
+        width = preferred_size(i_slint_core::layout::Orientation::Horizontal);
+        height = preferred_size(i_slint_core::layout::Orientation::Vertical);
+        window.set_window_item_geometry(width, height);
+    }
+    let background = window_item.map_or(Default::default(), |window_item| window_item.background());
+
+    let scale_factor = window.scale_factor();
+    let mut renderer = match renderer::SoftwareRenderer::new(
+        (width * scale_factor).ceil() as u32,
+        (height * scale_factor).ceil() as u32,
+        background,
+        scale_factor,
+        window.clone(),
+    ) {
+        Some(renderer) => renderer,
+        None => return SharedPixelBuffer::new(0, 0),
+    };
+
+    window.clone().draw_contents(|components| {
+        for (component, origin) in components {
+            i_slint_core::item_rendering::render_component_items(component, &mut renderer, *origin);
+        }
+    });
+
+    renderer.into_pixel_buffer()
+}
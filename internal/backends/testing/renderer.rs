@@ -0,0 +1,1113 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+A software renderer that draws the items into an in-memory pixel buffer using tiny-skia.
+
+The text is not rendered with real fonts, because the output needs to be the same
+on every machine. Instead, each glyph is drawn as a filled box in a cell of
+[`GLYPH_SIZE`] pixels, consistent with the metrics that the testing backend reports.
+*/
+
+use std::pin::Pin;
+use std::rc::Rc;
+
+use i_slint_core::graphics::euclid;
+use i_slint_core::graphics::{
+    Brush, Color, Image, IntRect, Point, Rect, Rgba8Pixel, SharedImageBuffer, SharedPixelBuffer,
+    Size,
+};
+use i_slint_core::item_rendering::{CachedRenderingData, ItemRenderer};
+use i_slint_core::items::{
+    FillRule, ImageFit, ImageRendering, TextHorizontalAlignment, TextVerticalAlignment,
+};
+use i_slint_core::window::WindowRc;
+use i_slint_core::ImageInner;
+use tiny_skia::{ClipMask, Paint, Pixmap, PixmapPaint, Shader, Transform};
+
+/// The width of a glyph cell for one byte of UTF-8 text, and the height of a line,
+/// in logical pixels.
+pub const GLYPH_SIZE: f32 = 10.;
+
+const KAPPA90: f32 = 0.55228;
+
+#[derive(Clone)]
+struct RenderState {
+    /// Transformation from the current item coordinates to physical pixels
+    transform: Transform,
+    /// The clip rectangle in the current item coordinates
+    clip: Rect,
+    /// The clip mask in physical pixels, if any clipping was applied
+    clip_mask: Option<Rc<ClipMask>>,
+    alpha: f32,
+}
+
+/// An [`ItemRenderer`](i_slint_core::item_rendering::ItemRenderer) that rasterizes the items
+/// with tiny-skia. This is the renderer used by [`screenshot`](crate::screenshot).
+pub struct SoftwareRenderer {
+    pixmap: Pixmap,
+    state_stack: Vec<RenderState>,
+    current_state: RenderState,
+    scale_factor: f32,
+    window: WindowRc,
+}
+
+impl SoftwareRenderer {
+    /// Creates a renderer for a target of the given physical size, filled with the background color.
+    /// Returns None if the size is empty.
+    pub fn new(
+        width: u32,
+        height: u32,
+        background: Color,
+        scale_factor: f32,
+        window: WindowRc,
+    ) -> Option<Self> {
+        let mut pixmap = Pixmap::new(width, height)?;
+        pixmap.fill(to_skia_color(&background));
+        Some(Self {
+            pixmap,
+            state_stack: vec![],
+            current_state: RenderState {
+                transform: Transform::from_scale(scale_factor, scale_factor),
+                clip: Rect::new(
+                    Point::default(),
+                    Size::new(width as f32, height as f32) / scale_factor,
+                ),
+                clip_mask: None,
+                alpha: 1.,
+            },
+            scale_factor,
+            window,
+        })
+    }
+
+    /// Returns the rendered pixels. In contrast to tiny-skia's internal format, the alpha
+    /// channel of the result is not pre-multiplied.
+    pub fn into_pixel_buffer(self) -> SharedPixelBuffer<Rgba8Pixel> {
+        let mut buffer =
+            SharedPixelBuffer::<Rgba8Pixel>::new(self.pixmap.width(), self.pixmap.height());
+        for (target, source) in buffer.make_mut_slice().iter_mut().zip(self.pixmap.pixels()) {
+            let color = source.demultiply();
+            *target = Rgba8Pixel::new(color.red(), color.green(), color.blue(), color.alpha());
+        }
+        buffer
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &tiny_skia::Path,
+        paint: &BrushPaint,
+        fill_rule: tiny_skia::FillRule,
+    ) {
+        self.pixmap.fill_path(
+            path,
+            &paint.paint(),
+            fill_rule,
+            self.current_state.transform,
+            self.current_state.clip_mask.as_deref(),
+        );
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: &BrushPaint) {
+        if let Some(rect) = to_skia_rect(rect) {
+            self.pixmap.fill_rect(
+                rect,
+                &paint.paint(),
+                self.current_state.transform,
+                self.current_state.clip_mask.as_deref(),
+            );
+        }
+    }
+
+    fn stroke_path(&mut self, path: &tiny_skia::Path, paint: &BrushPaint, width: f32) {
+        self.pixmap.stroke_path(
+            path,
+            &paint.paint(),
+            &tiny_skia::Stroke { width, ..Default::default() },
+            self.current_state.transform,
+            self.current_state.clip_mask.as_deref(),
+        );
+    }
+
+    /// Draws a pixmap that is in physical pixels at the current origin
+    fn draw_physical_pixmap(&mut self, x: f32, y: f32, pixmap: tiny_skia::PixmapRef) {
+        let transform = self
+            .current_state
+            .transform
+            .pre_translate(x, y)
+            .pre_scale(1. / self.scale_factor, 1. / self.scale_factor);
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            pixmap,
+            &PixmapPaint {
+                opacity: self.current_state.alpha,
+                quality: tiny_skia::FilterQuality::Bilinear,
+                ..Default::default()
+            },
+            transform,
+            self.current_state.clip_mask.as_deref(),
+        );
+    }
+
+    fn brush_to_paint(&self, brush: Brush, rect: Rect) -> Option<BrushPaint> {
+        let mut paint = brush_to_paint(brush, rect, self.scale_factor)?;
+        paint.apply_opacity(self.current_state.alpha);
+        Some(paint)
+    }
+
+    fn draw_glyph_boxes<'a>(
+        &mut self,
+        lines: &[TextLine],
+        paint_for_glyph: impl Fn(usize) -> &'a BrushPaint,
+    ) {
+        for line in lines {
+            for (byte_offset, glyph_rect) in line.glyphs() {
+                let paint = paint_for_glyph(byte_offset);
+                self.fill_rect(glyph_rect.inflate(-1., -1.), paint);
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_image_impl(
+        &mut self,
+        size: Size,
+        source: &Image,
+        source_clip_rect: IntRect,
+        image_fit: ImageFit,
+        colorize: Brush,
+        image_rendering: ImageRendering,
+    ) {
+        if size.width <= 0. || size.height <= 0. {
+            return;
+        }
+
+        let image = match image_to_pixmap(source.into()) {
+            Some(image) => image,
+            None => return,
+        };
+        let image =
+            if colorize.is_transparent() { image } else { colorize_pixmap(image, colorize) };
+
+        let image_size = Size::new(image.width() as f32, image.height() as f32);
+
+        let (source_width, source_height) = if source_clip_rect.is_empty() {
+            (image_size.width, image_size.height)
+        } else {
+            (source_clip_rect.width() as f32, source_clip_rect.height() as f32)
+        };
+
+        let mut source_x = source_clip_rect.min_x() as f32;
+        let mut source_y = source_clip_rect.min_y() as f32;
+
+        let mut image_fit_offset = Point::default();
+
+        let (scale_x, scale_y) = match image_fit {
+            ImageFit::fill => (size.width / source_width, size.height / source_height),
+            ImageFit::cover => {
+                let ratio = f32::max(size.width / source_width, size.height / source_height);
+                if source_width > size.width / ratio {
+                    source_x += (source_width - size.width / ratio) / 2.;
+                }
+                if source_height > size.height / ratio {
+                    source_y += (source_height - size.height / ratio) / 2.
+                }
+                (ratio, ratio)
+            }
+            ImageFit::contain => {
+                let ratio = f32::min(size.width / source_width, size.height / source_height);
+                if source_width < size.width / ratio {
+                    image_fit_offset.x = (size.width - source_width * ratio) / 2.;
+                }
+                if source_height < size.height / ratio {
+                    image_fit_offset.y = (size.height - source_height * ratio) / 2.
+                }
+                (ratio, ratio)
+            }
+        };
+
+        let quality = match image_rendering {
+            ImageRendering::smooth => tiny_skia::FilterQuality::Bilinear,
+            ImageRendering::pixelated => tiny_skia::FilterQuality::Nearest,
+        };
+
+        let paint = Paint {
+            shader: tiny_skia::Pattern::new(
+                image.as_ref(),
+                tiny_skia::SpreadMode::Pad,
+                quality,
+                self.current_state.alpha,
+                Transform::from_translate(-source_x, -source_y),
+            ),
+            anti_alias: true,
+            ..Default::default()
+        };
+
+        let transform = self
+            .current_state
+            .transform
+            .pre_translate(image_fit_offset.x, image_fit_offset.y)
+            .pre_scale(scale_x, scale_y);
+
+        if let Some(rect) = tiny_skia::Rect::from_xywh(0., 0., source_width, source_height) {
+            self.pixmap.fill_rect(rect, &paint, transform, self.current_state.clip_mask.as_deref());
+        }
+    }
+}
+
+impl ItemRenderer for SoftwareRenderer {
+    fn draw_rectangle(&mut self, rect: Pin<&i_slint_core::items::Rectangle>) {
+        let geometry = item_rect(rect.geometry());
+        if geometry.is_empty() {
+            return;
+        }
+        if let Some(paint) = self.brush_to_paint(rect.background(), geometry) {
+            self.fill_rect(geometry, &paint);
+        }
+    }
+
+    fn draw_border_rectangle(&mut self, rect: Pin<&i_slint_core::items::BorderRectangle>) {
+        let mut geometry = item_rect(rect.geometry());
+        if geometry.is_empty() {
+            return;
+        }
+
+        // The border is entirely towards the inside of the geometry, like in CSS. Since
+        // the stroke is centered on the path, the path is moved inwards by half the border width.
+        let mut border_width = rect.border_width();
+        adjust_rect_and_border_for_inner_drawing(&mut geometry, &mut border_width);
+
+        let path = match rounded_rect_path(geometry, rect.border_radius()) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let Some(paint) = self.brush_to_paint(rect.background(), geometry) {
+            self.fill_path(&path, &paint, tiny_skia::FillRule::Winding);
+        }
+        if border_width > 0. {
+            if let Some(paint) = self.brush_to_paint(rect.border_color(), geometry) {
+                self.stroke_path(&path, &paint, border_width);
+            }
+        }
+    }
+
+    fn draw_image(&mut self, image: Pin<&i_slint_core::items::ImageItem>) {
+        self.draw_image_impl(
+            image.geometry().size,
+            &image.source(),
+            IntRect::default(),
+            image.image_fit(),
+            Brush::default(),
+            image.image_rendering(),
+        );
+    }
+
+    fn draw_clipped_image(&mut self, image: Pin<&i_slint_core::items::ClippedImage>) {
+        let source_clip_rect = IntRect::new(
+            [image.source_clip_x(), image.source_clip_y()].into(),
+            [image.source_clip_width(), image.source_clip_height()].into(),
+        );
+        self.draw_image_impl(
+            image.geometry().size,
+            &image.source(),
+            source_clip_rect,
+            image.image_fit(),
+            image.colorize(),
+            image.image_rendering(),
+        );
+    }
+
+    fn draw_text(&mut self, text: Pin<&i_slint_core::items::Text>) {
+        let geometry = item_rect(text.geometry());
+        let paint = match self.brush_to_paint(text.color(), geometry) {
+            Some(paint) => paint,
+            None => return,
+        };
+        let string = text.text();
+        let lines = layout_text_lines(
+            string.as_str(),
+            geometry.size,
+            text.horizontal_alignment(),
+            text.vertical_alignment(),
+        );
+        self.draw_glyph_boxes(&lines, |_| &paint);
+    }
+
+    fn draw_text_input(&mut self, text_input: Pin<&i_slint_core::items::TextInput>) {
+        let geometry = item_rect(text_input.geometry());
+        if geometry.is_empty() {
+            return;
+        }
+        let paint = match self.brush_to_paint(text_input.color(), geometry) {
+            Some(paint) => paint,
+            None => return,
+        };
+
+        let visual_representation = text_input.visual_representation();
+        let string = visual_representation.text.as_str();
+        let lines = layout_text_lines(
+            string,
+            geometry.size,
+            text_input.horizontal_alignment(),
+            text_input.vertical_alignment(),
+        );
+
+        let selection = visual_representation.selection_range;
+        if !selection.is_empty() {
+            if let Some(selection_paint) = self.brush_to_paint(
+                Brush::SolidColor(text_input.selection_background_color()),
+                geometry,
+            ) {
+                for line in &lines {
+                    for (byte_offset, glyph_rect) in line.glyphs_with_whitespace() {
+                        if selection.contains(&byte_offset) {
+                            self.fill_rect(glyph_rect, &selection_paint);
+                        }
+                    }
+                }
+            }
+        }
+
+        let selected_paint = self
+            .brush_to_paint(Brush::SolidColor(text_input.selection_foreground_color()), geometry)
+            .unwrap_or_else(|| paint.clone());
+        self.draw_glyph_boxes(&lines, |byte_offset| {
+            if selection.contains(&byte_offset) {
+                &selected_paint
+            } else {
+                &paint
+            }
+        });
+
+        // Underline the text being composed by the input method
+        let preedit = visual_representation.preedit_range;
+        if !preedit.is_empty() {
+            for line in &lines {
+                for (byte_offset, glyph_rect) in line.glyphs_with_whitespace() {
+                    if preedit.contains(&byte_offset) {
+                        self.fill_rect(
+                            Rect::new(
+                                glyph_rect.origin + euclid::vec2(0., GLYPH_SIZE - 1.),
+                                Size::new(glyph_rect.width(), 1.),
+                            ),
+                            &paint,
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(cursor_pos) = visual_representation.cursor_position {
+            if let Some(cursor_point) = lines
+                .iter()
+                .find(|line| line.byte_range().contains(&cursor_pos) || line.end() == cursor_pos)
+                .map(|line| line.position_for_byte_offset(cursor_pos))
+            {
+                self.fill_rect(
+                    Rect::new(cursor_point, Size::new(text_input.text_cursor_width(), GLYPH_SIZE)),
+                    &paint,
+                );
+            }
+        }
+    }
+
+    fn draw_path(&mut self, path: Pin<&i_slint_core::items::Path>) {
+        if matches!(path.elements(), i_slint_core::PathData::None) {
+            return;
+        }
+
+        let (offset, path_events) = path.fitted_path_events();
+
+        let mut builder = tiny_skia::PathBuilder::new();
+        for event in path_events.iter() {
+            match event {
+                lyon_path::Event::Begin { at } => builder.move_to(at.x, at.y),
+                lyon_path::Event::Line { from: _, to } => builder.line_to(to.x, to.y),
+                lyon_path::Event::Quadratic { from: _, ctrl, to } => {
+                    builder.quad_to(ctrl.x, ctrl.y, to.x, to.y)
+                }
+                lyon_path::Event::Cubic { from: _, ctrl1, ctrl2, to } => {
+                    builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y)
+                }
+                lyon_path::Event::End { last: _, first: _, close } => {
+                    if close {
+                        builder.close()
+                    }
+                }
+            }
+        }
+        let skia_path = match builder.finish() {
+            Some(skia_path) => skia_path,
+            None => return,
+        };
+
+        let bounds = skia_path.bounds();
+        let bounds = euclid::rect(bounds.x(), bounds.y(), bounds.width(), bounds.height());
+
+        self.save_state();
+        self.translate(offset.x, offset.y);
+        if let Some(paint) = self.brush_to_paint(path.fill(), bounds) {
+            let fill_rule = match path.fill_rule() {
+                FillRule::nonzero => tiny_skia::FillRule::Winding,
+                FillRule::evenodd => tiny_skia::FillRule::EvenOdd,
+            };
+            self.fill_path(&skia_path, &paint, fill_rule);
+        }
+        if let Some(paint) = self.brush_to_paint(path.stroke(), bounds) {
+            self.stroke_path(&skia_path, &paint, path.stroke_width());
+        }
+        self.restore_state();
+    }
+
+    /// Draws the shadow into an intermediate image, blurs it, and draws the result with the
+    /// offset. This follows the same steps as the GL backend.
+    fn draw_box_shadow(&mut self, box_shadow: Pin<&i_slint_core::items::BoxShadow>) {
+        if box_shadow.color().alpha() == 0
+            || (box_shadow.blur() == 0.0
+                && box_shadow.offset_x() == 0.
+                && box_shadow.offset_y() == 0.)
+        {
+            return;
+        }
+
+        let blur = box_shadow.blur() * self.scale_factor;
+        let width = box_shadow.width() * self.scale_factor;
+        let height = box_shadow.height() * self.scale_factor;
+        let radius = box_shadow.border_radius() * self.scale_factor;
+
+        let mut shadow = match Pixmap::new(
+            (width + 2. * blur).ceil() as u32,
+            (height + 2. * blur).ceil() as u32,
+        ) {
+            Some(shadow) => shadow,
+            None => return,
+        };
+
+        let shadow_path = match rounded_rect_path(euclid::rect(blur, blur, width, height), radius) {
+            Some(path) => path,
+            None => return,
+        };
+        let mut paint = Paint { anti_alias: true, ..Default::default() };
+        paint.set_color(to_skia_color(&box_shadow.color()));
+        shadow.fill_path(
+            &shadow_path,
+            &paint,
+            tiny_skia::FillRule::Winding,
+            Transform::identity(),
+            None,
+        );
+
+        if blur > 0. {
+            gaussian_blur(&mut shadow, blur / 2.);
+        }
+
+        self.draw_physical_pixmap(
+            box_shadow.offset_x() - box_shadow.blur(),
+            box_shadow.offset_y() - box_shadow.blur(),
+            shadow.as_ref(),
+        );
+    }
+
+    fn combine_clip(&mut self, rect: Rect, radius: f32, border_width: f32) {
+        let clip = &mut self.current_state.clip;
+        *clip = clip.intersection(&rect).unwrap_or_default();
+
+        // Clip to the area inside the border
+        let clip_rect = rect.inflate(-border_width, -border_width);
+        let radius = (radius - border_width).max(0.);
+
+        let clip_path = match rounded_rect_path(clip_rect, radius)
+            .and_then(|path| path.transform(self.current_state.transform))
+        {
+            Some(path) => path,
+            None => {
+                self.current_state.clip = Rect::default();
+                return;
+            }
+        };
+
+        let mask = match &self.current_state.clip_mask {
+            Some(existing_mask) => {
+                let mut mask = ClipMask::clone(existing_mask);
+                mask.intersect_path(&clip_path, tiny_skia::FillRule::Winding, true);
+                mask
+            }
+            None => {
+                let mut mask = ClipMask::new();
+                mask.set_path(
+                    self.pixmap.width(),
+                    self.pixmap.height(),
+                    &clip_path,
+                    tiny_skia::FillRule::Winding,
+                    true,
+                );
+                mask
+            }
+        };
+        self.current_state.clip_mask = Some(Rc::new(mask));
+    }
+
+    fn get_current_clip(&self) -> Rect {
+        self.current_state.clip
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        self.current_state.transform = self.current_state.transform.pre_translate(x, y);
+        self.current_state.clip = self.current_state.clip.translate((-x, -y).into())
+    }
+
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        self.current_state.transform =
+            self.current_state.transform.pre_concat(Transform::from_rotate(angle_in_degrees));
+        // The clip in the new coordinate system is the bounding box of the old clip rotated
+        // in the opposite direction.
+        let clip = self.current_state.clip;
+        let (sin, cos) = (-angle_in_degrees).to_radians().sin_cos();
+        let rotate_point = |p: Point| Point::new(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+        self.current_state.clip = Rect::from_points(
+            [
+                clip.origin,
+                clip.origin + euclid::vec2(clip.width(), 0.),
+                clip.origin + euclid::vec2(0., clip.height()),
+                clip.origin + clip.size,
+            ]
+            .iter()
+            .map(|p| rotate_point(*p)),
+        );
+    }
+
+    fn scale(&mut self, x: f32, y: f32) {
+        self.current_state.transform = self.current_state.transform.pre_scale(x, y);
+        let clip = self.current_state.clip;
+        self.current_state.clip = if x == 0. || y == 0. {
+            Rect::default()
+        } else {
+            let unscale_point = |p: Point| Point::new(p.x / x, p.y / y);
+            Rect::from_points([unscale_point(clip.min()), unscale_point(clip.max())])
+        };
+    }
+
+    fn apply_opacity(&mut self, opacity: f32) {
+        self.current_state.alpha *= opacity;
+    }
+
+    fn save_state(&mut self) {
+        self.state_stack.push(self.current_state.clone());
+    }
+
+    fn restore_state(&mut self) {
+        self.current_state = self.state_stack.pop().unwrap();
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn draw_cached_pixmap(
+        &mut self,
+        _item_cache: &CachedRenderingData,
+        update_fn: &dyn Fn(&mut dyn FnMut(u32, u32, &[u8])),
+    ) {
+        update_fn(&mut |width: u32, height: u32, data: &[u8]| {
+            if let Some(pixmap) = tiny_skia::PixmapRef::from_bytes(data, width, height) {
+                self.draw_physical_pixmap(0., 0., pixmap);
+            }
+        });
+    }
+
+    fn draw_string(&mut self, string: &str, color: Color) {
+        let lines = layout_text_lines(
+            string,
+            Size::default(),
+            TextHorizontalAlignment::left,
+            TextVerticalAlignment::top,
+        );
+        if let Some(paint) = self.brush_to_paint(Brush::SolidColor(color), Rect::default()) {
+            self.draw_glyph_boxes(&lines, |_| &paint);
+        }
+    }
+
+    fn window(&self) -> WindowRc {
+        self.window.clone()
+    }
+
+    fn as_any(&mut self) -> &mut dyn core::any::Any {
+        self
+    }
+}
+
+/// A line of text laid out in glyph cells
+pub struct TextLine<'a> {
+    /// Byte offset of the line in the whole text
+    start: usize,
+    text: &'a str,
+    origin: Point,
+}
+
+impl<'a> TextLine<'a> {
+    fn byte_range(&self) -> core::ops::Range<usize> {
+        self.start..self.end()
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    fn position_for_byte_offset(&self, byte_offset: usize) -> Point {
+        self.origin + euclid::vec2((byte_offset - self.start) as f32 * GLYPH_SIZE, 0.)
+    }
+
+    /// Returns the byte offset of the glyph boundary closest to `x`. Positions past the end
+    /// of the line are at the end of the line, before its line break.
+    fn byte_offset_for_x(&self, x: f32) -> usize {
+        let x = x - self.origin.x;
+        self.text
+            .char_indices()
+            .find(|(offset, ch)| x < (*offset as f32 + ch.len_utf8() as f32 / 2.) * GLYPH_SIZE)
+            .map_or(self.end(), |(offset, _)| self.start + offset)
+    }
+
+    /// Returns the byte offset of the glyph and its cell, for all the visible glyphs
+    fn glyphs(&self) -> impl Iterator<Item = (usize, Rect)> + '_ {
+        self.text
+            .char_indices()
+            .filter(|(_, ch)| !ch.is_whitespace())
+            .map(move |(offset, ch)| self.glyph_cell(offset, ch))
+    }
+
+    /// Same as glyph, but also returns the cells of whitespace characters
+    fn glyphs_with_whitespace(&self) -> impl Iterator<Item = (usize, Rect)> + '_ {
+        self.text.char_indices().map(move |(offset, ch)| self.glyph_cell(offset, ch))
+    }
+
+    fn glyph_cell(&self, offset: usize, ch: char) -> (usize, Rect) {
+        // The advance depends on the UTF-8 length, to be consistent with the text_size()
+        // reported by the testing window.
+        (
+            self.start + offset,
+            Rect::new(
+                self.origin + euclid::vec2(offset as f32 * GLYPH_SIZE, 0.),
+                Size::new(ch.len_utf8() as f32 * GLYPH_SIZE, GLYPH_SIZE),
+            ),
+        )
+    }
+}
+
+/// Splits the text into lines and aligns them within a rectangle of the given size.
+pub fn layout_text_lines(
+    text: &str,
+    size: Size,
+    horizontal_alignment: TextHorizontalAlignment,
+    vertical_alignment: TextVerticalAlignment,
+) -> Vec<TextLine> {
+    let line_count = text.split('\n').count();
+    let text_height = line_count as f32 * GLYPH_SIZE;
+    let mut y = match vertical_alignment {
+        TextVerticalAlignment::top => 0.,
+        TextVerticalAlignment::center => (size.height - text_height) / 2.,
+        TextVerticalAlignment::bottom => size.height - text_height,
+    };
+
+    let mut start = 0;
+    let mut lines = Vec::with_capacity(line_count);
+    for line in text.split('\n') {
+        let line_width = line.len() as f32 * GLYPH_SIZE;
+        let x = match horizontal_alignment {
+            TextHorizontalAlignment::left => 0.,
+            TextHorizontalAlignment::center => (size.width - line_width) / 2.,
+            TextHorizontalAlignment::right => size.width - line_width,
+        };
+        lines.push(TextLine { start, text: line, origin: Point::new(x, y) });
+        start += line.len() + 1;
+        y += GLYPH_SIZE;
+    }
+    lines
+}
+
+/// Returns the byte offset in the text of the text input that is the closest to `pos`.
+/// Positions above the first line or below the last line are in that line.
+pub fn text_input_byte_offset_for_position(
+    text_input: Pin<&i_slint_core::items::TextInput>,
+    pos: Point,
+) -> usize {
+    let text = text_input.text();
+    let lines = layout_text_lines(
+        text.as_str(),
+        Size::new(text_input.width(), text_input.height()),
+        text_input.horizontal_alignment(),
+        text_input.vertical_alignment(),
+    );
+    lines
+        .iter()
+        .find(|line| pos.y < line.origin.y + GLYPH_SIZE)
+        .or_else(|| lines.last())
+        .map_or(0, |line| line.byte_offset_for_x(pos.x))
+}
+
+/// Returns the position of the cursor at the given byte offset in the text of the text input.
+pub fn text_input_position_for_byte_offset(
+    text_input: Pin<&i_slint_core::items::TextInput>,
+    byte_offset: usize,
+) -> Point {
+    let text = text_input.text();
+    let lines = layout_text_lines(
+        text.as_str(),
+        Size::new(text_input.width(), text_input.height()),
+        text_input.horizontal_alignment(),
+        text_input.vertical_alignment(),
+    );
+    lines
+        .iter()
+        .find(|line| line.byte_range().contains(&byte_offset) || line.end() == byte_offset)
+        .map_or_else(Point::default, |line| line.position_for_byte_offset(byte_offset))
+}
+
+fn item_rect(geometry: Rect) -> Rect {
+    Rect::new(Point::default(), geometry.size)
+}
+
+fn adjust_rect_and_border_for_inner_drawing(rect: &mut Rect, border_width: &mut f32) {
+    // If the border width exceeds the width, just fill the rectangle.
+    *border_width = border_width.min(rect.size.width / 2.);
+    // adjust the size so that the border is drawn within the geometry
+    rect.origin.x += *border_width / 2.;
+    rect.origin.y += *border_width / 2.;
+    rect.size.width -= *border_width;
+    rect.size.height -= *border_width;
+}
+
+fn to_skia_color(color: &Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.red(), color.green(), color.blue(), color.alpha())
+}
+
+fn to_skia_rect(rect: Rect) -> Option<tiny_skia::Rect> {
+    tiny_skia::Rect::from_xywh(rect.origin.x, rect.origin.y, rect.width(), rect.height())
+}
+
+/// The paint of a brush. The brushes for which tiny-skia has no shader are rendered into a pixmap
+/// that the paint owns.
+#[derive(Clone)]
+enum BrushPaint {
+    Shader(Paint<'static>),
+    Pixmap {
+        pixmap: Pixmap,
+        /// Maps the pixmap to the coordinates of the shape
+        transform: Transform,
+        opacity: f32,
+    },
+}
+
+impl BrushPaint {
+    fn paint(&self) -> Paint<'_> {
+        match self {
+            BrushPaint::Shader(paint) => paint.clone(),
+            BrushPaint::Pixmap { pixmap, transform, opacity } => Paint {
+                shader: tiny_skia::Pattern::new(
+                    pixmap.as_ref(),
+                    tiny_skia::SpreadMode::Pad,
+                    tiny_skia::FilterQuality::Bilinear,
+                    *opacity,
+                    *transform,
+                ),
+                anti_alias: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn apply_opacity(&mut self, alpha: f32) {
+        match self {
+            BrushPaint::Shader(paint) => paint.shader.apply_opacity(alpha),
+            BrushPaint::Pixmap { opacity, .. } => *opacity *= alpha,
+        }
+    }
+}
+
+/// Creates the paint for the brush. The rect is the bounding box of the shape, and is used
+/// to position the gradients. The gradients that are rendered into a pixmap use `resolution`
+/// pixels per unit of the rect.
+fn brush_to_paint(brush: Brush, rect: Rect, resolution: f32) -> Option<BrushPaint> {
+    if brush.is_transparent() {
+        return None;
+    }
+    let shader = match brush {
+        Brush::SolidColor(color) => Shader::SolidColor(to_skia_color(&color)),
+        Brush::LinearGradient(gradient) => {
+            let transform = euclid::Transform2D::scale(rect.width(), rect.height())
+                .then_translate(rect.origin.to_vector());
+            let (start, end) = i_slint_core::graphics::line_for_angle(gradient.angle());
+            let start: Point = transform.transform_point(start);
+            let end: Point = transform.transform_point(end);
+            let stops = gradient
+                .stops()
+                .map(|stop| tiny_skia::GradientStop::new(stop.position, to_skia_color(&stop.color)))
+                .collect();
+            tiny_skia::LinearGradient::new(
+                tiny_skia::Point::from_xy(start.x, start.y),
+                tiny_skia::Point::from_xy(end.x, end.y),
+                stops,
+                tiny_skia::SpreadMode::Pad,
+                Transform::identity(),
+            )?
+        }
+        Brush::RadialGradient(gradient) => {
+            let center = rect.center();
+            let center = tiny_skia::Point::from_xy(center.x, center.y);
+            let radius = (rect.width() * rect.width() + rect.height() * rect.height()).sqrt() / 2.;
+            let stops = gradient
+                .stops()
+                .map(|stop| tiny_skia::GradientStop::new(stop.position, to_skia_color(&stop.color)))
+                .collect();
+            tiny_skia::RadialGradient::new(
+                center,
+                center,
+                radius,
+                stops,
+                tiny_skia::SpreadMode::Pad,
+                Transform::identity(),
+            )?
+        }
+        Brush::ConicGradient(gradient) => {
+            // tiny-skia has no conic gradient shader, so render the gradient into a pixmap
+            let width = (rect.width() * resolution).ceil().max(1.);
+            let height = (rect.height() * resolution).ceil().max(1.);
+            let mut pixmap = Pixmap::new(width as u32, height as u32)?;
+            for (index, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+                let x = (index % width as usize) as f32 + 0.5;
+                let y = (index / width as usize) as f32 + 0.5;
+                let color = gradient.color_at(x - width / 2., y - height / 2.);
+                *pixel = tiny_skia::ColorU8::from_rgba(
+                    color.red(),
+                    color.green(),
+                    color.blue(),
+                    color.alpha(),
+                )
+                .premultiply();
+            }
+            return Some(BrushPaint::Pixmap {
+                pixmap,
+                transform: Transform::from_row(
+                    rect.width() / width,
+                    0.,
+                    0.,
+                    rect.height() / height,
+                    rect.origin.x,
+                    rect.origin.y,
+                ),
+                opacity: 1.,
+            });
+        }
+        _ => return None,
+    };
+    Some(BrushPaint::Shader(Paint { shader, anti_alias: true, ..Default::default() }))
+}
+
+fn rounded_rect_path(rect: Rect, radius: f32) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+    let radius = radius.min(rect.width() / 2.).min(rect.height() / 2.);
+    if radius <= 0. {
+        builder.push_rect(rect.origin.x, rect.origin.y, rect.width(), rect.height());
+    } else {
+        let (left, top, right, bottom) = (rect.min_x(), rect.min_y(), rect.max_x(), rect.max_y());
+        // distance of the bezier control points from the corner
+        let k = radius * (1. - KAPPA90);
+        builder.move_to(left + radius, top);
+        builder.line_to(right - radius, top);
+        builder.cubic_to(right - k, top, right, top + k, right, top + radius);
+        builder.line_to(right, bottom - radius);
+        builder.cubic_to(right, bottom - k, right - k, bottom, right - radius, bottom);
+        builder.line_to(left + radius, bottom);
+        builder.cubic_to(left + k, bottom, left, bottom - k, left, bottom - radius);
+        builder.line_to(left, top + radius);
+        builder.cubic_to(left, top + k, left + k, top, left + radius, top);
+        builder.close();
+    }
+    builder.finish()
+}
+
+/// Creates a pixmap with pre-multiplied alpha from an image. Returns None for images
+/// that cannot be loaded, and for SVG images, which are not supported.
+fn image_to_pixmap(image: &ImageInner) -> Option<Pixmap> {
+    if image.is_svg() {
+        return None;
+    }
+    match image {
+        ImageInner::None => None,
+        ImageInner::AbsoluteFilePath(path) => {
+            dynamic_image_to_pixmap(image::open(std::path::Path::new(path.as_str())).ok()?)
+        }
+        ImageInner::EmbeddedData { data, format } => {
+            let format = std::str::from_utf8(format.as_slice())
+                .ok()
+                .and_then(image::ImageFormat::from_extension)?;
+            dynamic_image_to_pixmap(
+                image::load_from_memory_with_format(data.as_slice(), format).ok()?,
+            )
+        }
+        ImageInner::EmbeddedImage(buffer) => match buffer {
+            SharedImageBuffer::RGB8(buffer) => pixmap_from_rgba(
+                buffer.width(),
+                buffer.height(),
+                pixel_rows(buffer).map(|p| tiny_skia::ColorU8::from_rgba(p.r, p.g, p.b, 255)),
+            ),
+            SharedImageBuffer::RGBA8(buffer) => pixmap_from_rgba(
+                buffer.width(),
+                buffer.height(),
+                pixel_rows(buffer).map(|p| tiny_skia::ColorU8::from_rgba(p.r, p.g, p.b, p.a)),
+            ),
+            SharedImageBuffer::RGBA8Premultiplied(buffer) => {
+                let mut pixmap = Pixmap::new(buffer.width(), buffer.height())?;
+                for (target, p) in pixmap.pixels_mut().iter_mut().zip(pixel_rows(buffer)) {
+                    *target = tiny_skia::PremultipliedColorU8::from_rgba(p.r, p.g, p.b, p.a)
+                        .unwrap_or(tiny_skia::PremultipliedColorU8::TRANSPARENT);
+                }
+                Some(pixmap)
+            }
+        },
+        ImageInner::StaticTextures { size, data, textures } => {
+            let mut pixmap = Pixmap::new(size.width, size.height)?;
+            let pixmap_width = pixmap.width() as usize;
+            let pixels = pixmap.pixels_mut();
+            for texture in textures.as_slice() {
+                let bpp = match texture.format {
+                    i_slint_core::graphics::PixelFormat::Rgb => 3,
+                    i_slint_core::graphics::PixelFormat::Rgba => 4,
+                    i_slint_core::graphics::PixelFormat::AlphaMap => 1,
+                };
+                let stride = texture.rect.width() as usize * bpp;
+                for y in 0..texture.rect.height() as usize {
+                    for x in 0..texture.rect.width() as usize {
+                        let pos = texture.index + y * stride + x * bpp;
+                        let p = &data.as_slice()[pos..pos + bpp];
+                        let color = match texture.format {
+                            i_slint_core::graphics::PixelFormat::Rgb => {
+                                tiny_skia::ColorU8::from_rgba(p[0], p[1], p[2], 255)
+                            }
+                            i_slint_core::graphics::PixelFormat::Rgba => {
+                                tiny_skia::ColorU8::from_rgba(p[0], p[1], p[2], p[3])
+                            }
+                            i_slint_core::graphics::PixelFormat::AlphaMap => {
+                                tiny_skia::ColorU8::from_rgba(
+                                    texture.color.red(),
+                                    texture.color.green(),
+                                    texture.color.blue(),
+                                    p[0],
+                                )
+                            }
+                        };
+                        let target_x = texture.rect.min_x() as usize + x;
+                        let target_y = texture.rect.min_y() as usize + y;
+                        if let Some(target) = pixels.get_mut(target_y * pixmap_width + target_x) {
+                            *target = color.premultiply();
+                        }
+                    }
+                }
+            }
+            Some(pixmap)
+        }
+    }
+}
+
+/// Iterates over the pixels of the buffer, row by row, skipping the padding of the stride.
+fn pixel_rows<Pixel: Copy>(buffer: &SharedPixelBuffer<Pixel>) -> impl Iterator<Item = Pixel> + '_ {
+    buffer
+        .as_slice()
+        .chunks(buffer.stride().max(1) as usize)
+        .flat_map(move |row| row.iter().take(buffer.width() as usize).copied())
+}
+
+fn pixmap_from_rgba(
+    width: u32,
+    height: u32,
+    pixels: impl Iterator<Item = tiny_skia::ColorU8>,
+) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(width, height)?;
+    for (target, color) in pixmap.pixels_mut().iter_mut().zip(pixels) {
+        *target = color.premultiply();
+    }
+    Some(pixmap)
+}
+
+fn dynamic_image_to_pixmap(image: image::DynamicImage) -> Option<Pixmap> {
+    let image = image.into_rgba8();
+    pixmap_from_rgba(
+        image.width(),
+        image.height(),
+        image.pixels().map(|p| tiny_skia::ColorU8::from_rgba(p[0], p[1], p[2], p[3])),
+    )
+}
+
+/// Returns a copy of the image where the color is replaced by the brush, keeping the alpha channel.
+fn colorize_pixmap(image: Pixmap, brush: Brush) -> Pixmap {
+    let mut colorized = match Pixmap::new(image.width(), image.height()) {
+        Some(colorized) => colorized,
+        None => return image,
+    };
+    let rect = euclid::rect(0., 0., image.width() as f32, image.height() as f32);
+    if let (Some(paint), Some(skia_rect)) = (brush_to_paint(brush, rect, 1.), to_skia_rect(rect)) {
+        colorized.fill_rect(skia_rect, &paint.paint(), Transform::identity(), None);
+    }
+    colorized.draw_pixmap(
+        0,
+        0,
+        image.as_ref(),
+        &PixmapPaint { blend_mode: tiny_skia::BlendMode::DestinationIn, ..Default::default() },
+        Transform::identity(),
+        None,
+    );
+    colorized
+}
+
+/// Approximates a gaussian blur with the given standard deviation, using three box blurs
+/// in each direction.
+fn gaussian_blur(pixmap: &mut Pixmap, sigma: f32) {
+    // Ideal width of the boxes for three passes, see "Fast Almost-Gaussian Filtering" by Peter Kovesi
+    let box_width = (12. * sigma * sigma / 3. + 1.).sqrt();
+    let radius = ((box_width - 1.) / 2.).round().max(1.) as usize;
+    let (width, height) = (pixmap.width() as usize, pixmap.height() as usize);
+    let data = pixmap.data_mut();
+    for _ in 0..3 {
+        box_blur_pass(data, width, height, radius, true);
+        box_blur_pass(data, width, height, radius, false);
+    }
+}
+
+/// Blurs all the lines (or columns if horizontal is false) of the RGBA data.
+/// Pixels outside of the image are considered transparent.
+fn box_blur_pass(data: &mut [u8], width: usize, height: usize, radius: usize, horizontal: bool) {
+    let (len, count, step, line_step) =
+        if horizontal { (width, height, 4, width * 4) } else { (height, width, width * 4, 4) };
+    let divisor = (2 * radius + 1) as u32;
+    let mut line = vec![0u32; len * 4];
+    for line_index in 0..count {
+        let base = line_index * line_step;
+        for i in 0..len {
+            for c in 0..4 {
+                line[i * 4 + c] = data[base + i * step + c] as u32;
+            }
+        }
+        let mut sum = [0u32; 4];
+        for i in 0..=radius.min(len - 1) {
+            for c in 0..4 {
+                sum[c] += line[i * 4 + c];
+            }
+        }
+        for i in 0..len {
+            for c in 0..4 {
+                data[base + i * step + c] = (sum[c] / divisor) as u8;
+            }
+            if i + radius + 1 < len {
+                for c in 0..4 {
+                    sum[c] += line[(i + radius + 1) * 4 + c];
+                }
+            }
+            if i >= radius {
+                for c in 0..4 {
+                    sum[c] -= line[(i - radius) * 4 + c];
+                }
+            }
+        }
+    }
+}
@@ -385,6 +385,13 @@ fn handle_property_inner(
     let mut processed = HashSet::new();
     crate::object_tree::recurse_elem(&component.root_element, &(), &mut |elem: &ElementRc, ()| {
         for (prop_name, binding_expression) in &elem.borrow().bindings {
+            if matches!(
+                elem.borrow().lookup_property(prop_name).property_type,
+                Type::Function { .. }
+            ) {
+                // The binding of a function is its body, not a property binding
+                continue;
+            }
             handle_property_inner(
                 &Rc::downgrade(component),
                 elem,
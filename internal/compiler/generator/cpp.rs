@@ -104,6 +104,7 @@ pub enum Declaration {
         Function(Function),
         Var(Var),
         TypeAlias(TypeAlias),
+        Enum(Enum),
     }
 
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -262,6 +263,25 @@ fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         }
     }
 
+    #[derive(Default, Debug)]
+    pub struct Enum {
+        pub name: String,
+        pub values: Vec<String>,
+    }
+
+    impl Display for Enum {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            indent(f)?;
+            writeln!(f, "enum class {} {{", self.name)?;
+            for value in &self.values {
+                indent(f)?;
+                writeln!(f, "    {},", value)?;
+            }
+            indent(f)?;
+            writeln!(f, "}};")
+        }
+    }
+
     pub trait CppType {
         fn cpp_type(&self) -> Option<String>;
     }
@@ -287,7 +307,7 @@ pub fn escape_string(str: &str) -> String {
 }
 
 use crate::expression_tree::{BuiltinFunction, EasingCurve};
-use crate::langtype::{NativeClass, Type};
+use crate::langtype::{Enumeration, NativeClass, Type};
 use crate::layout::Orientation;
 use crate::llr::{
     self, EvaluationContext as llr_EvaluationContext, ParentCtx as llr_ParentCtx,
@@ -334,7 +354,11 @@ fn cpp_type(&self) -> Option<String> {
             Type::Image => Some("slint::Image".to_owned()),
             Type::Builtin(elem) => elem.native_class.cpp_type.clone(),
             Type::Enumeration(enumeration) => {
-                Some(format!("slint::cbindgen_private::{}", ident(&enumeration.name)))
+                if enumeration.node.is_some() {
+                    Some(ident(&enumeration.name))
+                } else {
+                    Some(format!("slint::cbindgen_private::{}", ident(&enumeration.name)))
+                }
             }
             Type::Brush => Some("slint::Brush".to_owned()),
             Type::LayoutCache => Some("slint::SharedVector<float>".into()),
@@ -513,9 +537,15 @@ pub fn generate(doc: &Document) -> impl std::fmt::Display {
         },
     ));
 
-    for ty in doc.root_component.used_types.borrow().structs.iter() {
-        if let Type::Struct { fields, name: Some(name), node: Some(_) } = ty {
-            generate_struct(&mut file, name, fields);
+    for ty in doc.root_component.used_types.borrow().structs_and_enums.iter() {
+        match ty {
+            Type::Struct { fields, name: Some(name), node: Some(_) } => {
+                generate_struct(&mut file, name, fields);
+            }
+            Type::Enumeration(en) if en.node.is_some() => {
+                generate_enum(&mut file, en);
+            }
+            _ => (),
         }
     }
 
@@ -601,6 +631,13 @@ fn generate_struct(file: &mut File, name: &str, fields: &BTreeMap<String, Type>)
     }))
 }
 
+fn generate_enum(file: &mut File, en: &Enumeration) {
+    file.declarations.push(Declaration::Enum(Enum {
+        name: ident(&en.name),
+        values: en.values.iter().map(|value| ident(value)).collect(),
+    }))
+}
+
 /// Generate the component in `file`.
 ///
 /// `sub_components`, if Some, will be filled with all the sub component which needs to be added as friends
@@ -650,8 +687,9 @@ fn generate_public_component(file: &mut File, component: &llr::PublicComponent)
         };
     }
 
-    let declarations = generate_public_api_for_properties(&component.public_properties, &ctx);
-    component_struct.members.extend(declarations.into_iter().map(|decl| (Access::Public, decl)));
+    component_struct
+        .members
+        .extend(generate_public_api_for_properties(&component.public_properties, &ctx));
 
     component_struct.members.push((
         Access::Public,
@@ -788,10 +826,52 @@ fn generate_item_tree(
 
     let root_access = if parent_ctx.is_some() { "parent->root" } else { "self" };
 
+    let ctx = EvaluationContext::new_sub_component(
+        root,
+        &sub_tree.root,
+        "self->root".into(),
+        parent_ctx.clone(),
+    );
+
     let mut tree_array: Vec<String> = Default::default();
+    let mut item_element_id_cases: Vec<String> = Default::default();
+    let mut accessible_role_cases: Vec<String> = Default::default();
+    let mut accessible_checked_cases: Vec<String> = Default::default();
+    let mut accessible_string_property_cases: Vec<String> = Default::default();
 
     sub_tree.tree.visit_in_array(&mut |node, children_offset, parent_index| {
         let parent_index = parent_index as u32;
+        let original_id = node.original_id(&sub_tree.root);
+        if !original_id.is_empty() {
+            item_element_id_cases.push(format!(
+                "    case {}: *result = \"{}\"; break;",
+                tree_array.len(),
+                original_id
+            ));
+        }
+        for (name, prop_ref) in node.accessibility_props(&sub_tree.root) {
+            let prop = access_member(&prop_ref, &ctx);
+            match name.as_str() {
+                "accessible-role" => accessible_role_cases
+                    .push(format!("    case {}: return {}.get();", tree_array.len(), prop)),
+                "accessible-checked" => accessible_checked_cases
+                    .push(format!("    case {}: return {}.get();", tree_array.len(), prop)),
+                _ => {
+                    let what = match name.as_str() {
+                        "accessible-label" => "Label",
+                        "accessible-description" => "Description",
+                        "accessible-value" => "Value",
+                        _ => unreachable!("unknown accessibility property {}", name),
+                    };
+                    accessible_string_property_cases.push(format!(
+                        "    if (index == {} && what == slint::cbindgen_private::AccessibleStringProperty::{}) {{ *result = {}.get(); return; }}",
+                        tree_array.len(),
+                        what,
+                        prop
+                    ));
+                }
+            }
+        }
 
         if node.repeated {
             assert_eq!(node.children.len(), 0);
@@ -949,6 +1029,83 @@ fn generate_item_tree(
         }),
     ));
 
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "item_element_id".into(),
+            signature: "([[maybe_unused]] slint::private_api::ComponentRef component, [[maybe_unused]] uintptr_t index, [[maybe_unused]] slint::SharedString *result) -> void".into(),
+            is_static: true,
+            statements: Some(
+                std::iter::once("switch (index) {".to_owned())
+                    .chain(item_element_id_cases)
+                    .chain(std::iter::once("}".to_owned()))
+                    .collect(),
+            ),
+            ..Default::default()
+        }),
+    ));
+
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "accessible_role".into(),
+            signature: "([[maybe_unused]] slint::private_api::ComponentRef component, [[maybe_unused]] uintptr_t index) -> slint::cbindgen_private::AccessibleRole".into(),
+            is_static: true,
+            statements: Some(
+                [
+                    format!("[[maybe_unused]] auto self = reinterpret_cast<const {}*>(component.instance);", item_tree_class_name),
+                    "switch (index) {".to_owned(),
+                ]
+                .into_iter()
+                .chain(accessible_role_cases)
+                .chain([
+                    "}".to_owned(),
+                    "return slint::cbindgen_private::AccessibleRole::none;".to_owned(),
+                ])
+                .collect(),
+            ),
+            ..Default::default()
+        }),
+    ));
+
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "accessible_checked".into(),
+            signature: "([[maybe_unused]] slint::private_api::ComponentRef component, [[maybe_unused]] uintptr_t index) -> bool".into(),
+            is_static: true,
+            statements: Some(
+                [
+                    format!("[[maybe_unused]] auto self = reinterpret_cast<const {}*>(component.instance);", item_tree_class_name),
+                    "switch (index) {".to_owned(),
+                ]
+                .into_iter()
+                .chain(accessible_checked_cases)
+                .chain(["}".to_owned(), "return false;".to_owned()])
+                .collect(),
+            ),
+            ..Default::default()
+        }),
+    ));
+
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "accessible_string_property".into(),
+            signature: "([[maybe_unused]] slint::private_api::ComponentRef component, [[maybe_unused]] uintptr_t index, [[maybe_unused]] slint::cbindgen_private::AccessibleStringProperty what, [[maybe_unused]] slint::SharedString *result) -> void".into(),
+            is_static: true,
+            statements: Some(
+                std::iter::once(format!(
+                    "[[maybe_unused]] auto self = reinterpret_cast<const {}*>(component.instance);",
+                    item_tree_class_name
+                ))
+                .chain(accessible_string_property_cases)
+                .collect(),
+            ),
+            ..Default::default()
+        }),
+    ));
+
     target_struct.members.push((
         Access::Public,
         Declaration::Var(Var {
@@ -962,7 +1119,7 @@ fn generate_item_tree(
         ty: "const slint::private_api::ComponentVTable".to_owned(),
         name: format!("{}::static_vtable", item_tree_class_name),
         init: Some(format!(
-            "{{ visit_children, get_item_ref, parent_item,  layout_info, slint::private_api::drop_in_place<{}>, slint::private_api::dealloc }}",
+            "{{ visit_children, get_item_ref, parent_item,  layout_info, item_element_id, accessible_role, accessible_checked, accessible_string_property, slint::private_api::drop_in_place<{}>, slint::private_api::dealloc }}",
             item_tree_class_name)
         ),
         ..Default::default()
@@ -998,6 +1155,7 @@ fn generate_item_tree(
     create_code.extend([
         format!("{}->m_window.window_handle().init_items(self, item_tree());", root_access),
         format!("self->init({}, self->self_weak, 0, 1 {});", root_access, init_parent_parameters),
+        "self->init_change_trackers();".to_owned(),
         format!("return slint::ComponentHandle<{0}>{{ self_rc }};", target_struct.name),
     ]);
 
@@ -1150,6 +1308,7 @@ fn generate_sub_component(
     let mut children_visitor_cases = Vec::new();
 
     let mut subcomponent_init_code = Vec::new();
+    let mut change_trackers_init = vec!["[[maybe_unused]] auto self = this;".to_owned()];
     for sub in &component.sub_components {
         let field_name = ident(&sub.name);
         let local_tree_index: u32 = sub.index_in_tree as _;
@@ -1172,6 +1331,7 @@ fn generate_sub_component(
             "this->{}.init(root, self_weak.into_dyn(), {}, {});",
             field_name, global_index, global_children
         ));
+        change_trackers_init.push(format!("this->{}.init_change_trackers();", field_name));
 
         let sub_component_repeater_count = sub.ty.repeater_count();
         if sub_component_repeater_count > 0 {
@@ -1268,6 +1428,12 @@ fn generate_sub_component(
             let lv_h = access_member(&listview.listview_height, &ctx);
             let vp_w = access_member(&listview.viewport_width, &ctx);
             let lv_w = access_member(&listview.listview_width, &ctx);
+            let ensure_visible = access_member(&listview.ensure_visible, &ctx);
+
+            properties_init_code.push(format!(
+                "{}.set_handler([self](int row) {{ self->{}.ensure_visible_listview(row, &{}, {}.get()); }});",
+                ensure_visible, repeater_id, vp_y, lv_h
+            ));
 
             format!(
                 "self->{}.ensure_updated_listview(self, &{}, &{}, &{}, {}.get(), {}.get());",
@@ -1305,6 +1471,32 @@ fn generate_sub_component(
     init.extend(properties_init_code);
     init.extend(component.init_code.iter().map(|e| compile_expression(e, &ctx)));
 
+    for (idx, (prop, handler)) in component.change_callbacks.iter().enumerate() {
+        let change_tracker = format!("change_tracker{}", idx);
+        change_trackers_init.push(format!(
+            "self->{change_tracker}.init(
+                    [this]() {{
+                        [[maybe_unused]] auto self = this;
+                        return {prop_access}.get();
+                    }},
+                    [this]() {{
+                        [[maybe_unused]] auto self = this;
+                        {code};
+                    }});",
+            change_tracker = change_tracker,
+            prop_access = access_member(prop, &ctx),
+            code = compile_expression(handler, &ctx)
+        ));
+        target_struct.members.push((
+            Access::Private,
+            Declaration::Var(Var {
+                ty: "slint::private_api::ChangeTracker".into(),
+                name: change_tracker,
+                ..Default::default()
+            }),
+        ));
+    }
+
     target_struct.members.push((
         field_access,
         Declaration::Function(Function {
@@ -1315,6 +1507,21 @@ fn generate_sub_component(
         }),
     ));
 
+    // Called once the whole tree is initialized, so that the properties have their final bindings
+    target_struct.members.push((
+        field_access,
+        Declaration::Function(Function {
+            name: "init_change_trackers".to_owned(),
+            signature: "() -> void".to_owned(),
+            statements: Some(change_trackers_init),
+            ..Default::default()
+        }),
+    ));
+
+    target_struct.members.extend(
+        generate_functions(&component.functions, &ctx).into_iter().map(|f| (field_access, f)),
+    );
+
     target_struct.members.push((
         field_access,
         Declaration::Function(Function {
@@ -1503,8 +1710,13 @@ fn generate_global(file: &mut File, global: &llr::GlobalComponent, root: &llr::P
         }),
     ));
 
-    let declarations = generate_public_api_for_properties(&global.public_properties, &ctx);
-    global_struct.members.extend(declarations.into_iter().map(|decl| (Access::Public, decl)));
+    global_struct.members.extend(
+        generate_functions(&global.functions, &ctx).into_iter().map(|f| (Access::Public, f)),
+    );
+
+    global_struct
+        .members
+        .extend(generate_public_api_for_properties(&global.public_properties, &ctx));
 
     file.definitions.extend(global_struct.extract_definitions().collect::<Vec<_>>());
     file.declarations.push(Declaration::Struct(global_struct));
@@ -1513,11 +1725,44 @@ fn generate_global(file: &mut File, global: &llr::GlobalComponent, root: &llr::P
 fn generate_public_api_for_properties(
     public_properties: &llr::PublicProperties,
     ctx: &EvaluationContext,
-) -> Vec<Declaration> {
+) -> Vec<(Access, Declaration)> {
     let mut declarations = Vec::new();
+    // The change trackers used by the `on_<property>_changed` functions
+    let mut private_declarations = Vec::new();
     for (p, (ty, r)) in public_properties.iter() {
         let prop_ident = ident(p);
 
+        if let Type::Function { args, return_type } = ty {
+            let param_types = args.iter().map(|t| t.cpp_type().unwrap()).collect::<Vec<_>>();
+            let return_type = if **return_type == Type::Void {
+                "void".into()
+            } else {
+                return_type.cpp_type().unwrap()
+            };
+            declarations.push(Declaration::Function(Function {
+                name: format!("invoke_{}", prop_ident),
+                signature: format!(
+                    "({}) const -> {}",
+                    param_types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, ty)| format!("{} arg_{}", ty, i))
+                        .join(", "),
+                    return_type
+                ),
+                statements: Some(vec![
+                    "[[maybe_unused]] auto self = this;".into(),
+                    format!(
+                        "return {}({});",
+                        access_function(r, ctx),
+                        (0..args.len()).map(|i| format!("arg_{}", i)).join(", ")
+                    ),
+                ]),
+                ..Default::default()
+            }));
+            continue;
+        }
+
         let access = access_member(r, ctx);
 
         if let Type::Callback { args, return_type } = ty {
@@ -1578,9 +1823,40 @@ fn generate_public_api_for_properties(
                 statements: Some(prop_setter),
                 ..Default::default()
             }));
+
+            if !llr::has_on_changed_function(public_properties, p) {
+                continue;
+            }
+            let change_observer = format!("change_observer_{}", &prop_ident);
+            declarations.push(Declaration::Function(Function {
+                name: format!("on_{}_changed", &prop_ident),
+                template_parameters: Some("typename Functor".into()),
+                signature: "(Functor && callback_handler) const".into(),
+                statements: Some(vec![
+                    "[[maybe_unused]] auto self = this;".into(),
+                    format!(
+                        "self->{observer}.init([self] {{ return {access}.get(); }},
+                            [self, callback_handler = std::forward<Functor>(callback_handler)]() mutable {{
+                                callback_handler({access}.get());
+                            }});",
+                        observer = change_observer,
+                        access = access
+                    ),
+                ]),
+                ..Default::default()
+            }));
+            private_declarations.push(Declaration::Var(Var {
+                ty: "slint::private_api::ChangeTracker".into(),
+                name: change_observer,
+                ..Default::default()
+            }));
         }
     }
     declarations
+        .into_iter()
+        .map(|decl| (Access::Public, decl))
+        .chain(private_declarations.into_iter().map(|decl| (Access::Private, decl)))
+        .collect()
 }
 
 fn follow_sub_component_path<'a>(
@@ -1670,7 +1946,10 @@ fn in_native_item(
                     item_index,
                     prop_name,
                 } => in_native_item(ctx, sub_component_path, *item_index, prop_name, &path),
-                llr::PropertyReference::InParent { .. } | llr::PropertyReference::Global { .. } => {
+                llr::PropertyReference::InParent { .. }
+                | llr::PropertyReference::Global { .. }
+                | llr::PropertyReference::Function { .. }
+                | llr::PropertyReference::GlobalFunction { .. } => {
                     unreachable!()
                 }
             }
@@ -1684,9 +1963,103 @@ fn in_native_item(
             );
             format!("{}->{}->{}", root_access, global_id, property_name)
         }
+        llr::PropertyReference::Function { .. } | llr::PropertyReference::GlobalFunction { .. } => {
+            unreachable!("functions are accessed with access_function")
+        }
+    }
+}
+
+/// Returns the code that can access the member function generated for a function
+/// (but without the arguments)
+fn access_function(reference: &llr::PropertyReference, ctx: &EvaluationContext) -> String {
+    fn in_sub_component(
+        ctx: &EvaluationContext,
+        sub_component_path: &[usize],
+        function_index: usize,
+        path: &str,
+    ) -> String {
+        let (compo_path, sub_component) =
+            follow_sub_component_path(ctx.current_sub_component.unwrap(), sub_component_path);
+        format!(
+            "{}->{}{}",
+            path,
+            compo_path,
+            function_ident(&sub_component.functions[function_index])
+        )
+    }
+
+    match reference {
+        llr::PropertyReference::Function { sub_component_path, function_index } => {
+            if ctx.current_sub_component.is_some() {
+                in_sub_component(ctx, sub_component_path, *function_index, "self")
+            } else if let Some(current_global) = ctx.current_global {
+                format!("this->{}", function_ident(&current_global.functions[*function_index]))
+            } else {
+                unreachable!()
+            }
+        }
+        llr::PropertyReference::GlobalFunction { global_index, function_index } => {
+            let root_access = &ctx.generator_state;
+            let global = &ctx.public_component.globals[*global_index];
+            let global_id = format!("global_{}", ident(&global.name));
+            format!(
+                "{}->{}->{}",
+                root_access,
+                global_id,
+                function_ident(&global.functions[*function_index])
+            )
+        }
+        llr::PropertyReference::InParent { level, parent_reference } => {
+            let mut ctx = ctx;
+            let mut path = "self".to_string();
+            for _ in 0..level.get() {
+                write!(path, "->parent").unwrap();
+                ctx = ctx.parent.as_ref().unwrap().ctx;
+            }
+            match &**parent_reference {
+                llr::PropertyReference::Function { sub_component_path, function_index } => {
+                    in_sub_component(ctx, sub_component_path, *function_index, &path)
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!("not a function"),
     }
 }
 
+fn function_ident(function: &llr::Function) -> String {
+    format!("fn_{}", ident(&function.name))
+}
+
+/// Generate the member functions for the functions of a sub component or a global
+fn generate_functions(functions: &[llr::Function], ctx: &EvaluationContext) -> Vec<Declaration> {
+    functions
+        .iter()
+        .map(|f| {
+            let mut ctx2 = ctx.clone();
+            ctx2.argument_types = &f.args;
+            let mut params = f.args.iter().enumerate().map(|(i, ty)| {
+                format!("[[maybe_unused]] {} arg_{}", ty.cpp_type().unwrap_or_default(), i)
+            });
+            let return_type =
+                if f.ret_ty == Type::Void { "void".into() } else { f.ret_ty.cpp_type().unwrap() };
+            Declaration::Function(Function {
+                name: function_ident(f),
+                signature: format!("({}) const -> {}", params.join(", "), return_type),
+                statements: Some(vec![
+                    "[[maybe_unused]] auto self = this;".into(),
+                    if f.ret_ty == Type::Void {
+                        format!("{};", compile_expression_wrap_return(&f.code, &ctx2))
+                    } else {
+                        format!("return {};", compile_expression_wrap_return(&f.code, &ctx2))
+                    },
+                ]),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
 /// Returns the NativeClass for a PropertyReference::InNativeItem
 /// (or a InParent of InNativeItem )
 fn native_item<'a>(
@@ -1739,6 +2112,11 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
             let mut a = arguments.iter().map(|a| compile_expression(a, ctx));
             format!("{}.call({})", f, a.join(","))
         }
+        Expression::FunctionCall{ function, arguments } => {
+            let f = access_function(function, ctx);
+            let mut a = arguments.iter().map(|a| compile_expression(a, ctx));
+            format!("{}({})", f, a.join(","))
+        }
         Expression::ExtraBuiltinFunctionCall { function, arguments, return_ty: _ } => {
             let mut a = arguments.iter().map(|a| compile_expression(a, ctx));
             format!("slint::private_api::{}({})", ident(function), a.join(","))
@@ -2017,8 +2395,31 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 stops_it.join(", "), angle, stops.len()
             )
         }
+        Expression::RadialGradient{stops} => {
+            let mut stops_it = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, ctx);
+                let position = compile_expression(stop, ctx);
+                format!("slint::private_api::GradientStop{{ {}, {}, }}", color, position)
+            });
+            format!(
+                "[&] {{ const slint::private_api::GradientStop stops[] = {{ {} }}; return slint::Brush(slint::private_api::RadialGradientBrush(stops, {})); }}()",
+                stops_it.join(", "), stops.len()
+            )
+        }
+        Expression::ConicGradient{stops} => {
+            let mut stops_it = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, ctx);
+                let position = compile_expression(stop, ctx);
+                format!("slint::private_api::GradientStop{{ {}, {}, }}", color, position)
+            });
+            format!(
+                "[&] {{ const slint::private_api::GradientStop stops[] = {{ {} }}; return slint::Brush(slint::private_api::ConicGradientBrush(stops, {})); }}()",
+                stops_it.join(", "), stops.len()
+            )
+        }
         Expression::EnumerationValue(value) => {
-            format!("slint::cbindgen_private::{}::{}", value.enumeration.name, ident(&value.to_string()))
+            let prefix = if value.enumeration.node.is_some() { "" } else { "slint::cbindgen_private::" };
+            format!("{}{}::{}", prefix, ident(&value.enumeration.name), ident(&value.to_string()))
         }
         Expression::ReturnStatement(Some(expr)) => format!(
             "throw slint::private_api::ReturnWrapper<{}>({})",
@@ -2130,6 +2531,22 @@ fn compile_builtin_function_call(
         BuiltinFunction::StringToFloat => {
             format!("[](const auto &a){{ auto e1 = std::end(a); auto e2 = const_cast<char*>(e1); auto r = std::strtod(std::begin(a), &e2); return e1 == e2 ? r : 0; }}({})", a.next().unwrap())
         }
+        BuiltinFunction::Translate => {
+            if let [original, context, llr::Expression::Array { values, .. }, n, plural] = arguments
+            {
+                format!(
+                    "slint::private_api::translate({}, {}, std::array<slint::SharedString, {}>{{ {} }}, {}, {})",
+                    compile_expression(original, ctx),
+                    compile_expression(context, ctx),
+                    values.len(),
+                    values.iter().map(|v| compile_expression(v, ctx)).join(", "),
+                    compile_expression(n, ctx),
+                    compile_expression(plural, ctx)
+                )
+            } else {
+                panic!("internal error: invalid args to Translate {:?}", arguments)
+            }
+        }
         BuiltinFunction::ColorBrighter => {
             format!("{}.brighter({})", a.next().unwrap(), a.next().unwrap())
         }
@@ -2169,6 +2586,32 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::FlickableScrollTo => {
+            if let [llr::Expression::PropertyReference(pr), x, y, animated] = arguments {
+                format!(
+                    "slint::cbindgen_private::slint_flickable_scroll_to(&{}, {}, {}, {});",
+                    access_member(pr, ctx),
+                    compile_expression(x, ctx),
+                    compile_expression(y, ctx),
+                    compile_expression(animated, ctx)
+                )
+            } else {
+                panic!("internal error: invalid args to FlickableScrollTo {:?}", arguments)
+            }
+        }
+        BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                let window = access_window_field(ctx);
+                let method = if matches!(function, BuiltinFunction::TextInputUndo) {
+                    "undo"
+                } else {
+                    "redo"
+                };
+                format!("{}.text_input_{}(&{});", window, method, access_member(pr, ctx))
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
         BuiltinFunction::RegisterCustomFontByPath => {
             if let [llr::Expression::StringLiteral(path)] = arguments {
                 format!("slint::private_api::register_font_from_path(\"{}\");", escape_string(path))
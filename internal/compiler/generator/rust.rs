@@ -11,7 +11,7 @@
 */
 
 use crate::expression_tree::{BuiltinFunction, EasingCurve, OperatorClass};
-use crate::langtype::Type;
+use crate::langtype::{Enumeration, Type};
 use crate::layout::Orientation;
 use crate::llr::{
     self, EvaluationContext as llr_EvaluationContext, Expression, ParentCtx as llr_ParentCtx,
@@ -81,8 +81,12 @@ fn rust_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
             Some(quote!(slint::re_exports::ModelRc<#inner>))
         }
         Type::Enumeration(e) => {
-            let e = ident(&e.name);
-            Some(quote!(slint::re_exports::#e))
+            let e_ident = ident(&e.name);
+            if e.node.is_some() {
+                Some(quote!(#e_ident))
+            } else {
+                Some(quote!(slint::re_exports::#e_ident))
+            }
         }
         Type::Brush => Some(quote!(slint::Brush)),
         Type::LayoutCache => Some(quote!(SharedVector<f32>)),
@@ -97,18 +101,20 @@ pub fn generate(doc: &Document) -> TokenStream {
         return TokenStream::default();
     }
 
-    let (structs_ids, structs): (Vec<_>, Vec<_>) = doc
+    let (structs_and_enums_ids, structs_and_enum_def): (Vec<_>, Vec<_>) = doc
         .root_component
         .used_types
         .borrow()
-        .structs
+        .structs_and_enums
         .iter()
-        .filter_map(|ty| {
-            if let Type::Struct { fields, name: Some(name), node: Some(_) } = ty {
+        .filter_map(|ty| match ty {
+            Type::Struct { fields, name: Some(name), node: Some(_) } => {
                 Some((ident(name), generate_struct(name, fields)))
-            } else {
-                None
             }
+            Type::Enumeration(en) if en.node.is_some() => {
+                Some((ident(&en.name), generate_enum(en)))
+            }
+            _ => None,
         })
         .unzip();
 
@@ -220,14 +226,14 @@ pub fn generate(doc: &Document) -> TokenStream {
         #[allow(clippy::approx_constant)] // We may get those from .slint inputs!
         mod #compo_module {
             use slint::re_exports::*;
-            #(#structs)*
+            #(#structs_and_enum_def)*
             #(#globals)*
             #(#sub_compos)*
             #compo
             #(#resource_symbols)*
             const _THE_SAME_VERSION_MUST_BE_USED_FOR_THE_COMPILER_AND_THE_RUNTIME : slint::#version_check = slint::#version_check;
         }
-        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#globals_ids)* };
+        pub use #compo_module::{#compo_id #(,#structs_and_enums_ids)* #(,#globals_ids)* };
         pub use slint::{ComponentHandle, Global};
     }
 }
@@ -237,8 +243,13 @@ fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
     let inner_component_id = inner_component_id(&llr.item_tree.root);
     let global_container_id = format_ident!("Globals_{}", public_component_id);
 
-    let component =
-        generate_item_tree(&llr.item_tree, llr, None, quote!(globals: #global_container_id));
+    let change_observers = change_observer_fields(&llr.public_properties);
+    let component = generate_item_tree(
+        &llr.item_tree,
+        llr,
+        None,
+        quote!(globals: #global_container_id, #change_observers),
+    );
 
     let ctx = EvaluationContext {
         public_component: llr,
@@ -249,8 +260,13 @@ fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
         argument_types: &[],
     };
 
-    let property_and_callback_accessors =
-        public_api(&llr.public_properties, quote!(vtable::VRc::as_pin_ref(&self.0)), &ctx);
+    let property_and_callback_accessors = public_api(
+        &llr.public_properties,
+        quote!(vtable::VRc::as_pin_ref(&self.0)),
+        quote!(vtable::VRc::downgrade(&self.0)),
+        quote!(vtable::VRc::as_pin_ref(&self_rc)),
+        &ctx,
+    );
 
     let global_names =
         llr.globals.iter().map(|g| format_ident!("global_{}", ident(&g.name))).collect::<Vec<_>>();
@@ -337,6 +353,24 @@ pub struct #component_id {
     }
 }
 
+fn generate_enum(en: &Enumeration) -> TokenStream {
+    let enum_name = ident(&en.name);
+    let enum_values = en.values.iter().map(|value| ident(value));
+    let default_value = ident(&en.values[en.default_value]);
+
+    quote! {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+        pub enum #enum_name {
+            #(#enum_values,)*
+        }
+        impl Default for #enum_name {
+            fn default() -> Self {
+                Self::#default_value
+            }
+        }
+    }
+}
+
 fn handle_property_init(
     prop: &llr::PropertyReference,
     binding_expression: &llr::BindingExpression,
@@ -432,15 +466,54 @@ fn handle_property_init(
     }
 }
 
+/// The name of the field holding the `ChangeTracker` used by the `on_<property>_changed` function
+fn change_observer_ident(property: &str) -> Ident {
+    format_ident!("change_observer_{}", ident(property))
+}
+
+/// The change observers of the public properties, which are fields of the component or global
+fn change_observer_fields(public_properties: &llr::PublicProperties) -> TokenStream {
+    let names = public_properties
+        .iter()
+        .filter(|(p, (ty, _))| {
+            !matches!(ty, Type::Callback { .. } | Type::Function { .. })
+                && llr::has_on_changed_function(public_properties, p)
+        })
+        .map(|(p, _)| change_observer_ident(p));
+    quote!(#(#names : slint::re_exports::ChangeTracker,)*)
+}
+
 /// Public API for Global and root component
+///
+/// `self_init` gets the pinned inner component from `self`, `self_weak` creates a weak reference
+/// to it, and `self_from_rc` gets the pinned inner component from the upgraded `self_rc`.
 fn public_api(
     public_properties: &llr::PublicProperties,
     self_init: TokenStream,
+    self_weak: TokenStream,
+    self_from_rc: TokenStream,
     ctx: &EvaluationContext,
 ) -> TokenStream {
     let mut property_and_callback_accessors: Vec<TokenStream> = vec![];
     for (p, (ty, r)) in public_properties {
         let prop_ident = ident(p);
+
+        if let Type::Function { args, return_type } = ty {
+            let function_args = args.iter().map(|a| rust_type(a).unwrap()).collect::<Vec<_>>();
+            let return_type = rust_type(return_type).unwrap_or_else(|| quote!(()));
+            let args_name = (0..args.len()).map(|i| format_ident!("arg_{}", i)).collect::<Vec<_>>();
+            let caller_ident = format_ident!("invoke_{}", prop_ident);
+            let f = access_function(r, ctx);
+            property_and_callback_accessors.push(quote!(
+                #[allow(dead_code)]
+                pub fn #caller_ident(&self, #(#args_name : #function_args,)*) -> #return_type {
+                    let _self = #self_init;
+                    #f((#(#args_name,)*))
+                }
+            ));
+            continue;
+        }
+
         let prop = access_member(r, ctx);
 
         if let Type::Callback { args, return_type } = ty {
@@ -494,6 +567,37 @@ pub fn #setter_ident(&self, value: #rust_property_type) {
                     #set_value
                 }
             ));
+
+            if !llr::has_on_changed_function(public_properties, p) {
+                continue;
+            }
+            let on_changed_ident = format_ident!("on_{}_changed", prop_ident);
+            let observer_ident = change_observer_ident(p);
+            property_and_callback_accessors.push(quote!(
+                #[allow(dead_code)]
+                pub fn #on_changed_ident(&self, f: impl FnMut(#rust_property_type) + 'static) {
+                    #[allow(unused_imports)]
+                    use slint::re_exports::*;
+                    let _self = #self_init;
+                    let self_weak = #self_weak;
+                    let f = ::core::cell::RefCell::new(f);
+                    _self.#observer_ident.init(
+                        {
+                            let self_weak = self_weak.clone();
+                            move || {
+                                let self_rc = self_weak.upgrade().unwrap();
+                                let _self = #self_from_rc;
+                                #prop.get()
+                            }
+                        },
+                        move || {
+                            let self_rc = self_weak.upgrade().unwrap();
+                            let _self = #self_from_rc;
+                            (f.borrow_mut())(#prop.get())
+                        },
+                    );
+                }
+            ));
         }
     }
 
@@ -597,6 +701,20 @@ fn generate_sub_component(
             let lv_h = access_member(&listview.listview_height, &ctx);
             let vp_w = access_member(&listview.viewport_width, &ctx);
             let lv_w = access_member(&listview.listview_width, &ctx);
+            let ensure_visible = access_member(&listview.ensure_visible, &ctx);
+
+            init.push(quote! {
+                #ensure_visible.set_handler({
+                    let self_weak = slint::re_exports::VRcMapped::downgrade(&self_rc);
+                    move |args| {
+                        let self_rc = self_weak.upgrade().unwrap();
+                        let _self = self_rc.as_pin_ref();
+                        #inner_component_id::FIELD_OFFSETS.#repeater_id.apply_pin(_self).ensure_visible_listview(
+                            args.0.max(0) as usize, #vp_y, #lv_h.get()
+                        );
+                    }
+                });
+            });
 
             quote! {
                 #inner_component_id::FIELD_OFFSETS.#repeater_id.apply_pin(_self).ensure_updated_listview(
@@ -623,6 +741,7 @@ fn generate_sub_component(
 
     let mut sub_component_names: Vec<Ident> = vec![];
     let mut sub_component_types: Vec<Ident> = vec![];
+    let mut change_trackers_init = vec![];
 
     for sub in &component.sub_components {
         let field_name = ident(&sub.name);
@@ -651,6 +770,9 @@ fn generate_sub_component(
             &#root_ref_tokens,
             #global_index, #global_children
         );));
+        change_trackers_init.push(quote!(#sub_component_id::init_change_trackers(
+            VRcMapped::map(self_rc.clone(), |x| #sub_compo_field.apply_pin(x))
+        );));
 
         let sub_component_repeater_count = sub.ty.repeater_count();
         if sub_component_repeater_count > 0 {
@@ -698,8 +820,36 @@ fn generate_sub_component(
 
     init.extend(component.init_code.iter().map(|e| compile_expression(e, &ctx)));
 
+    let mut change_tracker_names = vec![];
+    for (idx, (prop, handler)) in component.change_callbacks.iter().enumerate() {
+        let change_tracker = format_ident!("change_tracker{}", idx);
+        let rust_property = access_member(prop, &ctx);
+        let handler = compile_expression(handler, &ctx);
+        change_trackers_init.push(quote!({
+            let self_weak = slint::re_exports::VRcMapped::downgrade(&self_rc);
+            #[allow(unreachable_code, unused)]
+            _self.#change_tracker.init(
+                {
+                    let self_weak = self_weak.clone();
+                    move || {
+                        let self_rc = self_weak.upgrade().unwrap();
+                        let _self = self_rc.as_pin_ref();
+                        #rust_property.get()
+                    }
+                },
+                move || {
+                    let self_rc = self_weak.upgrade().unwrap();
+                    let _self = self_rc.as_pin_ref();
+                    #handler;
+                },
+            );
+        }));
+        change_tracker_names.push(change_tracker);
+    }
+
     let layout_info_h = compile_expression(&component.layout_info_h, &ctx);
     let layout_info_v = compile_expression(&component.layout_info_v, &ctx);
+    let functions = generate_functions(&component.functions, &ctx);
 
     // FIXME! this is only public because of the ComponentHandle::Inner. we should find another way
     let visibility =
@@ -717,6 +867,7 @@ struct #inner_component_id {
             #(#declared_property_vars : slint::re_exports::Property<#declared_property_types>,)*
             #(#declared_callbacks : slint::re_exports::Callback<(#(#declared_callbacks_types,)*), #declared_callbacks_ret>,)*
             #(#repeated_element_names : slint::re_exports::Repeater<#repeated_element_components>,)*
+            #(#change_tracker_names : slint::re_exports::ChangeTracker,)*
             self_weak : slint::re_exports::OnceCell<slint::re_exports::VWeakMapped<slint::re_exports::ComponentVTable, #inner_component_id>>,
             #(parent : #parent_component_type,)*
             // FIXME: Do we really need a window all the time?
@@ -741,6 +892,13 @@ pub fn init(self_rc: slint::re_exports::VRcMapped<slint::re_exports::ComponentVT
                 #(#init)*
             }
 
+            // Called once the whole tree is initialized, so that the properties have their final bindings
+            pub fn init_change_trackers(self_rc: slint::re_exports::VRcMapped<slint::re_exports::ComponentVTable, Self>) {
+                #![allow(unused)]
+                let _self = self_rc.as_pin_ref();
+                #(#change_trackers_init)*
+            }
+
             fn visit_dynamic_children(
                 self: ::core::pin::Pin<&Self>,
                 dyn_index: usize,
@@ -765,6 +923,8 @@ fn layout_info(self: ::core::pin::Pin<&Self>, orientation: slint::re_exports::Or
                     slint::re_exports::Orientation::Vertical => #layout_info_v,
                 }
             }
+
+            #(#functions)*
         }
 
         #(#extra_components)*
@@ -825,9 +985,18 @@ fn generate_global(global: &llr::GlobalComponent, root: &llr::PublicComponent) -
     }
 
     let inner_component_id = format_ident!("Inner{}", ident(&global.name));
+    let functions = generate_functions(&global.functions, &ctx);
+    let change_observers =
+        global.exported.then(|| change_observer_fields(&global.public_properties));
 
     let public_interface = global.exported.then(|| {
-        let property_and_callback_accessors = public_api(&global.public_properties, quote!(self.0.as_ref()), &ctx);
+        let property_and_callback_accessors = public_api(
+            &global.public_properties,
+            quote!(self.0.as_ref()),
+            quote!(slint::re_exports::PinWeak::downgrade(self.0.clone())),
+            quote!(self_rc.as_ref()),
+            &ctx,
+        );
         let public_component_id = ident(&global.name);
         let root_component_id = self::public_component_id(&root.item_tree.root);
         let global_id = format_ident!("global_{}", public_component_id);
@@ -858,6 +1027,7 @@ fn get(component: &'a #root_component_id) -> Self {
         struct #inner_component_id {
             #(#declared_property_vars: slint::re_exports::Property<#declared_property_types>,)*
             #(#declared_callbacks: slint::re_exports::Callback<(#(#declared_callbacks_types,)*), #declared_callbacks_ret>,)*
+            #change_observers
         }
 
         impl #inner_component_id {
@@ -867,6 +1037,8 @@ fn new() -> ::core::pin::Pin<slint::re_exports::Rc<Self>> {
                 #(#init)*
                 self_rc
             }
+
+            #(#functions)*
         }
 
         #public_interface
@@ -902,6 +1074,13 @@ fn generate_item_tree(
         (None, None)
     };
 
+    let ctx = EvaluationContext::new_sub_component(
+        root,
+        &sub_tree.root,
+        quote!(_self.root.get().unwrap().upgrade().unwrap()),
+        parent_ctx.clone(),
+    );
+
     let parent_item_index = parent_ctx.and_then(|parent| {
         parent
             .repeater_index
@@ -909,8 +1088,39 @@ fn generate_item_tree(
     });
     let parent_item_index = parent_item_index.iter();
     let mut item_tree_array = vec![];
+    let mut item_element_id_index = vec![];
+    let mut item_element_id = vec![];
+    let mut accessible_role_cases = vec![];
+    let mut accessible_checked_cases = vec![];
+    let mut accessible_string_property_cases = vec![];
     sub_tree.tree.visit_in_array(&mut |node, children_offset, parent_index| {
         let parent_index = parent_index as u32;
+        let original_id = node.original_id(&sub_tree.root);
+        if !original_id.is_empty() {
+            item_element_id_index.push(item_tree_array.len());
+            item_element_id.push(original_id);
+        }
+        let index = item_tree_array.len();
+        for (name, prop_ref) in node.accessibility_props(&sub_tree.root) {
+            let prop = access_member(&prop_ref, &ctx);
+            match name.as_str() {
+                "accessible-role" => accessible_role_cases.push(quote!(#index => #prop.get(),)),
+                "accessible-checked" => {
+                    accessible_checked_cases.push(quote!(#index => #prop.get(),))
+                }
+                _ => {
+                    let what = ident(match name.as_str() {
+                        "accessible-label" => "Label",
+                        "accessible-description" => "Description",
+                        "accessible-value" => "Value",
+                        _ => unreachable!("unknown accessibility property {}", name),
+                    });
+                    accessible_string_property_cases.push(quote!(
+                        (#index, slint::re_exports::AccessibleStringProperty::#what) => *result = #prop.get(),
+                    ))
+                }
+            }
+        }
         let (path, component) = follow_sub_component_path(&sub_tree.root, &node.sub_component_path);
         if node.repeated {
             assert_eq!(node.children.len(), 0);
@@ -969,6 +1179,7 @@ pub fn new(#(parent: #parent_component_type)*)
                 #init_window
                 slint::re_exports::init_component_items(_self, Self::item_tree(), #root_token.window.get().unwrap().window_handle());
                 Self::init(slint::re_exports::VRc::map(self_rc.clone(), |x| x), #root_token, 0, 1);
+                Self::init_change_trackers(slint::re_exports::VRc::map(self_rc.clone(), |x| x));
                 self_rc
             }
 
@@ -1032,6 +1243,45 @@ fn parent_item(self: ::core::pin::Pin<&Self>, index: usize, result: &mut slint::
             fn layout_info(self: ::core::pin::Pin<&Self>, orientation: slint::re_exports::Orientation) -> slint::re_exports::LayoutInfo {
                 self.layout_info(orientation)
             }
+
+            fn item_element_id(self: ::core::pin::Pin<&Self>, index: usize, result: &mut slint::re_exports::SharedString) {
+                match index {
+                    #(#item_element_id_index => *result = #item_element_id.into(),)*
+                    _ => (),
+                }
+            }
+
+            fn accessible_role(self: ::core::pin::Pin<&Self>, index: usize) -> slint::re_exports::AccessibleRole {
+                #![allow(unused)]
+                let _self = self;
+                match index {
+                    #(#accessible_role_cases)*
+                    _ => slint::re_exports::AccessibleRole::none,
+                }
+            }
+
+            fn accessible_checked(self: ::core::pin::Pin<&Self>, index: usize) -> bool {
+                #![allow(unused)]
+                let _self = self;
+                match index {
+                    #(#accessible_checked_cases)*
+                    _ => false,
+                }
+            }
+
+            fn accessible_string_property(
+                self: ::core::pin::Pin<&Self>,
+                index: usize,
+                what: slint::re_exports::AccessibleStringProperty,
+                result: &mut slint::re_exports::SharedString,
+            ) {
+                #![allow(unused)]
+                let _self = self;
+                match (index, what) {
+                    #(#accessible_string_property_cases)*
+                    _ => (),
+                }
+            }
         }
 
 
@@ -1231,7 +1481,10 @@ fn in_native_item(
                     item_index,
                     prop_name,
                 } => in_native_item(ctx, sub_component_path, *item_index, prop_name, path),
-                llr::PropertyReference::InParent { .. } | llr::PropertyReference::Global { .. } => {
+                llr::PropertyReference::InParent { .. }
+                | llr::PropertyReference::Global { .. }
+                | llr::PropertyReference::Function { .. }
+                | llr::PropertyReference::GlobalFunction { .. } => {
                     unreachable!()
                 }
             }
@@ -1246,7 +1499,99 @@ fn in_native_item(
             );
             quote!(#global_name::FIELD_OFFSETS.#property_name.apply_pin(#root_access.globals.#global_id.as_ref()))
         }
+        llr::PropertyReference::Function { .. } | llr::PropertyReference::GlobalFunction { .. } => {
+            unreachable!("functions are accessed with access_function")
+        }
+    }
+}
+
+/// Returns the tokens to access the method generated for a function. To be followed by the arguments
+fn access_function(reference: &llr::PropertyReference, ctx: &EvaluationContext) -> TokenStream {
+    fn in_sub_component(
+        ctx: &EvaluationContext,
+        sub_component_path: &[usize],
+        function_index: usize,
+        path: TokenStream,
+    ) -> TokenStream {
+        let mut sub_component = ctx.current_sub_component.unwrap();
+        let mut compo_path = path;
+        for i in sub_component_path {
+            let component_id = inner_component_id(sub_component);
+            let sub_component_name = ident(&sub_component.sub_components[*i].name);
+            compo_path =
+                quote!(#component_id::FIELD_OFFSETS.#sub_component_name.apply_pin(#compo_path));
+            sub_component = &sub_component.sub_components[*i].ty;
+        }
+        let fn_id = function_ident(&sub_component.functions[function_index]);
+        quote!(#compo_path.#fn_id)
     }
+
+    match reference {
+        llr::PropertyReference::Function { sub_component_path, function_index } => {
+            if ctx.current_sub_component.is_some() {
+                in_sub_component(ctx, sub_component_path, *function_index, quote!(_self))
+            } else if let Some(current_global) = ctx.current_global {
+                let fn_id = function_ident(&current_global.functions[*function_index]);
+                quote!(_self.#fn_id)
+            } else {
+                unreachable!()
+            }
+        }
+        llr::PropertyReference::GlobalFunction { global_index, function_index } => {
+            let root_access = &ctx.generator_state;
+            let global = &ctx.public_component.globals[*global_index];
+            let global_id = format_ident!("global_{}", ident(&global.name));
+            let fn_id = function_ident(&global.functions[*function_index]);
+            quote!(#root_access.globals.#global_id.as_ref().#fn_id)
+        }
+        llr::PropertyReference::InParent { level, parent_reference } => {
+            let mut ctx = ctx;
+            let mut path = quote!(_self);
+            for _ in 0..level.get() {
+                path = quote!(#path.parent.upgrade().unwrap().as_pin_ref());
+                ctx = ctx.parent.as_ref().unwrap().ctx;
+            }
+            match &**parent_reference {
+                llr::PropertyReference::Function { sub_component_path, function_index } => {
+                    in_sub_component(ctx, sub_component_path, *function_index, path)
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!("not a function"),
+    }
+}
+
+fn function_ident(function: &llr::Function) -> Ident {
+    ident(&format!("fn_{}", function.name))
+}
+
+/// Generate the methods for the functions of a sub component or a global
+fn generate_functions(functions: &[llr::Function], ctx: &EvaluationContext) -> Vec<TokenStream> {
+    functions
+        .iter()
+        .map(|f| {
+            let mut ctx2 = ctx.clone();
+            ctx2.argument_types = &f.args;
+            let tokens_for_expression = compile_expression(&f.code, &ctx2);
+            let fn_id = function_ident(f);
+            let args_ty = f.args.iter().map(|a| rust_type(a).unwrap());
+            let (return_type, as_) = if f.ret_ty == Type::Void {
+                (quote!(()), quote!(;))
+            } else {
+                let return_type = rust_type(&f.ret_ty).unwrap();
+                (return_type.clone(), quote!(as #return_type))
+            };
+            quote! {
+                #[allow(dead_code, unreachable_code, unused)]
+                fn #fn_id(self: ::core::pin::Pin<&Self>, args: (#(#args_ty,)*)) -> #return_type {
+                    use slint::re_exports::*;
+                    let _self = self;
+                    (#tokens_for_expression) #as_
+                }
+            }
+        })
+        .collect()
 }
 
 fn follow_sub_component_path<'a>(
@@ -1348,7 +1693,11 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                         quote!(#name: obj.#index as _)
                     });
                     let id = struct_name_to_tokens(n);
-                    quote!({ let obj = #f; #id { #(#fields),*} })
+                    if is_euclid_point(n) {
+                        quote!({ let obj = #f; #id { #(#fields,)* ..Default::default() } })
+                    } else {
+                        quote!({ let obj = #f; #id { #(#fields),*} })
+                    }
                 }
                 (Type::Array(..), Type::PathData)
                     if matches!(
@@ -1409,6 +1758,11 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
             let a = arguments.iter().map(|a| compile_expression(a, ctx));
             quote! { #f.call(&(#(#a.clone() as _,)*).into())}
         }
+        Expression::FunctionCall { function, arguments } => {
+            let f = access_function(function, ctx);
+            let a = arguments.iter().map(|a| compile_expression(a, ctx));
+            quote! { #f((#((#a) as _,)*)) }
+        }
         Expression::ExtraBuiltinFunctionCall { function, arguments, return_ty: _ } => {
             let f = ident(function);
             let a = arguments.iter().map(|a| {
@@ -1599,7 +1953,7 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 if let Some(name) = name {
                     let name_tokens: TokenStream = struct_name_to_tokens(name.as_str());
                     let keys = fields.keys().map(|k| ident(k));
-                    if name == "Point" {
+                    if is_euclid_point(name) {
                         quote!(#name_tokens{#(#keys: #elem as _,)* ..Default::default()})
                     } else {
                         quote!(#name_tokens { #(#keys: #elem as _,)* })
@@ -1649,10 +2003,34 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 slint::re_exports::LinearGradientBrush::new(#angle as _, [#(#stops),*].iter().cloned())
             ))
         }
+        Expression::RadialGradient { stops } => {
+            let stops = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, ctx);
+                let position = compile_expression(stop, ctx);
+                quote!(slint::re_exports::GradientStop{ color: #color, position: #position as _ })
+            });
+            quote!(slint::Brush::RadialGradient(
+                slint::re_exports::RadialGradientBrush::new_circle([#(#stops),*].iter().cloned())
+            ))
+        }
+        Expression::ConicGradient { stops } => {
+            let stops = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, ctx);
+                let position = compile_expression(stop, ctx);
+                quote!(slint::re_exports::GradientStop{ color: #color, position: #position as _ })
+            });
+            quote!(slint::Brush::ConicGradient(
+                slint::re_exports::ConicGradientBrush::new([#(#stops),*].iter().cloned())
+            ))
+        }
         Expression::EnumerationValue(value) => {
             let base_ident = ident(&value.enumeration.name);
             let value_ident = ident(&value.to_string());
-            quote!(slint::re_exports::#base_ident::#value_ident)
+            if value.enumeration.node.is_some() {
+                quote!(#base_ident::#value_ident)
+            } else {
+                quote!(slint::re_exports::#base_ident::#value_ident)
+            }
         }
         Expression::ReturnStatement(expr) => {
             let return_expr = expr.as_ref().map(|expr| compile_expression(expr, ctx));
@@ -1743,6 +2121,32 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
+        BuiltinFunction::FlickableScrollTo => {
+            if let [Expression::PropertyReference(pr), x, y, animated] = arguments {
+                let item = access_member(pr, ctx);
+                let x = compile_expression(x, ctx);
+                let y = compile_expression(y, ctx);
+                let animated = compile_expression(animated, ctx);
+                quote!(
+                    #item.scroll_to(Point::new(#x, #y), #animated)
+                )
+            } else {
+                panic!("internal error: invalid args to FlickableScrollTo {:?}", arguments)
+            }
+        }
+        BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let item = access_member(pr, ctx);
+                let window_tokens = access_window_field(ctx);
+                if matches!(function, BuiltinFunction::TextInputUndo) {
+                    quote!(#item.undo(#window_tokens))
+                } else {
+                    quote!(#item.redo(#window_tokens))
+                }
+            } else {
+                panic!("internal error: invalid args to {:?} {:?}", function, arguments)
+            }
+        }
         BuiltinFunction::ImplicitLayoutInfo(orient) => {
             if let [Expression::PropertyReference(pr)] = arguments {
                 let item = access_member(pr, ctx);
@@ -1808,6 +2212,24 @@ fn compile_builtin_function_call(
             quote!(#(#a)*.as_str().parse::<f64>().unwrap_or_default())
         }
         BuiltinFunction::StringIsFloat => quote!(#(#a)*.as_str().parse::<f64>().is_ok()),
+        BuiltinFunction::Translate => {
+            if let [original, context, Expression::Array { values, .. }, n, plural] = arguments {
+                let original = compile_expression(original, ctx);
+                let context = compile_expression(context, ctx);
+                let values = values.iter().map(|v| compile_expression(v, ctx));
+                let n = compile_expression(n, ctx);
+                let plural = compile_expression(plural, ctx);
+                quote!(slint::re_exports::translate(
+                    &#original,
+                    &#context,
+                    &[#(#values),*],
+                    (#n) as i32,
+                    &#plural,
+                ))
+            } else {
+                panic!("internal error: invalid args to Translate {:?}", arguments)
+            }
+        }
         BuiltinFunction::ColorBrighter => {
             let x = a.next().unwrap();
             let factor = a.next().unwrap();
@@ -1850,6 +2272,11 @@ fn struct_name_to_tokens(name: &str) -> TokenStream {
     name.parse().unwrap()
 }
 
+/// The `Point` structs are euclid points, which have a hidden unit field that needs to be defaulted
+fn is_euclid_point(name: &str) -> bool {
+    name == "Point" || name == "slint::private_api::Point"
+}
+
 fn box_layout_function(
     cells_variable: &str,
     repeated_indices: Option<&str>,
@@ -645,6 +645,9 @@ pub struct Enumeration {
     pub name: String,
     pub values: Vec<String>,
     pub default_value: usize, // index in values
+    /// When declared in .slint as `enum Foo := { ... }`, this is the node of the declaration.
+    /// When there is no node, then it is a builtin enum
+    pub node: Option<syntax_nodes::EnumDeclaration>,
 }
 
 impl PartialEq for Enumeration {
@@ -71,6 +71,12 @@ pub enum Expression {
         callback: PropertyReference,
         arguments: Vec<Expression>,
     },
+    /// Call of a function declared with the `function` keyword.
+    /// `function` is a `PropertyReference::Function` or `PropertyReference::GlobalFunction`
+    FunctionCall {
+        function: PropertyReference,
+        arguments: Vec<Expression>,
+    },
 
     /// A BuiltinFunctionCall, but the function is not yet in the `BuiltinFunction` enum
     /// TODO: merge in BuiltinFunctionCall
@@ -140,6 +146,16 @@ pub enum Expression {
         stops: Vec<(Expression, Expression)>,
     },
 
+    RadialGradient {
+        /// First expression in the tuple is a color, second expression is the stop position
+        stops: Vec<(Expression, Expression)>,
+    },
+
+    ConicGradient {
+        /// First expression in the tuple is a color, second expression is the stop position
+        stops: Vec<(Expression, Expression)>,
+    },
+
     EnumerationValue(crate::langtype::EnumerationValue),
 
     ReturnStatement(Option<Box<Expression>>),
@@ -258,6 +274,7 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
                     Type::Invalid
                 }
             }
+            Self::FunctionCall { function, .. } => ctx.property_ty(function).clone(),
             Self::ExtraBuiltinFunctionCall { return_ty, .. } => return_ty.clone(),
             Self::PropertyAssignment { .. } => Type::Void,
             Self::ModelDataAssignment { .. } => Type::Void,
@@ -276,6 +293,8 @@ pub fn ty(&self, ctx: &dyn TypeResolutionContext) -> Type {
             Self::Struct { ty, .. } => ty.clone(),
             Self::EasingCurve(_) => Type::Easing,
             Self::LinearGradient { .. } => Type::Brush,
+            Self::RadialGradient { .. } => Type::Brush,
+            Self::ConicGradient { .. } => Type::Brush,
             Self::EnumerationValue(e) => Type::Enumeration(e.enumeration.clone()),
             Self::ReturnStatement(_) => Type::Invalid,
             Self::LayoutCacheAccess { .. } => Type::Array(Type::Int32.into()),
@@ -305,6 +324,7 @@ fn visit(&self, mut visitor: impl FnMut(&Self)) {
             Expression::CodeBlock(b) => b.iter().for_each(visitor),
             Expression::BuiltinFunctionCall { arguments, .. } => arguments.iter().for_each(visitor),
             Expression::CallBackCall { arguments, .. } => arguments.iter().for_each(visitor),
+            Expression::FunctionCall { arguments, .. } => arguments.iter().for_each(visitor),
             Expression::ExtraBuiltinFunctionCall { arguments, .. } => {
                 arguments.iter().for_each(visitor)
             }
@@ -338,6 +358,12 @@ fn visit(&self, mut visitor: impl FnMut(&Self)) {
                     visitor(b);
                 }
             }
+            Expression::RadialGradient { stops } | Expression::ConicGradient { stops } => {
+                for (a, b) in stops {
+                    visitor(a);
+                    visitor(b);
+                }
+            }
             Expression::EnumerationValue(_) => {}
             Expression::ReturnStatement(_) => {}
             Expression::LayoutCacheAccess { repeater_index, .. } => {
@@ -460,6 +486,21 @@ fn property_ty(&self, prop: &PropertyReference) -> &Type {
             PropertyReference::Global { global_index, property_index } => {
                 &self.public_component.globals[*global_index].properties[*property_index].ty
             }
+            PropertyReference::Function { sub_component_path, function_index } => {
+                if let Some(mut sub_component) = self.current_sub_component {
+                    for i in sub_component_path {
+                        sub_component = &sub_component.sub_components[*i].ty;
+                    }
+                    &sub_component.functions[*function_index].ret_ty
+                } else if let Some(current_global) = self.current_global {
+                    &current_global.functions[*function_index].ret_ty
+                } else {
+                    unreachable!()
+                }
+            }
+            PropertyReference::GlobalFunction { global_index, function_index } => {
+                &self.public_component.globals[*global_index].functions[*function_index].ret_ty
+            }
         }
     }
 
@@ -29,6 +29,7 @@ pub struct BindingExpression {
 pub struct GlobalComponent {
     pub name: String,
     pub properties: Vec<Property>,
+    pub functions: Vec<Function>,
     /// One entry per property
     pub init_values: Vec<Option<BindingExpression>>,
     pub const_properties: Vec<bool>,
@@ -53,6 +54,10 @@ pub enum PropertyReference {
     InParent { level: NonZeroUsize, parent_reference: Box<PropertyReference> },
     /// The property within a GlobalComponent
     Global { global_index: usize, property_index: usize },
+    /// A function relative to this SubComponent
+    Function { sub_component_path: Vec<usize>, function_index: usize },
+    /// A function within a GlobalComponent
+    GlobalFunction { global_index: usize, function_index: usize },
 }
 
 #[derive(Debug)]
@@ -61,6 +66,14 @@ pub struct Property {
     pub ty: Type,
 }
 
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub ret_ty: Type,
+    pub args: Vec<Type>,
+    pub code: Expression,
+}
+
 #[derive(Debug, Clone)]
 /// The property references might be either in the parent context, or in the
 /// repeated's component context
@@ -72,6 +85,8 @@ pub struct ListViewInfo {
     pub listview_height: PropertyReference,
     /// The ListView's inner visible width (not counting eventual scrollbar)
     pub listview_width: PropertyReference,
+    /// The ListView's `ensure-visible-impl` callback, called by its `ensure-visible` function
+    pub ensure_visible: PropertyReference,
 
     // In the repeated component context
     pub prop_y: PropertyReference,
@@ -98,6 +113,10 @@ pub struct RepeatedElement {
 pub struct Item {
     pub ty: Rc<NativeClass>,
     pub name: String,
+    /// The id of the element in the .slint file, or empty
+    pub original_id: String,
+    /// The `accessible-*` properties set on the element, by name
+    pub accessibility_props: BTreeMap<String, PropertyReference>,
     /// Index in the item tree array
     pub index_in_tree: usize,
     /// When this is true, this item does not need to be created because it is
@@ -111,6 +130,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Item")
             .field("ty", &self.ty.class_name)
             .field("name", &self.name)
+            .field("original_id", &self.original_id)
+            .field("accessibility_props", &self.accessibility_props)
             .field("index_in_tree", &self.index_in_tree)
             .field("is_flickable_viewport", &self.is_flickable_viewport)
             .finish()
@@ -127,6 +148,74 @@ pub struct TreeNode {
 }
 
 impl TreeNode {
+    /// Returns the id in the .slint file of the element for this node, or an empty string.
+    /// The root item of a sub-component has the id of the element that instantiates it, if any.
+    pub fn original_id<'a>(&self, root: &'a SubComponent) -> &'a str {
+        if self.repeated {
+            return "";
+        }
+        let mut sub_component = root;
+        let mut instance_id = "";
+        for i in &self.sub_component_path {
+            let instance = &sub_component.sub_components[*i];
+            instance_id = &instance.original_id;
+            sub_component = &instance.ty;
+        }
+        let item = &sub_component.items[self.item_index];
+        if item.index_in_tree == 0 && !instance_id.is_empty() {
+            instance_id
+        } else {
+            &item.original_id
+        }
+    }
+
+    /// Returns the `accessible-*` properties of the element for this node, relative to `root`.
+    /// For the root item of a sub-component, the properties set on the element that instantiates
+    /// it take precedence.
+    pub fn accessibility_props(&self, root: &SubComponent) -> BTreeMap<String, PropertyReference> {
+        if self.repeated {
+            return Default::default();
+        }
+        let prefixed = |prop_ref: &PropertyReference, path: &[usize]| {
+            let mut prop_ref = prop_ref.clone();
+            match &mut prop_ref {
+                PropertyReference::Local { sub_component_path, .. }
+                | PropertyReference::InNativeItem { sub_component_path, .. }
+                | PropertyReference::Function { sub_component_path, .. } => {
+                    sub_component_path.splice(0..0, path.iter().cloned());
+                }
+                PropertyReference::InParent { .. }
+                | PropertyReference::Global { .. }
+                | PropertyReference::GlobalFunction { .. } => (),
+            }
+            prop_ref
+        };
+        let mut sub_components = vec![root];
+        for i in &self.sub_component_path {
+            let parent: &SubComponent = *sub_components.last().unwrap();
+            sub_components.push(&parent.sub_components[*i].ty);
+        }
+        let item = &sub_components.last().unwrap().items[self.item_index];
+        let mut result: BTreeMap<_, _> = item
+            .accessibility_props
+            .iter()
+            .map(|(k, v)| (k.clone(), prefixed(v, &self.sub_component_path)))
+            .collect();
+        if item.index_in_tree == 0 {
+            for depth in (0..self.sub_component_path.len()).rev() {
+                let instance =
+                    &sub_components[depth].sub_components[self.sub_component_path[depth]];
+                for (k, v) in &instance.accessibility_props {
+                    result.insert(k.clone(), prefixed(v, &self.sub_component_path[..depth]));
+                }
+                if instance.index_in_tree != 0 {
+                    break;
+                }
+            }
+        }
+        result
+    }
+
     fn children_count(&self) -> usize {
         let mut count = self.children.len();
         for c in &self.children {
@@ -173,6 +262,7 @@ fn visit_in_array_recursive(
 pub struct SubComponent {
     pub name: String,
     pub properties: Vec<Property>,
+    pub functions: Vec<Function>,
     pub items: Vec<Item>,
     pub repeated: Vec<RepeatedElement>,
     pub popup_windows: Vec<ItemTree>,
@@ -186,6 +276,8 @@ pub struct SubComponent {
     pub const_properties: Vec<PropertyReference>,
     // Code that is run in the sub component constructor, after property initializations
     pub init_code: Vec<Expression>,
+    /// The `changed` handlers: the code to run when the property changes
+    pub change_callbacks: Vec<(PropertyReference, Expression)>,
 
     pub layout_info_h: Expression,
     pub layout_info_v: Expression,
@@ -205,6 +297,10 @@ pub fn repeater_count(&self) -> usize {
 pub struct SubComponentInstance {
     pub ty: Rc<SubComponent>,
     pub name: String,
+    /// The id of the element in the .slint file, or empty
+    pub original_id: String,
+    /// The `accessible-*` properties set on the element, by name
+    pub accessibility_props: BTreeMap<String, PropertyReference>,
     pub index_in_tree: usize,
     pub index_of_first_child_in_tree: usize,
     pub repeater_offset: usize,
@@ -216,6 +312,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             // only dump ty.name, not the whole structure
             .field("ty", &self.ty.name)
             .field("name", &self.name)
+            .field("original_id", &self.original_id)
+            .field("accessibility_props", &self.accessibility_props)
             .field("index_in_tree", &self.index_in_tree)
             .field("index_of_first_child_in_tree", &self.index_of_first_child_in_tree)
             .field("repeater_offset", &self.repeater_offset)
@@ -242,3 +340,13 @@ pub struct PublicComponent {
 }
 
 pub type PublicProperties = BTreeMap<String, (Type, PropertyReference)>;
+
+/// Returns true if the `on_<property>_changed` function should be generated for the property.
+/// It is not generated when a `<property>-changed` callback exists, since the `on_<callback>`
+/// function of that callback has the same name.
+pub fn has_on_changed_function(public_properties: &PublicProperties, property: &str) -> bool {
+    !matches!(
+        public_properties.get(&format!("{}-changed", property)),
+        Some((Type::Callback { .. }, _))
+    )
+}
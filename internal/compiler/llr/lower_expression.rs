@@ -74,6 +74,7 @@ pub fn lower_expression(
         tree_Expression::PropertyReference(nr) => {
             llr_Expression::PropertyReference(ctx.map_property_reference(nr))
         }
+        tree_Expression::FunctionReference(_) => panic!(),
         tree_Expression::BuiltinFunctionReference(_, _) => panic!(),
         tree_Expression::MemberFunction { .. } => panic!(),
         tree_Expression::BuiltinMacroReference(_, _) => panic!(),
@@ -125,6 +126,10 @@ pub fn lower_expression(
                 let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
                 llr_Expression::CallBackCall { callback: ctx.map_property_reference(nr), arguments }
             }
+            tree_Expression::FunctionReference(nr) => {
+                let arguments = arguments.iter().map(|e| lower_expression(e, ctx)).collect::<_>();
+                llr_Expression::FunctionCall { function: ctx.map_property_reference(nr), arguments }
+            }
             _ => panic!("not calling a function"),
         },
         tree_Expression::SelfAssignment { lhs, rhs, op } => lower_assignment(lhs, rhs, *op, ctx),
@@ -167,6 +172,18 @@ pub fn lower_expression(
                 .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
                 .collect::<_>(),
         },
+        tree_Expression::RadialGradient { stops } => llr_Expression::RadialGradient {
+            stops: stops
+                .iter()
+                .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
+                .collect::<_>(),
+        },
+        tree_Expression::ConicGradient { stops } => llr_Expression::ConicGradient {
+            stops: stops
+                .iter()
+                .map(|(a, b)| (lower_expression(a, ctx), lower_expression(b, ctx)))
+                .collect::<_>(),
+        },
         tree_Expression::EnumerationValue(e) => llr_Expression::EnumerationValue(e.clone()),
         tree_Expression::ReturnStatement(x) => {
             llr_Expression::ReturnStatement(x.as_ref().map(|e| lower_expression(e, ctx).into()))
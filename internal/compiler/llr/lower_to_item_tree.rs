@@ -142,11 +142,12 @@ fn property_reference_within_sub_component(
 ) -> PropertyReference {
     match &mut prop_ref {
         PropertyReference::Local { sub_component_path, .. }
-        | PropertyReference::InNativeItem { sub_component_path, .. } => {
+        | PropertyReference::InNativeItem { sub_component_path, .. }
+        | PropertyReference::Function { sub_component_path, .. } => {
             sub_component_path.insert(0, sub_component);
         }
         PropertyReference::InParent { .. } => panic!("the sub-component had no parents"),
-        PropertyReference::Global { .. } => (),
+        PropertyReference::Global { .. } | PropertyReference::GlobalFunction { .. } => (),
     }
     prop_ref
 }
@@ -177,6 +178,7 @@ fn lower_sub_component(
     let mut sub_component = SubComponent {
         name: component_id(component),
         properties: Default::default(),
+        functions: Default::default(),
         items: Default::default(),
         repeated: Default::default(),
         popup_windows: Default::default(),
@@ -186,12 +188,14 @@ fn lower_sub_component(
         two_way_bindings: Default::default(),
         const_properties: Default::default(),
         init_code: Default::default(),
+        change_callbacks: Default::default(),
         // just initialize to dummy expression right now and it will be set later
         layout_info_h: super::Expression::BoolLiteral(false),
         layout_info_v: super::Expression::BoolLiteral(false),
     };
     let mut mapping = LoweredSubComponentMapping::default();
     let mut repeated = vec![];
+    let mut functions = vec![];
 
     if let Some(parent) = component.parent_element.upgrade() {
         // Add properties for the model data and index
@@ -215,6 +219,22 @@ fn lower_sub_component(
             if x.is_alias.is_some() {
                 continue;
             }
+            if let Type::Function { return_type, args } = &x.property_type {
+                let function_index = sub_component.functions.len();
+                mapping.property_mapping.insert(
+                    NamedReference::new(element, p),
+                    PropertyReference::Function { sub_component_path: vec![], function_index },
+                );
+                sub_component.functions.push(Function {
+                    name: format!("{}_{}", elem.id, p),
+                    ret_ty: (**return_type).clone(),
+                    args: args.clone(),
+                    // will be set later
+                    code: super::Expression::CodeBlock(vec![]),
+                });
+                functions.push((element.clone(), p.clone()));
+                continue;
+            }
             let property_index = sub_component.properties.len();
             mapping.property_mapping.insert(
                 NamedReference::new(element, p),
@@ -244,6 +264,8 @@ fn lower_sub_component(
                 sub_component.sub_components.push(SubComponentInstance {
                     ty: ty.clone(),
                     name: elem.id.clone(),
+                    original_id: elem.original_id.clone(),
+                    accessibility_props: Default::default(),
                     index_in_tree: *elem.item_index.get().unwrap(),
                     index_of_first_child_in_tree: *elem.item_index_of_first_children.get().unwrap(),
                     repeater_offset,
@@ -264,6 +286,12 @@ fn lower_sub_component(
                     } else {
                         elem.id.clone()
                     },
+                    original_id: if is_flickable_viewport {
+                        String::new()
+                    } else {
+                        elem.original_id.clone()
+                    },
+                    accessibility_props: Default::default(),
                     index_in_tree: *elem.item_index.get().unwrap(),
                     is_flickable_viewport,
                 })
@@ -273,6 +301,33 @@ fn lower_sub_component(
         Some(element.clone())
     });
     let ctx = ExpressionContext { mapping: &mapping, state, parent: parent_context, component };
+    crate::object_tree::recurse_elem(&component.root_element, &(), &mut |element, _| {
+        let elem = element.borrow();
+        if elem.accessibility_props.is_empty() {
+            return;
+        }
+        let accessibility_props = elem
+            .accessibility_props
+            .iter()
+            .map(|(name, nr)| (name.clone(), ctx.map_property_reference(nr)))
+            .collect();
+        match mapping.element_mapping.get(&element.clone().into()) {
+            Some(LoweredElement::NativeItem { item_index }) => {
+                sub_component.items[*item_index].accessibility_props = accessibility_props
+            }
+            Some(LoweredElement::SubComponent { sub_component_index }) => {
+                sub_component.sub_components[*sub_component_index].accessibility_props =
+                    accessibility_props
+            }
+            _ => (),
+        }
+    });
+    for (function, (element, name)) in sub_component.functions.iter_mut().zip(functions) {
+        if let Some(binding) = element.borrow().bindings.get(&name) {
+            function.code =
+                super::lower_expression::lower_expression(&binding.borrow().expression, &ctx);
+        }
+    }
     crate::generator::handle_property_bindings_init(component, |e, p, binding| {
         let prop = ctx.map_property_reference(&NamedReference::new(e, p));
         for tw in &binding.two_way_bindings {
@@ -334,6 +389,17 @@ fn lower_sub_component(
         .map(|e| super::lower_expression::lower_expression(e, &ctx))
         .collect();
 
+    crate::object_tree::recurse_elem(&component.root_element, &(), &mut |element, _| {
+        for (name, handlers) in &element.borrow().change_callbacks {
+            let prop = ctx.map_property_reference(&NamedReference::new(element, name));
+            for handler in handlers.borrow().iter() {
+                sub_component
+                    .change_callbacks
+                    .push((prop.clone(), super::lower_expression::lower_expression(handler, &ctx)));
+            }
+        }
+    });
+
     sub_component.layout_info_h = super::lower_expression::get_layout_info(
         &component.root_element,
         &ctx,
@@ -368,6 +434,7 @@ fn lower_repeated_component(elem: &ElementRc, ctx: &ExpressionContext) -> Repeat
         viewport_width: ctx.map_property_reference(&lv.viewport_width),
         listview_height: ctx.map_property_reference(&lv.listview_height),
         listview_width: ctx.map_property_reference(&lv.listview_width),
+        ensure_visible: ctx.map_property_reference(&lv.ensure_visible),
 
         prop_y: map_inner_prop("y"),
         prop_width: map_inner_prop("width"),
@@ -415,11 +482,30 @@ fn lower_global(
 ) -> GlobalComponent {
     let mut mapping = LoweredSubComponentMapping::default();
     let mut properties = vec![];
+    let mut functions = vec![];
     let mut const_properties = vec![];
 
     for (p, x) in &global.root_element.borrow().property_declarations {
-        let property_index = properties.len();
         let nr = NamedReference::new(&global.root_element, p);
+        if let Type::Function { return_type, args } = &x.property_type {
+            let function_index = functions.len();
+            mapping.property_mapping.insert(
+                nr.clone(),
+                PropertyReference::Function { sub_component_path: vec![], function_index },
+            );
+            functions.push(Function {
+                name: p.clone(),
+                ret_ty: (**return_type).clone(),
+                args: args.clone(),
+                // will be set later
+                code: super::Expression::CodeBlock(vec![]),
+            });
+            state
+                .global_properties
+                .insert(nr, PropertyReference::GlobalFunction { global_index, function_index });
+            continue;
+        }
+        let property_index = properties.len();
         mapping.property_mapping.insert(
             nr.clone(),
             PropertyReference::Local { sub_component_path: vec![], property_index },
@@ -446,6 +532,10 @@ fn lower_global(
         let nr = NamedReference::new(&global.root_element, prop);
         let property_index = match mapping.property_mapping[&nr] {
             PropertyReference::Local { property_index, .. } => property_index,
+            PropertyReference::Function { function_index, .. } => {
+                functions[function_index].code = expression;
+                continue;
+            }
             _ => unreachable!(),
         };
         let is_constant = binding.borrow().analysis.as_ref().map_or(false, |a| a.is_const);
@@ -472,6 +562,7 @@ fn lower_global(
     GlobalComponent {
         name: global.root_element.borrow().id.clone(),
         properties,
+        functions,
         init_values,
         const_properties,
         public_properties,
@@ -62,6 +62,8 @@ pub fn empty_context(type_register: &'a TypeRegister, diag: &'a mut BuildDiagnos
     pub fn return_type(&self) -> &Type {
         if let Type::Callback { return_type, .. } = &self.property_type {
             return_type.as_ref().map_or(&Type::Void, |b| &(**b))
+        } else if let Type::Function { return_type, .. } = &self.property_type {
+            return_type
         } else {
             &self.property_type
         }
@@ -343,6 +345,8 @@ fn lookup(&self, _ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
 fn expression_from_reference(n: NamedReference, ty: &Type) -> Expression {
     if matches!(ty, Type::Callback { .. }) {
         Expression::CallbackReference(n)
+    } else if matches!(ty, Type::Function { .. }) && n.element().borrow().is_function(n.name()) {
+        Expression::FunctionReference(n)
     } else {
         Expression::PropertyReference(n)
     }
@@ -5,12 +5,10 @@
  This module contains the intermediate representation of the code in the form of an object tree
 */
 
-use itertools::Either;
-
 use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
 use crate::langtype::PropertyLookupResult;
-use crate::langtype::{BuiltinElement, NativeClass, Type};
+use crate::langtype::{BuiltinElement, Enumeration, NativeClass, Type};
 use crate::layout::{LayoutConstraints, Orientation};
 use crate::namedreference::NamedReference;
 use crate::parser;
@@ -39,7 +37,8 @@ macro_rules! unwrap_or_continue {
 pub struct Document {
     pub node: Option<syntax_nodes::Document>,
     pub inner_components: Vec<Rc<Component>>,
-    pub inner_structs: Vec<Type>,
+    /// The structs and enums declared in this document
+    pub inner_types: Vec<Type>,
     pub root_component: Rc<Component>,
     pub local_registry: TypeRegister,
     /// A list of paths to .ttf/.ttc files that are supposed to be registered on
@@ -59,7 +58,7 @@ pub fn from_node(
 
         let mut local_registry = TypeRegister::new(parent_registry);
         let mut inner_components = vec![];
-        let mut inner_structs = vec![];
+        let mut inner_types = vec![];
 
         let mut process_component =
             |n: syntax_nodes::Component,
@@ -69,26 +68,63 @@ pub fn from_node(
                 local_registry.add(compo.clone());
                 inner_components.push(compo);
             };
-        let mut process_struct =
-            |n: syntax_nodes::StructDeclaration,
-             diag: &mut BuildDiagnostics,
-             local_registry: &mut TypeRegister| {
-                let mut ty = type_struct_from_node(n.ObjectType(), diag, local_registry);
-                if let Type::Struct { name, .. } = &mut ty {
-                    *name = parser::identifier_text(&n.DeclaredIdentifier());
-                } else {
+        let process_struct = |n: syntax_nodes::StructDeclaration,
+                              diag: &mut BuildDiagnostics,
+                              local_registry: &mut TypeRegister| {
+            let mut ty = type_struct_from_node(n.ObjectType(), diag, local_registry);
+            if let Type::Struct { name, .. } = &mut ty {
+                *name = parser::identifier_text(&n.DeclaredIdentifier());
+            } else {
+                assert!(diag.has_error());
+                return None;
+            }
+            local_registry.insert_type(ty.clone());
+            Some(ty)
+        };
+        let process_enum = |n: syntax_nodes::EnumDeclaration,
+                            diag: &mut BuildDiagnostics,
+                            local_registry: &mut TypeRegister| {
+            let name = match parser::identifier_text(&n.DeclaredIdentifier()) {
+                Some(name) => name,
+                None => {
                     assert!(diag.has_error());
-                    return;
+                    return None;
                 }
-                local_registry.insert_type(ty.clone());
-                inner_structs.push(ty);
             };
+            let mut values: Vec<String> = vec![];
+            for v in n.EnumValue() {
+                let value = match parser::identifier_text(&v) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                if values.contains(&value) {
+                    diag.push_error(format!("Duplicated enum value '{}'", value), &v);
+                } else {
+                    values.push(value);
+                }
+            }
+            if values.is_empty() {
+                diag.push_error(format!("Enum '{}' must have at least one value", name), &n);
+                return None;
+            }
+            let ty = Type::Enumeration(Rc::new(Enumeration {
+                name: name.clone(),
+                values,
+                default_value: 0,
+                node: Some(n),
+            }));
+            local_registry.insert_type_with_name(ty.clone(), name);
+            Some(ty)
+        };
 
         for n in node.children() {
             match n.kind() {
                 SyntaxKind::Component => process_component(n.into(), diag, &mut local_registry),
                 SyntaxKind::StructDeclaration => {
-                    process_struct(n.into(), diag, &mut local_registry)
+                    inner_types.extend(process_struct(n.into(), diag, &mut local_registry))
+                }
+                SyntaxKind::EnumDeclaration => {
+                    inner_types.extend(process_enum(n.into(), diag, &mut local_registry))
                 }
                 SyntaxKind::ExportsList => {
                     for n in n.children() {
@@ -96,9 +132,16 @@ pub fn from_node(
                             SyntaxKind::Component => {
                                 process_component(n.into(), diag, &mut local_registry)
                             }
-                            SyntaxKind::StructDeclaration => {
-                                process_struct(n.into(), diag, &mut local_registry)
-                            }
+                            SyntaxKind::StructDeclaration => inner_types.extend(process_struct(
+                                n.into(),
+                                diag,
+                                &mut local_registry,
+                            )),
+                            SyntaxKind::EnumDeclaration => inner_types.extend(process_enum(
+                                n.into(),
+                                diag,
+                                &mut local_registry,
+                            )),
                             _ => {}
                         }
                     }
@@ -161,7 +204,7 @@ pub fn from_node(
             node: Some(node),
             root_component,
             inner_components,
-            inner_structs,
+            inner_types,
             local_registry,
             custom_fonts,
             exports,
@@ -188,8 +231,8 @@ pub struct PopupWindow {
 pub struct UsedSubTypes {
     /// All the globals used by the component and its children.
     pub globals: Vec<Rc<Component>>,
-    /// All the structs used by the component and its children.
-    pub structs: Vec<Type>,
+    /// All the structs and enums used by the component and its children.
+    pub structs_and_enums: Vec<Type>,
     /// All the sub components use by this components and its children,
     /// and the amount of time it is used
     pub sub_components: Vec<Rc<Component>>,
@@ -325,21 +368,24 @@ pub fn repeater_count(&self) -> u32 {
 #[derive(Clone, Debug, Default)]
 pub struct PropertyDeclaration {
     pub property_type: Type,
-    pub node: Option<Either<syntax_nodes::PropertyDeclaration, syntax_nodes::CallbackDeclaration>>,
+    /// The PropertyDeclaration, CallbackDeclaration or Function node
+    pub node: Option<SyntaxNode>,
     /// Tells if getter and setter will be added to expose in the native language API
     pub expose_in_public_api: bool,
     /// Public API property exposed as an alias: it shouldn't be generated but instead forward to the alias.
     pub is_alias: Option<NamedReference>,
+    /// For functions: true if the function was not declared `public` and can only be called
+    /// from the component that declares it
+    pub is_private: bool,
 }
 
 impl PropertyDeclaration {
     // For diagnostics: return a node pointing to the type
     pub fn type_node(&self) -> Option<SyntaxNode> {
-        self.node.as_ref().map(|x| -> crate::parser::SyntaxNode {
-            x.as_ref().either(
-                |x| x.Type().map_or_else(|| x.clone().into(), |x| x.into()),
-                |x| x.clone().into(),
-            )
+        self.node.as_ref().map(|x| {
+            syntax_nodes::PropertyDeclaration::new(x.clone())
+                .and_then(|x| x.Type())
+                .map_or_else(|| x.clone(), |x| x.into())
         })
     }
 }
@@ -429,6 +475,9 @@ pub struct Element {
     /// After inlining there can be duplicated id in the component.
     /// The id are then re-assigned unique id in the assign_id pass
     pub id: String,
+    /// The id as written in the .slint file. It is not changed by the passes, so it is still
+    /// available to the generators. Empty for elements without id, or created by the compiler.
+    pub original_id: String,
     //pub base: QualifiedTypeName,
     pub base_type: crate::langtype::Type,
     /// Currently contains also the callbacks. FIXME: should that be changed?
@@ -450,11 +499,19 @@ pub struct Element {
     pub states: Vec<State>,
     pub transitions: Vec<Transition>,
 
+    /// The `changed prop => { ... }` handlers, by property name.
+    /// There can be several handlers for the same property after inlining.
+    pub change_callbacks: BTreeMap<String, RefCell<Vec<Expression>>>,
+
     /// true when this item's geometry is handled by a layout
     pub child_of_layout: bool,
     /// The property pointing to the layout info. `(horizontal, vertical)`
     pub layout_info_prop: Option<(NamedReference, NamedReference)>,
 
+    /// The `accessible-*` properties that are set on this element, by name.
+    /// Filled by the lower_accessibility pass
+    pub accessibility_props: BTreeMap<String, NamedReference>,
+
     /// true if this Element is the fake Flickable viewport
     pub is_flickable_viewport: bool,
 
@@ -530,6 +587,14 @@ macro_rules! indent {
             writeln!(f, "{} <=> {:?};", name, nr)?;
         }
     }
+    for (name, ch) in &e.change_callbacks {
+        for ex in &*ch.borrow() {
+            indent!();
+            write!(f, "changed {} => ", name)?;
+            expression_tree::pretty_print(f, ex)?;
+            writeln!(f)?;
+        }
+    }
     if !e.states.is_empty() {
         indent!();
         writeln!(f, "states {:?}", e.states)?;
@@ -600,6 +665,8 @@ pub struct ListViewInfo {
     pub listview_height: NamedReference,
     /// The ListView's inner visible width (not counting eventual scrollbar)
     pub listview_width: NamedReference,
+    /// The ListView's `ensure-visible-impl` callback, called by its `ensure-visible` function
+    pub ensure_visible: NamedReference,
 }
 
 #[derive(Debug, Clone)]
@@ -667,9 +734,16 @@ pub fn from_node(
             node.PropertyAnimation().for_each(|n| error_on(&n, "animations"));
             node.States().for_each(|n| error_on(&n, "states"));
             node.Transitions().for_each(|n| error_on(&n, "transitions"));
+            node.PropertyChangedCallback().for_each(|n| error_on(&n, "change callbacks"));
             Type::Void
         };
-        let mut r = Element { id, base_type, node: Some(node.clone()), ..Default::default() };
+        let mut r = Element {
+            original_id: id.clone(),
+            id,
+            base_type,
+            node: Some(node.clone()),
+            ..Default::default()
+        };
 
         for prop_decl in node.PropertyDeclaration() {
             let prop_type = prop_decl
@@ -705,7 +779,7 @@ pub fn from_node(
                 prop_name.to_string(),
                 PropertyDeclaration {
                     property_type: prop_type,
-                    node: Some(Either::Left(prop_decl.clone())),
+                    node: Some(prop_decl.clone().into()),
                     ..Default::default()
                 },
             );
@@ -762,7 +836,7 @@ pub fn from_node(
                     name,
                     PropertyDeclaration {
                         property_type: Type::InferredCallback,
-                        node: Some(Either::Right(sig_decl)),
+                        node: Some(sig_decl.into()),
                         ..Default::default()
                     },
                 );
@@ -777,10 +851,52 @@ pub fn from_node(
                 name,
                 PropertyDeclaration {
                     property_type: Type::Callback { return_type, args },
-                    node: Some(Either::Right(sig_decl)),
+                    node: Some(sig_decl.into()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        for func in node.Function() {
+            let name =
+                unwrap_or_continue!(parser::identifier_text(&func.DeclaredIdentifier()); diag);
+            if r.property_declarations
+                .get(&name)
+                .map_or(false, |d| matches!(d.property_type, Type::Function { .. }))
+            {
+                diag.push_error("Duplicated function".into(), &func.DeclaredIdentifier());
+                continue;
+            }
+            let PropertyLookupResult { resolved_name, property_type: existing_type } =
+                r.lookup_property(&name);
+            if !matches!(existing_type, Type::Invalid) {
+                diag.push_error(
+                    format!("Cannot override '{}'", resolved_name),
+                    &func.DeclaredIdentifier(),
+                );
+                continue;
+            }
+
+            let args = func
+                .ArgumentDeclaration()
+                .map(|a| type_from_node(a.Type(), diag, tr))
+                .collect::<Vec<_>>();
+            let return_type = Box::new(
+                func.ReturnType()
+                    .map_or(Type::Void, |ret_ty| type_from_node(ret_ty.Type(), diag, tr)),
+            );
+            let is_private =
+                func.child_token(SyntaxKind::Identifier).map_or(true, |t| t.text() != "public");
+            r.property_declarations.insert(
+                name.clone(),
+                PropertyDeclaration {
+                    property_type: Type::Function { return_type, args },
+                    node: Some(func.clone().into()),
+                    is_private,
                     ..Default::default()
                 },
             );
+            r.bindings.insert(name, BindingExpression::new_uncompiled(func.clone().into()).into());
         }
 
         for con_node in node.CallbackConnection() {
@@ -823,6 +939,41 @@ pub fn from_node(
             }
         }
 
+        for changed in node.PropertyChangedCallback() {
+            if r.base_type == Type::Void {
+                // Already reported as an error for globals
+                break;
+            }
+            let unresolved_name =
+                unwrap_or_continue!(parser::identifier_text(&changed.DeclaredIdentifier()); diag);
+            let PropertyLookupResult { resolved_name, property_type } =
+                r.lookup_property(&unresolved_name);
+            if !property_type.is_property_type() {
+                if property_type != Type::Invalid || r.base_type != Type::Invalid {
+                    diag.push_error(
+                        format!(
+                            "Change callback can only be set on properties, and '{}' is not a property in {}",
+                            unresolved_name, r.base_type
+                        ),
+                        &changed.DeclaredIdentifier(),
+                    );
+                }
+                continue;
+            }
+            if resolved_name != unresolved_name {
+                diag.push_property_deprecation_warning(
+                    &unresolved_name,
+                    &resolved_name,
+                    &changed.DeclaredIdentifier(),
+                );
+            }
+            r.change_callbacks
+                .entry(resolved_name.into_owned())
+                .or_default()
+                .get_mut()
+                .push(Expression::Uncompiled(changed.clone().into()));
+        }
+
         for anim in node.PropertyAnimation() {
             if let Some(star) = anim.child_token(SyntaxKind::Star) {
                 diag.push_error(
@@ -1010,6 +1161,7 @@ fn from_repeated_node(
                 viewport_width: NamedReference::new(parent, "viewport-width"),
                 listview_height: NamedReference::new(parent, "visible-height"),
                 listview_width: NamedReference::new(parent, "visible-width"),
+                ensure_visible: NamedReference::new(parent, "ensure-visible-impl"),
             })
         } else {
             None
@@ -1073,6 +1225,18 @@ pub fn lookup_property<'a>(&self, name: &'a str) -> PropertyLookupResult<'a> {
         )
     }
 
+    /// Return true if `name` is a function declared with the `function` keyword in this element
+    /// or in the component it derives from. (As opposed to the builtin member functions)
+    pub fn is_function(&self, name: &str) -> bool {
+        match self.property_declarations.get(name) {
+            Some(decl) => matches!(decl.property_type, Type::Function { .. }),
+            None => match &self.base_type {
+                Type::Component(c) => c.root_element.borrow().is_function(name),
+                _ => false,
+            },
+        }
+    }
+
     /// Return the Span of this element in the AST for error reporting
     pub fn span(&self) -> crate::diagnostics::Span {
         self.node.as_ref().map(|n| n.span()).unwrap_or_default()
@@ -1543,6 +1707,11 @@ fn visit_element_expressions_simple(
         elem.borrow_mut().repeated = Some(r)
     }
     visit_element_expressions_simple(elem, &mut vis);
+    for expressions in elem.borrow().change_callbacks.values() {
+        for expr in expressions.borrow_mut().iter_mut() {
+            vis(expr, None, &|| Type::Void);
+        }
+    }
     let mut states = std::mem::take(&mut elem.borrow_mut().states);
     for s in &mut states {
         if let Some(cond) = s.condition.as_mut() {
@@ -1574,7 +1743,9 @@ pub fn visit_all_named_references_in_element(
     fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedReference)) {
         expr.visit_mut(|sub| recurse_expression(sub, vis));
         match expr {
-            Expression::PropertyReference(r) | Expression::CallbackReference(r) => vis(r),
+            Expression::PropertyReference(r)
+            | Expression::CallbackReference(r)
+            | Expression::FunctionReference(r) => vis(r),
             Expression::LayoutCacheAccess { layout_cache_prop, .. } => vis(layout_cache_prop),
             Expression::SolveLayout(l, _) => l.visit_named_references(vis),
             Expression::ComputeLayoutInfo(l, _) => l.visit_named_references(vis),
@@ -1614,6 +1785,7 @@ fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedRefe
             vis(&mut lv.viewport_width);
             vis(&mut lv.listview_height);
             vis(&mut lv.listview_width);
+            vis(&mut lv.ensure_visible);
         }
     }
     elem.borrow_mut().repeated = repeated;
@@ -1621,6 +1793,12 @@ fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedRefe
     layout_info_prop.as_mut().map(|(h, b)| (vis(h), vis(b)));
     elem.borrow_mut().layout_info_prop = layout_info_prop;
 
+    let mut accessibility_props = std::mem::take(&mut elem.borrow_mut().accessibility_props);
+    for nr in accessibility_props.values_mut() {
+        vis(nr);
+    }
+    elem.borrow_mut().accessibility_props = accessibility_props;
+
     // visit two way bindings
     for expr in elem.borrow().bindings.values() {
         for nr in &mut expr.borrow_mut().two_way_bindings {
@@ -1779,6 +1957,21 @@ struct NamedExport {
                 }
             }),
         );
+        let exports_it = exports_it.chain(
+            doc.ExportsList().flat_map(|exports| exports.EnumDeclaration()).map(|en| {
+                let name_location: SyntaxNode = en.DeclaredIdentifier().into();
+                let name = parser::identifier_text(&en.DeclaredIdentifier()).unwrap_or_else(|| {
+                    debug_assert!(diag.has_error());
+                    String::new()
+                });
+                NamedExport {
+                    internal_name_ident: name_location.clone(),
+                    internal_name: name.clone(),
+                    external_name_ident: name_location,
+                    exported_name: name,
+                }
+            }),
+        );
 
         struct SeenExport {
             name_location: SyntaxNode,
@@ -1828,7 +2021,9 @@ struct SeenExport {
 
         let mut resolve_export_to_inner_component_or_import =
             |export: &NamedExport| match type_registry.lookup(export.internal_name.as_str()) {
-                ty @ Type::Component(_) | ty @ Type::Struct { .. } => Some(ty),
+                ty @ Type::Component(_) | ty @ Type::Struct { .. } | ty @ Type::Enumeration(_) => {
+                    Some(ty)
+                }
                 Type::Invalid => {
                     diag.push_error(
                         format!("'{}' not found", export.internal_name),
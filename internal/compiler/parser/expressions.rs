@@ -211,6 +211,9 @@ fn parse_expression_helper(p: &mut impl Parser, precedence: OperatorPrecedence)
 /// ```test
 /// @image-url("/foo/bar.png")
 /// @linear-gradient(0deg, blue, red)
+/// @radial-gradient(circle, blue, red)
+/// @conic-gradient(blue, red)
+/// @tr("Hello {}", name)
 /// ```
 fn parse_at_keyword(p: &mut impl Parser) {
     debug_assert_eq!(p.peek().kind(), SyntaxKind::At);
@@ -226,9 +229,20 @@ fn parse_at_keyword(p: &mut impl Parser) {
         "linear-gradient" | "linear_gradient" => {
             parse_at_linear_gradient(p);
         }
+        "radial-gradient" | "radial_gradient" => {
+            parse_at_radial_gradient(p);
+        }
+        "conic-gradient" | "conic_gradient" => {
+            parse_at_conic_gradient(p);
+        }
+        "tr" => {
+            parse_at_tr(p);
+        }
         _ => {
             p.consume();
-            p.error("Expected 'image-url' or 'linear-gradient' after '@'");
+            p.error(
+                "Expected 'image-url', 'linear-gradient', 'radial-gradient', 'conic-gradient' or 'tr' after '@'",
+            );
         }
     }
 }
@@ -331,10 +345,94 @@ fn parse_template_string(p: &mut impl Parser) {
 /// @linear_gradient(217deg, rgba(255,0,0,0.8), rgba(255,0,0,0) 70.71%)
 /// ```
 fn parse_at_linear_gradient(p: &mut impl Parser) {
-    let mut p = p.start_node(SyntaxKind::AtLinearGradient);
+    parse_at_gradient(p, SyntaxKind::AtLinearGradient)
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,AtRadialGradient
+/// @radial-gradient(circle, #e66465, #9198e5)
+/// @radial-gradient(circle, #333, #333 50%, #eee 75%, #333 75%)
+/// @radial_gradient(circle, rgba(255,0,0,0.8), rgba(255,0,0,0) 70.71%)
+/// ```
+fn parse_at_radial_gradient(p: &mut impl Parser) {
+    parse_at_gradient(p, SyntaxKind::AtRadialGradient)
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,AtConicGradient
+/// @conic-gradient(#e66465, #9198e5)
+/// @conic-gradient(red 0deg, yellow 0.25turn, red 360deg)
+/// @conic_gradient(rgba(255,0,0,0.8), rgba(255,0,0,0) 270deg)
+/// ```
+fn parse_at_conic_gradient(p: &mut impl Parser) {
+    parse_at_gradient(p, SyntaxKind::AtConicGradient)
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,AtTr
+/// @tr("foo")
+/// @tr("foo{0}", bar(1))
+/// @tr("context" => "ccc{}", 0)
+/// @tr("xxx" => "ccc{n}" | "ddd{}" % 42, 45)
+/// @tr("{n} apple" | "{n} apples" % apples.count + 1)
+/// ```
+fn parse_at_tr(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::AtTr);
+    p.expect(SyntaxKind::At);
+    debug_assert_eq!(p.peek().as_str(), "tr");
+    p.consume(); // "tr"
+    p.expect(SyntaxKind::LParent);
+
+    /// Only plain string literals can be translated, not string templates
+    fn expect_plain_string(p: &mut impl Parser) -> bool {
+        let peek = p.peek();
+        if peek.kind() != SyntaxKind::StringLiteral
+            || !peek.as_str().starts_with('"')
+            || !peek.as_str().ends_with('"')
+        {
+            p.error("Expected a plain string literal");
+            return false;
+        }
+        p.consume();
+        true
+    }
+
+    let checkpoint = p.checkpoint();
+    if !expect_plain_string(&mut *p) {
+        return;
+    }
+    if p.peek().kind() == SyntaxKind::FatArrow {
+        {
+            let mut p = p.start_node_at(checkpoint, SyntaxKind::TrContext);
+            p.consume(); // "=>"
+        }
+        if !expect_plain_string(&mut *p) {
+            return;
+        }
+    }
+    if p.peek().kind() == SyntaxKind::Pipe {
+        let mut p = p.start_node(SyntaxKind::TrPlural);
+        p.consume(); // "|"
+        if !expect_plain_string(&mut *p) {
+            return;
+        }
+        p.expect(SyntaxKind::Percent);
+        parse_expression(&mut *p);
+    }
+    while p.test(SyntaxKind::Comma) {
+        if !parse_expression(&mut *p) {
+            return;
+        }
+    }
+    p.expect(SyntaxKind::RParent);
+}
+
+/// Parse `@xxx-gradient(...)`, the kind is one of AtLinearGradient, AtRadialGradient or AtConicGradient
+fn parse_at_gradient(p: &mut impl Parser, kind: SyntaxKind) {
+    let mut p = p.start_node(kind);
     p.expect(SyntaxKind::At);
-    debug_assert!(p.peek().as_str() == "linear-gradient" || p.peek().as_str() == "linear_gradient");
-    p.consume(); //"linear-gradient"
+    debug_assert!(p.peek().as_str().ends_with("gradient"));
+    p.consume(); //"xxx-gradient"
 
     p.expect(SyntaxKind::LParent);
 
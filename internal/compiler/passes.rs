@@ -8,7 +8,7 @@
 mod clip;
 mod collect_custom_fonts;
 mod collect_globals;
-mod collect_structs;
+mod collect_structs_and_enums;
 mod collect_subcomponents;
 mod compile_paths;
 mod const_propagation;
@@ -22,6 +22,8 @@
 mod generate_item_indices;
 mod infer_aliases_types;
 mod inlining;
+mod lower_accessibility;
+mod lower_drag_payload;
 mod lower_layout;
 mod lower_popups;
 mod lower_shadows;
@@ -77,6 +79,7 @@ pub async fn run_passes(
     check_public_api::check_public_api(doc, diag);
 
     collect_subcomponents::collect_subcomponents(root_component);
+    lower_drag_payload::lower_drag_payload(doc);
     for component in (root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(root_component))
     {
@@ -124,6 +127,7 @@ pub async fn run_passes(
         lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
         clip::handle_clip(component, &global_type_registry.borrow(), diag);
         visible::handle_visible(component, &global_type_registry.borrow());
+        lower_accessibility::lower_accessibility_properties(component);
         materialize_fake_properties::materialize_fake_properties(component);
     }
     collect_globals::collect_globals(doc, diag);
@@ -156,7 +160,7 @@ pub async fn run_passes(
         remove_unused_properties::remove_unused_properties(component);
     }
 
-    collect_structs::collect_structs(doc);
+    collect_structs_and_enums::collect_structs_and_enums(doc);
 
     for component in (root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(root_component))
@@ -10,6 +10,7 @@
 use by_address::ByAddress;
 
 use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::SourceLocation;
 use crate::diagnostics::Spanned;
 use crate::expression_tree::BindingExpression;
 use crate::expression_tree::BuiltinFunction;
@@ -146,6 +147,113 @@ fn analyze_element(
             diag,
         );
     }
+    for (name, handlers) in &elem.borrow().change_callbacks {
+        let watched = NamedReference::new(elem, name);
+        process_property(&PropertyPath::from(watched.clone()), context, reverse_aliases, diag);
+        for expr in handlers.borrow().iter() {
+            recurse_expression(expr, &mut |nr| {
+                process_property(&PropertyPath::from(nr.clone()), context, reverse_aliases, diag);
+            });
+            check_change_callback_loop(&watched, expr, diag);
+        }
+    }
+}
+
+/// Report an error if the `changed` handler of `watched` sets a property that `watched`
+/// depends on, as the handler would then be called again and again.
+fn check_change_callback_loop(
+    watched: &NamedReference,
+    handler: &Expression,
+    diag: &mut BuildDiagnostics,
+) {
+    // All the properties the watched property depends on, including itself
+    let mut dependencies = HashSet::new();
+    let mut to_visit = vec![watched.clone()];
+    while let Some(nr) = to_visit.pop() {
+        if !dependencies.insert(nr.clone()) {
+            continue;
+        }
+        let element = nr.element();
+        let element = element.borrow();
+        if let Some(binding) = element.bindings.get(nr.name()) {
+            let binding = binding.borrow();
+            to_visit.extend(binding.two_way_bindings.iter().cloned());
+            recurse_expression(&binding.expression, &mut |dep| to_visit.push(dep.clone()));
+        } else if let Type::Component(base) = &element.base_type {
+            to_visit.push(NamedReference::new(&base.root_element, nr.name()));
+        }
+    }
+
+    // Visit the properties that are assigned by the handler, and the functions it calls
+    fn visit_assigned(
+        expr: &Expression,
+        visited_functions: &mut HashSet<NamedReference>,
+        vis: &mut impl FnMut(&NamedReference),
+    ) {
+        fn assigned_property(lhs: &Expression) -> Option<&NamedReference> {
+            match lhs {
+                Expression::PropertyReference(nr) => Some(nr),
+                Expression::StructFieldAccess { base, .. } => assigned_property(base),
+                Expression::ArrayIndex { array, .. } => assigned_property(array),
+                _ => None,
+            }
+        }
+        expr.visit(|sub| visit_assigned(sub, visited_functions, vis));
+        match expr {
+            Expression::SelfAssignment { lhs, .. } => {
+                if let Some(nr) = assigned_property(lhs) {
+                    vis(nr)
+                }
+            }
+            Expression::FunctionReference(nr) => {
+                if visited_functions.insert(nr.clone()) {
+                    let element = nr.element();
+                    let element = element.borrow();
+                    if let Some(body) = element.bindings.get(nr.name()) {
+                        visit_assigned(&body.borrow().expression, visited_functions, vis);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut reported = false;
+    visit_assigned(handler, &mut HashSet::new(), &mut |nr| {
+        if !reported && dependencies.contains(nr) {
+            reported = true;
+            let message = if nr == watched {
+                format!(
+                    "The change callback of '{}' sets '{}', which would cause a loop",
+                    watched.name(),
+                    nr.name()
+                )
+            } else {
+                format!(
+                    "The change callback of '{}' sets '{}' which '{}' depends on. This would cause a loop",
+                    watched.name(),
+                    nr.name(),
+                    watched.name()
+                )
+            };
+            diag.push_error(message, &change_callback_location(watched));
+        }
+    });
+}
+
+/// The location of the `changed` handler for `watched`, or of its element if it can't be found
+fn change_callback_location(watched: &NamedReference) -> Option<SourceLocation> {
+    let element = watched.element();
+    let element = element.borrow();
+    element.node.as_ref().and_then(|n| {
+        n.PropertyChangedCallback()
+            .find(|c| {
+                crate::parser::identifier_text(&c.DeclaredIdentifier()).as_deref()
+                    == Some(watched.name())
+            })
+            .map(|c| c.to_source_location())
+            .or_else(|| Some(n.to_source_location()))
+    })
 }
 
 #[derive(Copy, Clone, dm::BitAnd, dm::BitOr, dm::BitAndAssign, dm::BitOrAssign)]
@@ -307,7 +415,9 @@ fn process_property(
 fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&NamedReference)) {
     expr.visit(|sub| recurse_expression(sub, vis));
     match expr {
-        Expression::PropertyReference(r) | Expression::CallbackReference(r) => vis(r),
+        Expression::PropertyReference(r)
+        | Expression::CallbackReference(r)
+        | Expression::FunctionReference(r) => vis(r),
         Expression::LayoutCacheAccess { layout_cache_prop, .. } => vis(layout_cache_prop),
         Expression::SolveLayout(l, o) | Expression::ComputeLayoutInfo(l, o) => {
             // we should only visit the layout geometry for the orientation
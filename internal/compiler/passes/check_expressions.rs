@@ -1,18 +1,26 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
+use std::collections::HashSet;
 use std::rc::Rc;
 
 use crate::diagnostics::BuildDiagnostics;
-use crate::expression_tree::{BuiltinFunction, Expression};
-use crate::object_tree::{visit_all_expressions, Component};
+use crate::expression_tree::{BuiltinFunction, Expression, NamedReference};
+use crate::langtype::Type;
+use crate::object_tree::{
+    recurse_elem_including_sub_components, visit_all_expressions, Component, ElementRc,
+};
 
 /// Check the validity of expressions
 ///
 /// - Make sure that there is no uncalled member function or macro
+/// - Make sure that the property bindings don't call functions with side effects
 pub fn check_expressions(doc: &crate::object_tree::Document, diag: &mut BuildDiagnostics) {
     for component in &doc.inner_components {
         visit_all_expressions(component, |e, _| check_expression(component, e, diag));
+        recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
+            check_binding_side_effects(elem, diag)
+        });
     }
 }
 
@@ -33,3 +41,99 @@ fn check_expression(component: &Rc<Component>, e: &Expression, diag: &mut BuildD
         _ => e.visit(|e| check_expression(component, e, diag)),
     }
 }
+
+/// Report an error for the calls, in the bindings of the properties of `elem`, to functions
+/// that set properties or call callbacks. Only callbacks and functions may have side effects,
+/// as bindings are evaluated lazily.
+fn check_binding_side_effects(elem: &ElementRc, diag: &mut BuildDiagnostics) {
+    for (name, binding) in &elem.borrow().bindings {
+        if matches!(
+            elem.borrow().lookup_property(name).property_type,
+            Type::Callback { .. } | Type::Function { .. } | Type::InferredCallback
+        ) {
+            continue;
+        }
+        let binding = binding.borrow();
+        binding.expression.visit_recursive(&mut |e| {
+            if let Expression::FunctionCall { function, source_location, .. } = e {
+                if let Expression::FunctionReference(nr) = &**function {
+                    if let Some(effect) = function_side_effect(nr, &mut HashSet::new()) {
+                        diag.push_error(
+                            format!(
+                                "The binding of '{}' cannot call the function '{}', because it {}",
+                                name,
+                                nr.name(),
+                                effect
+                            ),
+                            source_location,
+                        );
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Returns a description of the first side effect of the function `nr`, or of the functions it
+/// calls, if any.
+fn function_side_effect(
+    nr: &NamedReference,
+    visited_functions: &mut HashSet<NamedReference>,
+) -> Option<String> {
+    if !visited_functions.insert(nr.clone()) {
+        return None;
+    }
+    // The function may be declared in the component the element derives from
+    let mut element = nr.element();
+    let body = loop {
+        let base = match element.borrow().bindings.get(nr.name()) {
+            Some(body) => break body.borrow().expression.clone(),
+            None => match &element.borrow().base_type {
+                Type::Component(c) => c.root_element.clone(),
+                _ => return None,
+            },
+        };
+        element = base;
+    };
+    let mut effect = None;
+    body.visit_recursive(&mut |e| {
+        if effect.is_some() {
+            return;
+        }
+        effect = match e {
+            Expression::SelfAssignment { lhs, .. } => Some(match &**lhs {
+                Expression::PropertyReference(p) => format!("sets '{}'", p.name()),
+                _ => "assigns a value".into(),
+            }),
+            Expression::FunctionCall { function, .. } => match &**function {
+                Expression::CallbackReference(c) => {
+                    Some(format!("calls the callback '{}'", c.name()))
+                }
+                Expression::FunctionReference(f) => function_side_effect(f, visited_functions)
+                    .map(|effect| format!("calls '{}', which {}", f.name(), effect)),
+                Expression::BuiltinFunctionReference(b, _) if has_side_effect(b) => {
+                    Some("calls a builtin function with side effects".into())
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+    });
+    effect
+}
+
+/// Functions that change the state of the items or of the application. (The other non-pure
+/// builtin functions only depend on some state, which is fine in a binding.)
+fn has_side_effect(function: &BuiltinFunction) -> bool {
+    matches!(
+        function,
+        BuiltinFunction::SetFocusItem
+            | BuiltinFunction::ShowPopupWindow
+            | BuiltinFunction::FlickableScrollTo
+            | BuiltinFunction::TextInputUndo
+            | BuiltinFunction::TextInputRedo
+            | BuiltinFunction::RegisterCustomFontByPath
+            | BuiltinFunction::RegisterCustomFontByMemory
+            | BuiltinFunction::RegisterBitmapFont
+    )
+}
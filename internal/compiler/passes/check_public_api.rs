@@ -24,6 +24,10 @@ pub fn check_public_api(doc: &Document, diag: &mut BuildDiagnostics) {
 
 fn check_public_api_component(root_component: &Rc<Component>, diag: &mut BuildDiagnostics) {
     root_component.root_element.borrow_mut().property_declarations.values_mut().for_each(|d| {
+        if d.is_private {
+            // functions that are not declared `public` are not part of the public API
+            return;
+        }
         if d.property_type.ok_for_public_api() {
             d.expose_in_public_api = true
         } else {
@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-//! Passes that fills the root component used_types.structs
+//! Passes that fills the root component used_types.structs_and_enums
 
 use crate::expression_tree::Expression;
 use crate::langtype::Type;
@@ -9,27 +9,27 @@
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
-/// Fill the root_component's used_types.structs
-pub fn collect_structs(doc: &Document) {
+/// Fill the root_component's used_types.structs_and_enums
+pub fn collect_structs_and_enums(doc: &Document) {
     let mut hash = BTreeMap::new();
 
     for component in (doc.root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(&doc.root_component))
     {
-        collect_structs_in_component(component, &mut hash)
+        collect_types_in_component(component, &mut hash)
     }
 
     let mut used_types = doc.root_component.used_types.borrow_mut();
-    let used_struct = &mut used_types.structs;
-    *used_struct = Vec::with_capacity(hash.len());
+    let used_struct_and_enums = &mut used_types.structs_and_enums;
+    *used_struct_and_enums = Vec::with_capacity(hash.len());
     while let Some(next) = hash.iter().next() {
         // Here, using BTreeMap::pop_first would be great when it is stable
         let key = next.0.clone();
-        sort_struct(&mut hash, used_struct, &key);
+        sort_types(&mut hash, used_struct_and_enums, &key);
     }
 }
 
-fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTreeMap<String, Type>) {
+fn collect_types_in_component(root_component: &Rc<Component>, hash: &mut BTreeMap<String, Type>) {
     let mut maybe_collect_object = |ty: &Type| {
         visit_named_object(ty, &mut |name, sub_ty| {
             hash.entry(name.clone()).or_insert_with(|| sub_ty.clone());
@@ -43,17 +43,19 @@ fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTree
     });
 
     visit_all_expressions(root_component, |expr, _| {
-        expr.visit_recursive(&mut |expr| {
-            if let Expression::Struct { ty, .. } = expr {
-                maybe_collect_object(ty)
+        expr.visit_recursive(&mut |expr| match expr {
+            Expression::Struct { ty, .. } => maybe_collect_object(ty),
+            Expression::EnumerationValue(value) => {
+                maybe_collect_object(&Type::Enumeration(value.enumeration.clone()))
             }
+            _ => (),
         })
     });
 }
 
-/// Move the object named `key` from hash to vector, making sure that all object used by
+/// Move the type named `key` from hash to vector, making sure that all types used by
 /// it are placed before in the vector
-fn sort_struct(hash: &mut BTreeMap<String, Type>, vec: &mut Vec<Type>, key: &str) {
+fn sort_types(hash: &mut BTreeMap<String, Type>, vec: &mut Vec<Type>, key: &str) {
     let ty = if let Some(ty) = hash.remove(key) { ty } else { return };
     if let Type::Struct { fields, name: Some(name), .. } = &ty {
         if name.contains("::") {
@@ -63,7 +65,7 @@ fn sort_struct(hash: &mut BTreeMap<String, Type>, vec: &mut Vec<Type>, key: &str
         }
 
         for sub_ty in fields.values() {
-            visit_named_object(sub_ty, &mut |name, _| sort_struct(hash, vec, name));
+            visit_named_object(sub_ty, &mut |name, _| sort_types(hash, vec, name));
         }
     }
     vec.push(ty)
@@ -79,6 +81,7 @@ fn visit_named_object(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
                 visit_named_object(sub_ty, visitor);
             }
         }
+        Type::Enumeration(e) if e.node.is_some() => visitor(&e.name, ty),
         Type::Array(x) => visit_named_object(x, visitor),
         Type::Callback { return_type, args } => {
             if let Some(rt) = return_type {
@@ -88,6 +91,12 @@ fn visit_named_object(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
                 visit_named_object(a, visitor);
             }
         }
+        Type::Function { return_type, args } => {
+            visit_named_object(return_type, visitor);
+            for a in args {
+                visit_named_object(a, visitor);
+            }
+        }
         _ => {}
     }
 }
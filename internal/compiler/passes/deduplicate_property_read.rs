@@ -11,9 +11,10 @@
 
 pub fn deduplicate_property_read(component: &Component) {
     visit_all_expressions(component, |expr, ty| {
-        if matches!(ty(), Type::Callback { .. }) {
+        if matches!(ty(), Type::Callback { .. } | Type::Function { .. }) {
             // Callback handler can't be optimizes because they can have side effect.
-            // But that's fine as they also do not register dependencies
+            // But that's fine as they also do not register dependencies.
+            // The body of functions can also have side effects.
             return;
         }
         process_expression(expr, &DedupPropState::default());
@@ -30,6 +30,7 @@ pub fn ensure_window(
     let mut win_elem_mut = win_elem.borrow_mut();
     let new_root = Element {
         id: std::mem::replace(&mut win_elem_mut.id, "root_window".into()),
+        original_id: std::mem::take(&mut win_elem_mut.original_id),
         base_type: std::mem::replace(&mut win_elem_mut.base_type, window_type),
         bindings: Default::default(),
         property_analysis: Default::default(),
@@ -39,9 +40,11 @@ pub fn ensure_window(
         named_references: Default::default(),
         repeated: Default::default(),
         states: Default::default(),
+        change_callbacks: Default::default(),
         transitions: Default::default(),
         child_of_layout: false,
         layout_info_prop: Default::default(),
+        accessibility_props: Default::default(),
         is_flickable_viewport: false,
         item_index: Default::default(),
         item_index_of_first_children: Default::default(),
@@ -158,6 +158,15 @@ fn inline_element(
         }
     }
 
+    for (k, val) in inlined_component.root_element.borrow().change_callbacks.iter() {
+        elem_mut
+            .change_callbacks
+            .entry(k.clone())
+            .or_default()
+            .get_mut()
+            .extend(val.borrow().iter().cloned());
+    }
+
     if let Some(orig) = &inlined_component.root_element.borrow().layout_info_prop {
         if let Some(_new) = &mut elem_mut.layout_info_prop {
             todo!("Merge layout infos");
@@ -166,6 +175,10 @@ fn inline_element(
         }
     }
 
+    for (k, nr) in &inlined_component.root_element.borrow().accessibility_props {
+        elem_mut.accessibility_props.entry(k.clone()).or_insert_with(|| nr.clone());
+    }
+
     core::mem::drop(elem_mut);
 
     // Now fixup all binding and reference
@@ -189,6 +202,7 @@ fn duplicate_element_with_mapping(
     let new = Rc::new(RefCell::new(Element {
         base_type: elem.base_type.clone(),
         id: elem.id.clone(),
+        original_id: elem.original_id.clone(),
         property_declarations: elem.property_declarations.clone(),
         // We will do the fixup of the references in bindings later
         bindings: elem
@@ -206,6 +220,7 @@ fn duplicate_element_with_mapping(
         node: elem.node.clone(),
         enclosing_component: Rc::downgrade(root_component),
         states: elem.states.clone(),
+        change_callbacks: elem.change_callbacks.clone(),
         transitions: elem
             .transitions
             .iter()
@@ -213,6 +228,7 @@ fn duplicate_element_with_mapping(
             .collect(),
         child_of_layout: elem.child_of_layout,
         layout_info_prop: elem.layout_info_prop.clone(),
+        accessibility_props: elem.accessibility_props.clone(),
         named_references: Default::default(),
         item_index: Default::default(), // Not determined yet
         item_index_of_first_children: Default::default(),
@@ -437,9 +453,11 @@ fn component_requires_inlining(component: &Rc<Component>) -> bool {
 
     for (prop, binding) in &root_element.borrow().bindings {
         let binding = binding.borrow();
-        // The passes that dp the drop shadow or the opacity currently won't allow this property
-        // on the top level of a component. This could be changed in the future.
-        if prop.starts_with("drop-shadow-") || prop == "opacity" {
+        // The passes that dp the drop shadow, the opacity or the transformations currently won't
+        // allow this property on the top level of a component. This could be changed in the future.
+        if prop.starts_with("drop-shadow-")
+            || matches!(prop.as_str(), "opacity" | "rotation-angle" | "scale-x" | "scale-y")
+        {
             return true;
         }
         if (prop == "height" || prop == "width") && binding.expression.ty() == Type::Percent {
@@ -0,0 +1,27 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that collects the `accessible-*` properties set on the elements, so that they are kept
+//! until the item tree is generated.
+//!
+//! Must be run before materialize_fake_properties, which declares these properties
+
+use crate::namedreference::NamedReference;
+use crate::object_tree::*;
+use crate::typeregister::RESERVED_ACCESSIBILITY_PROPERTIES;
+use std::rc::Rc;
+
+pub fn lower_accessibility_properties(component: &Rc<Component>) {
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        for prop in std::iter::once("accessible-role")
+            .chain(RESERVED_ACCESSIBILITY_PROPERTIES.iter().map(|(prop, _)| *prop))
+        {
+            let is_set = elem.borrow().bindings.contains_key(prop)
+                || elem.borrow().property_analysis.borrow().get(prop).map_or(false, |a| a.is_set);
+            if is_set {
+                let nr = NamedReference::new(elem, prop);
+                elem.borrow_mut().accessibility_props.insert(prop.into(), nr);
+            }
+        }
+    });
+}
@@ -0,0 +1,109 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that transports the `payload` property declared on a `DragArea` to the `payload`
+//! property declared on a `DropArea`.
+//!
+//! The native items only pass strings around, so the payloads are stored in a global
+//! with one property per payload type: the `drag-started` callback of the DragArea copies its
+//! payload to the global, and the `can-drop` and `dropped` callbacks of the DropArea copy it
+//! back before running their handler.
+
+use crate::expression_tree::{BindingExpression, Expression, NamedReference};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn lower_drag_payload(doc: &Document) {
+    let root_component = &doc.root_component;
+    let mut payloads = Payloads::default();
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
+    {
+        recurse_elem_no_borrow(&component.root_element, &(), &mut |elem, _| {
+            let (is_drag_area, handlers): (bool, &[&str]) =
+                match elem.borrow().builtin_type().as_ref().map(|ty| ty.name.as_str()) {
+                    Some("DragArea") => (true, &["drag-started"]),
+                    Some("DropArea") => (false, &["can-drop", "dropped"]),
+                    _ => return,
+                };
+            let ty = elem.borrow().lookup_property("payload").property_type;
+            if !ty.is_property_type() {
+                return;
+            }
+            let payload = NamedReference::new(elem, "payload");
+            let stored_payload = payloads.property_for(ty);
+            let (lhs, rhs) =
+                if is_drag_area { (stored_payload, payload) } else { (payload, stored_payload) };
+            let copy = Expression::SelfAssignment {
+                lhs: Box::new(Expression::PropertyReference(lhs)),
+                rhs: Box::new(Expression::PropertyReference(rhs)),
+                op: '=',
+            };
+            // Handlers set on an element using a component based on the DragArea or DropArea
+            // replace the handler of the component, so they need the copy as well.
+            let declares_payload = elem.borrow().property_declarations.contains_key("payload");
+            let mut elem = elem.borrow_mut();
+            for handler in handlers {
+                match elem.bindings.get(*handler) {
+                    Some(binding) => {
+                        let mut binding = binding.borrow_mut();
+                        let code = std::mem::take(&mut binding.expression);
+                        binding.expression = Expression::CodeBlock(vec![copy.clone(), code]);
+                    }
+                    None if declares_payload => {
+                        elem.bindings.insert(
+                            handler.to_string(),
+                            RefCell::new(BindingExpression::from(Expression::CodeBlock(vec![
+                                copy.clone(),
+                            ]))),
+                        );
+                    }
+                    None => {}
+                }
+            }
+        });
+    }
+}
+
+/// The global that stores the payloads while they are dragged
+#[derive(Default)]
+struct Payloads {
+    global: Option<Rc<Component>>,
+    types: Vec<Type>,
+}
+
+impl Payloads {
+    /// Returns the property of the global that stores the payloads of the given type
+    fn property_for(&mut self, ty: Type) -> NamedReference {
+        let global = self.global.get_or_insert_with(|| {
+            let component = Rc::new(Component {
+                id: "DragPayloads".into(),
+                root_element: Rc::new(RefCell::new(Element {
+                    id: "DragPayloads".into(),
+                    base_type: Type::Void,
+                    ..Default::default()
+                })),
+                ..Default::default()
+            });
+            component.root_element.borrow_mut().enclosing_component = Rc::downgrade(&component);
+            component
+        });
+        let index = match self.types.iter().position(|t| *t == ty) {
+            Some(index) => index,
+            None => {
+                self.types.push(ty.clone());
+                self.types.len() - 1
+            }
+        };
+        let name = format!("payload-{}", index);
+        global
+            .root_element
+            .borrow_mut()
+            .property_declarations
+            .entry(name.clone())
+            .or_insert_with(|| PropertyDeclaration { property_type: ty, ..Default::default() });
+        NamedReference::new(&global.root_element, &name)
+    }
+}
@@ -494,6 +494,7 @@ fn lower_dialog_layout(
                                             layout_child,
                                             "clicked",
                                         )),
+                                        is_private: false,
                                     });
                             }
                         }
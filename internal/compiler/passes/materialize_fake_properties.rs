@@ -132,6 +132,21 @@ fn initialize(elem: &ElementRc, name: &str) -> Option<Expression> {
         "horizontal-stretch" => layout_constraint_prop(elem, "stretch", Orientation::Horizontal),
         "vertical-stretch" => layout_constraint_prop(elem, "stretch", Orientation::Vertical),
         "opacity" => Expression::NumberLiteral(1., Unit::None),
+        "scale-x" | "scale-y" => Expression::NumberLiteral(1., Unit::None),
+        "transform-origin" => Expression::Struct {
+            ty: crate::typeregister::logical_point_type(),
+            values: [("x", "width"), ("y", "height")]
+                .iter()
+                .map(|(field, size)| {
+                    let half = Expression::BinaryExpression {
+                        lhs: Expression::PropertyReference(NamedReference::new(elem, size)).into(),
+                        rhs: Expression::NumberLiteral(2., Unit::None).into(),
+                        op: '/',
+                    };
+                    (field.to_string(), half)
+                })
+                .collect(),
+        },
         "visible" => Expression::BoolLiteral(true),
         _ => return None,
     };
@@ -28,6 +28,7 @@ fn create_repeater_components(component: &Rc<Component>) {
         let comp = Rc::new(Component {
             root_element: Rc::new(RefCell::new(Element {
                 id: elem.id.clone(),
+                original_id: std::mem::take(&mut elem.original_id),
                 base_type: std::mem::take(&mut elem.base_type),
                 bindings: std::mem::take(&mut elem.bindings),
                 property_analysis: std::mem::take(&mut elem.property_analysis),
@@ -38,9 +39,11 @@ fn create_repeater_components(component: &Rc<Component>) {
                 node: elem.node.clone(),
                 enclosing_component: Default::default(),
                 states: std::mem::take(&mut elem.states),
+                change_callbacks: std::mem::take(&mut elem.change_callbacks),
                 transitions: std::mem::take(&mut elem.transitions),
                 child_of_layout: elem.child_of_layout || is_listview.is_some(),
                 layout_info_prop: elem.layout_info_prop.take(),
+                accessibility_props: std::mem::take(&mut elem.accessibility_props),
                 is_flickable_viewport: elem.is_flickable_viewport,
                 item_index: Default::default(), // Not determined yet
                 item_index_of_first_children: Default::default(),
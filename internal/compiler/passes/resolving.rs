@@ -50,6 +50,10 @@ fn resolve_expression(
                 //FIXME: proper callback support (node is a codeblock)
                 Expression::from_callback_connection(node.clone().into(), &mut lookup_ctx)
             }
+            SyntaxKind::Function => Expression::from_function(node.clone().into(), &mut lookup_ctx),
+            SyntaxKind::PropertyChangedCallback => {
+                Expression::from_changed_callback(node.clone().into(), &mut lookup_ctx)
+            }
             SyntaxKind::Expression => {
                 //FIXME again: this happen for non-binding expression (i.e: model)
                 Expression::from_expression_node(node.clone().into(), &mut lookup_ctx)
@@ -232,11 +236,37 @@ fn from_callback_connection(
         )
     }
 
+    fn from_changed_callback(
+        node: syntax_nodes::PropertyChangedCallback,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        Self::from_codeblock_node(node.CodeBlock(), ctx).maybe_convert_to(
+            Type::Void,
+            &node,
+            ctx.diag,
+        )
+    }
+
+    fn from_function(node: syntax_nodes::Function, ctx: &mut LookupCtx) -> Expression {
+        ctx.arguments = node
+            .ArgumentDeclaration()
+            .map(|x| identifier_text(&x.DeclaredIdentifier()).unwrap_or_default())
+            .collect();
+        Self::from_codeblock_node(node.CodeBlock(), ctx).maybe_convert_to(
+            ctx.return_type().clone(),
+            &node,
+            ctx.diag,
+        )
+    }
+
     fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) -> Self {
         node.Expression()
             .map(|n| Self::from_expression_node(n, ctx))
             .or_else(|| node.AtImageUrl().map(|n| Self::from_at_image_url_node(n, ctx)))
             .or_else(|| node.AtLinearGradient().map(|n| Self::from_at_linear_gradient(n, ctx)))
+            .or_else(|| node.AtRadialGradient().map(|n| Self::from_at_radial_gradient(n, ctx)))
+            .or_else(|| node.AtConicGradient().map(|n| Self::from_at_conic_gradient(n, ctx)))
+            .or_else(|| node.AtTr().map(|n| Self::from_at_tr(n, ctx)))
             .or_else(|| {
                 node.QualifiedName().map(|n| {
                     let exp = Self::from_qualified_name_node(n.clone(), ctx);
@@ -371,6 +401,111 @@ fn from_at_linear_gradient(node: syntax_nodes::AtLinearGradient, ctx: &mut Looku
                 ctx.diag,
             ));
 
+        let stops = Self::gradient_stops(subs, Type::Float32, ctx);
+        Expression::LinearGradient { angle, stops }
+    }
+
+    fn from_at_radial_gradient(node: syntax_nodes::AtRadialGradient, ctx: &mut LookupCtx) -> Self {
+        let mut subs = node
+            .children_with_tokens()
+            .filter(|n| matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Expression));
+        let is_circle = match subs.next() {
+            Some(e) if e.kind() == SyntaxKind::Expression => {
+                syntax_nodes::Expression::from(e.into_node().unwrap())
+                    .QualifiedName()
+                    .and_then(|n| identifier_text(&n))
+                    .map_or(false, |id| id == "circle")
+            }
+            _ => false,
+        };
+        if !is_circle || subs.next().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
+            ctx.diag.push_error(
+                "Expected 'circle' followed by a comma. Only circular radial gradients are supported"
+                    .into(),
+                &node,
+            );
+            return Expression::Invalid;
+        }
+        let stops = Self::gradient_stops(subs, Type::Float32, ctx);
+        Expression::RadialGradient { stops }
+    }
+
+    fn from_at_conic_gradient(node: syntax_nodes::AtConicGradient, ctx: &mut LookupCtx) -> Self {
+        let subs = node
+            .children_with_tokens()
+            .filter(|n| matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Expression));
+        let stops = Self::gradient_stops(subs, Type::Angle, ctx);
+        Expression::ConicGradient { stops }
+    }
+
+    fn from_at_tr(node: syntax_nodes::AtTr, ctx: &mut LookupCtx) -> Self {
+        let string_literal = |n: &SyntaxNode, diag: &mut BuildDiagnostics| -> String {
+            n.child_text(SyntaxKind::StringLiteral)
+                .and_then(|s| crate::literals::unescape_string(&s))
+                .unwrap_or_else(|| {
+                    diag.push_error("Cannot parse string literal".into(), n);
+                    String::new()
+                })
+        };
+        let original = string_literal(&node, ctx.diag);
+        let context = node.TrContext().map(|n| string_literal(&n, ctx.diag)).unwrap_or_default();
+        let (plural, n) = match node.TrPlural() {
+            Some(plural) => {
+                let n = plural.Expression();
+                (
+                    string_literal(&plural, ctx.diag),
+                    Expression::from_expression_node(n.clone(), ctx).maybe_convert_to(
+                        Type::Int32,
+                        &n,
+                        ctx.diag,
+                    ),
+                )
+            }
+            None => (String::new(), Expression::NumberLiteral(1., Unit::None)),
+        };
+        let values = node
+            .Expression()
+            .map(|n| {
+                Expression::from_expression_node(n.clone(), ctx).maybe_convert_to(
+                    Type::String,
+                    &n,
+                    ctx.diag,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for format_str in std::iter::once(&original).chain((!plural.is_empty()).then(|| &plural)) {
+            if let Err(e) = check_tr_placeholders(format_str, values.len(), !plural.is_empty()) {
+                ctx.diag.push_error(e, &node);
+                return Expression::Invalid;
+            }
+        }
+
+        Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(
+                BuiltinFunction::Translate,
+                Some(node.to_source_location()),
+            )),
+            arguments: vec![
+                Expression::StringLiteral(original),
+                Expression::StringLiteral(context),
+                Expression::Array { element_ty: Type::String, values },
+                n,
+                Expression::StringLiteral(plural),
+            ],
+            source_location: Some(node.to_source_location()),
+        }
+    }
+
+    /// Resolve the color stops of a gradient. The position of the stops are expressions of the
+    /// `position_type`, which is either a Float32 (percentage), or an Angle for conic gradients.
+    /// The returned positions are always a float, angles are converted to a fraction of a turn.
+    /// Stops without position are distributed evenly between the stops around them.
+    fn gradient_stops(
+        subs: impl Iterator<Item = NodeOrToken>,
+        position_type: Type,
+        ctx: &mut LookupCtx,
+    ) -> Vec<(Expression, Expression)> {
         let mut stops = vec![];
         enum Stop {
             Empty,
@@ -413,7 +548,17 @@ enum Stop {
                         break;
                     }
                     Stop::Color(col) => {
-                        stops.push((col, e.maybe_convert_to(Type::Float32, &n, ctx.diag)))
+                        let position = e.maybe_convert_to(position_type.clone(), &n, ctx.diag);
+                        let position = if position_type == Type::Angle {
+                            Expression::BinaryExpression {
+                                lhs: Box::new(position),
+                                rhs: Box::new(Expression::NumberLiteral(360., Unit::Deg)),
+                                op: '/',
+                            }
+                        } else {
+                            position
+                        };
+                        stops.push((col, position))
                     }
                 }
             }
@@ -467,7 +612,7 @@ enum Stop {
             start += pos + 1;
         }
 
-        Expression::LinearGradient { angle, stops }
+        stops
     }
 
     /// Perform the lookup
@@ -531,6 +676,13 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                 }
                 r
             }
+            LookupResult::Expression { expression: Expression::FunctionReference(nr), .. } => {
+                check_function_visibility(&nr, &first, ctx);
+                if let Some(x) = it.next() {
+                    ctx.diag.push_error("Cannot access fields of a function".into(), &x)
+                }
+                Expression::FunctionReference(nr)
+            }
             LookupResult::Enumeration(enumeration) => {
                 if let Some(next_identifier) = it.next() {
                     match enumeration
@@ -979,6 +1131,52 @@ fn common_target_type_for_type_list(types: impl Iterator<Item = Type>) -> Type {
     }
 }
 
+/// Checks the placeholders of a format string of `@tr`. `{}` is replaced by the next argument,
+/// `{0}`, `{1}`, ... by the argument at that index, and `{n}` by the count of the plural form.
+fn check_tr_placeholders(
+    format_str: &str,
+    argument_count: usize,
+    has_plural: bool,
+) -> Result<(), String> {
+    let mut next_argument = 0;
+    let mut rest = format_str;
+    while let Some(pos) = rest.find(|c: char| c == '{' || c == '}') {
+        let (c, after) = rest[pos..].split_at(1);
+        if after.starts_with(c) {
+            // `{{` or `}}`
+            rest = &after[1..];
+            continue;
+        }
+        if c == "}" {
+            return Err("Unescaped '}' in format string. Use '}}' for a literal '}'".into());
+        }
+        let end = after.find('}').ok_or_else(|| {
+            "Unterminated placeholder in format string. Use '{{' for a literal '{'".to_string()
+        })?;
+        let index = match &after[..end] {
+            "" => {
+                next_argument += 1;
+                next_argument - 1
+            }
+            "n" if has_plural => 0,
+            placeholder => placeholder.parse::<usize>().map_err(|_| {
+                format!(
+                    "Invalid '{{{}}}' placeholder in format string. Use '{{}}', the index of an argument, or '{{n}}' with a plural form",
+                    placeholder
+                )
+            })?,
+        };
+        if index >= argument_count && &after[..end] != "n" {
+            return Err(format!(
+                "Format string contains more placeholders than the {} given arguments",
+                argument_count
+            ));
+        }
+        rest = &after[end + 1..];
+    }
+    Ok(())
+}
+
 fn continue_lookup_within_element(
     elem: &ElementRc,
     it: &mut impl Iterator<Item = crate::parser::SyntaxToken>,
@@ -1008,6 +1206,15 @@ fn continue_lookup_within_element(
             ctx.diag.push_error("Cannot access fields of callback".into(), &x)
         }
         Expression::CallbackReference(NamedReference::new(elem, &resolved_name))
+    } else if matches!(property_type, Type::Function { .. })
+        && elem.borrow().is_function(&resolved_name)
+    {
+        let nr = NamedReference::new(elem, &resolved_name);
+        check_function_visibility(&nr, &second, ctx);
+        if let Some(x) = it.next() {
+            ctx.diag.push_error("Cannot access fields of a function".into(), &x)
+        }
+        Expression::FunctionReference(nr)
     } else if matches!(property_type, Type::Function { .. }) {
         let member = elem.borrow().base_type.lookup_member_function(&resolved_name);
         Expression::MemberFunction {
@@ -1052,6 +1259,42 @@ fn continue_lookup_within_element(
     }
 }
 
+/// Report an error if `nr` refers to a function which is not `public` and which is
+/// used outside of the component that declares it.
+fn check_function_visibility(nr: &NamedReference, node: &dyn Spanned, ctx: &mut LookupCtx) {
+    let mut elem = nr.element();
+    loop {
+        let base = match elem.borrow().property_declarations.get(nr.name()) {
+            Some(decl) if !decl.is_private => return,
+            Some(_) => None,
+            None => match &elem.borrow().base_type {
+                Type::Component(c) => Some(c.root_element.clone()),
+                _ => return,
+            },
+        };
+        match base {
+            Some(base) => elem = base,
+            None => break,
+        }
+    }
+    let declaring_component = elem.borrow().enclosing_component.upgrade();
+    let current_component =
+        ctx.component_scope.first().and_then(|e| e.borrow().enclosing_component.upgrade());
+    if let (Some(declaring_component), Some(current_component)) =
+        (declaring_component, current_component)
+    {
+        if !Rc::ptr_eq(&declaring_component, &current_component) {
+            ctx.diag.push_error(
+                format!(
+                    "The function '{}' is private. Annotate it with 'public' to make it accessible from other components",
+                    nr.name()
+                ),
+                node,
+            );
+        }
+    }
+}
+
 fn maybe_lookup_object(
     mut base: Expression,
     it: impl Iterator<Item = crate::parser::SyntaxToken>,
@@ -1,14 +1,17 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-//! Pass that lowers synthetic `opacity`, `visibility`, or `rotate` properties to their Element.
-//! TODO: the rotation is not yet implemented
+//! Pass that lowers synthetic `opacity`, `rotation-angle`, `scale-x` and `scale-y` properties to
+//! their Element.
+//!
+//! The element is wrapped in a `Scale`, then a `Rotate`, then an `Opacity` element, whichever are
+//! needed. The origin of the transformations is the `transform-origin` property of the element.
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::diagnostics::BuildDiagnostics;
-use crate::expression_tree::{BindingExpression, NamedReference};
+use crate::expression_tree::{BindingExpression, Expression, NamedReference};
 use crate::langtype::Type;
 use crate::object_tree::{self, Component, Element, ElementRc};
 use crate::typeregister::TypeRegister;
@@ -18,16 +21,20 @@ pub(crate) fn handle_transform_and_opacity(
     type_register: &TypeRegister,
     diag: &mut BuildDiagnostics,
 ) {
-    if let Some(b) = component.root_element.borrow().bindings.get("opacity") {
-        diag.push_warning(
-            "The opacity property cannot be used on the root element, it will not be applied"
-                .to_string(),
-            &*b.borrow(),
-        );
+    for prop in ["opacity", "rotation-angle", "scale-x", "scale-y"] {
+        if let Some(b) = component.root_element.borrow().bindings.get(prop) {
+            diag.push_warning(
+                format!(
+                    "The {} property cannot be used on the root element, it will not be applied",
+                    prop
+                ),
+                &*b.borrow(),
+            );
+        }
     }
 
     object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
-        if elem.borrow().base_type.to_string() == "Opacity" {
+        if matches!(elem.borrow().base_type.to_string().as_str(), "Opacity" | "Rotate" | "Scale") {
             return;
         }
 
@@ -37,30 +44,18 @@ pub(crate) fn handle_transform_and_opacity(
             std::mem::replace(&mut elem.children, new_children)
         };
 
-        let has_opacity_binding = |e: &ElementRc| {
-            e.borrow().base_type.lookup_property("opacity").property_type != Type::Invalid
-                && (e.borrow().bindings.contains_key("opacity")
-                    || e.borrow()
-                        .property_analysis
-                        .borrow()
-                        .get("opacity")
-                        .map_or(false, |a| a.is_set))
-        };
-
         for mut child in old_children {
             if child.borrow().repeated.is_some() {
                 let root_elem = child.borrow().base_type.as_component().root_element.clone();
-                if has_opacity_binding(&root_elem) {
-                    object_tree::inject_element_as_repeated_element(
-                        &child,
-                        create_opacity_element(&root_elem, type_register),
-                    )
+                for new_root in create_wrapper_elements(&root_elem, type_register) {
+                    object_tree::inject_element_as_repeated_element(&child, new_root)
+                }
+            } else {
+                for new_child in create_wrapper_elements(&child, type_register) {
+                    crate::object_tree::adjust_geometry_for_injected_parent(&new_child, &child);
+                    new_child.borrow_mut().children.push(child);
+                    child = new_child;
                 }
-            } else if has_opacity_binding(&child) {
-                let new_child = create_opacity_element(&child, type_register);
-                crate::object_tree::adjust_geometry_for_injected_parent(&new_child, &child);
-                new_child.borrow_mut().children.push(child);
-                child = new_child;
             }
 
             elem.borrow_mut().children.push(child);
@@ -68,17 +63,90 @@ pub(crate) fn handle_transform_and_opacity(
     });
 }
 
-fn create_opacity_element(child: &ElementRc, type_register: &TypeRegister) -> ElementRc {
-    let element = Element {
-        id: format!("{}-opacity", child.borrow().id),
-        base_type: type_register.lookup_element("Opacity").unwrap(),
+fn has_binding(e: &ElementRc, prop: &str) -> bool {
+    e.borrow().base_type.lookup_property(prop).property_type != Type::Invalid
+        && (e.borrow().bindings.contains_key(prop)
+            || e.borrow().property_analysis.borrow().get(prop).map_or(false, |a| a.is_set))
+}
+
+/// Returns the elements that need to be injected as parents of `child`, from the innermost to the
+/// outermost.
+fn create_wrapper_elements(child: &ElementRc, type_register: &TypeRegister) -> Vec<ElementRc> {
+    let mut result = Vec::new();
+    if has_binding(child, "scale-x") || has_binding(child, "scale-y") {
+        result.push(create_wrapper_element(
+            child,
+            "Scale",
+            "scale",
+            &[("scale-x", "scale-x"), ("scale-y", "scale-y")],
+            true,
+            type_register,
+        ));
+    }
+    if has_binding(child, "rotation-angle") {
+        result.push(create_wrapper_element(
+            child,
+            "Rotate",
+            "rotation",
+            &[("angle", "rotation-angle")],
+            true,
+            type_register,
+        ));
+    }
+    if has_binding(child, "opacity") {
+        result.push(create_wrapper_element(
+            child,
+            "Opacity",
+            "opacity",
+            &[("opacity", "opacity")],
+            false,
+            type_register,
+        ));
+    }
+    result
+}
+
+/// Creates an element of type `element_type` whose properties are bound, as specified by `bindings`,
+/// to the properties of `child`. If `with_origin` is true, the `origin-x` and `origin-y` properties
+/// are bound to the `transform-origin` of the child.
+fn create_wrapper_element(
+    child: &ElementRc,
+    element_type: &str,
+    id_suffix: &str,
+    bindings: &[(&str, &str)],
+    with_origin: bool,
+    type_register: &TypeRegister,
+) -> ElementRc {
+    let mut element = Element {
+        id: format!("{}-{}", child.borrow().id, id_suffix),
+        base_type: type_register.lookup_element(element_type).unwrap(),
         enclosing_component: child.borrow().enclosing_component.clone(),
-        bindings: std::iter::once((
-            "opacity".to_owned(),
-            BindingExpression::new_two_way(NamedReference::new(child, "opacity")).into(),
-        ))
-        .collect(),
+        bindings: bindings
+            .iter()
+            .map(|(prop, child_prop)| {
+                (
+                    prop.to_string(),
+                    BindingExpression::new_two_way(NamedReference::new(child, child_prop)).into(),
+                )
+            })
+            .collect(),
         ..Default::default()
     };
+    if with_origin {
+        for (prop, field) in [("origin-x", "x"), ("origin-y", "y")] {
+            element.bindings.insert(
+                prop.to_owned(),
+                BindingExpression::from(Expression::StructFieldAccess {
+                    base: Expression::PropertyReference(NamedReference::new(
+                        child,
+                        "transform-origin",
+                    ))
+                    .into(),
+                    name: field.into(),
+                })
+                .into(),
+            );
+        }
+    }
     Rc::new(RefCell::new(element))
 }
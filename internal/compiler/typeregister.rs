@@ -52,6 +52,7 @@
             ])
             .collect(),
             default_value: 0,
+            node: None,
         });
 
     pub static LAYOUT_ALIGNMENT_ENUM: Rc<Enumeration> =
@@ -61,6 +62,30 @@
                 ["stretch", "center", "start", "end", "space-between", "space-around"]
             ).map(String::from).collect(),
             default_value: 0,
+            node: None,
+        });
+
+    pub static ACCESSIBLE_ROLE_ENUM: Rc<Enumeration> =
+        Rc::new(Enumeration {
+            name: "AccessibleRole".into(),
+            values: IntoIterator::into_iter([
+                "none",
+                "button",
+                "checkbox",
+                "combobox",
+                "list",
+                "list-item",
+                "progress-indicator",
+                "slider",
+                "spinbox",
+                "tab",
+                "text",
+                "text-input",
+            ])
+            .map(String::from)
+            .collect(),
+            default_value: 0,
+            node: None,
         });
 
     pub static PATH_EVENT_ENUM: Rc<Enumeration> =
@@ -70,6 +95,7 @@
             ["begin", "line", "quadratic", "cubic", "end_open", "end_closed"]
         ).map(String::from).collect(),
         default_value: 0,
+        node: None,
     });
 }
 
@@ -79,6 +105,16 @@
     ("visible", Type::Bool), // ("enabled", Type::Bool),
 ];
 
+pub(crate) const RESERVED_TRANSFORM_PROPERTIES: &[(&str, Type)] =
+    &[("rotation-angle", Type::Angle), ("scale-x", Type::Float32), ("scale-y", Type::Float32)];
+
+pub(crate) const RESERVED_ACCESSIBILITY_PROPERTIES: &[(&str, Type)] = &[
+    ("accessible-label", Type::String),
+    ("accessible-description", Type::String),
+    ("accessible-value", Type::String),
+    ("accessible-checked", Type::Bool),
+];
+
 pub(crate) const RESERVED_DROP_SHADOW_PROPERTIES: &[(&str, Type)] = &[
     ("drop-shadow-offset-x", Type::LogicalLength),
     ("drop-shadow-offset-y", Type::LogicalLength),
@@ -86,6 +122,15 @@
     ("drop-shadow-color", Type::Color),
 ];
 
+/// The [`Type`] of the builtin `Point` struct, used by the `transform-origin` property
+pub fn logical_point_type() -> Type {
+    Type::Struct {
+        fields: ["x", "y"].iter().map(|s| (s.to_string(), Type::LogicalLength)).collect(),
+        name: Some("slint::private_api::Point".into()),
+        node: None,
+    }
+}
+
 /// list of reserved property injected in every item
 pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
     RESERVED_GEOMETRY_PROPERTIES
@@ -93,11 +138,15 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
         .chain(RESERVED_LAYOUT_PROPERTIES.iter())
         .chain(RESERVED_OTHER_PROPERTIES.iter())
         .chain(RESERVED_DROP_SHADOW_PROPERTIES.iter())
+        .chain(RESERVED_TRANSFORM_PROPERTIES.iter())
+        .chain(RESERVED_ACCESSIBILITY_PROPERTIES.iter())
         .map(|(k, v)| (*k, v.clone()))
         .chain(IntoIterator::into_iter([
+            ("transform-origin", logical_point_type()),
             ("forward-focus", Type::ElementReference),
             ("focus", BuiltinFunction::SetFocusItem.ty()),
             ("dialog-button-role", Type::Enumeration(DIALOG_BUTTON_ROLE_ENUM.with(|e| e.clone()))),
+            ("accessible-role", Type::Enumeration(ACCESSIBLE_ROLE_ENUM.with(|e| e.clone()))),
         ]))
 }
 
@@ -188,6 +237,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
                     name: name.to_owned(),
                     values: values.iter().cloned().map(String::from).collect(),
                     default_value: 0,
+                    node: None,
                 })),
                 name.to_owned(),
             );
@@ -247,6 +297,8 @@ pub fn builtin() -> Rc<RefCell<Self>> {
             .with(|e| register.insert_type_with_name(Type::Enumeration(e.clone()), e.name.clone()));
         LAYOUT_ALIGNMENT_ENUM
             .with(|e| register.insert_type_with_name(Type::Enumeration(e.clone()), e.name.clone()));
+        ACCESSIBLE_ROLE_ENUM
+            .with(|e| register.insert_type_with_name(Type::Enumeration(e.clone()), e.name.clone()));
 
         register.supported_property_animation_types.insert(Type::Float32.to_string());
         register.supported_property_animation_types.insert(Type::Int32.to_string());
@@ -279,6 +331,39 @@ pub fn builtin() -> Rc<RefCell<Self>> {
             _ => unreachable!(),
         };
 
+        match &mut register.types.get_mut("Flickable").unwrap() {
+            Type::Builtin(ref mut b) => {
+                Rc::get_mut(b).unwrap().properties.insert(
+                    "scroll-to".into(),
+                    BuiltinPropertyInfo::new(BuiltinFunction::FlickableScrollTo.ty()),
+                );
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "scroll-to".into(),
+                    Expression::BuiltinFunctionReference(BuiltinFunction::FlickableScrollTo, None),
+                );
+            }
+            _ => unreachable!(),
+        };
+
+        match &mut register.types.get_mut("TextInput").unwrap() {
+            Type::Builtin(ref mut b) => {
+                for (name, function) in [
+                    ("undo", BuiltinFunction::TextInputUndo),
+                    ("redo", BuiltinFunction::TextInputRedo),
+                ] {
+                    Rc::get_mut(b)
+                        .unwrap()
+                        .properties
+                        .insert(name.into(), BuiltinPropertyInfo::new(function.ty()));
+                    Rc::get_mut(b)
+                        .unwrap()
+                        .member_functions
+                        .insert(name.into(), Expression::BuiltinFunctionReference(function, None));
+                }
+            }
+            _ => unreachable!(),
+        };
+
         Rc::new(RefCell::new(register))
     }
 
@@ -0,0 +1,153 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the platform neutral accessibility tree.
+
+The `accessible-*` properties of the elements are queried through the
+[`ComponentVTable`](crate::component::ComponentVTable). The backends build the tree of
+[`AccessibleNode`] with [`accessibility_tree`] and bridge it to the accessibility API of the platform.
+*/
+
+#![warn(missing_docs)]
+
+use crate::component::ComponentRc;
+use crate::graphics::{Point, Rect};
+use crate::item_tree::{ItemVisitor, ItemVisitorVTable, TraversalOrder, VisitChildrenResult};
+use crate::items::{AccessibleRole, ItemRc, ItemRef};
+use crate::SharedString;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::pin::Pin;
+use vtable::{VRc, VRefMut};
+
+/// The string properties of an item that can be queried with
+/// [`ItemRc::accessible_string_property`]
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AccessibleStringProperty {
+    /// The `accessible-label` property
+    Label,
+    /// The `accessible-description` property
+    Description,
+    /// The `accessible-value` property
+    Value,
+}
+
+/// A node of the accessibility tree, for an item that has an `accessible-role`
+#[derive(Clone)]
+pub struct AccessibleNode {
+    /// The item of this node
+    pub item: ItemRc,
+    /// The value of the `accessible-role` property
+    pub role: AccessibleRole,
+    /// The value of the `accessible-label` property
+    pub label: SharedString,
+    /// The value of the `accessible-description` property
+    pub description: SharedString,
+    /// The value of the `accessible-value` property
+    pub value: SharedString,
+    /// The value of the `accessible-checked` property
+    pub checked: bool,
+    /// The geometry of the item, relative to the window
+    pub geometry: Rect,
+    /// The nodes of the accessible items within this item, in the order of the item tree
+    pub children: Vec<AccessibleNode>,
+}
+
+impl AccessibleNode {
+    fn new(item: ItemRc, role: AccessibleRole) -> Self {
+        let size = item.borrow().as_ref().geometry().size;
+        Self {
+            role,
+            label: item.accessible_string_property(AccessibleStringProperty::Label),
+            description: item.accessible_string_property(AccessibleStringProperty::Description),
+            value: item.accessible_string_property(AccessibleStringProperty::Value),
+            checked: item.accessible_checked(),
+            geometry: Rect::new(item.map_to_window(Point::default()), size),
+            children: Vec::new(),
+            item,
+        }
+    }
+
+    /// Returns a key that identifies the item of this node for as long as its component exists.
+    /// The backends use it to match the nodes of two successive trees.
+    pub fn item_key(&self) -> (usize, usize) {
+        (VRc::borrow_pin(&self.item.component()).as_ptr() as usize, self.item.index())
+    }
+
+    fn dump_into(&self, result: &mut String, depth: usize) {
+        for _ in 0..depth {
+            result.push_str("  ");
+        }
+        result.push_str(&self.role.to_string().replace('_', "-"));
+        for (name, value) in
+            [("label", &self.label), ("value", &self.value), ("description", &self.description)]
+        {
+            if !value.is_empty() {
+                write!(result, " {}={:?}", name, value.as_str()).unwrap();
+            }
+        }
+        if self.checked {
+            result.push_str(" checked");
+        }
+        result.push('\n');
+        for child in &self.children {
+            child.dump_into(result, depth + 1);
+        }
+    }
+}
+
+/// Returns the accessibility tree of the component.
+///
+/// Only the items with an `accessible-role` other than `none` are part of the tree. The accessible
+/// items within an item that is not accessible are children of the closest accessible ancestor,
+/// or top level nodes of the result.
+pub fn accessibility_tree(component: &ComponentRc) -> Vec<AccessibleNode> {
+    let mut result = Vec::new();
+    collect_accessible_nodes(component, -1, &mut result);
+    result
+}
+
+fn collect_accessible_nodes(
+    component: &ComponentRc,
+    index: isize,
+    result: &mut Vec<AccessibleNode>,
+) {
+    let mut visitor =
+        |component: &ComponentRc, index: usize, _: Pin<ItemRef>| -> VisitChildrenResult {
+            let item = ItemRc::new(component.clone(), index);
+            match item.accessible_role() {
+                AccessibleRole::none => collect_accessible_nodes(component, index as isize, result),
+                role => {
+                    let mut node = AccessibleNode::new(item, role);
+                    collect_accessible_nodes(component, index as isize, &mut node.children);
+                    result.push(node);
+                }
+            }
+            VisitChildrenResult::CONTINUE
+        };
+    vtable::new_vref!(let mut visitor : VRefMut<ItemVisitorVTable> for ItemVisitor = &mut visitor);
+    VRc::borrow_pin(component).as_ref().visit_children_item(
+        index,
+        TraversalOrder::BackToFront,
+        visitor,
+    );
+}
+
+/// Returns a textual representation of the accessibility tree, one line per node, with the
+/// children indented below their parent. For example:
+///
+/// ```text
+/// checkbox label="Enable feature" checked
+/// list
+///   list-item label="First"
+/// ```
+pub fn dump_accessibility_tree(nodes: &[AccessibleNode]) -> String {
+    let mut result = String::new();
+    for node in nodes {
+        node.dump_into(&mut result, 0);
+    }
+    result
+}
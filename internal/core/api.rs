@@ -11,6 +11,10 @@
 use crate::component::ComponentVTable;
 use crate::window::WindowRc;
 
+pub use crate::translations::{
+    set_translation_catalog, TranslationCatalog, TranslationCatalogError,
+};
+
 /// This enum describes a low-level access to specific graphics APIs used
 /// by the renderer.
 #[derive(Clone)]
@@ -325,11 +329,17 @@ pub fn upgrade_in_event_loop(&self, func: impl FnOnce(T) + Send + 'static)
             T: 'static,
         {
             let weak_handle = self.clone();
-            super::invoke_from_event_loop(move || {
+            let func = move || {
                 if let Some(h) = weak_handle.upgrade() {
                     func(h);
                 }
-            })
+            };
+            // Post to the thread that created the component, which is the only one that can upgrade it
+            if let Some(backend) = crate::backend::instance() {
+                backend.post_event_to_thread(self.thread, alloc::boxed::Box::new(func))
+            } else {
+                panic!("slint::Weak::upgrade_in_event_loop() must be called after the Slint backend is initialized.")
+            }
         }
     }
 
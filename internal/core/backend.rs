@@ -67,6 +67,23 @@ fn register_bitmap_font(&'static self, _font_data: &'static crate::graphics::Bit
     /// Send an user event to from another thread that should be run in the GUI event loop
     fn post_event(&'static self, event: Box<dyn FnOnce() + Send>);
 
+    #[cfg(feature = "std")]
+    /// Send an user event from another thread that should be run in the event loop of the given
+    /// thread. Backends that run a single event loop can ignore the thread.
+    fn post_event_to_thread(
+        &'static self,
+        _thread: std::thread::ThreadId,
+        event: Box<dyn FnOnce() + Send>,
+    ) {
+        self.post_event(event)
+    }
+
+    /// Called when the value watched by a [`ChangeTracker`](crate::properties::ChangeTracker)
+    /// changed while no other change handler was pending. The backend must wake up its event
+    /// loop, if needed, and call [`ChangeTracker::run_change_handlers`](crate::properties::ChangeTracker::run_change_handlers)
+    /// from it. Unlike animations, this does not require to redraw the windows.
+    fn request_change_handlers_run(&'static self) {}
+
     fn image_size(&'static self, image: &Image) -> IntSize;
 
     fn duration_since_start(&'static self) -> core::time::Duration {
@@ -5,10 +5,12 @@
 
 //! This module contains the basic datastructures that are exposed to the C API
 
+use crate::accessibility::AccessibleStringProperty;
 use crate::item_tree::{ItemVisitorVTable, TraversalOrder, VisitChildrenResult};
-use crate::items::{ItemVTable, ItemWeak};
+use crate::items::{AccessibleRole, ItemVTable, ItemWeak};
 use crate::layout::{LayoutInfo, Orientation};
 use crate::window::WindowRc;
+use crate::SharedString;
 use vtable::*;
 
 /// A Component is representing an unit that is allocated together
@@ -41,6 +43,31 @@ pub struct ComponentVTable {
     pub layout_info:
         extern "C" fn(core::pin::Pin<VRef<ComponentVTable>>, Orientation) -> LayoutInfo,
 
+    /// Returns the id of the element that the item at the given index was created from, as written
+    /// in the .slint file. The result is left unchanged if the element had no id.
+    pub item_element_id: extern "C" fn(
+        core::pin::Pin<VRef<ComponentVTable>>,
+        index: usize,
+        result: &mut SharedString,
+    ),
+
+    /// Returns the value of the `accessible-role` property of the item at the given index.
+    pub accessible_role:
+        extern "C" fn(core::pin::Pin<VRef<ComponentVTable>>, index: usize) -> AccessibleRole,
+
+    /// Returns the value of the `accessible-checked` property of the item at the given index.
+    pub accessible_checked:
+        extern "C" fn(core::pin::Pin<VRef<ComponentVTable>>, index: usize) -> bool,
+
+    /// Returns the value of the `accessible-*` string property of the item at the given index.
+    /// The result is left unchanged if the property is not set.
+    pub accessible_string_property: extern "C" fn(
+        core::pin::Pin<VRef<ComponentVTable>>,
+        index: usize,
+        what: AccessibleStringProperty,
+        result: &mut SharedString,
+    ),
+
     /// in-place destructor (for VRc)
     pub drop_in_place: unsafe fn(VRefMut<ComponentVTable>) -> vtable::Layout,
     /// dealloc function (for VRc)
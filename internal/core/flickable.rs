@@ -7,18 +7,34 @@
 
 use crate::animations::EasingCurve;
 use crate::animations::Instant;
-use crate::graphics::Point;
+use crate::graphics::{Point, Rect};
 use crate::input::{InputEventFilterResult, InputEventResult, MouseEvent};
 use crate::items::PointerEventButton;
-use crate::items::{Flickable, PropertyAnimation, Rectangle};
+use crate::items::{Flickable, ItemRc, ItemRef, PropertyAnimation, Rectangle};
+use alloc::vec::Vec;
 use core::cell::RefCell;
 use core::pin::Pin;
+use euclid::default::Vector2D;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
 /// The distance required before it starts flicking if there is another item intercepting the mouse.
 /// FIXME: this is currently physical pixels, but it should be logical
 const DISTANCE_THRESHOLD: f32 = 4.;
 /// Time required before we stop caring about child event if the mouse hasn't been moved
 const DURATION_THRESHOLD: Duration = Duration::from_millis(500);
+/// Only the mouse positions within that duration before the release are used for the velocity
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+/// Maximum number of mouse positions kept to compute the velocity
+const VELOCITY_SAMPLES: usize = 8;
+/// How much faster the viewport decelerates once it went past an edge
+const OVERSHOOT_DECELERATION_FACTOR: f32 = 8.;
+/// The maximum distance the viewport can go past an edge, relative to the size of the Flickable
+const MAX_OVERSHOOT_RATIO: f32 = 0.25;
+/// Duration, in milliseconds, of the animation bringing the viewport back to the edge
+const BOUNCE_DURATION: f32 = 300.;
+/// Duration, in milliseconds, of the animation of `scroll-to`
+const SCROLL_TO_DURATION: i32 = 250;
 
 #[derive(Default, Debug)]
 struct FlickableDataInner {
@@ -26,10 +42,41 @@ struct FlickableDataInner {
     pressed_pos: Point,
     pressed_time: Option<Instant>,
     pressed_viewport_pos: Point,
+    /// The last positions of the mouse while it is pressed, used to compute the velocity
+    samples: Vec<(Instant, Point)>,
     /// Set to true if the flickable is flicking and capturing all mouse event, not forwarding back to the children
     capture_events: bool,
 }
 
+impl FlickableDataInner {
+    fn add_sample(&mut self, time: Instant, pos: Point) {
+        if self.samples.len() >= VELOCITY_SAMPLES {
+            self.samples.remove(0);
+        }
+        self.samples.push((time, pos));
+    }
+
+    /// The velocity of the mouse, in pixels per millisecond, over the samples that are recent enough
+    fn velocity(&self, now: Instant) -> Vector2D<f32> {
+        let (last_time, last_pos) = match self.samples.last() {
+            Some(last) => *last,
+            None => return Vector2D::zero(),
+        };
+        let (first_time, first_pos) = self
+            .samples
+            .iter()
+            .find(|(time, _)| now - *time <= VELOCITY_WINDOW)
+            .copied()
+            .unwrap_or((last_time, last_pos));
+        let elapsed = (last_time - first_time).as_millis() as f32;
+        if elapsed > 0. {
+            (last_pos - first_pos) / elapsed
+        } else {
+            Vector2D::zero()
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct FlickableData {
     inner: RefCell<FlickableDataInner>,
@@ -44,17 +91,15 @@ pub fn handle_mouse_filter(
         let mut inner = self.inner.borrow_mut();
         match event {
             MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
+                // A press while the viewport is still moving only stops it and is not forwarded
+                let was_flicking = flick.flicking();
+                stop_flick(flick);
                 inner.pressed_pos = pos;
                 inner.pressed_time = Some(crate::animations::current_tick());
-                inner.pressed_viewport_pos = Point::new(
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
-                if inner.capture_events {
+                inner.pressed_viewport_pos = viewport_pos(flick);
+                inner.samples.clear();
+                inner.add_sample(crate::animations::current_tick(), pos);
+                if inner.capture_events || was_flicking {
                     InputEventFilterResult::Intercept
                 } else {
                     InputEventFilterResult::ForwardAndInterceptGrab
@@ -74,7 +119,7 @@ pub fn handle_mouse_filter(
                 let do_intercept = inner.capture_events
                     || inner.pressed_time.map_or(false, |pressed_time| {
                         crate::animations::current_tick() - pressed_time < DURATION_THRESHOLD
-                            && (pos - inner.pressed_pos).square_length()
+                            && interactive_axes(flick, pos - inner.pressed_pos).square_length()
                                 > DISTANCE_THRESHOLD * DISTANCE_THRESHOLD
                     });
                 if do_intercept {
@@ -86,6 +131,7 @@ pub fn handle_mouse_filter(
                 }
             }
             MouseEvent::MouseWheel { .. } => InputEventFilterResult::Intercept,
+            MouseEvent::Touch { .. } => InputEventFilterResult::ForwardEvent,
             // Not the left button
             MouseEvent::MousePressed { .. } | MouseEvent::MouseReleased { .. } => {
                 InputEventFilterResult::ForwardAndIgnore
@@ -107,16 +153,19 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
             MouseEvent::MouseMoved { pos } => {
                 if inner.pressed_time.is_some() {
                     inner.capture_events = true;
-                    let new_pos = ensure_in_bound(
-                        flick,
-                        inner.pressed_viewport_pos + (pos - inner.pressed_pos),
+                    inner.add_sample(crate::animations::current_tick(), pos);
+                    let current_pos = viewport_pos(flick);
+                    let dragged_pos = inner.pressed_viewport_pos + (pos - inner.pressed_pos);
+                    let new_pos = Point::new(
+                        if flick.horizontal_interactive() { dragged_pos.x } else { current_pos.x },
+                        if flick.vertical_interactive() { dragged_pos.y } else { current_pos.y },
                     );
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .set(new_pos.x);
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .set(new_pos.y);
+                    let new_pos = if flick.overshoot() {
+                        rubber_band(flick, new_pos)
+                    } else {
+                        ensure_in_bound(flick, new_pos)
+                    };
+                    set_viewport_pos(flick, new_pos);
                     InputEventResult::GrabMouse
                 } else {
                     inner.capture_events = false;
@@ -124,56 +173,288 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
                 }
             }
             MouseEvent::MouseWheel { delta, .. } => {
-                let old_pos = Point::new(
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
+                stop_flick(flick);
+                let old_pos = viewport_pos(flick);
                 let new_pos = ensure_in_bound(flick, old_pos + delta.to_vector());
-                (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                    .apply_pin(flick)
-                    .set(new_pos.x);
-                (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                    .apply_pin(flick)
-                    .set(new_pos.y);
+                set_viewport_pos(flick, new_pos);
                 InputEventResult::EventAccepted
             }
+            MouseEvent::Touch { .. } => {
+                if inner.pressed_time.is_some() {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
         }
     }
 
     fn mouse_released(inner: &mut FlickableDataInner, flick: Pin<&Flickable>, event: MouseEvent) {
-        if let (Some(pressed_time), Some(pos)) = (inner.pressed_time, event.pos()) {
-            let dist = pos - inner.pressed_pos;
-            let speed =
-                dist / ((crate::animations::current_tick() - pressed_time).as_millis() as f32);
-
-            let duration = 100;
-            let final_pos = ensure_in_bound(
-                flick,
-                inner.pressed_viewport_pos + dist + speed * (duration as f32),
-            );
-            let anim = PropertyAnimation {
-                duration,
-                easing: EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]),
-                ..PropertyAnimation::default()
+        if inner.pressed_time.is_some() {
+            let now = crate::animations::current_tick();
+            let velocity = match event.pos() {
+                Some(pos) => {
+                    inner.add_sample(now, pos);
+                    interactive_axes(flick, inner.velocity(now))
+                }
+                None => Vector2D::zero(),
             };
-            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                .apply_pin(flick)
-                .set_animated_value(final_pos.x, anim.clone());
-            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                .apply_pin(flick)
-                .set_animated_value(final_pos.y, anim);
+            start_flick(flick, velocity);
         }
-        inner.capture_events = false; // FIXME: should only be set to false once the flick animation is over
+        // A press during the flick is intercepted because the flickable is `flicking`
+        inner.capture_events = false;
         inner.pressed_time = None;
+        inner.samples.clear();
     }
 }
 
-/// Make sure that the point is within the bounds
-fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+/// Scroll the viewport so that the point `pos` of the viewport is at the top left corner of the
+/// Flickable, or as close as the bounds allow.
+pub fn scroll_to(flick: Pin<&Flickable>, pos: Point, animated: bool) {
+    stop_flick(flick);
+    let new_pos = ensure_in_bound(flick, Point::new(-pos.x, -pos.y));
+    if animated {
+        let anim = PropertyAnimation {
+            duration: SCROLL_TO_DURATION,
+            easing: EasingCurve::CubicBezier([0.25, 0.1, 0.25, 1.0]),
+            ..PropertyAnimation::default()
+        };
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
+            .apply_pin(flick)
+            .set_animated_value(new_pos.x, anim.clone());
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
+            .apply_pin(flick)
+            .set_animated_value(new_pos.y, anim);
+    } else {
+        set_viewport_pos(flick, new_pos);
+    }
+}
+
+/// Scroll the viewport by the smallest amount that makes the rectangle, in the coordinates of the
+/// Flickable, visible. If it is bigger than the Flickable, its top left corner is made visible.
+/// Returns by how much the viewport moved.
+fn ensure_rect_visible(flick: Pin<&Flickable>, rect: Rect) -> Vector2D<f32> {
+    let axis = |min: f32, max: f32, size: f32| {
+        if min < 0. {
+            -min
+        } else if max > size {
+            -(max - size).min(min)
+        } else {
+            0.
+        }
+    };
+    let old_pos = viewport_pos(flick);
+    let new_pos = ensure_in_bound(
+        flick,
+        old_pos
+            + Vector2D::new(
+                axis(rect.min_x(), rect.max_x(), flick.width()),
+                axis(rect.min_y(), rect.max_y(), flick.height()),
+            ),
+    );
+    if new_pos != old_pos {
+        stop_flick(flick);
+        set_viewport_pos(flick, new_pos);
+    }
+    new_pos - old_pos
+}
+
+/// Scroll all the Flickables containing the item so that it becomes visible
+pub(crate) fn ensure_item_visible(item: &ItemRc) {
+    let geometry = item.borrow().as_ref().geometry();
+    // The rectangle of the item, relative to `current`
+    let mut rect = Rect::new(Point::default(), geometry.size);
+    let mut current = item.clone();
+    while let Some(parent) = current.parent_item().upgrade() {
+        rect.origin += current.borrow().as_ref().geometry().origin.to_vector();
+        if let Some(flick) = ItemRef::downcast_pin::<Flickable>(parent.borrow()) {
+            rect.origin += ensure_rect_visible(flick, rect);
+        }
+        current = parent;
+    }
+}
+
+/// One part of the motion of the viewport along an axis, after the mouse is released
+#[derive(Debug, Clone, Copy)]
+enum MotionPhase {
+    /// Uniform deceleration from `from` with the initial `velocity` (in pixels per millisecond),
+    /// which reaches zero after `stop_time` milliseconds
+    Decelerate { from: f32, velocity: f32, stop_time: f32 },
+    /// Animation bringing the viewport back to the edge after an overshoot
+    Bounce { from: f32, to: f32 },
+}
+
+impl MotionPhase {
+    fn position(&self, time: f32, duration: f32) -> f32 {
+        match *self {
+            MotionPhase::Decelerate { from, velocity, stop_time } => {
+                from + velocity * time * (1. - time / (2. * stop_time))
+            }
+            MotionPhase::Bounce { from, to } => {
+                let easing = EasingCurve::CubicBezier([0.25, 0.1, 0.25, 1.0]);
+                from + (to - from) * crate::animations::easing_curve(&easing, time / duration)
+            }
+        }
+    }
+}
+
+/// The motion of the viewport along one axis: the phases with their duration in milliseconds
+#[derive(Debug)]
+struct AxisMotion {
+    phases: Vec<(MotionPhase, f32)>,
+    end: f32,
+}
+
+impl AxisMotion {
+    /// Computes the motion starting at `from` with the given velocity (in pixels per millisecond)
+    /// and deceleration (in pixels per millisecond squared) within the `min` and `max` bounds.
+    /// `overshoot` is the maximum distance past the bounds, if overshooting is allowed.
+    /// Returns None if the viewport does not need to move.
+    fn new(
+        from: f32,
+        velocity: f32,
+        deceleration: f32,
+        min: f32,
+        max: f32,
+        overshoot: Option<f32>,
+    ) -> Option<Self> {
+        if from < min || from > max {
+            let to = from.max(min).min(max);
+            return Some(Self {
+                phases: alloc::vec![(MotionPhase::Bounce { from, to }, BOUNCE_DURATION)],
+                end: to,
+            });
+        }
+        if velocity == 0. || deceleration <= 0. {
+            return None;
+        }
+        let stop_time = velocity.abs() / deceleration;
+        let stop = from + velocity * stop_time / 2.;
+        let decelerate = MotionPhase::Decelerate { from, velocity, stop_time };
+        if (min..=max).contains(&stop) {
+            return Some(Self { phases: alloc::vec![(decelerate, stop_time)], end: stop });
+        }
+
+        let edge = if stop < min { min } else { max };
+        // Solve `from + velocity * t * (1 - t / (2 * stop_time)) = edge` for the first t
+        let edge_time = stop_time
+            - (stop_time * stop_time - 2. * stop_time * (edge - from) / velocity).max(0.).sqrt();
+        let mut phases = alloc::vec![(decelerate, edge_time)];
+        if let Some(limit) = overshoot.filter(|limit| *limit > 0.) {
+            let edge_velocity = velocity * (1. - edge_time / stop_time);
+            let overshoot_deceleration = (deceleration * OVERSHOOT_DECELERATION_FACTOR)
+                .max(edge_velocity * edge_velocity / (2. * limit));
+            let overshoot_time = edge_velocity.abs() / overshoot_deceleration;
+            phases.push((
+                MotionPhase::Decelerate {
+                    from: edge,
+                    velocity: edge_velocity,
+                    stop_time: overshoot_time,
+                },
+                overshoot_time,
+            ));
+            phases.push((
+                MotionPhase::Bounce { from: edge + edge_velocity * overshoot_time / 2., to: edge },
+                BOUNCE_DURATION,
+            ));
+        }
+        Some(Self { phases, end: edge })
+    }
+
+    fn duration(&self) -> f32 {
+        self.phases.iter().map(|(_, duration)| duration).sum()
+    }
+
+    /// Returns the position after `time` milliseconds, and whether the motion is finished
+    fn position(&self, mut time: f32) -> (f32, bool) {
+        for (phase, duration) in &self.phases {
+            if time < *duration {
+                return (phase.position(time, *duration), false);
+            }
+            time -= duration;
+        }
+        (self.end, true)
+    }
+}
+
+/// Start moving the viewport with the given velocity in pixels per millisecond, or bring it
+/// back within the bounds if it was dragged past them.
+fn start_flick(flick: Pin<&Flickable>, velocity: Vector2D<f32>) {
+    // The deceleration property is in pixels per second squared
+    let deceleration = flick.deceleration() / 1_000_000.;
+    let overshoot = flick.overshoot();
+    let pos = viewport_pos(flick);
+    let (min, max) = bounds(flick);
+    let x = AxisMotion::new(
+        pos.x,
+        velocity.x,
+        deceleration,
+        min.x,
+        max.x,
+        overshoot.then(|| flick.width() * MAX_OVERSHOOT_RATIO),
+    );
+    let y = AxisMotion::new(
+        pos.y,
+        velocity.y,
+        deceleration,
+        min.y,
+        max.y,
+        overshoot.then(|| flick.height() * MAX_OVERSHOOT_RATIO),
+    );
+    if x.is_none() && y.is_none() {
+        return;
+    }
+
+    let start = crate::animations::current_tick();
+    let elapsed = move || (crate::animations::current_tick() - start).as_millis() as f32;
+    let duration = x.iter().chain(y.iter()).map(AxisMotion::duration).fold(0., f32::max);
+    if let Some(x) = x {
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
+            .apply_pin(flick)
+            .set_animation_function(move || x.position(elapsed()));
+    }
+    if let Some(y) = y {
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
+            .apply_pin(flick)
+            .set_animation_function(move || y.position(elapsed()));
+    }
+    Flickable::FIELD_OFFSETS.flicking.apply_pin(flick).set_animation_function(move || {
+        let finished = elapsed() >= duration;
+        (!finished, finished)
+    });
+}
+
+/// Stop the viewport where it currently is, if it is moving
+fn stop_flick(flick: Pin<&Flickable>) {
+    if flick.flicking() {
+        // Setting the value removes the animation
+        set_viewport_pos(flick, viewport_pos(flick));
+        Flickable::FIELD_OFFSETS.flicking.apply_pin(flick).set(false);
+    }
+}
+
+fn viewport_pos(flick: Pin<&Flickable>) -> Point {
+    Point::new(
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(flick).get(),
+        (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(flick).get(),
+    )
+}
+
+fn set_viewport_pos(flick: Pin<&Flickable>, pos: Point) {
+    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(flick).set(pos.x);
+    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(flick).set(pos.y);
+}
+
+/// Zero the components of the vector for the axes that are not interactive
+fn interactive_axes(flick: Pin<&Flickable>, v: Vector2D<f32>) -> Vector2D<f32> {
+    Vector2D::new(
+        if flick.horizontal_interactive() { v.x } else { 0. },
+        if flick.vertical_interactive() { v.y } else { 0. },
+    )
+}
+
+/// The minimum and maximum position of the viewport
+fn bounds(flick: Pin<&Flickable>) -> (Point, Point) {
     let w = flick.width();
     let h = flick.height();
     let vw =
@@ -182,7 +463,24 @@ fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
         .apply_pin(flick)
         .get();
 
-    let min = Point::new(w - vw, h - vh);
     let max = Point::new(0., 0.);
+    let min = Point::new(w - vw, h - vh).min(max);
+    (min, max)
+}
+
+/// Make sure that the point is within the bounds
+fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+    let (min, max) = bounds(flick);
     p.max(min).min(max)
 }
+
+/// Like ensure_in_bound, but lets the point go past the bounds with some resistance
+fn rubber_band(flick: Pin<&Flickable>, p: Point) -> Point {
+    let (min, max) = bounds(flick);
+    let axis = |v: f32, min: f32, max: f32, size: f32| {
+        let clamped = v.max(min).min(max);
+        let limit = size * MAX_OVERSHOOT_RATIO;
+        clamped + ((v - clamped) / 2.).max(-limit).min(limit)
+    };
+    Point::new(axis(p.x, min.x, max.x, flick.width()), axis(p.y, min.y, max.y, flick.height()))
+}
@@ -31,9 +31,7 @@
 pub(crate) mod color;
 pub use color::*;
 
-#[cfg(feature = "std")]
 mod path;
-#[cfg(feature = "std")]
 pub use path::*;
 
 mod brush;
@@ -25,6 +25,12 @@ pub enum Brush {
     /// The linear gradient variant of a brush describes the gradient stops for a fill
     /// where all color stops are along a line that's rotated by the specified angle.
     LinearGradient(LinearGradientBrush),
+    /// The radial gradient variant of a brush describes the gradient stops for a fill
+    /// where all color stops are on circles around the center of the shape.
+    RadialGradient(RadialGradientBrush),
+    /// The conic gradient variant of a brush describes the gradient stops for a fill
+    /// where all color stops are rotated around the center of the shape, starting at the top.
+    ConicGradient(ConicGradientBrush),
 }
 
 /// Construct a brush with transparent color
@@ -36,13 +42,19 @@ fn default() -> Self {
 
 impl Brush {
     /// If the brush is SolidColor, the contained color is returned.
-    /// If the brush is a LinearGradient, the color of the first stop is returned.
+    /// If the brush is a gradient, the color of the first stop is returned.
     pub fn color(&self) -> Color {
         match self {
             Brush::SolidColor(col) => *col,
             Brush::LinearGradient(gradient) => {
                 gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
             }
+            Brush::RadialGradient(gradient) => {
+                gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
+            }
+            Brush::ConicGradient(gradient) => {
+                gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
+            }
         }
     }
 
@@ -58,6 +70,8 @@ pub fn is_transparent(&self) -> bool {
         match self {
             Brush::SolidColor(c) => c.alpha() == 0,
             Brush::LinearGradient(_) => false,
+            Brush::RadialGradient(_) => false,
+            Brush::ConicGradient(_) => false,
         }
     }
 }
@@ -94,6 +108,52 @@ pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
     }
 }
 
+/// The RadialGradientBrush describes a way of filling a shape with a circular gradient.
+/// The circle is centered in the shape, and its radius goes to the farthest corner of the
+/// shape's bounding rectangle.
+#[derive(Clone, PartialEq, Debug)]
+#[repr(transparent)]
+pub struct RadialGradientBrush(SharedVector<GradientStop>);
+
+impl RadialGradientBrush {
+    /// Creates a new circular radial gradient with the provided color stops.
+    pub fn new_circle(stops: impl IntoIterator<Item = GradientStop>) -> Self {
+        Self(stops.into_iter().collect())
+    }
+    /// Returns the color stops of the radial gradient.
+    pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
+        self.0.iter()
+    }
+}
+
+/// The ConicGradientBrush describes a way of filling a shape with colors that are interpolated
+/// while rotating around the center of the shape. The position of the stops is the fraction of a
+/// full turn, where 0 is at the top and the angle increases clockwise.
+#[derive(Clone, PartialEq, Debug)]
+#[repr(transparent)]
+pub struct ConicGradientBrush(SharedVector<GradientStop>);
+
+impl ConicGradientBrush {
+    /// Creates a new conic gradient with the provided color stops.
+    pub fn new(stops: impl IntoIterator<Item = GradientStop>) -> Self {
+        Self(stops.into_iter().collect())
+    }
+    /// Returns the color stops of the conic gradient.
+    pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
+        self.0.iter()
+    }
+    /// Returns the color of the gradient at the offset `(dx, dy)` from the center of the shape.
+    /// This is meant for renderers that compute the color of each pixel.
+    pub fn color_at(&self, dx: f32, dy: f32) -> Color {
+        // The angle from the top going clockwise, as a fraction of the turn
+        let mut angle = dx.atan2(-dy) / (2. * core::f32::consts::PI);
+        if angle < 0. {
+            angle += 1.;
+        }
+        gradient_color_at(self.stops(), angle)
+    }
+}
+
 /// GradientStop describes a single color stop in a gradient. The colors between multiple
 /// stops are interpolated.
 #[repr(C)]
@@ -116,6 +176,59 @@ pub fn line_for_angle(angle: f32) -> (Point, Point) {
     (start, end)
 }
 
+/// Returns the color at the given position of a gradient with the given stops.
+///
+/// The position is between 0 and 1, the colors are interpolated between the two stops around
+/// the position. This is meant for renderers that compute the color of each pixel.
+pub fn gradient_color_at<'a>(
+    stops: impl Iterator<Item = &'a GradientStop>,
+    position: f32,
+) -> Color {
+    let mut previous: Option<&GradientStop> = None;
+    for stop in stops {
+        if position <= stop.position {
+            return match previous {
+                Some(previous) if stop.position > previous.position => previous.color.interpolate(
+                    &stop.color,
+                    (position - previous.position) / (stop.position - previous.position),
+                ),
+                _ => stop.color,
+            };
+        }
+        previous = Some(stop);
+    }
+    previous.map(|stop| stop.color).unwrap_or_default()
+}
+
+/// Interpolates the color of all the stops from the given color
+fn interpolate_stops_from_color(color: &Color, stops: &mut [GradientStop], t: f32) {
+    for x in stops {
+        x.color = color.interpolate(&x.color, t);
+    }
+}
+
+/// Interpolates the stops of two gradients. The stops that are only in the gradient with
+/// more stops are moved toward the end.
+fn interpolate_gradient_stops(
+    lhs: &SharedVector<GradientStop>,
+    rhs: &SharedVector<GradientStop>,
+    t: f32,
+) -> SharedVector<GradientStop> {
+    if lhs.len() < rhs.len() {
+        return interpolate_gradient_stops(rhs, lhs, 1. - t);
+    }
+    let mut result = lhs.clone();
+    let mut iter = result.make_mut_slice().iter_mut();
+    for (s2, s1) in rhs.iter().zip(&mut iter) {
+        s1.color = s1.color.interpolate(&s2.color, t);
+        s1.position = s1.position.interpolate(&s2.position, t);
+    }
+    for x in iter {
+        x.position = x.position.interpolate(&1.0, t);
+    }
+    result
+}
+
 impl InterpolatedPropertyValue for Brush {
     fn interpolate(&self, target_value: &Self, t: f32) -> Self {
         match (self, target_value) {
@@ -153,6 +266,34 @@ fn interpolate(&self, target_value: &Self, t: f32) -> Self {
                     Brush::LinearGradient(new_grad)
                 }
             }
+            (Brush::SolidColor(col), Brush::RadialGradient(grad)) => {
+                let mut new_grad = grad.clone();
+                interpolate_stops_from_color(col, new_grad.0.make_mut_slice(), t);
+                Brush::RadialGradient(new_grad)
+            }
+            (Brush::SolidColor(col), Brush::ConicGradient(grad)) => {
+                let mut new_grad = grad.clone();
+                interpolate_stops_from_color(col, new_grad.0.make_mut_slice(), t);
+                Brush::ConicGradient(new_grad)
+            }
+            (a @ Brush::RadialGradient(_), b @ Brush::SolidColor(_))
+            | (a @ Brush::ConicGradient(_), b @ Brush::SolidColor(_)) => {
+                Self::interpolate(b, a, 1. - t)
+            }
+            (Brush::RadialGradient(lhs), Brush::RadialGradient(rhs)) => Brush::RadialGradient(
+                RadialGradientBrush(interpolate_gradient_stops(&lhs.0, &rhs.0, t)),
+            ),
+            (Brush::ConicGradient(lhs), Brush::ConicGradient(rhs)) => Brush::ConicGradient(
+                ConicGradientBrush(interpolate_gradient_stops(&lhs.0, &rhs.0, t)),
+            ),
+            // Gradients of different kinds cannot be interpolated, switch in the middle
+            (a, b) => {
+                if t < 0.5 {
+                    a.clone()
+                } else {
+                    b.clone()
+                }
+            }
         }
     }
 }
@@ -170,3 +311,17 @@ fn test_linear_gradient_encoding() {
     assert_eq!(grad.angle(), 256.);
     assert!(grad.stops().eq(stops.iter()));
 }
+
+#[test]
+fn test_gradient_color_at() {
+    let red = Color::from_rgb_u8(255, 0, 0);
+    let blue = Color::from_rgb_u8(0, 0, 255);
+    let stops =
+        [GradientStop { position: 0.25, color: red }, GradientStop { position: 0.75, color: blue }];
+    assert_eq!(gradient_color_at(stops.iter(), 0.), red);
+    assert_eq!(gradient_color_at(stops.iter(), 0.25), red);
+    assert_eq!(gradient_color_at(stops.iter(), 0.5), red.interpolate(&blue, 0.5));
+    assert_eq!(gradient_color_at(stops.iter(), 0.75), blue);
+    assert_eq!(gradient_color_at(stops.iter(), 1.), blue);
+    assert_eq!(gradient_color_at([].iter(), 0.5), Color::default());
+}
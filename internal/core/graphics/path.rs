@@ -3,14 +3,23 @@
 
 /*!
 This module contains path related types and functions for the run-time library.
+
+The iteration through lyon events requires the `std` feature. Without it, the paths can only be
+approximated with lines with [`PathData::flatten`].
 */
 
-use super::{Point, Rect, Size};
+#[cfg(feature = "std")]
+use super::Size;
+use super::{Point, Rect, Transform};
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use auto_enums::auto_enum;
 use const_field_offset::FieldOffsets;
 use i_slint_core_macros::*;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement, Clone, Debug, PartialEq)]
@@ -155,6 +164,7 @@ pub enum PathEvent {
     end_closed,
 }
 
+#[cfg(feature = "std")]
 struct ToLyonPathEventIterator<'a> {
     events_it: core::slice::Iter<'a, PathEvent>,
     coordinates_it: core::slice::Iter<'a, Point>,
@@ -162,6 +172,7 @@ struct ToLyonPathEventIterator<'a> {
     last: Option<&'a Point>,
 }
 
+#[cfg(feature = "std")]
 impl<'a> Iterator for ToLyonPathEventIterator<'a> {
     type Item = lyon_path::Event<lyon_path::math::Point, lyon_path::math::Point>;
     fn next(&mut self) -> Option<Self::Item> {
@@ -198,13 +209,16 @@ fn size_hint(&self) -> (usize, Option<usize>) {
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a> ExactSizeIterator for ToLyonPathEventIterator<'a> {}
 
+#[cfg(feature = "std")]
 struct TransformedLyonPathIterator<EventIt> {
     it: EventIt,
     transform: lyon_path::math::Transform,
 }
 
+#[cfg(feature = "std")]
 impl<
         EventIt: Iterator<Item = lyon_path::Event<lyon_path::math::Point, lyon_path::math::Point>>,
     > Iterator for TransformedLyonPathIterator<EventIt>
@@ -219,12 +233,14 @@ fn size_hint(&self) -> (usize, Option<usize>) {
     }
 }
 
+#[cfg(feature = "std")]
 impl<
         EventIt: Iterator<Item = lyon_path::Event<lyon_path::math::Point, lyon_path::math::Point>>,
     > ExactSizeIterator for TransformedLyonPathIterator<EventIt>
 {
 }
 
+#[cfg(feature = "std")]
 /// PathDataIterator is a data structure that acts as starting point for iterating
 /// through the low-level events of a path. If the path was constructed from said
 /// events, then it is a very thin abstraction. If the path was created from higher-level
@@ -234,11 +250,13 @@ pub struct PathDataIterator {
     transform: lyon_path::math::Transform,
 }
 
+#[cfg(feature = "std")]
 enum LyonPathIteratorVariant {
     FromPath(lyon_path::Path),
     FromEvents(crate::SharedVector<PathEvent>, crate::SharedVector<Point>),
 }
 
+#[cfg(feature = "std")]
 impl PathDataIterator {
     /// Create a new iterator for path traversal.
     #[auto_enum(Iterator)]
@@ -304,6 +322,7 @@ fn default() -> Self {
 
 impl PathData {
     /// This function returns an iterator that allows traversing the path by means of lyon events.
+    #[cfg(feature = "std")]
     pub fn iter(self) -> PathDataIterator {
         PathDataIterator {
             it: match self {
@@ -326,6 +345,7 @@ pub fn iter(self) -> PathDataIterator {
         }
     }
 
+    #[cfg(feature = "std")]
     fn build_path(element_it: core::slice::Iter<PathElement>) -> lyon_path::Path {
         use lyon_geom::SvgArc;
         use lyon_path::math::{Angle, Point, Vector};
@@ -397,6 +417,233 @@ fn build_path(element_it: core::slice::Iter<PathElement>) -> lyon_path::Path {
     }
 }
 
+/// The number of lines approximating each curve and arc in [`PathData::flatten`]
+const FLATTENED_CURVE_SEGMENTS: usize = 16;
+
+/// A sub-path approximated with lines, as returned by [`PathData::flatten`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlattenedSubPath {
+    /// The end points of the lines, starting with the first point of the sub-path
+    pub points: Vec<Point>,
+    /// Whether the last point of the sub-path is connected to its first point
+    pub closed: bool,
+}
+
+impl PathData {
+    /// Approximates the path with lines, each curve and arc being split into a fixed number of
+    /// lines. Unlike [`Self::iter`], this doesn't need lyon, so it's also available without the
+    /// `std` feature. Paths made of SVG commands that are not known at compile time are only
+    /// supported with the `std` feature, and are empty otherwise.
+    pub fn flatten(&self) -> Vec<FlattenedSubPath> {
+        let mut flattener = Flattener::default();
+        match self {
+            PathData::None => {}
+            PathData::Elements(elements) => {
+                for element in elements.iter() {
+                    match element {
+                        PathElement::MoveTo(PathMoveTo { x, y }) => {
+                            flattener.move_to(Point::new(*x, *y))
+                        }
+                        PathElement::LineTo(PathLineTo { x, y }) => {
+                            flattener.line_to(Point::new(*x, *y))
+                        }
+                        PathElement::ArcTo(arc) => flattener.arc_to(arc),
+                        PathElement::CubicTo(PathCubicTo {
+                            x,
+                            y,
+                            control_1_x,
+                            control_1_y,
+                            control_2_x,
+                            control_2_y,
+                        }) => flattener.cubic_to(
+                            Point::new(*control_1_x, *control_1_y),
+                            Point::new(*control_2_x, *control_2_y),
+                            Point::new(*x, *y),
+                        ),
+                        PathElement::QuadraticTo(PathQuadraticTo {
+                            x,
+                            y,
+                            control_x,
+                            control_y,
+                        }) => flattener
+                            .quadratic_to(Point::new(*control_x, *control_y), Point::new(*x, *y)),
+                        PathElement::Close => flattener.end(true),
+                    }
+                }
+            }
+            PathData::Events(events, coordinates) => {
+                let mut coordinates = coordinates.iter().copied();
+                let mut next = || coordinates.next().unwrap_or_default();
+                for event in events.iter() {
+                    match event {
+                        PathEvent::begin => flattener.move_to(next()),
+                        PathEvent::line => {
+                            let _from = next();
+                            flattener.line_to(next());
+                        }
+                        PathEvent::quadratic => {
+                            let _from = next();
+                            let ctrl = next();
+                            flattener.quadratic_to(ctrl, next());
+                        }
+                        PathEvent::cubic => {
+                            let _from = next();
+                            let (ctrl1, ctrl2) = (next(), next());
+                            flattener.cubic_to(ctrl1, ctrl2, next());
+                        }
+                        PathEvent::end_open => flattener.end(false),
+                        PathEvent::end_closed => flattener.end(true),
+                    }
+                }
+            }
+            #[cfg(feature = "std")]
+            PathData::Commands(_) => {
+                for event in self.clone().iter().iter() {
+                    match event {
+                        lyon_path::Event::Begin { at } => flattener.move_to(at),
+                        lyon_path::Event::Line { to, .. } => flattener.line_to(to),
+                        lyon_path::Event::Quadratic { ctrl, to, .. } => {
+                            flattener.quadratic_to(ctrl, to)
+                        }
+                        lyon_path::Event::Cubic { ctrl1, ctrl2, to, .. } => {
+                            flattener.cubic_to(ctrl1, ctrl2, to)
+                        }
+                        lyon_path::Event::End { close, .. } => flattener.end(close),
+                    }
+                }
+            }
+            #[cfg(not(feature = "std"))]
+            PathData::Commands(_) => {}
+        }
+        flattener.end(false);
+        flattener.sub_paths
+    }
+}
+
+/// Builds the sub-paths of [`PathData::flatten`]
+#[derive(Default)]
+struct Flattener {
+    sub_paths: Vec<FlattenedSubPath>,
+    current: FlattenedSubPath,
+    /// The first point of the current sub-path, which is also the current position after
+    /// a sub-path was closed
+    start: Point,
+}
+
+impl Flattener {
+    fn position(&self) -> Point {
+        self.current.points.last().copied().unwrap_or(self.start)
+    }
+
+    fn move_to(&mut self, to: Point) {
+        self.end(false);
+        self.start = to;
+        self.current.points.push(to);
+    }
+
+    fn line_to(&mut self, to: Point) {
+        if self.current.points.is_empty() {
+            self.current.points.push(self.start);
+        }
+        self.current.points.push(to);
+    }
+
+    /// Adds the lines approximating the curve from the current position to `to`, whose points
+    /// are given by `point_at` for t between 0 and 1
+    fn curve_to(&mut self, to: Point, point_at: impl Fn(f32) -> Point) {
+        for i in 1..FLATTENED_CURVE_SEGMENTS {
+            self.line_to(point_at(i as f32 / FLATTENED_CURVE_SEGMENTS as f32));
+        }
+        self.line_to(to);
+    }
+
+    fn quadratic_to(&mut self, ctrl: Point, to: Point) {
+        let from = self.position();
+        self.curve_to(to, |t| from.lerp(ctrl, t).lerp(ctrl.lerp(to, t), t));
+    }
+
+    fn cubic_to(&mut self, ctrl1: Point, ctrl2: Point, to: Point) {
+        let from = self.position();
+        self.curve_to(to, |t| {
+            let a = from.lerp(ctrl1, t);
+            let b = ctrl1.lerp(ctrl2, t);
+            let c = ctrl2.lerp(to, t);
+            a.lerp(b, t).lerp(b.lerp(c, t), t)
+        });
+    }
+
+    /// Converts the SVG arc to its center parametrization, as described in the implementation
+    /// notes of the SVG specification, and approximates it with lines.
+    fn arc_to(&mut self, arc: &PathArcTo) {
+        let from = self.position();
+        let to = Point::new(arc.x, arc.y);
+        let (mut radius_x, mut radius_y) = (arc.radius_x.abs(), arc.radius_y.abs());
+        if radius_x == 0. || radius_y == 0. || from == to {
+            self.line_to(to);
+            return;
+        }
+        let (sin, cos) = arc.x_rotation.to_radians().sin_cos();
+        let half_x = (from.x - to.x) / 2.;
+        let half_y = (from.y - to.y) / 2.;
+        let x1 = cos * half_x + sin * half_y;
+        let y1 = -sin * half_x + cos * half_y;
+
+        // Scale up the radii if they are too small to join the points
+        let lambda = (x1 * x1) / (radius_x * radius_x) + (y1 * y1) / (radius_y * radius_y);
+        if lambda > 1. {
+            radius_x *= lambda.sqrt();
+            radius_y *= lambda.sqrt();
+        }
+        let (rx2, ry2) = (radius_x * radius_x, radius_y * radius_y);
+        let numerator = rx2 * ry2 - rx2 * y1 * y1 - ry2 * x1 * x1;
+        let denominator = rx2 * y1 * y1 + ry2 * x1 * x1;
+        let mut coefficient = (numerator / denominator).max(0.).sqrt();
+        if arc.large_arc == arc.sweep {
+            coefficient = -coefficient;
+        }
+        let center_x1 = coefficient * radius_x * y1 / radius_y;
+        let center_y1 = -coefficient * radius_y * x1 / radius_x;
+        let center = Point::new(
+            cos * center_x1 - sin * center_y1 + (from.x + to.x) / 2.,
+            sin * center_x1 + cos * center_y1 + (from.y + to.y) / 2.,
+        );
+
+        let start_angle = ((y1 - center_y1) / radius_y).atan2((x1 - center_x1) / radius_x);
+        let end_angle = ((-y1 - center_y1) / radius_y).atan2((-x1 - center_x1) / radius_x);
+        let mut sweep_angle = end_angle - start_angle;
+        if arc.sweep && sweep_angle < 0. {
+            sweep_angle += 2. * core::f32::consts::PI;
+        } else if !arc.sweep && sweep_angle > 0. {
+            sweep_angle -= 2. * core::f32::consts::PI;
+        }
+
+        self.curve_to(to, |t| {
+            let (sin_angle, cos_angle) = (start_angle + sweep_angle * t).sin_cos();
+            let (x, y) = (radius_x * cos_angle, radius_y * sin_angle);
+            Point::new(cos * x - sin * y + center.x, sin * x + cos * y + center.y)
+        });
+    }
+
+    /// Ends the current sub-path, if it has at least one line
+    fn end(&mut self, closed: bool) {
+        if self.current.points.len() > 1 {
+            self.current.closed = closed;
+            self.sub_paths.push(core::mem::take(&mut self.current));
+        } else {
+            self.current.points.clear();
+        }
+    }
+}
+
+/// Returns the transformation that scales `source` to fit into `target` while keeping its aspect
+/// ratio, and centers it. This is the same as lyon's `fit_rectangle` with `FitStyle::Min`.
+pub fn fit_rectangle(source: &Rect, target: &Rect) -> Transform {
+    let scale = (target.width() / source.width()).min(target.height() / source.height());
+    Transform::translation(-source.center().x, -source.center().y)
+        .then_scale(scale, scale)
+        .then_translate(target.center().to_vector())
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) mod ffi {
     #![allow(unsafe_code)]
@@ -438,3 +685,63 @@ pub(crate) mod ffi {
         core::ptr::write(out_coordinates as *mut crate::SharedVector<Point>, coordinates);
     }
 }
+
+#[test]
+fn test_flatten_path_elements() {
+    let path = PathData::Elements(crate::SharedVector::from_slice(&[
+        PathElement::MoveTo(PathMoveTo { x: 0., y: 0. }),
+        PathElement::LineTo(PathLineTo { x: 10., y: 0. }),
+        PathElement::LineTo(PathLineTo { x: 10., y: 10. }),
+        PathElement::Close,
+        PathElement::LineTo(PathLineTo { x: 0., y: 10. }),
+    ]));
+    let sub_paths = path.flatten();
+    assert_eq!(
+        sub_paths,
+        [
+            FlattenedSubPath {
+                points: alloc::vec![Point::new(0., 0.), Point::new(10., 0.), Point::new(10., 10.)],
+                closed: true,
+            },
+            // After the close, the sub-path starts again at the first point of the previous one
+            FlattenedSubPath {
+                points: alloc::vec![Point::new(0., 0.), Point::new(0., 10.)],
+                closed: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_arc() {
+    // A half circle of radius 5 from (0, 5) to (10, 5), going clockwise through (5, 0)
+    let path = PathData::Elements(crate::SharedVector::from_slice(&[
+        PathElement::MoveTo(PathMoveTo { x: 0., y: 5. }),
+        PathElement::ArcTo(PathArcTo {
+            x: 10.,
+            y: 5.,
+            radius_x: 5.,
+            radius_y: 5.,
+            x_rotation: 0.,
+            large_arc: false,
+            sweep: true,
+        }),
+    ]));
+    let sub_paths = path.flatten();
+    assert_eq!(sub_paths.len(), 1);
+    let points = &sub_paths[0].points;
+    assert_eq!(points.len(), FLATTENED_CURVE_SEGMENTS + 1);
+    assert_eq!(points.last(), Some(&Point::new(10., 5.)));
+    let center = Point::new(5., 5.);
+    assert!(points.iter().all(|p| ((*p - center).length() - 5.).abs() < 0.001));
+    let middle = points[FLATTENED_CURVE_SEGMENTS / 2];
+    assert!((middle - Point::new(5., 0.)).length() < 0.001);
+}
+
+#[test]
+fn test_fit_rectangle() {
+    let transform =
+        fit_rectangle(&euclid::rect(10., 10., 20., 10.), &euclid::rect(0., 0., 100., 100.));
+    assert_eq!(transform.transform_point(Point::new(10., 10.)), Point::new(0., 25.));
+    assert_eq!(transform.transform_point(Point::new(30., 20.)), Point::new(100., 75.));
+}
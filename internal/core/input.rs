@@ -34,6 +34,11 @@ pub enum MouseEvent {
     MouseWheel { pos: Point, delta: Point },
     /// The mouse exited the item or component
     MouseExit,
+    /// A touch point was pressed, moved or released. `id` identifies the touch point for as
+    /// long as it is pressed.
+    /// The window converts the events of the first touch point into mouse events. The events of
+    /// the other touch points are only sent to the item that grabbed the mouse.
+    Touch { pos: Point, id: u64, phase: TouchPhase },
 }
 
 impl MouseEvent {
@@ -45,24 +50,41 @@ pub fn pos(&self) -> Option<Point> {
             MouseEvent::MouseMoved { pos } => Some(*pos),
             MouseEvent::MouseWheel { pos, .. } => Some(*pos),
             MouseEvent::MouseExit => None,
+            MouseEvent::Touch { pos, .. } => Some(*pos),
         }
     }
 
     /// Translate the position by the given value
     pub fn translate(&mut self, vec: Vector2D<f32>) {
-        let pos = match self {
+        if let Some(pos) = self.pos_mut() {
+            *pos += vec;
+        }
+    }
+
+    fn pos_mut(&mut self) -> Option<&mut Point> {
+        match self {
             MouseEvent::MousePressed { pos, .. } => Some(pos),
             MouseEvent::MouseReleased { pos, .. } => Some(pos),
             MouseEvent::MouseMoved { pos } => Some(pos),
             MouseEvent::MouseWheel { pos, .. } => Some(pos),
             MouseEvent::MouseExit => None,
-        };
-        if let Some(pos) = pos {
-            *pos += vec;
+            MouseEvent::Touch { pos, .. } => Some(pos),
         }
     }
 }
 
+/// The phase of a touch point in a [`MouseEvent::Touch`] event
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TouchPhase {
+    /// The touch point was pressed
+    Pressed,
+    /// The touch point moved
+    Moved,
+    /// The touch point was released, or the touch was cancelled
+    Released,
+}
+
 /// This value is returned by the `input_event` function of an Item
 /// to notify the run-time about how the event was handled and
 /// what the next steps are.
@@ -150,6 +172,11 @@ pub enum KeyEventType {
     KeyPressed,
     /// A key on a keyboard was released.
     KeyReleased,
+    /// The input method updated the text it is composing (the pre-edit text). The event's text
+    /// is the new pre-edit text, which is empty when the composition was cancelled.
+    UpdateComposition,
+    /// The input method finished the composition. The event's text is the text to insert.
+    CommitComposition,
 }
 
 impl Default for KeyEventType {
@@ -172,6 +199,22 @@ pub struct KeyEvent {
     pub event_type: KeyEventType,
 }
 
+/// Data being dragged over a `DropArea`, either from a `DragArea` or from another application.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[repr(C)]
+pub struct DropEvent {
+    /// The mime type of the data, for example `text/plain`. Files dropped from other
+    /// applications have the `text/uri-list` type.
+    pub mime_type: SharedString,
+    /// The data being dragged. For `text/uri-list`, these are the percent-encoded URLs of the
+    /// files, separated by line breaks. Values of other types are passed with the `payload`
+    /// properties that the compiler handles for the `DragArea` and `DropArea` elements.
+    pub data: SharedString,
+    /// The position of the drag, relative to the `DropArea`. When the event is passed to the
+    /// window, it is relative to the window.
+    pub position: Point,
+}
+
 /// Represents how an item's key_event handler dealt with a key event.
 /// An accepted event results in no further event propagation.
 #[repr(C)]
@@ -208,6 +251,24 @@ pub struct MouseInputState {
     grabbed: bool,
 }
 
+/// Maps a position from the coordinates of `item` to the coordinates of its children, taking into
+/// account the transformation that the item applies to them. Returns None if that transformation
+/// cannot be inverted, for example when the children are scaled down to nothing.
+fn map_to_children(item: Pin<ItemRef>, pos: Point) -> Option<Point> {
+    match crate::item_rendering::item_children_transform(item) {
+        Some(transform) => transform.inverse().map(|t| t.transform_point(pos)),
+        None => Some(pos),
+    }
+}
+
+/// Same as [`map_to_children`], for the position of the event
+fn event_for_children(item: Pin<ItemRef>, mut event: MouseEvent) -> Option<MouseEvent> {
+    if let Some(pos) = event.pos_mut() {
+        *pos = map_to_children(item, *pos)?;
+    }
+    Some(event)
+}
+
 /// Try to handle the mouse grabber. Return true if the event has handled, or false otherwise
 fn handle_mouse_grab(
     mouse_event: &MouseEvent,
@@ -218,7 +279,10 @@ fn handle_mouse_grab(
         return false;
     };
 
-    let mut event = *mouse_event;
+    // The event in the coordinates of the parent of the current item
+    let mut event = Some(*mouse_event);
+    // The event in the coordinates of the last item kept in the stack
+    let mut grabber_event = *mouse_event;
     let mut intercept = false;
     let mut invalid = false;
 
@@ -236,15 +300,24 @@ fn handle_mouse_grab(
             item.borrow().as_ref().input_event(MouseEvent::MouseExit, window, &item);
             return false;
         }
+        let mut item_event = if let Some(event) = event {
+            event
+        } else {
+            // The item is not reachable anymore
+            invalid = true;
+            return false;
+        };
         let g = item.borrow().as_ref().geometry();
-        event.translate(-g.origin.to_vector());
+        item_event.translate(-g.origin.to_vector());
 
         if it.1 == InputEventFilterResult::ForwardAndInterceptGrab
-            && item.borrow().as_ref().input_event_filter_before_children(event, window, &item)
+            && item.borrow().as_ref().input_event_filter_before_children(item_event, window, &item)
                 == InputEventFilterResult::Intercept
         {
             intercept = true;
         }
+        event = event_for_children(item.borrow(), item_event);
+        grabber_event = item_event;
         true
     });
     if invalid {
@@ -252,7 +325,7 @@ fn handle_mouse_grab(
     }
 
     let grabber = mouse_input_state.item_stack.last().unwrap().0.upgrade().unwrap();
-    let input_result = grabber.borrow().as_ref().input_event(event, window, &grabber);
+    let input_result = grabber.borrow().as_ref().input_event(grabber_event, window, &grabber);
     if input_result != InputEventResult::GrabMouse {
         mouse_input_state.grabbed = false;
         send_exit_events(mouse_input_state, mouse_event.pos(), window);
@@ -270,9 +343,7 @@ fn send_exit_events(
         let item = if let Some(item) = it.0.upgrade() { item } else { break };
         let g = item.borrow().as_ref().geometry();
         let contains = pos.map_or(false, |p| g.contains(p));
-        if let Some(p) = pos.as_mut() {
-            *p -= g.origin.to_vector();
-        }
+        pos = pos.and_then(|p| map_to_children(item.borrow(), p - g.origin.to_vector()));
         if !contains {
             item.borrow().as_ref().input_event(MouseEvent::MouseExit, window, &item);
         }
@@ -291,58 +362,68 @@ pub fn process_mouse_input(
     if handle_mouse_grab(&mouse_event, window, &mut mouse_input_state) {
         return mouse_input_state;
     }
+    if matches!(mouse_event, MouseEvent::Touch { .. }) {
+        // The additional touch points are only sent to the item that grabbed the mouse
+        return mouse_input_state;
+    }
 
     send_exit_events(&mouse_input_state, mouse_event.pos(), window);
 
     let mut result = MouseInputState::default();
-    type State = (Vector2D<f32>, Vec<(ItemWeak, InputEventFilterResult)>);
+    // The state holds the event in the coordinates of the parent item, or None if the position
+    // cannot be mapped to these coordinates.
+    type State = (Option<MouseEvent>, Vec<(ItemWeak, InputEventFilterResult)>);
     crate::item_tree::visit_items_with_post_visit(
         &component,
         crate::item_tree::TraversalOrder::FrontToBack,
         |comp_rc: &ComponentRc,
          item: core::pin::Pin<ItemRef>,
          item_index: usize,
-         (offset, mouse_grabber_stack): &State| {
+         (parent_event, mouse_grabber_stack): &State| {
             let item_rc = ItemRc::new(comp_rc.clone(), item_index);
 
             let geom = item.as_ref().geometry();
-            let geom = geom.translate(*offset);
 
             let mut mouse_grabber_stack = mouse_grabber_stack.clone();
 
-            let post_visit_state = if mouse_event.pos().map_or(false, |p| geom.contains(p))
-                || crate::item_rendering::is_clipping_item(item)
-            {
-                let mut event2 = mouse_event;
-                event2.translate(-geom.origin.to_vector());
-                let filter_result =
-                    item.as_ref().input_event_filter_before_children(event2, window, &item_rc);
-                mouse_grabber_stack.push((item_rc.downgrade(), filter_result));
-                match filter_result {
-                    InputEventFilterResult::ForwardAndIgnore => None,
-                    InputEventFilterResult::ForwardEvent => {
-                        Some((event2, mouse_grabber_stack.clone(), item_rc, false))
-                    }
-                    InputEventFilterResult::ForwardAndInterceptGrab => {
-                        Some((event2, mouse_grabber_stack.clone(), item_rc, false))
-                    }
-                    InputEventFilterResult::Intercept => {
-                        return (
-                            ItemVisitorResult::Abort,
-                            Some((event2, mouse_grabber_stack, item_rc, true)),
-                        )
+            let event2 = parent_event.map(|mut event| {
+                event.translate(-geom.origin.to_vector());
+                event
+            });
+
+            let post_visit_state = match event2 {
+                Some(event2)
+                    if parent_event.and_then(|e| e.pos()).map_or(false, |p| geom.contains(p))
+                        || crate::item_rendering::is_clipping_item(item) =>
+                {
+                    let filter_result =
+                        item.as_ref().input_event_filter_before_children(event2, window, &item_rc);
+                    mouse_grabber_stack.push((item_rc.downgrade(), filter_result));
+                    match filter_result {
+                        InputEventFilterResult::ForwardAndIgnore => None,
+                        InputEventFilterResult::ForwardEvent => {
+                            Some((event2, mouse_grabber_stack.clone(), item_rc, false))
+                        }
+                        InputEventFilterResult::ForwardAndInterceptGrab => {
+                            Some((event2, mouse_grabber_stack.clone(), item_rc, false))
+                        }
+                        InputEventFilterResult::Intercept => {
+                            return (
+                                ItemVisitorResult::Abort,
+                                Some((event2, mouse_grabber_stack, item_rc, true)),
+                            )
+                        }
                     }
                 }
-            } else {
-                mouse_grabber_stack
-                    .push((item_rc.downgrade(), InputEventFilterResult::ForwardAndIgnore));
-                None
+                _ => {
+                    mouse_grabber_stack
+                        .push((item_rc.downgrade(), InputEventFilterResult::ForwardAndIgnore));
+                    None
+                }
             };
 
-            (
-                ItemVisitorResult::Continue((geom.origin.to_vector(), mouse_grabber_stack)),
-                post_visit_state,
-            )
+            let children_event = event2.and_then(|event| event_for_children(item, event));
+            (ItemVisitorResult::Continue((children_event, mouse_grabber_stack)), post_visit_state)
         },
         |_, item, post_state, r| {
             if let Some((event2, mouse_grabber_stack, item_rc, intercept)) = post_state {
@@ -369,7 +450,54 @@ pub fn process_mouse_input(
             }
             r
         },
-        (Vector2D::new(0., 0.), Vec::new()),
+        (Some(mouse_event), Vec::new()),
+    );
+    result
+}
+
+/// Returns the `DropArea` under the position of the `event`, which is in window coordinates,
+/// whose `can-drop` callback accepts the event. Returns it along with the event translated to
+/// the coordinates of the `DropArea`.
+pub(crate) fn locate_drop_area(
+    component: &ComponentRc,
+    event: &DropEvent,
+) -> Option<(ItemRc, DropEvent)> {
+    let mut result = None;
+    crate::item_tree::visit_items_with_post_visit(
+        component,
+        crate::item_tree::TraversalOrder::FrontToBack,
+        |comp_rc: &ComponentRc,
+         item: Pin<ItemRef>,
+         item_index: usize,
+         parent_position: &Option<Point>| {
+            let geom = item.as_ref().geometry();
+            let offset = geom.origin.to_vector();
+            let post_visit_state = match *parent_position {
+                Some(p) if geom.contains(p) => {
+                    Some((ItemRc::new(comp_rc.clone(), item_index), p - offset))
+                }
+                _ => None,
+            };
+            let children_position = parent_position.and_then(|p| map_to_children(item, p - offset));
+            (ItemVisitorResult::Continue(children_position), post_visit_state)
+        },
+        |_, item, post_state, r| {
+            if r.has_aborted() {
+                return r;
+            }
+            if let Some((item_rc, position)) = post_state {
+                if let Some(drop_area) = ItemRef::downcast_pin::<crate::items::DropArea>(item) {
+                    let event = DropEvent { position, ..event.clone() };
+                    if drop_area.can_drop(&event) {
+                        let index = item_rc.index();
+                        result = Some((item_rc, event));
+                        return VisitChildrenResult::abort(index, 0);
+                    }
+                }
+            }
+            r
+        },
+        Some(event.position),
     );
     result
 }
@@ -9,6 +9,7 @@
 use crate::component::ComponentRc;
 use crate::graphics::Rect;
 use crate::item_tree::ItemVisitorResult;
+use alloc::boxed::Box;
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
 
@@ -80,12 +81,49 @@ pub(crate) fn is_clipping_item(item: Pin<ItemRef>) -> bool {
         || ItemRef::downcast_pin::<Clip>(item).is_some()
 }
 
+/// Returns the transformation that the item applies to its children, relative to the item's
+/// origin, or None if the item doesn't transform its children.
+pub(crate) fn item_children_transform(item: Pin<ItemRef>) -> Option<crate::graphics::Transform> {
+    //(FIXME: there should be some flag in the vtable instead of downcasting)
+    let (origin, transform) = if let Some(rotate) = ItemRef::downcast_pin::<Rotate>(item) {
+        (
+            euclid::vec2(rotate.origin_x(), rotate.origin_y()),
+            crate::graphics::Transform::rotation(euclid::Angle::degrees(rotate.angle())),
+        )
+    } else if let Some(scale) = ItemRef::downcast_pin::<Scale>(item) {
+        (
+            euclid::vec2(scale.origin_x(), scale.origin_y()),
+            crate::graphics::Transform::scale(scale.scale_x(), scale.scale_y()),
+        )
+    } else {
+        return None;
+    };
+    Some(
+        crate::graphics::Transform::translation(-origin.x, -origin.y)
+            .then(&transform)
+            .then_translate(origin),
+    )
+}
+
 /// Renders the tree of items that component holds, using the specified renderer. Rendering is done
 /// relative to the specified origin.
 pub fn render_component_items(
     component: &ComponentRc,
     renderer: &mut dyn ItemRenderer,
     origin: crate::graphics::Point,
+) {
+    render_items_with(component, renderer, origin, |renderer, item| {
+        item.as_ref().render(&mut (renderer as &mut dyn ItemRenderer))
+    })
+}
+
+/// Visits the items of the component and calls `render_item` for each of the items that are not
+/// clipped, after the renderer was translated to the item's position.
+fn render_items_with<R: ItemRenderer + ?Sized>(
+    component: &ComponentRc,
+    renderer: &mut R,
+    origin: crate::graphics::Point,
+    mut render_item: impl FnMut(&mut R, Pin<ItemRef>),
 ) {
     renderer.save_state();
     renderer.translate(origin.x, origin.y);
@@ -102,9 +140,11 @@ pub fn render_component_items(
             let item_origin = item_geometry.origin;
 
             // Don't render items that are clipped, with the exception of the Clip or Flickable since
-            // they themselves clip their content.
+            // they themselves clip their content, and of the items that transform their children
+            // since the children may be moved into the clip.
             if !renderer.borrow().get_current_clip().intersects(&item_geometry)
                 && !is_clipping_item(item)
+                && item_children_transform(item).is_none()
                 // HACK, the geometry of the box shadow does not include the shadow, because when the shadow is the root for repeated elements it would translate the children
                 && ItemRef::downcast_pin::<BoxShadow>(item).is_none()
             {
@@ -114,7 +154,7 @@ pub fn render_component_items(
 
             renderer.borrow_mut().translate(item_origin.x, item_origin.y);
 
-            item.as_ref().render(&mut (*renderer.borrow_mut() as &mut dyn ItemRenderer));
+            render_item(&mut **renderer.borrow_mut(), item);
 
             (ItemVisitorResult::Continue(()), ())
         },
@@ -141,7 +181,6 @@ pub trait ItemRenderer {
     fn draw_clipped_image(&mut self, image: Pin<&ClippedImage>);
     fn draw_text(&mut self, text: Pin<&Text>);
     fn draw_text_input(&mut self, text_input: Pin<&TextInput>);
-    #[cfg(feature = "std")]
     fn draw_path(&mut self, path: Pin<&Path>);
     fn draw_box_shadow(&mut self, box_shadow: Pin<&BoxShadow>);
     /// Clip the further call until restore_state.
@@ -153,6 +192,8 @@ pub trait ItemRenderer {
 
     fn translate(&mut self, x: f32, y: f32);
     fn rotate(&mut self, angle_in_degrees: f32);
+    /// Scale the coordinate system by the given factors, which may be negative to mirror it
+    fn scale(&mut self, x: f32, y: f32);
     /// Apply the opacity (between 0 and 1) for all following items until the next call to restore_state.
     fn apply_opacity(&mut self, opacity: f32);
 
@@ -181,3 +222,267 @@ fn draw_cached_pixmap(
     /// Return the internal renderer
     fn as_any(&mut self) -> &mut dyn core::any::Any;
 }
+
+/// The cache used by the [`PartialRenderer`]. For every item, it holds the rectangle that the item
+/// covered, in window coordinates, when it was last rendered, and tracks the properties that were
+/// read while rendering it.
+pub type PartialRenderingCache = RenderingCache<Rect>;
+
+/// A region of the window, in logical coordinates, that needs to be redrawn
+pub type DirtyRegion = Rect;
+
+/// Returns the rectangle covered by the rendering of the item, in the coordinates of its parent
+fn item_rendering_rect(item: Pin<ItemRef>) -> Rect {
+    let geometry = item.as_ref().geometry();
+    if let Some(shadow) = ItemRef::downcast_pin::<BoxShadow>(item) {
+        // The shadow is drawn outside of the geometry
+        let blur = shadow.blur();
+        euclid::rect(
+            geometry.origin.x + shadow.offset_x() - blur,
+            geometry.origin.y + shadow.offset_y() - blur,
+            geometry.width() + 2. * blur,
+            geometry.height() + 2. * blur,
+        )
+    } else {
+        geometry
+    }
+}
+
+/// A renderer that forwards the rendering to another renderer and keeps track of the items that
+/// changed since the last frame, so that only the parts of the window that changed need to be
+/// redrawn.
+///
+/// Call [`Self::compute_dirty_regions`] for every component to find the [`Self::dirty_region`],
+/// then render only that region with [`Self::render_component_items`].
+pub struct PartialRenderer<'a, T> {
+    cache: &'a RefCell<PartialRenderingCache>,
+    /// The region of the window that needs to be redrawn
+    pub dirty_region: DirtyRegion,
+    /// The renderer to which the drawing is forwarded
+    pub actual_renderer: T,
+    /// The transformation from the coordinates of the current item to the window coordinates
+    transform: crate::graphics::Transform,
+    transform_stack: alloc::vec::Vec<crate::graphics::Transform>,
+}
+
+impl<'a, T: ItemRenderer> PartialRenderer<'a, T> {
+    /// Creates a new PartialRenderer. `initial_dirty_region` is a region that needs to be redrawn
+    /// regardless of the changes in the items, for example the whole window for the first frame.
+    pub fn new(
+        cache: &'a RefCell<PartialRenderingCache>,
+        initial_dirty_region: DirtyRegion,
+        actual_renderer: T,
+    ) -> Self {
+        Self {
+            cache,
+            dirty_region: initial_dirty_region,
+            actual_renderer,
+            transform: Default::default(),
+            transform_stack: Default::default(),
+        }
+    }
+
+    /// Visits the items of the component and adds to the dirty region the current and the
+    /// previous location of the items that moved or whose properties relevant for the rendering
+    /// changed since they were last rendered.
+    pub fn compute_dirty_regions(
+        &mut self,
+        component: &ComponentRc,
+        origin: crate::graphics::Point,
+    ) {
+        let cache = self.cache;
+        crate::item_tree::visit_items(
+            component,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |_, item, _, transform: &crate::graphics::Transform| {
+                let rendering_rect = transform.outer_transformed_rect(&item_rendering_rect(item));
+                let rendering_data = item.cached_rendering_data_offset();
+
+                let is_dirty = {
+                    let mut cache = cache.borrow_mut();
+                    let entry = if rendering_data.cache_generation.get() == cache.generation() {
+                        cache.get_mut(rendering_data.cache_index.get())
+                    } else {
+                        None
+                    };
+                    match entry {
+                        Some(entry) => {
+                            let changed = entry.data != rendering_rect
+                                || entry.dependency_tracker.as_ref().map_or(true, |t| t.is_dirty());
+                            if changed {
+                                self.dirty_region = self.dirty_region.union(&entry.data);
+                                entry.data = rendering_rect;
+                            }
+                            changed
+                        }
+                        None => {
+                            let entry = crate::graphics::CachedGraphicsData::new(|| rendering_rect);
+                            rendering_data.cache_index.set(cache.insert(entry));
+                            rendering_data.cache_generation.set(cache.generation());
+                            true
+                        }
+                    }
+                };
+                if is_dirty {
+                    self.dirty_region = self.dirty_region.union(&rendering_rect);
+                    // Until the item is rendered again, only its geometry is tracked, as the
+                    // item might be clipped out and not be rendered.
+                    track_rendering(cache, item, || {
+                        item.as_ref().geometry();
+                    });
+                }
+
+                let origin = item.as_ref().geometry().origin;
+                let children_transform = transform.pre_translate(origin.to_vector());
+                ItemVisitorResult::Continue(match item_children_transform(item) {
+                    Some(item_transform) => item_transform.then(&children_transform),
+                    None => children_transform,
+                })
+            },
+            crate::graphics::Transform::translation(origin.x, origin.y),
+        );
+    }
+
+    /// Renders the items of the component, like [`render_component_items`], and records the
+    /// properties read while rendering each item.
+    pub fn render_component_items(
+        &mut self,
+        component: &ComponentRc,
+        origin: crate::graphics::Point,
+    ) {
+        let cache = self.cache;
+        render_items_with(component, self, origin, |renderer, item| {
+            let local_rect =
+                item_rendering_rect(item).translate(-item.as_ref().geometry().origin.to_vector());
+            let rendering_rect = renderer.transform.outer_transformed_rect(&local_rect);
+            track_rendering(cache, item, || {
+                item.as_ref().render(&mut (renderer as &mut dyn ItemRenderer))
+            });
+            let rendering_data = item.cached_rendering_data_offset();
+            let mut cache = cache.borrow_mut();
+            if rendering_data.cache_generation.get() == cache.generation() {
+                if let Some(entry) = cache.get_mut(rendering_data.cache_index.get()) {
+                    entry.data = rendering_rect;
+                }
+            }
+        })
+    }
+}
+
+/// Evaluates `f` with the property tracker of the item, creating the cache entry if needed
+fn track_rendering(cache: &RefCell<PartialRenderingCache>, item: Pin<ItemRef>, f: impl FnOnce()) {
+    let rendering_data = item.cached_rendering_data_offset();
+    let tracker = {
+        let mut cache = cache.borrow_mut();
+        if rendering_data.cache_generation.get() != cache.generation()
+            || !cache.contains(rendering_data.cache_index.get())
+        {
+            let entry = crate::graphics::CachedGraphicsData::new(Rect::default);
+            rendering_data.cache_index.set(cache.insert(entry));
+            rendering_data.cache_generation.set(cache.generation());
+        }
+        cache.get_mut(rendering_data.cache_index.get()).unwrap().dependency_tracker.take()
+    };
+    let tracker = tracker.unwrap_or_else(|| Box::pin(Default::default()));
+    tracker.as_ref().evaluate(f);
+    if let Some(entry) = cache.borrow_mut().get_mut(rendering_data.cache_index.get()) {
+        entry.dependency_tracker = Some(tracker);
+    }
+}
+
+impl<'a, T: ItemRenderer> ItemRenderer for PartialRenderer<'a, T> {
+    fn draw_rectangle(&mut self, rect: Pin<&Rectangle>) {
+        self.actual_renderer.draw_rectangle(rect)
+    }
+
+    fn draw_border_rectangle(&mut self, rect: Pin<&BorderRectangle>) {
+        self.actual_renderer.draw_border_rectangle(rect)
+    }
+
+    fn draw_image(&mut self, image: Pin<&ImageItem>) {
+        self.actual_renderer.draw_image(image)
+    }
+
+    fn draw_clipped_image(&mut self, image: Pin<&ClippedImage>) {
+        self.actual_renderer.draw_clipped_image(image)
+    }
+
+    fn draw_text(&mut self, text: Pin<&Text>) {
+        self.actual_renderer.draw_text(text)
+    }
+
+    fn draw_text_input(&mut self, text_input: Pin<&TextInput>) {
+        self.actual_renderer.draw_text_input(text_input)
+    }
+
+    fn draw_path(&mut self, path: Pin<&Path>) {
+        self.actual_renderer.draw_path(path)
+    }
+
+    fn draw_box_shadow(&mut self, box_shadow: Pin<&BoxShadow>) {
+        self.actual_renderer.draw_box_shadow(box_shadow)
+    }
+
+    fn combine_clip(&mut self, rect: Rect, radius: f32, border_width: f32) {
+        self.actual_renderer.combine_clip(rect, radius, border_width)
+    }
+
+    fn get_current_clip(&self) -> Rect {
+        self.actual_renderer.get_current_clip()
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        self.transform = self.transform.pre_translate(euclid::vec2(x, y));
+        self.actual_renderer.translate(x, y)
+    }
+
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        self.transform = self.transform.pre_rotate(euclid::Angle::degrees(angle_in_degrees));
+        self.actual_renderer.rotate(angle_in_degrees)
+    }
+
+    fn scale(&mut self, x: f32, y: f32) {
+        self.transform = self.transform.pre_scale(x, y);
+        self.actual_renderer.scale(x, y)
+    }
+
+    fn apply_opacity(&mut self, opacity: f32) {
+        self.actual_renderer.apply_opacity(opacity)
+    }
+
+    fn save_state(&mut self) {
+        self.transform_stack.push(self.transform);
+        self.actual_renderer.save_state()
+    }
+
+    fn restore_state(&mut self) {
+        if let Some(transform) = self.transform_stack.pop() {
+            self.transform = transform;
+        }
+        self.actual_renderer.restore_state()
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.actual_renderer.scale_factor()
+    }
+
+    fn draw_cached_pixmap(
+        &mut self,
+        item_cache: &CachedRenderingData,
+        update_fn: &dyn Fn(&mut dyn FnMut(u32, u32, &[u8])),
+    ) {
+        self.actual_renderer.draw_cached_pixmap(item_cache, update_fn)
+    }
+
+    fn draw_string(&mut self, string: &str, color: crate::Color) {
+        self.actual_renderer.draw_string(string, color)
+    }
+
+    fn window(&self) -> crate::window::WindowRc {
+        self.actual_renderer.window()
+    }
+
+    fn as_any(&mut self) -> &mut dyn core::any::Any {
+        self.actual_renderer.as_any()
+    }
+}
@@ -18,11 +18,12 @@
 #![allow(non_upper_case_globals)]
 #![allow(missing_docs)] // because documenting each property of items is redundant
 
+use crate::accessibility::AccessibleStringProperty;
 use crate::component::ComponentVTable;
 use crate::graphics::{Brush, Color, Point, Rect};
 use crate::input::{
     FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult, KeyEventType,
-    MouseEvent,
+    MouseEvent, TouchPhase,
 };
 use crate::item_rendering::CachedRenderingData;
 use crate::layout::{LayoutInfo, Orientation};
@@ -35,15 +36,17 @@
 use core::cell::Cell;
 use core::pin::Pin;
 use i_slint_core_macros::*;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 use vtable::*;
 
 mod text;
 pub use text::*;
+mod drag_and_drop;
+pub use drag_and_drop::*;
 mod image;
 pub use self::image::*;
-#[cfg(feature = "std")]
 mod path;
-#[cfg(feature = "std")]
 pub use path::*;
 
 /// Alias for `&mut dyn ItemRenderer`. Required so cbindgen generates the ItemVTable
@@ -183,6 +186,44 @@ pub fn index(&self) -> usize {
     pub fn component(&self) -> vtable::VRc<ComponentVTable> {
         self.component.clone()
     }
+
+    /// Maps a point relative to this item to a point relative to the window
+    pub fn map_to_window(&self, p: Point) -> Point {
+        let mut current = Some(self.clone());
+        let mut result = p;
+        while let Some(item) = current {
+            result += item.borrow().as_ref().geometry().origin.to_vector();
+            current = item.parent_item().upgrade();
+            if let Some(transform) = current
+                .as_ref()
+                .and_then(|parent| crate::item_rendering::item_children_transform(parent.borrow()))
+            {
+                result = transform.transform_point(result);
+            }
+        }
+        result
+    }
+
+    /// Returns the role of the item for the assistive technologies
+    pub fn accessible_role(&self) -> AccessibleRole {
+        let comp_ref_pin = vtable::VRc::borrow_pin(&self.component);
+        comp_ref_pin.as_ref().accessible_role(self.index)
+    }
+
+    /// Returns true if the item is checked, for example a checked check box
+    pub fn accessible_checked(&self) -> bool {
+        let comp_ref_pin = vtable::VRc::borrow_pin(&self.component);
+        comp_ref_pin.as_ref().accessible_checked(self.index)
+    }
+
+    /// Returns the value of one of the `accessible-*` string properties of the item, or an
+    /// empty string if it is not set
+    pub fn accessible_string_property(&self, what: AccessibleStringProperty) -> SharedString {
+        let comp_ref_pin = vtable::VRc::borrow_pin(&self.component);
+        let mut result = SharedString::default();
+        comp_ref_pin.as_ref().accessible_string_property(self.index, what, &mut result);
+        result
+    }
 }
 
 /// A Weak reference to an item that can be constructed from an ItemRc.
@@ -428,6 +469,10 @@ fn input_event_filter_before_children(
         if !self.enabled() {
             return InputEventFilterResult::ForwardAndIgnore;
         }
+        if matches!(event, MouseEvent::Touch { .. }) {
+            // Only the first touch point, which is converted to mouse events, moves the mouse
+            return InputEventFilterResult::ForwardAndInterceptGrab;
+        }
         if let Some(pos) = event.pos() {
             Self::FIELD_OFFSETS.mouse_x.apply_pin(self).set(pos.x);
             Self::FIELD_OFFSETS.mouse_y.apply_pin(self).set(pos.y);
@@ -511,6 +556,13 @@ fn input_event(
                     InputEventResult::EventAccepted
                 }
             }
+            MouseEvent::Touch { .. } => {
+                return if self.grabbed.get() {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
         };
         result
     }
@@ -535,6 +587,224 @@ impl ItemConsts for TouchArea {
     fn slint_get_TouchAreaVTable() -> TouchAreaVTable for TouchArea
 }
 
+/// The state of a touch point of a [`PinchArea`]
+#[repr(C)]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct PinchAreaTouchPoint {
+    pub pressed: bool,
+    pub id: u64,
+    /// The position when the gesture started
+    pub start_pos: Point,
+    pub pos: Point,
+}
+
+/// The implementation of the `PinchArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct PinchArea {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub enabled: Property<bool>,
+    pub active: Property<bool>,
+    pub scale: Property<f32>,
+    pub rotation: Property<f32>,
+    pub translation_x: Property<f32>,
+    pub translation_y: Property<f32>,
+    pub started: Callback<VoidArg>,
+    pub finished: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+    /// The first touch point, which is the one sent as mouse events
+    first_point: Cell<PinchAreaTouchPoint>,
+    second_point: Cell<PinchAreaTouchPoint>,
+}
+
+impl PinchArea {
+    fn start_gesture(self: Pin<&Self>) {
+        let mut first = self.first_point.get();
+        first.start_pos = first.pos;
+        self.first_point.set(first);
+        Self::FIELD_OFFSETS.scale.apply_pin(self).set(1.);
+        Self::FIELD_OFFSETS.rotation.apply_pin(self).set(0.);
+        Self::FIELD_OFFSETS.translation_x.apply_pin(self).set(0.);
+        Self::FIELD_OFFSETS.translation_y.apply_pin(self).set(0.);
+        Self::FIELD_OFFSETS.active.apply_pin(self).set(true);
+        Self::FIELD_OFFSETS.started.apply_pin(self).call(&());
+    }
+
+    fn update_gesture(self: Pin<&Self>) {
+        if !self.active() {
+            return;
+        }
+        let (first, second) = (self.first_point.get(), self.second_point.get());
+        let start_vector = second.start_pos - first.start_pos;
+        let vector = second.pos - first.pos;
+        let start_length = start_vector.square_length().sqrt();
+        if start_length > 0. {
+            Self::FIELD_OFFSETS
+                .scale
+                .apply_pin(self)
+                .set(vector.square_length().sqrt() / start_length);
+        }
+        let mut rotation =
+            (vector.y.atan2(vector.x) - start_vector.y.atan2(start_vector.x)).to_degrees();
+        if rotation > 180. {
+            rotation -= 360.;
+        } else if rotation <= -180. {
+            rotation += 360.;
+        }
+        Self::FIELD_OFFSETS.rotation.apply_pin(self).set(rotation);
+        let translation = (first.pos.to_vector() + second.pos.to_vector()
+            - first.start_pos.to_vector()
+            - second.start_pos.to_vector())
+            / 2.;
+        Self::FIELD_OFFSETS.translation_x.apply_pin(self).set(translation.x);
+        Self::FIELD_OFFSETS.translation_y.apply_pin(self).set(translation.y);
+    }
+
+    fn finish_gesture(self: Pin<&Self>) {
+        if self.active() {
+            Self::FIELD_OFFSETS.active.apply_pin(self).set(false);
+            Self::FIELD_OFFSETS.finished.apply_pin(self).call(&());
+        }
+    }
+}
+
+impl Item for PinchArea {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {
+        Self::FIELD_OFFSETS.scale.apply_pin(self).set(1.);
+    }
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if !self.enabled() {
+            return InputEventFilterResult::ForwardAndIgnore;
+        }
+        // Track the first touch point even if a child grabs it, so that the gesture can be
+        // taken over when a second touch point is pressed
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
+                self.first_point.set(PinchAreaTouchPoint {
+                    pressed: true,
+                    start_pos: pos,
+                    pos,
+                    ..Default::default()
+                });
+                self.second_point.set(Default::default());
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            MouseEvent::MouseMoved { pos } => {
+                let mut first = self.first_point.get();
+                first.pos = pos;
+                self.first_point.set(first);
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            MouseEvent::Touch { phase: TouchPhase::Pressed, .. }
+                if self.first_point.get().pressed && !self.second_point.get().pressed =>
+            {
+                InputEventFilterResult::Intercept
+            }
+            _ => InputEventFilterResult::ForwardAndInterceptGrab,
+        }
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        if !self.enabled() {
+            return InputEventResult::EventIgnored;
+        }
+        match event {
+            MouseEvent::MousePressed { button: PointerEventButton::left, .. } => {
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseMoved { .. } => {
+                if self.first_point.get().pressed {
+                    self.update_gesture();
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
+            MouseEvent::MouseReleased { button: PointerEventButton::left, .. }
+            | MouseEvent::MouseExit => {
+                self.first_point.set(Default::default());
+                self.second_point.set(Default::default());
+                self.finish_gesture();
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::Touch { pos, id, phase } => {
+                if !self.first_point.get().pressed {
+                    return InputEventResult::EventIgnored;
+                }
+                let mut second = self.second_point.get();
+                match phase {
+                    TouchPhase::Pressed if !second.pressed => {
+                        self.second_point.set(PinchAreaTouchPoint {
+                            pressed: true,
+                            id,
+                            start_pos: pos,
+                            pos,
+                        });
+                        self.start_gesture();
+                    }
+                    TouchPhase::Moved if second.pressed && second.id == id => {
+                        second.pos = pos;
+                        self.second_point.set(second);
+                        self.update_gesture();
+                    }
+                    TouchPhase::Released if second.pressed && second.id == id => {
+                        self.second_point.set(Default::default());
+                        self.finish_gesture();
+                    }
+                    _ => {}
+                }
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MousePressed { .. }
+            | MouseEvent::MouseReleased { .. }
+            | MouseEvent::MouseWheel { .. } => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, _backend: &mut ItemRendererRef) {}
+}
+
+impl ItemConsts for PinchArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        PinchArea,
+        CachedRenderingData,
+    > = PinchArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_PinchAreaVTable() -> PinchAreaVTable for PinchArea
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -609,6 +879,9 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, _window: &WindowRc) -> KeyEvent
             KeyEventType::KeyReleased => {
                 Self::FIELD_OFFSETS.key_released.apply_pin(self).call(&(event.clone(),))
             }
+            KeyEventType::UpdateComposition | KeyEventType::CommitComposition => {
+                EventResult::reject
+            }
         };
         match r {
             EventResult::accept => KeyEventResult::EventAccepted,
@@ -789,6 +1062,8 @@ fn slint_get_OpacityVTable() -> OpacityVTable for Opacity
 #[pin]
 /// The implementation of the `Rotate` element
 pub struct Rotate {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
     pub angle: Property<f32>,
     pub origin_x: Property<f32>,
     pub origin_y: Property<f32>,
@@ -801,7 +1076,7 @@ impl Item for Rotate {
     fn init(self: Pin<&Self>, _window: &WindowRc) {}
 
     fn geometry(self: Pin<&Self>) -> Rect {
-        euclid::rect(0., 0., 0., 0.)
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
     }
 
     fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
@@ -850,6 +1125,75 @@ impl ItemConsts for Rotate {
     fn slint_get_RotateVTable() -> RotateVTable for Rotate
 }
 
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of the `Scale` element
+pub struct Scale {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub scale_x: Property<f32>,
+    pub scale_y: Property<f32>,
+    pub origin_x: Property<f32>,
+    pub origin_y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for Scale {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, backend: &mut ItemRendererRef) {
+        (*backend).translate(self.origin_x(), self.origin_y());
+        (*backend).scale(self.scale_x(), self.scale_y());
+        (*backend).translate(-self.origin_x(), -self.origin_y());
+    }
+}
+
+impl ItemConsts for Scale {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Scale,
+        CachedRenderingData,
+    > = Scale::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_ScaleVTable() -> ScaleVTable for Scale
+}
+
 /// The implementation of the `Flickable` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
@@ -861,6 +1205,11 @@ pub struct Flickable {
     pub height: Property<f32>,
     pub viewport: Rectangle,
     pub interactive: Property<bool>,
+    pub horizontal_interactive: Property<bool>,
+    pub vertical_interactive: Property<bool>,
+    pub deceleration: Property<f32>,
+    pub overshoot: Property<bool>,
+    pub flicking: Property<bool>,
     data: FlickableDataBox,
 
     /// FIXME: remove this
@@ -884,6 +1233,10 @@ fn input_event_filter_before_children(
         _window: &WindowRc,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
+        if matches!(event, MouseEvent::Touch { .. }) {
+            // Additional touch points may be outside of the Flickable, let the grabber handle them
+            return InputEventFilterResult::ForwardEvent;
+        }
         if let Some(pos) = event.pos() {
             if pos.x < 0. || pos.y < 0. || pos.x > self.width() || pos.y > self.height() {
                 return InputEventFilterResult::Intercept;
@@ -927,6 +1280,14 @@ fn render(self: Pin<&Self>, backend: &mut ItemRendererRef) {
     }
 }
 
+impl Flickable {
+    /// Scroll the viewport so that the point `pos` of the viewport is at the top left corner of
+    /// the Flickable, within the bounds of the viewport.
+    pub fn scroll_to(self: Pin<&Self>, pos: Point, animated: bool) {
+        crate::flickable::scroll_to(self, pos, animated)
+    }
+}
+
 impl ItemConsts for Flickable {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
         Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
@@ -978,6 +1339,18 @@ fn deref(&self) -> &Self::Target {
     core::ptr::drop_in_place(data);
 }
 
+/// # Safety
+/// This must be called with a pointer to a Flickable that is pinned within its component
+#[no_mangle]
+pub unsafe extern "C" fn slint_flickable_scroll_to(
+    flick: &Flickable,
+    x: f32,
+    y: f32,
+    animated: bool,
+) {
+    Pin::new_unchecked(flick).scroll_to(Point::new(x, y), animated)
+}
+
 /// The implementation of the `PropertyAnimation` element
 #[repr(C)]
 #[derive(FieldOffsets, SlintElement, Clone, Debug)]
@@ -1182,11 +1555,18 @@ fn slint_get_ImageItemVTable() -> ImageItemVTable for ImageItem
     fn slint_get_ClippedImageVTable() -> ClippedImageVTable for ClippedImage
 }
 
-#[cfg(feature = "std")]
 declare_item_vtable! {
     fn slint_get_PathVTable() -> PathVTable for Path
 }
 
+declare_item_vtable! {
+    fn slint_get_DragAreaVTable() -> DragAreaVTable for DragArea
+}
+
+declare_item_vtable! {
+    fn slint_get_DropAreaVTable() -> DropAreaVTable for DropArea
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -1260,6 +1640,31 @@ fn default() -> Self {
     }
 }
 
+/// The role of an element for the assistive technologies, set with the `accessible-role` property
+#[derive(Copy, Clone, Debug, PartialEq, Eq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum AccessibleRole {
+    none,
+    button,
+    checkbox,
+    combobox,
+    list,
+    list_item,
+    progress_indicator,
+    slider,
+    spinbox,
+    tab,
+    text,
+    text_input,
+}
+
+impl Default for AccessibleRole {
+    fn default() -> Self {
+        Self::none
+    }
+}
+
 /// Represents a key event sent by the windowing system.
 #[derive(Debug, Clone, PartialEq, Default)]
 #[repr(C)]
@@ -0,0 +1,264 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the builtin drag and drop related items.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{Item, ItemConsts, ItemRc, ItemRendererRef, PointerEventButton, VoidArg};
+use crate::graphics::{Point, Rect};
+use crate::input::{
+    DropEvent, FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult,
+    MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowRc;
+use crate::{Callback, Property, SharedString};
+use const_field_offset::FieldOffsets;
+use core::cell::Cell;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+type DropEventArg = (DropEvent,);
+
+/// The distance in logical pixels the mouse needs to move while pressed before a drag starts
+const DRAG_THRESHOLD: f32 = 8.;
+
+/// The implementation of the `DragArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct DragArea {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub enabled: Property<bool>,
+    pub mime_type: Property<SharedString>,
+    pub data: Property<SharedString>,
+    /// FIXME: We should annotate this as an "output" property.
+    pub dragging: Property<bool>,
+    pub drag_started: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+    pressed: Cell<bool>,
+    pressed_position: Cell<Point>,
+}
+
+impl DragArea {
+    fn drop_event(self: Pin<&Self>, pos: Point, self_rc: &ItemRc) -> DropEvent {
+        DropEvent {
+            mime_type: self.mime_type(),
+            data: self.data(),
+            position: self_rc.map_to_window(pos),
+        }
+    }
+
+    fn reset(self: Pin<&Self>) {
+        self.pressed.set(false);
+        Self::FIELD_OFFSETS.dragging.apply_pin(self).set(false);
+    }
+}
+
+impl Item for DragArea {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if !self.enabled() {
+            self.reset();
+            return InputEventFilterResult::ForwardAndIgnore;
+        }
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
+                self.pressed.set(true);
+                self.pressed_position.set(pos);
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            MouseEvent::MouseMoved { pos } if self.pressed.get() => {
+                // Take the mouse away from the children once the mouse moved far enough
+                if (pos - self.pressed_position.get()).square_length()
+                    > DRAG_THRESHOLD * DRAG_THRESHOLD
+                {
+                    InputEventFilterResult::Intercept
+                } else {
+                    InputEventFilterResult::ForwardAndInterceptGrab
+                }
+            }
+            MouseEvent::MouseReleased { button: PointerEventButton::left, .. } => {
+                self.pressed.set(false);
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            _ => InputEventFilterResult::ForwardAndInterceptGrab,
+        }
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window: &WindowRc,
+        self_rc: &ItemRc,
+    ) -> InputEventResult {
+        if !self.enabled() {
+            return InputEventResult::EventIgnored;
+        }
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
+                self.pressed.set(true);
+                self.pressed_position.set(pos);
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseMoved { pos } => {
+                if !self.pressed.get() {
+                    return InputEventResult::EventIgnored;
+                }
+                if !self.dragging()
+                    && (pos - self.pressed_position.get()).square_length()
+                        > DRAG_THRESHOLD * DRAG_THRESHOLD
+                {
+                    Self::FIELD_OFFSETS.dragging.apply_pin(self).set(true);
+                    Self::FIELD_OFFSETS.drag_started.apply_pin(self).call(&());
+                }
+                if self.dragging() {
+                    window.clone().process_drag_move(self.drop_event(pos, self_rc));
+                }
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseReleased { pos, button: PointerEventButton::left } => {
+                if self.dragging() {
+                    window.clone().process_drop(self.drop_event(pos, self_rc));
+                }
+                self.reset();
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::MouseExit => {
+                if self.dragging() {
+                    window.cancel_drag();
+                }
+                self.reset();
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::MousePressed { .. }
+            | MouseEvent::MouseReleased { .. }
+            | MouseEvent::MouseWheel { .. }
+            | MouseEvent::Touch { .. } => {
+                if self.pressed.get() {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
+        }
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, _backend: &mut ItemRendererRef) {}
+}
+
+impl ItemConsts for DragArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DragArea,
+        CachedRenderingData,
+    > = DragArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// The implementation of the `DropArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct DropArea {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub enabled: Property<bool>,
+    /// FIXME: We should annotate this as an "output" property.
+    pub contains_drag: Property<bool>,
+    pub can_drop: Callback<DropEventArg, bool>,
+    pub dropped: Callback<DropEventArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl DropArea {
+    /// Returns true if the `can-drop` callback accepts the event, which is relative to this item
+    pub fn can_drop(self: Pin<&Self>, event: &DropEvent) -> bool {
+        self.enabled() && Self::FIELD_OFFSETS.can_drop.apply_pin(self).call(&(event.clone(),))
+    }
+
+    /// Invokes the `dropped` callback with the event, which is relative to this item
+    pub fn drop(self: Pin<&Self>, event: DropEvent) {
+        Self::FIELD_OFFSETS.dropped.apply_pin(self).call(&(event,))
+    }
+
+    pub(crate) fn set_contains_drag(self: Pin<&Self>, contains_drag: bool) {
+        Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(contains_drag)
+    }
+}
+
+impl Item for DropArea {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, _backend: &mut ItemRendererRef) {}
+}
+
+impl ItemConsts for DropArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DropArea,
+        CachedRenderingData,
+    > = DropArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
@@ -9,7 +9,9 @@
 */
 
 use super::{Item, ItemConsts, ItemRc, ItemRendererRef};
-use crate::graphics::{Brush, PathData, PathDataIterator, Rect};
+#[cfg(feature = "std")]
+use crate::graphics::PathDataIterator;
+use crate::graphics::{Brush, FlattenedSubPath, PathData, Rect};
 use crate::input::{
     FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult, MouseEvent,
 };
@@ -20,6 +22,7 @@
 use crate::rtti::*;
 use crate::window::WindowRc;
 use crate::Property;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
 use core::pin::Pin;
 use i_slint_core_macros::*;
@@ -119,9 +122,40 @@ impl Path {
     /// Returns an iterator of the events of the path and an offset, so that the
     /// shape fits into the width/height of the path while respecting the stroke
     /// width.
+    #[cfg(feature = "std")]
     pub fn fitted_path_events(
         self: Pin<&Self>,
     ) -> (euclid::default::Vector2D<f32>, PathDataIterator) {
+        let (offset, bounds, maybe_viewbox) = self.fit_parameters();
+        let mut elements_iter = self.elements().iter();
+        elements_iter.fit(bounds.width, bounds.height, maybe_viewbox);
+        (offset, elements_iter)
+    }
+
+    /// Returns the path approximated with lines, and an offset, with the same fitting
+    /// as [`Self::fitted_path_events`]. This doesn't need lyon, so it's also available
+    /// without the `std` feature.
+    pub fn fitted_flattened_path(
+        self: Pin<&Self>,
+    ) -> (euclid::default::Vector2D<f32>, Vec<FlattenedSubPath>) {
+        let (offset, bounds, maybe_viewbox) = self.fit_parameters();
+        let mut sub_paths = self.elements().flatten();
+        if bounds.width > 0. || bounds.height > 0. {
+            let viewbox = maybe_viewbox.unwrap_or_else(|| {
+                Rect::from_points(sub_paths.iter().flat_map(|sub_path| sub_path.points.iter()))
+            });
+            let transform = crate::graphics::fit_rectangle(&viewbox, &Rect::from_size(bounds));
+            for point in sub_paths.iter_mut().flat_map(|sub_path| sub_path.points.iter_mut()) {
+                *point = transform.transform_point(*point);
+            }
+        }
+        (offset, sub_paths)
+    }
+
+    /// Returns the offset and the size into which the path is fitted, and the viewbox, if any
+    fn fit_parameters(
+        self: Pin<&Self>,
+    ) -> (euclid::default::Vector2D<f32>, euclid::default::Size2D<f32>, Option<Rect>) {
         let stroke_width = self.stroke_width();
         let bounds_width = (self.width() - stroke_width).max(0.);
         let bounds_height = (self.height() - stroke_width).max(0.);
@@ -130,16 +164,13 @@ pub fn fitted_path_events(
         let viewbox_width = self.viewbox_width();
         let viewbox_height = self.viewbox_height();
 
-        let mut elements_iter = self.elements().iter();
-
         let maybe_viewbox = if viewbox_width > 0. && viewbox_height > 0. {
             Some(euclid::rect(self.viewbox_x(), self.viewbox_y(), viewbox_width, viewbox_height))
         } else {
             None
         };
 
-        elements_iter.fit(bounds_width, bounds_height, maybe_viewbox);
-        (offset, elements_iter)
+        (offset, euclid::size2(bounds_width, bounds_height), maybe_viewbox)
     }
 }
 
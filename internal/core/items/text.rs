@@ -9,25 +9,33 @@
 */
 
 use super::{Item, ItemConsts, ItemRc, PointArg, PointerEventButton, VoidArg};
-use crate::graphics::{Brush, Color, FontRequest, Rect};
+use crate::graphics::{Brush, Color, FontRequest, Point, Rect};
 use crate::input::{
     key_codes, FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult,
     KeyEventType, KeyboardModifiers, MouseEvent,
 };
 use crate::item_rendering::{CachedRenderingData, ItemRenderer};
 use crate::layout::{LayoutInfo, Orientation};
+use crate::properties::PropertyTracker;
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
 use crate::window::WindowRc;
-use crate::{Callback, Property, SharedString};
+use crate::{Callback, Property, SharedString, SharedVector};
 use alloc::string::String;
 use const_field_offset::FieldOffsets;
+use core::cell::Cell;
 use core::pin::Pin;
 use i_slint_core_macros::*;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[cfg(not(feature = "std"))]
 use num_traits::float::Float;
 
+/// The maximum time between two mouse presses for them to form a double or triple click
+const DOUBLE_CLICK_INTERVAL_MS: u64 = 500;
+/// The maximum distance in logical pixels between two mouse presses of a double or triple click
+const DOUBLE_CLICK_DISTANCE: f32 = 5.;
+
 #[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -249,7 +257,21 @@ pub struct TextInput {
     pub edited: Callback<VoidArg>,
     pub pressed: core::cell::Cell<bool>,
     pub single_line: Property<bool>,
+    /// The text being composed by the input method, shown at the cursor position until committed
+    pub preedit_text: Property<SharedString>,
     pub cached_rendering_data: CachedRenderingData,
+    /// The edits that can be undone, the most recent one last
+    undo_items: Cell<SharedVector<UndoItem>>,
+    /// The edits that were undone and can be redone, the most recently undone one last
+    redo_items: Cell<SharedVector<UndoItem>>,
+    /// Tracks the text after the last edit in the history. When it is dirty, the text was set
+    /// from outside of the TextInput and the history no longer applies.
+    text_tracker: PropertyTracker,
+    /// The time, in milliseconds, and the position of the last mouse press
+    last_click_time: Cell<u64>,
+    last_click_position: Cell<Point>,
+    /// 1 for a single click, 2 for a double click and 3 for a triple click
+    click_count: Cell<u32>,
 }
 
 impl Item for TextInput {
@@ -323,10 +345,19 @@ fn input_event(
         }
         match event {
             MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
-                let clicked_offset = window.text_input_byte_offset_for_position(self, pos) as i32;
+                let clicked_offset = window.text_input_byte_offset_for_position(self, pos);
                 self.as_ref().pressed.set(true);
-                self.as_ref().anchor_position.set(clicked_offset);
-                self.set_cursor_position(clicked_offset, window);
+                match self.register_click(pos) {
+                    2 => self.select_word(clicked_offset, window),
+                    3 => self.select_line(clicked_offset, window),
+                    _ => {
+                        // Shift+click extends the selection from the anchor
+                        if !window.current_keyboard_modifiers().shift {
+                            self.as_ref().anchor_position.set(clicked_offset as i32);
+                        }
+                        self.set_cursor_position(clicked_offset as i32, window);
+                    }
+                }
                 if !self.has_focus() {
                     window.clone().set_focus_item(self_rc);
                 }
@@ -334,7 +365,8 @@ fn input_event(
             MouseEvent::MouseReleased { button: PointerEventButton::left, .. }
             | MouseEvent::MouseExit => self.as_ref().pressed.set(false),
             MouseEvent::MouseMoved { pos } => {
-                if self.as_ref().pressed.get() {
+                // Dragging after a double or triple click keeps the selected word or line
+                if self.as_ref().pressed.get() && self.click_count.get() <= 1 {
                     let clicked_offset =
                         window.text_input_byte_offset_for_position(self, pos) as i32;
                     self.set_cursor_position(clicked_offset, window);
@@ -354,6 +386,15 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
             KeyEventType::KeyPressed => {
                 if let Some(keycode) = event.text.chars().next() {
                     if let Ok(text_cursor_movement) = TextCursorDirection::try_from(keycode) {
+                        let text_cursor_movement = match text_cursor_movement {
+                            TextCursorDirection::Forward if event.modifiers.control => {
+                                TextCursorDirection::ForwardByWord
+                            }
+                            TextCursorDirection::Backward if event.modifiers.control => {
+                                TextCursorDirection::BackwardByWord
+                            }
+                            direction => direction,
+                        };
                         TextInput::move_cursor(
                             self,
                             text_cursor_movement,
@@ -396,11 +437,22 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
                         self.copy();
                         self.delete_selection(window);
                         return KeyEventResult::EventAccepted;
+                    } else if event.text == "z" || event.text == "Z" {
+                        if event.modifiers.shift {
+                            self.redo(window);
+                        } else {
+                            self.undo(window);
+                        }
+                        return KeyEventResult::EventAccepted;
+                    } else if event.text == "y" {
+                        self.redo(window);
+                        return KeyEventResult::EventAccepted;
                     }
                     return KeyEventResult::EventIgnored;
                 }
                 self.delete_selection(window);
 
+                let history = self.undo_history();
                 let mut text: String = self.text().into();
 
                 // FIXME: respect grapheme boundaries
@@ -408,6 +460,10 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
                 text.insert_str(insert_pos, &event.text);
 
                 self.as_ref().text.set(text.into());
+                self.add_undo_item(
+                    history,
+                    UndoItem::new(UndoItemKind::TextInsert, insert_pos, event.text.clone()),
+                );
                 let new_cursor_pos = (insert_pos + event.text.len()) as i32;
                 self.as_ref().anchor_position.set(new_cursor_pos);
                 self.set_cursor_position(new_cursor_pos, window);
@@ -420,6 +476,23 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
 
                 KeyEventResult::EventAccepted
             }
+            KeyEventType::UpdateComposition => {
+                if !event.text.is_empty() {
+                    // The composition replaces the selection, like typed text would
+                    self.delete_selection(window);
+                }
+                self.preedit_text.set(event.text.clone());
+                self.as_ref().show_cursor(window);
+                KeyEventResult::EventAccepted
+            }
+            KeyEventType::CommitComposition => {
+                self.preedit_text.set(Default::default());
+                if !event.text.is_empty() {
+                    self.insert(&event.text, window);
+                }
+                self.as_ref().show_cursor(window);
+                KeyEventResult::EventAccepted
+            }
             _ => KeyEventResult::EventIgnored,
         }
     }
@@ -430,7 +503,12 @@ fn focus_event(self: Pin<&Self>, event: &FocusEvent, window: &WindowRc) {
                 self.has_focus.set(true);
                 self.show_cursor(window);
             }
-            FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
+            FocusEvent::FocusOut => {
+                self.has_focus.set(false);
+                self.preedit_text.set(Default::default());
+                self.hide_cursor()
+            }
+            FocusEvent::WindowLostFocus => {
                 self.has_focus.set(false);
                 self.hide_cursor()
             }
@@ -449,9 +527,94 @@ impl ItemConsts for TextInput {
     > = TextInput::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(C)]
+pub enum UndoItemKind {
+    TextInsert,
+    TextRemove,
+}
+
+impl Default for UndoItemKind {
+    fn default() -> Self {
+        Self::TextInsert
+    }
+}
+
+/// An edit of the text of a [`TextInput`] in its undo history
+#[derive(Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct UndoItem {
+    /// The byte offset where the text was inserted or removed
+    pos: usize,
+    text: SharedString,
+    /// The cursor and anchor positions to restore when the edit is undone
+    cursor: usize,
+    anchor: usize,
+    kind: UndoItemKind,
+}
+
+impl UndoItem {
+    fn new(kind: UndoItemKind, pos: usize, text: SharedString) -> Self {
+        Self { pos, text, cursor: pos, anchor: pos, kind }
+    }
+
+    /// Merges `next`, the edit made right after this one, into this one if both are the typing
+    /// or the deletion of single characters of the same word. Returns false if `next` must be
+    /// recorded separately.
+    fn merge(&mut self, next: &UndoItem) -> bool {
+        if self.kind != next.kind || next.text.chars().count() != 1 {
+            return false;
+        }
+        // A new word starts after spaces
+        let next_is_space = next.text.starts_with(char::is_whitespace);
+        match self.kind {
+            UndoItemKind::TextInsert => {
+                if next.pos != self.pos + self.text.len()
+                    || (self.text.ends_with(char::is_whitespace) && !next_is_space)
+                {
+                    return false;
+                }
+                self.text = [self.text.as_str(), next.text.as_str()].concat().into();
+            }
+            UndoItemKind::TextRemove => {
+                if next.pos + next.text.len() == self.pos {
+                    // Backspace
+                    if self.text.starts_with(char::is_whitespace) && !next_is_space {
+                        return false;
+                    }
+                    self.text = [next.text.as_str(), self.text.as_str()].concat().into();
+                    self.pos = next.pos;
+                } else if next.pos == self.pos {
+                    // Delete
+                    if self.text.ends_with(char::is_whitespace) && !next_is_space {
+                        return false;
+                    }
+                    self.text = [self.text.as_str(), next.text.as_str()].concat().into();
+                } else {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// The text of a [`TextInput`] as it is shown on screen: the text with the pre-edit text of the
+/// input method inserted at the cursor position. All the positions are byte offsets in `text`.
+pub struct TextInputVisualRepresentation {
+    pub text: String,
+    /// The range of the pre-edit text, which should be rendered underlined
+    pub preedit_range: core::ops::Range<usize>,
+    pub selection_range: core::ops::Range<usize>,
+    /// The position of the cursor, or None if the cursor should not be drawn
+    pub cursor_position: Option<usize>,
+}
+
 enum TextCursorDirection {
     Forward,
     Backward,
+    ForwardByWord,     // to the start of the next word
+    BackwardByWord,    // to the start of the previous word
     PreviousCharacter, // breaks grapheme boundaries, so only used by delete-previous-char
     StartOfLine,
     EndOfLine,
@@ -520,6 +683,17 @@ fn move_cursor(
             TextCursorDirection::Backward => {
                 grapheme_cursor.prev_boundary(&text, 0).ok().flatten().unwrap_or(0)
             }
+            TextCursorDirection::ForwardByWord => text
+                .unicode_word_indices()
+                .map(|(offset, _)| offset)
+                .find(|offset| *offset > last_cursor_pos)
+                .unwrap_or_else(|| text.len()),
+            TextCursorDirection::BackwardByWord => text
+                .unicode_word_indices()
+                .map(|(offset, _)| offset)
+                .take_while(|offset| *offset < last_cursor_pos)
+                .last()
+                .unwrap_or(0),
             TextCursorDirection::PreviousCharacter => {
                 let mut i = last_cursor_pos;
                 loop {
@@ -551,6 +725,56 @@ fn move_cursor(
         new_cursor_pos != last_cursor_pos
     }
 
+    /// Records a mouse press at `pos` and returns whether it is a single (1), double (2) or
+    /// triple (3) click.
+    fn register_click(self: Pin<&Self>, pos: Point) -> u32 {
+        let now = crate::animations::current_tick().0;
+        let is_repeated_click = now.saturating_sub(self.last_click_time.get())
+            <= DOUBLE_CLICK_INTERVAL_MS
+            && (pos - self.last_click_position.get()).square_length()
+                <= DOUBLE_CLICK_DISTANCE * DOUBLE_CLICK_DISTANCE;
+        let click_count = if is_repeated_click { self.click_count.get() % 3 + 1 } else { 1 };
+        self.last_click_time.set(now);
+        self.last_click_position.set(pos);
+        self.click_count.set(click_count);
+        click_count
+    }
+
+    /// Selects the word at the byte offset, or the whitespace or punctuation between words.
+    fn select_word(self: Pin<&Self>, offset: usize, window: &WindowRc) {
+        let text = self.text();
+        // A position at the end of the text selects the last word
+        let offset = offset.min(text.len().saturating_sub(1));
+        let (start, end) = text
+            .split_word_bound_indices()
+            .map(|(start, word)| (start, start + word.len()))
+            .find(|(_, end)| *end > offset)
+            .unwrap_or_default();
+        self.select_range(start, end, window);
+    }
+
+    /// Selects the line at the byte offset, up to the line breaks, or the whole text of a
+    /// single line TextInput.
+    fn select_line(self: Pin<&Self>, offset: usize, window: &WindowRc) {
+        let text = self.text();
+        let offset = offset.min(text.len());
+        let (start, end) = if self.single_line() || !text.is_char_boundary(offset) {
+            (0, text.len())
+        } else {
+            (
+                text[..offset].rfind('\n').map_or(0, |i| i + 1),
+                text[offset..].find('\n').map_or(text.len(), |i| offset + i),
+            )
+        };
+        self.select_range(start, end, window);
+    }
+
+    fn select_range(self: Pin<&Self>, anchor: usize, cursor: usize, window: &WindowRc) {
+        self.as_ref().anchor_position.set(anchor as i32);
+        self.set_cursor_position(cursor as i32, window);
+        self.as_ref().show_cursor(window);
+    }
+
     fn set_cursor_position(self: Pin<&Self>, new_position: i32, window: &WindowRc) {
         self.cursor_position.set(new_position);
         if new_position >= 0 {
@@ -560,10 +784,13 @@ fn set_cursor_position(self: Pin<&Self>, new_position: i32, window: &WindowRc) {
     }
 
     fn delete_char(self: Pin<&Self>, window: &WindowRc) {
-        if !self.has_selection() {
-            self.move_cursor(TextCursorDirection::Forward, AnchorMode::KeepAnchor, window);
+        if self.has_selection() {
+            self.delete_selection(window);
+            return;
         }
-        self.delete_selection(window);
+        let cursor = self.selection_anchor_and_cursor().1;
+        self.move_cursor(TextCursorDirection::Forward, AnchorMode::KeepAnchor, window);
+        self.remove_selection(Some(cursor), window);
     }
 
     fn delete_previous(self: Pin<&Self>, window: &WindowRc) {
@@ -571,13 +798,21 @@ fn delete_previous(self: Pin<&Self>, window: &WindowRc) {
             self.delete_selection(window);
             return;
         }
+        let cursor = self.selection_anchor_and_cursor().1;
         if self.move_cursor(TextCursorDirection::PreviousCharacter, AnchorMode::MoveAnchor, window)
         {
-            self.delete_char(window);
+            self.move_cursor(TextCursorDirection::Forward, AnchorMode::KeepAnchor, window);
+            self.remove_selection(Some(cursor), window);
         }
     }
 
     fn delete_selection(self: Pin<&Self>, window: &WindowRc) {
+        self.remove_selection(None, window)
+    }
+
+    /// Removes the selected text. Undoing the removal puts the cursor at `undo_cursor`, or selects
+    /// the text again if it is None.
+    fn remove_selection(self: Pin<&Self>, undo_cursor: Option<usize>, window: &WindowRc) {
         let text: String = self.text().into();
         if text.is_empty() {
             return;
@@ -588,13 +823,117 @@ fn delete_selection(self: Pin<&Self>, window: &WindowRc) {
             return;
         }
 
+        let history = self.undo_history();
+        let mut undo_item =
+            UndoItem::new(UndoItemKind::TextRemove, anchor, text[anchor..cursor].into());
+        if let Some(undo_cursor) = undo_cursor {
+            undo_item.cursor = undo_cursor;
+            undo_item.anchor = undo_cursor;
+        } else {
+            let max_pos = text.len() as i32;
+            undo_item.cursor = self.cursor_position().max(0).min(max_pos) as usize;
+            undo_item.anchor = self.anchor_position().max(0).min(max_pos) as usize;
+        }
+
         let text = [text.split_at(anchor).0, text.split_at(cursor).1].concat();
         self.text.set(text.into());
+        self.add_undo_item(history, undo_item);
         self.anchor_position.set(anchor as i32);
         self.set_cursor_position(anchor as i32, window);
         Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
+    /// Returns the undo and redo items, or empty histories if the text was changed from outside
+    /// of the TextInput since the last edit, for example by setting the `text` property.
+    fn undo_history(self: Pin<&Self>) -> (SharedVector<UndoItem>, SharedVector<UndoItem>) {
+        let undo_items = self.undo_items.take();
+        let redo_items = self.redo_items.take();
+        if self.text_tracker.is_dirty() {
+            return Default::default();
+        }
+        (undo_items, redo_items)
+    }
+
+    /// Stores the history after an edit, once the text was changed.
+    fn set_undo_history(
+        self: Pin<&Self>,
+        undo_items: SharedVector<UndoItem>,
+        redo_items: SharedVector<UndoItem>,
+    ) {
+        self.undo_items.set(undo_items);
+        self.redo_items.set(redo_items);
+        Self::FIELD_OFFSETS.text_tracker.apply_pin(self).evaluate_as_dependency_root(|| {
+            self.text();
+        });
+    }
+
+    fn add_undo_item(
+        self: Pin<&Self>,
+        (mut undo_items, redo_items): (SharedVector<UndoItem>, SharedVector<UndoItem>),
+        item: UndoItem,
+    ) {
+        // Typing right after an undo is not merged with the edit before the undone one
+        let merged = redo_items.is_empty()
+            && undo_items.make_mut_slice().last_mut().map_or(false, |last| last.merge(&item));
+        if !merged {
+            undo_items.push(item);
+        }
+        // A new edit discards the edits that were undone
+        self.set_undo_history(undo_items, Default::default());
+    }
+
+    /// Reverts the last edit of the text made by the user.
+    pub fn undo(self: Pin<&Self>, window: &WindowRc) {
+        let (mut undo_items, mut redo_items) = self.undo_history();
+        let item = match undo_items.pop() {
+            Some(item) => item,
+            None => return self.set_undo_history(undo_items, redo_items),
+        };
+
+        let mut text: String = self.text().into();
+        match item.kind {
+            UndoItemKind::TextInsert => {
+                text.replace_range(item.pos..item.pos + item.text.len(), "")
+            }
+            UndoItemKind::TextRemove => text.insert_str(item.pos, &item.text),
+        }
+        self.text.set(text.into());
+        self.anchor_position.set(item.anchor as i32);
+        self.set_cursor_position(item.cursor as i32, window);
+        redo_items.push(item);
+        self.set_undo_history(undo_items, redo_items);
+        self.show_cursor(window);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
+    /// Applies again the last edit that was reverted by [`Self::undo`].
+    pub fn redo(self: Pin<&Self>, window: &WindowRc) {
+        let (mut undo_items, mut redo_items) = self.undo_history();
+        let item = match redo_items.pop() {
+            Some(item) => item,
+            None => return self.set_undo_history(undo_items, redo_items),
+        };
+
+        let mut text: String = self.text().into();
+        let cursor = match item.kind {
+            UndoItemKind::TextInsert => {
+                text.insert_str(item.pos, &item.text);
+                item.pos + item.text.len()
+            }
+            UndoItemKind::TextRemove => {
+                text.replace_range(item.pos..item.pos + item.text.len(), "");
+                item.pos
+            }
+        };
+        self.text.set(text.into());
+        self.anchor_position.set(cursor as i32);
+        self.set_cursor_position(cursor as i32, window);
+        undo_items.push(item);
+        self.set_undo_history(undo_items, redo_items);
+        self.show_cursor(window);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
     // Avoid accessing self.cursor_position()/self.anchor_position() directly, always
     // use this bounds-checking function.
     pub fn selection_anchor_and_cursor(self: Pin<&Self>) -> (usize, usize) {
@@ -622,20 +961,65 @@ fn selected_text(self: Pin<&Self>) -> String {
 
     fn insert(self: Pin<&Self>, text_to_insert: &str, window: &WindowRc) {
         self.delete_selection(window);
+        let history = self.undo_history();
         let mut text: String = self.text().into();
         let cursor_pos = self.selection_anchor_and_cursor().1;
-        if text_to_insert.contains('\n') && self.single_line() {
-            text.insert_str(cursor_pos, &text_to_insert.replace('\n', " "));
+        let text_to_insert: SharedString = if text_to_insert.contains('\n') && self.single_line() {
+            text_to_insert.replace('\n', " ").into()
         } else {
-            text.insert_str(cursor_pos, text_to_insert);
-        }
-        let cursor_pos = cursor_pos + text_to_insert.len();
+            text_to_insert.into()
+        };
+        text.insert_str(cursor_pos, &text_to_insert);
         self.text.set(text.into());
+        self.add_undo_item(
+            history,
+            UndoItem::new(UndoItemKind::TextInsert, cursor_pos, text_to_insert.clone()),
+        );
+        let cursor_pos = cursor_pos + text_to_insert.len();
         self.anchor_position.set(cursor_pos as i32);
         self.set_cursor_position(cursor_pos as i32, window);
         Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
+    /// Returns the text to render, with the pre-edit text inserted at the cursor position, and the
+    /// positions of the selection and of the cursor adjusted accordingly.
+    pub fn visual_representation(self: Pin<&Self>) -> TextInputVisualRepresentation {
+        let mut text: String = self.text().into();
+        let preedit_text = self.preedit_text();
+        let (anchor, cursor) = self.selection_anchor_and_cursor();
+        let cursor_position = (self.cursor_position() >= 0)
+            .then(|| (self.cursor_position() as usize).min(text.len()));
+
+        let mut representation = TextInputVisualRepresentation {
+            text: Default::default(),
+            preedit_range: 0..0,
+            selection_range: anchor..cursor,
+            cursor_position: cursor_position.filter(|_| self.cursor_visible() && self.enabled()),
+        };
+
+        if let (Some(cursor_position), false) = (cursor_position, preedit_text.is_empty()) {
+            text.insert_str(cursor_position, &preedit_text);
+            let preedit_end = cursor_position + preedit_text.len();
+            representation.preedit_range = cursor_position..preedit_end;
+            // The composition replaces the selection, so don't show it while composing
+            representation.selection_range = preedit_end..preedit_end;
+            // The cursor is shown at the end of the text being composed
+            representation.cursor_position = representation.cursor_position.map(|_| preedit_end);
+        }
+
+        representation.text = text;
+        representation
+    }
+
+    /// Returns the rectangle of the text cursor, relative to the text input. Used to tell the
+    /// platform where to show the candidate window of the input method.
+    pub fn cursor_rect(self: Pin<&Self>, window: &WindowRc) -> Rect {
+        let cursor_position = (self.cursor_position().max(0) as usize).min(self.text().len());
+        let origin = window.text_input_position_for_byte_offset(self, cursor_position);
+        let height = window.text_size(self.unresolved_font_request(), "*", None).height;
+        Rect::new(origin, crate::graphics::Size::new(self.text_cursor_width(), height))
+    }
+
     fn select_all(self: Pin<&Self>, window: &WindowRc) {
         self.move_cursor(TextCursorDirection::StartOfText, AnchorMode::MoveAnchor, window);
         self.move_cursor(TextCursorDirection::EndOfText, AnchorMode::KeepAnchor, window);
@@ -684,3 +1068,29 @@ pub fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
         }
     }
 }
+
+/// # Safety
+/// This must be called with a pointer to a TextInput that is pinned within its component, and
+/// a valid window handle
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_textinput_undo(
+    text_input: &TextInput,
+    window: *const crate::window::ffi::WindowRcOpaque,
+) {
+    let window = &*(window as *const WindowRc);
+    Pin::new_unchecked(text_input).undo(window)
+}
+
+/// # Safety
+/// This must be called with a pointer to a TextInput that is pinned within its component, and
+/// a valid window handle
+#[cfg(feature = "ffi")]
+#[no_mangle]
+pub unsafe extern "C" fn slint_textinput_redo(
+    text_input: &TextInput,
+    window: *const crate::window::ffi::WindowRcOpaque,
+) {
+    let window = &*(window as *const WindowRc);
+    Pin::new_unchecked(text_input).redo(window)
+}
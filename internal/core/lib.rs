@@ -60,6 +60,7 @@ unsafe impl<T> Send for OnceCell<T> {}
     unsafe impl<T> Sync for OnceCell<T> {}
 }
 
+pub mod accessibility;
 pub mod animations;
 pub mod api;
 pub mod backend;
@@ -79,6 +80,7 @@ unsafe impl<T> Sync for OnceCell<T> {}
 pub mod string;
 pub mod tests;
 pub mod timers;
+pub mod translations;
 pub mod window;
 
 #[cfg(feature = "rtti")]
@@ -108,7 +110,6 @@ unsafe impl<T> Sync for OnceCell<T> {}
 #[doc(inline)]
 pub use graphics::RgbaColor;
 
-#[cfg(feature = "std")]
 #[doc(inline)]
 pub use graphics::PathData;
 
@@ -134,6 +135,7 @@ pub fn use_modules() -> usize {
             + timers::ffi::slint_timer_start as usize
             + graphics::color::ffi::slint_color_brighter as usize
             + graphics::image::ffi::slint_image_size as usize
+            + translations::ffi::slint_translate as usize
     }
     #[cfg(not(feature = "ffi"))]
     {
@@ -4,7 +4,8 @@
 //! Model and Repeater
 
 // Safety: we use pointer to Repeater in the DependencyList, bue the Drop of the Repeater
-// will remove them from the list so it will not be accessed after it is dropped
+// will remove them from the list so it will not be accessed after it is dropped.
+// The same applies to the ModelChangeListenerContainer.
 #![allow(unsafe_code)]
 
 use crate::item_tree::TraversalOrder;
@@ -23,8 +24,11 @@
 #[cfg(not(feature = "std"))]
 use num_traits::float::Float;
 
+mod adapters;
+pub use adapters::*;
+
 type DependencyListHead =
-    crate::properties::dependency_tracker::DependencyListHead<*const dyn ErasedRepeater>;
+    crate::properties::dependency_tracker::DependencyListHead<*const dyn ModelChangeListener>;
 type ComponentRc<C> = vtable::VRc<crate::component::ComponentVTable, C>;
 
 /// Represent a handle to a view that listens to changes to a model.
@@ -34,7 +38,74 @@
 #[derive(Clone)]
 pub struct ModelPeer {
     // FIXME: add a lifetime to ModelPeer so we can put the DependencyNode directly in the Repeater
-    inner: PinWeak<DependencyNode<*const dyn ErasedRepeater>>,
+    inner: PinWeak<DependencyNode<*const dyn ModelChangeListener>>,
+}
+
+/// This trait is implemented by the views of a model, which want to be notified of the
+/// changes of the model. They are attached to the model's [`ModelTracker`] with a [`ModelPeer`]
+/// obtained from a [`ModelChangeListenerContainer`].
+pub trait ModelChangeListener {
+    /// Called when the data of the given row changed
+    fn row_changed(&self, row: usize);
+    /// Called when `count` rows were inserted at the given `index`
+    fn row_added(&self, index: usize, count: usize);
+    /// Called when `count` rows were removed from the given `index`
+    fn row_removed(&self, index: usize, count: usize);
+    /// Called when `count` rows starting at `from` were moved, so that they now start at row `to`
+    fn row_moved(&self, from: usize, count: usize, to: usize);
+    /// Called when the whole content of the model changed
+    fn reset(&self);
+}
+
+/// Holds a [`ModelChangeListener`] and provides the [`ModelPeer`] to attach it to a model.
+///
+/// The container must stay pinned as the peer points to it. It detaches itself from the model
+/// when it is dropped.
+#[pin_project(PinnedDrop)]
+pub struct ModelChangeListenerContainer<T: ModelChangeListener> {
+    /// Will be initialized when the ModelPeer is initialized.
+    /// The DependencyNode points to data
+    peer: OnceCell<Pin<Rc<DependencyNode<*const dyn ModelChangeListener>>>>,
+    data: T,
+}
+
+#[pin_project::pinned_drop]
+impl<T: ModelChangeListener> PinnedDrop for ModelChangeListenerContainer<T> {
+    fn drop(self: Pin<&mut Self>) {
+        if let Some(peer) = self.peer.get() {
+            peer.remove();
+            // We should be the only one still holding a ref count to it, so that
+            // it cannot be re-added in any list, and the pointer to the data will not
+            // be accessed anymore
+            debug_assert_eq!(PinWeak::downgrade(peer.clone()).strong_count(), 1);
+        }
+    }
+}
+
+impl<T: ModelChangeListener + 'static> ModelChangeListenerContainer<T> {
+    /// Creates a container for the given listener
+    pub fn new(data: T) -> Self {
+        Self { peer: Default::default(), data }
+    }
+
+    /// Returns the peer to pass to [`ModelTracker::attach_peer`]
+    pub fn model_peer(self: Pin<&Self>) -> ModelPeer {
+        let peer = self.peer.get_or_init(|| {
+            //Safety: we will reset it when we Drop the ModelChangeListenerContainer
+            Rc::pin(DependencyNode::new(
+                &self.data as &dyn ModelChangeListener as *const dyn ModelChangeListener,
+            ))
+        });
+        ModelPeer { inner: PinWeak::downgrade(peer.clone()) }
+    }
+}
+
+impl<T: ModelChangeListener> core::ops::Deref for ModelChangeListenerContainer<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
 }
 
 /// This trait defines the interface that users of a model can use to track changes
@@ -112,6 +183,29 @@ pub fn row_removed(&self, index: usize, count: usize) {
                 .for_each(|p| unsafe { &**p }.row_removed(index, count))
         }
     }
+    /// Notify the peers that `count` rows starting at `from` were moved, so that they now
+    /// start at row `to`. Views can reuse the elements they created for the moved rows.
+    pub fn row_moved(&self, from: usize, count: usize, to: usize) {
+        if let Some(inner) = self.inner.get() {
+            inner.tracked_rows.borrow_mut().clear();
+            inner.model_row_data_dirty_property.mark_dirty();
+            inner
+                .as_ref()
+                .project_ref()
+                .peers
+                .for_each(|p| unsafe { &**p }.row_moved(from, count, to))
+        }
+    }
+    /// Notify the peers that the whole content of the model changed, including the row count.
+    /// This is cheaper than removing and adding all rows, as views can reuse their elements.
+    pub fn reset(&self) {
+        if let Some(inner) = self.inner.get() {
+            inner.model_row_count_dirty_property.mark_dirty();
+            inner.tracked_rows.borrow_mut().clear();
+            inner.model_row_data_dirty_property.mark_dirty();
+            inner.as_ref().project_ref().peers.for_each(|p| unsafe { &**p }.reset())
+        }
+    }
 }
 
 impl ModelTracker for ModelNotify {
@@ -296,6 +390,30 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 
 impl<'a, T> ExactSizeIterator for ModelIterator<'a, T> {}
 
+impl<M: Model> Model for Rc<M> {
+    type Data = M::Data;
+
+    fn row_count(&self) -> usize {
+        (**self).row_count()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        (**self).row_data(row)
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        (**self).set_row_data(row, data)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        (**self).model_tracker()
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        (**self).as_any()
+    }
+}
+
 /// A model backed by a `Vec<T>`
 #[derive(Default)]
 pub struct VecModel<T> {
@@ -330,6 +448,27 @@ pub fn remove(&self, index: usize) {
         self.array.borrow_mut().remove(index);
         self.notify.row_removed(index, 1)
     }
+
+    /// Replace the inner Vec by the new vector
+    pub fn set_vec(&self, new: impl Into<Vec<T>>) {
+        *self.array.borrow_mut() = new.into();
+        self.notify.reset();
+    }
+
+    /// Swaps two rows in the model.
+    /// This function panics if `a` or `b` are >= row_count().
+    pub fn swap(&self, a: usize, b: usize) {
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        if a == b {
+            return;
+        }
+        self.array.borrow_mut().swap(a, b);
+        // Move the row at `a` after the one at `b`, then the row at `b` in place of `a`
+        self.notify.row_moved(a, 1, b);
+        if b - 1 != a {
+            self.notify.row_moved(b - 1, 1, a);
+        }
+    }
 }
 
 impl<T> From<Vec<T>> for VecModel<T> {
@@ -608,13 +747,7 @@ fn default() -> Self {
         RepeaterInner { components: Default::default(), offset: 0, cached_item_height: 0. }
     }
 }
-trait ErasedRepeater {
-    fn row_changed(&self, row: usize);
-    fn row_added(&self, index: usize, count: usize);
-    fn row_removed(&self, index: usize, count: usize);
-}
-
-impl<C: RepeatedComponent> ErasedRepeater for Repeater<C> {
+impl<C: RepeatedComponent> ModelChangeListener for Repeater<C> {
     /// Notify the peers that a specific row was changed
     fn row_changed(&self, row: usize) {
         self.is_dirty.set(true);
@@ -670,6 +803,41 @@ fn row_removed(&self, mut index: usize, mut count: usize) {
             c.0 = RepeatedComponentState::Dirty;
         }
     }
+    /// Notify the peers that rows were moved. The components of the moved rows are moved
+    /// along and kept, only their index and data are updated.
+    fn row_moved(&self, from: usize, count: usize, to: usize) {
+        if count == 0 || from == to {
+            return;
+        }
+        self.is_dirty.set(true);
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+        // All the rows between the old and the new position change their index
+        let begin = from.min(to);
+        let end = from.max(to) + count;
+        let window = inner.offset..inner.offset + inner.components.len();
+        if window.contains(&begin) && end <= window.end {
+            let moved = &mut inner.components[begin - inner.offset..end - inner.offset];
+            if from < to {
+                moved.rotate_left(count);
+            } else {
+                moved.rotate_right(count);
+            }
+        }
+        let first = begin.max(window.start).min(window.end) - inner.offset;
+        let last = end.max(window.start).min(window.end) - inner.offset;
+        for c in inner.components[first..last].iter_mut() {
+            c.0 = RepeatedComponentState::Dirty;
+        }
+    }
+    /// Notify the peers that the model was reset. The existing components are kept and
+    /// updated with the new data, the ones that are not needed anymore are dropped.
+    fn reset(&self) {
+        self.is_dirty.set(true);
+        for c in self.inner.borrow_mut().components.iter_mut() {
+            c.0 = RepeatedComponentState::Dirty;
+        }
+    }
 }
 
 /// This field is put in a component when using the `for` syntax
@@ -687,7 +855,7 @@ pub struct Repeater<C: RepeatedComponent> {
 
     /// Will be initialized when the ModelPeer is initialized.
     /// The DependencyNode points to self
-    peer: OnceCell<Pin<Rc<DependencyNode<*const dyn ErasedRepeater>>>>,
+    peer: OnceCell<Pin<Rc<DependencyNode<*const dyn ModelChangeListener>>>>,
 }
 
 impl<C: RepeatedComponent> Default for Repeater<C> {
@@ -727,7 +895,7 @@ fn model(self: Pin<&Self>) -> ModelRc<C::Data> {
             let peer = self.peer.get_or_init(|| {
                 //Safety: we will reset it when we Drop the Repeater
                 Rc::pin(DependencyNode::new(
-                    self.get_ref() as &dyn ErasedRepeater as *const dyn ErasedRepeater
+                    self.get_ref() as &dyn ModelChangeListener as *const dyn ModelChangeListener
                 ))
             });
 
@@ -875,7 +1043,10 @@ pub fn ensure_updated_listview(
                         continue;
                     }
                     viewport_height.set((element_height * row_count as f32).max(end));
-                    viewport_y.set(-offset_y);
+                    // Only set the position if it changed, not to interrupt an animation
+                    if viewport_y.get_untracked() != -offset_y {
+                        viewport_y.set(-offset_y);
+                    }
                     break;
                 }
             })
@@ -987,6 +1158,33 @@ pub fn compute_layout_listview(
         }
         y_offset
     }
+
+    /// Scroll the ListView the least possible so that the given row becomes visible.
+    /// The position of rows that are not instantiated is estimated from the average item height,
+    /// like in [`Self::ensure_updated_listview`].
+    pub fn ensure_visible_listview(
+        &self,
+        row: usize,
+        viewport_y: Pin<&Property<f32>>,
+        listview_height: f32,
+    ) {
+        let (y, height) = {
+            let inner = self.inner.borrow();
+            match inner.components.get(row.wrapping_sub(inner.offset)).and_then(|c| c.1.as_ref()) {
+                Some(c) => {
+                    let geometry = c.as_pin_ref().get_item_ref(0).as_ref().geometry();
+                    (geometry.min_y(), geometry.height())
+                }
+                None => (row as f32 * inner.cached_item_height, inner.cached_item_height),
+            }
+        };
+        let offset_y = -viewport_y.get();
+        if y < offset_y {
+            viewport_y.set(-y);
+        } else if y + height > offset_y + listview_height {
+            viewport_y.set(-(y + height - listview_height).min(y));
+        }
+    }
 }
 
 /// Represent an item in a StandardListView
@@ -1079,3 +1277,30 @@ fn test_data_tracking() {
     model.insert(0, 255);
     assert!(tracker.is_dirty());
 }
+
+#[test]
+fn test_vecmodel_set_vec_and_swap() {
+    let model: Rc<VecModel<u8>> = Rc::new(VecModel::from(vec![0, 1, 2, 3, 4]));
+    let handle = ModelRc::from(model.clone());
+    let tracker = Box::pin(crate::properties::PropertyTracker::default());
+    let evaluate = || {
+        tracker.as_ref().evaluate(|| {
+            handle.model_tracker().track_row_count_changes();
+            handle.model_tracker().track_row_data_changes(1);
+            handle.iter().collect::<Vec<_>>()
+        })
+    };
+    assert_eq!(evaluate(), vec![0, 1, 2, 3, 4]);
+    assert!(!tracker.is_dirty());
+
+    model.swap(3, 1);
+    assert!(tracker.is_dirty());
+    assert_eq!(evaluate(), vec![0, 3, 2, 1, 4]);
+
+    model.swap(0, 1);
+    assert_eq!(evaluate(), vec![3, 0, 2, 1, 4]);
+
+    model.set_vec(vec![42, 43]);
+    assert!(tracker.is_dirty());
+    assert_eq!(evaluate(), vec![42, 43]);
+}
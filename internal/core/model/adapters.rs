@@ -0,0 +1,788 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Models that wrap another model to present a transformed view of its data:
+//! [`MapModel`], [`FilterModel`] and [`SortModel`].
+//!
+//! The filter and sort models keep a mapping between their rows and the rows of the wrapped
+//! model. They listen to the changes of the wrapped model and forward them to their own views
+//! as incremental changes.
+
+use super::*;
+use core::cmp::Ordering;
+
+/// Returns the new index of `row` after `count` rows starting at `from` were moved to `to`
+fn moved_row(row: usize, from: usize, count: usize, to: usize) -> usize {
+    if (from..from + count).contains(&row) {
+        return row - from + to;
+    }
+    let row = if row >= from + count { row - count } else { row };
+    if row >= to {
+        row + count
+    } else {
+        row
+    }
+}
+
+/// Provides rows that are generated by a map function based on the rows of another Model
+///
+/// When the other Model is updated, the `MapModel` is updated accordingly.
+///
+/// ## Example
+///
+/// Here we have a [`VecModel`] holding rows of a custom type `Name`.
+/// It is then mapped into a `MapModel` of [`SharedString`](crate::SharedString)s
+///
+/// ```
+/// # use i_slint_core::model::{Model, VecModel, MapModel};
+/// # use i_slint_core::SharedString;
+/// #[derive(Clone)]
+/// struct Name {
+///     first: String,
+///     last: String,
+/// }
+///
+/// let model = VecModel::from(vec![
+///     Name { first: "Hans".to_string(), last: "Emil".to_string() },
+///     Name { first: "Max".to_string(), last: "Mustermann".to_string() },
+///     Name { first: "Roman".to_string(), last: "Tisch".to_string() },
+/// ]);
+///
+/// let mapped_model = MapModel::new(model, |n|
+///     SharedString::from(format!("{}, {}", n.last, n.first))
+/// );
+///
+/// assert_eq!(mapped_model.row_data(0).unwrap(), SharedString::from("Emil, Hans"));
+/// assert_eq!(mapped_model.row_data(1).unwrap(), SharedString::from("Mustermann, Max"));
+/// assert_eq!(mapped_model.row_data(2).unwrap(), SharedString::from("Tisch, Roman"));
+/// ```
+pub struct MapModel<M, F> {
+    wrapped_model: M,
+    map_function: F,
+}
+
+impl<M, F, T, U> Model for MapModel<M, F>
+where
+    M: 'static + Model<Data = T>,
+    F: 'static + Fn(T) -> U,
+{
+    type Data = U;
+
+    fn row_count(&self) -> usize {
+        self.wrapped_model.row_count()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        self.wrapped_model.row_data(row).map(|x| (self.map_function)(x))
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        // The rows are the same as the rows of the wrapped model, so are the notifications
+        self.wrapped_model.model_tracker()
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+impl<M, F, T, U> MapModel<M, F>
+where
+    M: 'static + Model<Data = T>,
+    F: 'static + Fn(T) -> U,
+{
+    /// Creates a new MapModel based on the given `wrapped_model` and `map_function`.
+    /// Alternatively you can use [`ModelExt::map`] on your Model.
+    pub fn new(wrapped_model: M, map_function: F) -> Self {
+        Self { wrapped_model, map_function }
+    }
+
+    /// Returns a reference to the inner model
+    pub fn source_model(&self) -> &M {
+        &self.wrapped_model
+    }
+}
+
+struct FilterModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data) -> bool + 'static,
+{
+    wrapped_model: M,
+    filter_function: F,
+    // The rows of the wrapped model that are accepted by the filter, in increasing order
+    mapping: RefCell<Vec<usize>>,
+    notify: ModelNotify,
+}
+
+impl<M, F> FilterModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data) -> bool + 'static,
+{
+    fn accepts(&self, row: usize) -> bool {
+        self.wrapped_model.row_data(row).map_or(false, |data| (self.filter_function)(&data))
+    }
+
+    fn build_mapping_vec(&self) {
+        let mapping =
+            (0..self.wrapped_model.row_count()).filter(|row| self.accepts(*row)).collect();
+        *self.mapping.borrow_mut() = mapping;
+    }
+}
+
+impl<M, F> ModelChangeListener for FilterModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data) -> bool + 'static,
+{
+    fn row_changed(&self, row: usize) {
+        let mut mapping = self.mapping.borrow_mut();
+        let (index, is_contained) = match mapping.binary_search(&row) {
+            Ok(index) => (index, true),
+            Err(index) => (index, false),
+        };
+        let should_be_contained = self.accepts(row);
+
+        match (is_contained, should_be_contained) {
+            (true, true) => {
+                drop(mapping);
+                self.notify.row_changed(index);
+            }
+            (false, true) => {
+                mapping.insert(index, row);
+                drop(mapping);
+                self.notify.row_added(index, 1);
+            }
+            (true, false) => {
+                mapping.remove(index);
+                drop(mapping);
+                self.notify.row_removed(index, 1);
+            }
+            (false, false) => {}
+        }
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let insertion: Vec<usize> =
+            (index..index + count).filter(|row| self.accepts(*row)).collect();
+
+        let mut mapping = self.mapping.borrow_mut();
+        let insertion_point = mapping.partition_point(|row| *row < index);
+        mapping[insertion_point..].iter_mut().for_each(|row| *row += count);
+
+        if !insertion.is_empty() {
+            let inserted_count = insertion.len();
+            mapping.splice(insertion_point..insertion_point, insertion);
+            drop(mapping);
+            self.notify.row_added(insertion_point, inserted_count);
+        }
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let mut mapping = self.mapping.borrow_mut();
+        let start = mapping.partition_point(|row| *row < index);
+        let end = mapping.partition_point(|row| *row < index + count);
+        mapping.drain(start..end);
+        mapping[start..].iter_mut().for_each(|row| *row -= count);
+
+        if end > start {
+            drop(mapping);
+            self.notify.row_removed(start, end - start);
+        }
+    }
+
+    fn row_moved(&self, from: usize, count: usize, to: usize) {
+        let mut mapping = self.mapping.borrow_mut();
+        let filtered_from = mapping.partition_point(|row| *row < from);
+        let filtered_count = mapping.partition_point(|row| *row < from + count) - filtered_from;
+        mapping.iter_mut().for_each(|row| *row = moved_row(*row, from, count, to));
+        mapping.sort_unstable();
+        let filtered_to = mapping.partition_point(|row| *row < to);
+        drop(mapping);
+
+        if filtered_count > 0 && filtered_from != filtered_to {
+            self.notify.row_moved(filtered_from, filtered_count, filtered_to);
+        }
+    }
+
+    fn reset(&self) {
+        self.build_mapping_vec();
+        self.notify.reset();
+    }
+}
+
+/// Provides a filtered subset of rows by another [`Model`].
+///
+/// When the other Model is updated, the `FilterModel` is updated accordingly, and the views
+/// are notified of the rows that were added, removed or changed in the filtered model.
+///
+/// ## Example
+///
+/// Here we have a [`VecModel`] holding [`SharedString`](crate::SharedString)s.
+/// It is then filtered into a `FilterModel`.
+///
+/// ```
+/// # use i_slint_core::model::{Model, VecModel, FilterModel};
+/// # use i_slint_core::SharedString;
+/// let model = VecModel::from(vec![
+///     SharedString::from("Lorem"),
+///     SharedString::from("ipsum"),
+///     SharedString::from("dolor"),
+/// ]);
+///
+/// let filtered_model = FilterModel::new(model, |s| s.contains('o'));
+///
+/// assert_eq!(filtered_model.row_data(0).unwrap(), SharedString::from("Lorem"));
+/// assert_eq!(filtered_model.row_data(1).unwrap(), SharedString::from("dolor"));
+/// ```
+///
+/// If you want to modify the underlying model, wrap it in a [`ModelRc`] or an `Rc` and keep
+/// a clone of it:
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use i_slint_core::model::{Model, VecModel, FilterModel};
+/// # use i_slint_core::SharedString;
+/// let model = Rc::new(VecModel::from(vec![
+///     SharedString::from("Lorem"),
+///     SharedString::from("ipsum"),
+///     SharedString::from("dolor"),
+/// ]));
+///
+/// let filtered_model = FilterModel::new(model.clone(), |s| s.contains('o'));
+///
+/// assert_eq!(filtered_model.row_data(0).unwrap(), SharedString::from("Lorem"));
+/// assert_eq!(filtered_model.row_data(1).unwrap(), SharedString::from("dolor"));
+///
+/// model.set_row_data(1, SharedString::from("opsom"));
+///
+/// assert_eq!(filtered_model.row_data(0).unwrap(), SharedString::from("Lorem"));
+/// assert_eq!(filtered_model.row_data(1).unwrap(), SharedString::from("opsom"));
+/// assert_eq!(filtered_model.row_data(2).unwrap(), SharedString::from("dolor"));
+/// ```
+pub struct FilterModel<M, F>(Pin<Box<ModelChangeListenerContainer<FilterModelInner<M, F>>>>)
+where
+    M: Model + 'static,
+    F: Fn(&M::Data) -> bool + 'static;
+
+impl<M, F> FilterModel<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data) -> bool + 'static,
+{
+    /// Creates a new FilterModel based on the given `wrapped_model` and filtered by `filter_function`.
+    /// Alternatively you can use [`ModelExt::filter`] on your Model.
+    pub fn new(wrapped_model: M, filter_function: F) -> Self {
+        let filter_model_inner = FilterModelInner {
+            wrapped_model,
+            filter_function,
+            mapping: RefCell::new(Vec::new()),
+            notify: Default::default(),
+        };
+
+        filter_model_inner.build_mapping_vec();
+
+        let container = Box::pin(ModelChangeListenerContainer::new(filter_model_inner));
+
+        container.wrapped_model.model_tracker().attach_peer(container.as_ref().model_peer());
+
+        Self(container)
+    }
+
+    /// Manually reapply the filter. You need to run this e.g. if the filtering function depends on
+    /// mutable state and it has changed.
+    pub fn reset(&self) {
+        self.0.reset();
+    }
+
+    /// Gets the row index of the underlying unfiltered model for a given filtered row index.
+    pub fn unfiltered_row(&self, filtered_row: usize) -> usize {
+        self.0.mapping.borrow()[filtered_row]
+    }
+
+    /// Returns a reference to the inner model
+    pub fn source_model(&self) -> &M {
+        &self.0.wrapped_model
+    }
+}
+
+impl<M, F> Model for FilterModel<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data) -> bool + 'static,
+{
+    type Data = M::Data;
+
+    fn row_count(&self) -> usize {
+        self.0.mapping.borrow().len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        let unfiltered_row = self.0.mapping.borrow().get(row).copied();
+        unfiltered_row.and_then(|row| self.0.wrapped_model.row_data(row))
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        let unfiltered_row = self.0.mapping.borrow()[row];
+        self.0.wrapped_model.set_row_data(unfiltered_row, data);
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.0.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+struct SortModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> Ordering + 'static,
+{
+    wrapped_model: M,
+    sort_function: F,
+    // For each row of the sorted model, the row of the wrapped model
+    mapping: RefCell<Vec<usize>>,
+    notify: ModelNotify,
+}
+
+impl<M, F> SortModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> Ordering + 'static,
+{
+    fn build_mapping_vec(&self) {
+        let mut rows: Vec<(usize, M::Data)> = (0..self.wrapped_model.row_count())
+            .filter_map(|row| self.wrapped_model.row_data(row).map(|data| (row, data)))
+            .collect();
+        rows.sort_by(|a, b| (self.sort_function)(&a.1, &b.1));
+        *self.mapping.borrow_mut() = rows.into_iter().map(|(row, _)| row).collect();
+    }
+
+    /// Returns the position at which the data should be inserted in the mapping, after the
+    /// rows that compare equal so that the sort is stable.
+    fn insertion_point(&self, mapping: &[usize], data: &M::Data) -> usize {
+        mapping.partition_point(|row| {
+            self.wrapped_model
+                .row_data(*row)
+                .map_or(true, |other| (self.sort_function)(&other, data) != Ordering::Greater)
+        })
+    }
+}
+
+impl<M, F> ModelChangeListener for SortModelInner<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> Ordering + 'static,
+{
+    fn row_changed(&self, row: usize) {
+        let data = match self.wrapped_model.row_data(row) {
+            Some(data) => data,
+            None => return,
+        };
+        let mut mapping = self.mapping.borrow_mut();
+        let old_index = match mapping.iter().position(|r| *r == row) {
+            Some(index) => index,
+            None => return,
+        };
+        mapping.remove(old_index);
+        let new_index = self.insertion_point(&mapping, &data);
+        mapping.insert(new_index, row);
+        drop(mapping);
+
+        if old_index != new_index {
+            self.notify.row_moved(old_index, 1, new_index);
+        }
+        self.notify.row_changed(new_index);
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.mapping.borrow_mut().iter_mut().filter(|row| **row >= index).for_each(|row| {
+            *row += count;
+        });
+
+        for row in index..index + count {
+            let data = match self.wrapped_model.row_data(row) {
+                Some(data) => data,
+                None => continue,
+            };
+            let mut mapping = self.mapping.borrow_mut();
+            let insertion_point = self.insertion_point(&mapping, &data);
+            mapping.insert(insertion_point, row);
+            drop(mapping);
+            self.notify.row_added(insertion_point, 1);
+        }
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let mut removed = Vec::new();
+        let mut mapping = self.mapping.borrow_mut();
+        let mut i = 0;
+        while i < mapping.len() {
+            let row = mapping[i];
+            if (index..index + count).contains(&row) {
+                mapping.remove(i);
+                // The indices are the ones at the time of the removal, so they must be
+                // notified in that order
+                removed.push(i);
+            } else {
+                if row >= index + count {
+                    mapping[i] = row - count;
+                }
+                i += 1;
+            }
+        }
+        drop(mapping);
+
+        for i in removed {
+            self.notify.row_removed(i, 1);
+        }
+    }
+
+    fn row_moved(&self, from: usize, count: usize, to: usize) {
+        // The data didn't change, so the sorted rows stay in place
+        self.mapping
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|row| *row = moved_row(*row, from, count, to));
+    }
+
+    fn reset(&self) {
+        self.build_mapping_vec();
+        self.notify.reset();
+    }
+}
+
+/// Provides a sorted view of rows by another [`Model`].
+///
+/// When the other Model is updated, the `SortModel` is updated accordingly: the changed rows
+/// are moved to their new position, and the views are notified of the changes.
+///
+/// ## Example
+///
+/// Here we have a [`VecModel`] holding [`SharedString`](crate::SharedString)s.
+/// It is then sorted into a `SortModel`.
+///
+/// ```
+/// # use i_slint_core::model::{Model, VecModel, SortModel};
+/// # use i_slint_core::SharedString;
+/// let model = VecModel::from(vec![
+///     SharedString::from("Lorem"),
+///     SharedString::from("ipsum"),
+///     SharedString::from("dolor"),
+/// ]);
+///
+/// let sorted_model = SortModel::new(model, |lhs, rhs| lhs.to_lowercase().cmp(&rhs.to_lowercase()));
+///
+/// assert_eq!(sorted_model.row_data(0).unwrap(), SharedString::from("dolor"));
+/// assert_eq!(sorted_model.row_data(1).unwrap(), SharedString::from("ipsum"));
+/// assert_eq!(sorted_model.row_data(2).unwrap(), SharedString::from("Lorem"));
+/// ```
+///
+/// Alternatively you can use the shortcut [`ModelExt::sort`] for data that implements `Ord`.
+pub struct SortModel<M, F>(Pin<Box<ModelChangeListenerContainer<SortModelInner<M, F>>>>)
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> Ordering + 'static;
+
+impl<M, F> SortModel<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> Ordering + 'static,
+{
+    /// Creates a new SortModel based on the given `wrapped_model` and sorted by `sort_function`.
+    /// The sort is stable: rows that compare equal keep the order of the wrapped model.
+    /// Alternatively you can use [`ModelExt::sort_by`] on your Model.
+    pub fn new(wrapped_model: M, sort_function: F) -> Self {
+        let sorted_model_inner = SortModelInner {
+            wrapped_model,
+            sort_function,
+            mapping: RefCell::new(Vec::new()),
+            notify: Default::default(),
+        };
+
+        sorted_model_inner.build_mapping_vec();
+
+        let container = Box::pin(ModelChangeListenerContainer::new(sorted_model_inner));
+
+        container.wrapped_model.model_tracker().attach_peer(container.as_ref().model_peer());
+
+        Self(container)
+    }
+
+    /// Manually sort the model again. You need to run this e.g. if the sort function depends on
+    /// mutable state and it has changed.
+    pub fn reset(&self) {
+        self.0.reset();
+    }
+
+    /// Gets the row index of the underlying unsorted model for a given sorted row index.
+    pub fn unsorted_row(&self, sorted_row: usize) -> usize {
+        self.0.mapping.borrow()[sorted_row]
+    }
+
+    /// Returns a reference to the inner model
+    pub fn source_model(&self) -> &M {
+        &self.0.wrapped_model
+    }
+}
+
+impl<M> SortModel<M, fn(&M::Data, &M::Data) -> Ordering>
+where
+    M: Model + 'static,
+    M::Data: Ord,
+{
+    /// Creates a new SortModel based on the given `wrapped_model`, sorted in ascending order.
+    pub fn new_ascending(wrapped_model: M) -> Self {
+        Self::new(wrapped_model, <M::Data as Ord>::cmp)
+    }
+}
+
+impl<M, F> Model for SortModel<M, F>
+where
+    M: Model + 'static,
+    F: Fn(&M::Data, &M::Data) -> Ordering + 'static,
+{
+    type Data = M::Data;
+
+    fn row_count(&self) -> usize {
+        self.0.mapping.borrow().len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        let unsorted_row = self.0.mapping.borrow().get(row).copied();
+        unsorted_row.and_then(|row| self.0.wrapped_model.row_data(row))
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        let unsorted_row = self.0.mapping.borrow()[row];
+        self.0.wrapped_model.set_row_data(unsorted_row, data);
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.0.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+/// Extension trait with the adapters that can be applied to any [`Model`]
+pub trait ModelExt: Model {
+    /// Returns a new Model where all elements are mapped by the function `map_function`.
+    /// This is a shortcut for [`MapModel::new()`].
+    fn map<F, U>(self, map_function: F) -> MapModel<Self, F>
+    where
+        Self: Sized + 'static,
+        F: Fn(Self::Data) -> U + 'static,
+    {
+        MapModel::new(self, map_function)
+    }
+
+    /// Returns a new Model where the elements are filtered by the function `filter_function`.
+    /// This is a shortcut for [`FilterModel::new()`].
+    fn filter<F>(self, filter_function: F) -> FilterModel<Self, F>
+    where
+        Self: Sized + 'static,
+        F: Fn(&Self::Data) -> bool + 'static,
+    {
+        FilterModel::new(self, filter_function)
+    }
+
+    /// Returns a new Model where the elements are sorted ascending.
+    /// This is a shortcut for [`SortModel::new_ascending()`].
+    fn sort(self) -> SortModel<Self, fn(&Self::Data, &Self::Data) -> Ordering>
+    where
+        Self: Sized + 'static,
+        Self::Data: Ord,
+    {
+        SortModel::new_ascending(self)
+    }
+
+    /// Returns a new Model where the elements are sorted by the function `sort_function`.
+    /// This is a shortcut for [`SortModel::new()`].
+    fn sort_by<F>(self, sort_function: F) -> SortModel<Self, F>
+    where
+        Self: Sized + 'static,
+        F: Fn(&Self::Data, &Self::Data) -> Ordering + 'static,
+    {
+        SortModel::new(self, sort_function)
+    }
+}
+
+impl<T: Model> ModelExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the notifications of a model, to check that they are forwarded incrementally
+    #[derive(Default)]
+    struct TestView {
+        changed_rows: RefCell<Vec<usize>>,
+        added_rows: RefCell<Vec<(usize, usize)>>,
+        removed_rows: RefCell<Vec<(usize, usize)>>,
+        moved_rows: RefCell<Vec<(usize, usize, usize)>>,
+        reset_count: Cell<usize>,
+    }
+
+    impl TestView {
+        fn clear(&self) {
+            self.changed_rows.borrow_mut().clear();
+            self.added_rows.borrow_mut().clear();
+            self.removed_rows.borrow_mut().clear();
+            self.moved_rows.borrow_mut().clear();
+            self.reset_count.set(0);
+        }
+    }
+
+    impl ModelChangeListener for TestView {
+        fn row_changed(&self, row: usize) {
+            self.changed_rows.borrow_mut().push(row);
+        }
+
+        fn row_added(&self, index: usize, count: usize) {
+            self.added_rows.borrow_mut().push((index, count));
+        }
+
+        fn row_removed(&self, index: usize, count: usize) {
+            self.removed_rows.borrow_mut().push((index, count));
+        }
+
+        fn row_moved(&self, from: usize, count: usize, to: usize) {
+            self.moved_rows.borrow_mut().push((from, count, to));
+        }
+
+        fn reset(&self) {
+            self.reset_count.set(self.reset_count.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_filter_model() {
+        let wrapped_rc = Rc::new(VecModel::from(vec![1, 2, 3, 4, 5, 6]));
+        let filter = FilterModel::new(wrapped_rc.clone(), |x| x % 2 == 0);
+
+        let view = Box::pin(ModelChangeListenerContainer::<TestView>::new(Default::default()));
+        filter.model_tracker().attach_peer(view.as_ref().model_peer());
+
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+
+        wrapped_rc.insert(0, 10);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 2, 4, 6]);
+        assert_eq!(&*view.added_rows.borrow(), &[(0, 1)]);
+        view.clear();
+
+        // rows that are filtered out don't cause notifications
+        wrapped_rc.insert(2, 11);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 2, 4, 6]);
+        assert!(view.added_rows.borrow().is_empty());
+
+        wrapped_rc.set_row_data(3, 13);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 4, 6]);
+        assert_eq!(&*view.removed_rows.borrow(), &[(1, 1)]);
+        view.clear();
+
+        wrapped_rc.set_row_data(1, 8);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 8, 4, 6]);
+        assert_eq!(&*view.added_rows.borrow(), &[(1, 1)]);
+        view.clear();
+
+        wrapped_rc.set_row_data(5, 14);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 8, 14, 6]);
+        assert_eq!(&*view.changed_rows.borrow(), &[2]);
+        view.clear();
+
+        wrapped_rc.remove(5);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![10, 8, 6]);
+        assert_eq!(&*view.removed_rows.borrow(), &[(2, 1)]);
+        assert_eq!(filter.unfiltered_row(2), 6);
+
+        filter.set_row_data(1, 16);
+        assert_eq!(wrapped_rc.row_data(1), Some(16));
+        view.clear();
+
+        wrapped_rc.swap(0, 6);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![6, 16, 10]);
+        assert_eq!(&*view.moved_rows.borrow(), &[(0, 1, 2), (1, 1, 0)]);
+        assert!(view.added_rows.borrow().is_empty());
+        assert!(view.removed_rows.borrow().is_empty());
+
+        wrapped_rc.set_vec(vec![1, 2, 3, 4]);
+        assert_eq!(filter.iter().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(view.reset_count.get(), 1);
+    }
+
+    #[test]
+    fn test_sort_model() {
+        let wrapped_rc = Rc::new(VecModel::from(vec![3, 1, 2]));
+        let sorted = SortModel::new_ascending(wrapped_rc.clone());
+
+        let view = Box::pin(ModelChangeListenerContainer::<TestView>::new(Default::default()));
+        sorted.model_tracker().attach_peer(view.as_ref().model_peer());
+
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        wrapped_rc.push(0);
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(&*view.added_rows.borrow(), &[(0, 1)]);
+        assert_eq!(sorted.unsorted_row(0), 3);
+        view.clear();
+
+        wrapped_rc.set_row_data(1, 4);
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 2, 3, 4]);
+        assert_eq!(&*view.moved_rows.borrow(), &[(1, 1, 3)]);
+        assert_eq!(&*view.changed_rows.borrow(), &[3]);
+        assert!(view.removed_rows.borrow().is_empty());
+        assert!(view.added_rows.borrow().is_empty());
+        view.clear();
+
+        wrapped_rc.set_row_data(2, 1);
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+        assert_eq!(&*view.changed_rows.borrow(), &[1]);
+        view.clear();
+
+        wrapped_rc.remove(0);
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 4]);
+        assert_eq!(&*view.removed_rows.borrow(), &[(2, 1)]);
+        assert_eq!(sorted.unsorted_row(0), 2);
+        view.clear();
+
+        // Moving rows in the source model doesn't change the sorted view
+        wrapped_rc.swap(0, 2);
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 4]);
+        assert_eq!(sorted.unsorted_row(0), 0);
+        assert!(view.moved_rows.borrow().is_empty());
+
+        wrapped_rc.set_vec(vec![5, 3]);
+        assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![3, 5]);
+        assert_eq!(view.reset_count.get(), 1);
+    }
+
+    #[test]
+    fn test_map_model() {
+        let wrapped_rc = Rc::new(VecModel::from(vec![1, 2, 3]));
+        let map = wrapped_rc.clone().map(|x| (x * 2).to_string());
+
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec!["2", "4", "6"]);
+        wrapped_rc.push(4);
+        assert_eq!(map.row_count(), 4);
+        assert_eq!(map.row_data(3), Some("8".into()));
+    }
+}
@@ -829,6 +829,34 @@ pub fn set_binding(&self, binding: impl Binding<T> + 'static) {
         self.handle.mark_dirty();
     }
 
+    /// Set a binding that animates this property: the function is evaluated at every animation
+    /// tick and returns the new value, and whether the animation is finished. Once finished,
+    /// the binding is removed and the property keeps the last value.
+    ///
+    /// The function must call [`crate::animations::current_tick()`] so that it is re-evaluated
+    /// when the time changes.
+    pub(crate) fn set_animation_function(&self, f: impl Fn() -> (T, bool) + 'static) {
+        // Safety: the BindingCallable will cast its argument to T
+        unsafe {
+            self.handle.set_binding(
+                move |val: *mut ()| {
+                    let (value, finished) = f();
+                    *(val as *mut T) = value;
+                    if finished {
+                        BindingResult::RemoveBinding
+                    } else {
+                        crate::animations::CURRENT_ANIMATION_DRIVER
+                            .with(|driver| driver.set_has_active_animations());
+                        BindingResult::KeepBinding
+                    }
+                },
+                #[cfg(slint_debug_property)]
+                self.debug_name.borrow().as_str(),
+            );
+        }
+        self.handle.mark_dirty();
+    }
+
     /// Any of the properties accessed during the last evaluation of the closure called
     /// from the last call to evaluate is potentially dirty.
     pub fn is_dirty(&self) -> bool {
@@ -2157,6 +2185,160 @@ impl<B: PropertyChangeHandler> HasBindingVTable for B {
     }
 }
 
+#[cfg(all(not(feature = "std"), feature = "unsafe_single_core"))]
+use crate::unsafe_single_core::thread_local;
+
+thread_local!(
+    /// The change trackers that are dirty and whose handler need to be run
+    static PENDING_CHANGE_TRACKERS: RefCell<alloc::vec::Vec<alloc::rc::Weak<ChangeTrackerInner>>> =
+        Default::default()
+);
+
+/// The change handler of the PropertyTracker of a ChangeTracker: it schedules the tracker
+/// so that its handler is run later by [`ChangeTracker::run_change_handlers`]
+struct ChangeTrackerNotify(RefCell<alloc::rc::Weak<ChangeTrackerInner>>);
+
+impl PropertyChangeHandler for ChangeTrackerNotify {
+    fn notify(&self) {
+        let weak = self.0.borrow().clone();
+        let was_empty = PENDING_CHANGE_TRACKERS.with(|pending| {
+            let mut pending = pending.borrow_mut();
+            pending.push(weak);
+            pending.len() == 1
+        });
+        // Make sure the event loop wakes up to run the change handlers
+        if was_empty {
+            if let Some(backend) = crate::backend::instance() {
+                backend.request_change_handlers_run();
+            }
+        }
+    }
+}
+
+struct ChangeTrackerInner {
+    tracker: PropertyTracker<ChangeTrackerNotify>,
+    /// Evaluate the value and return true if it changed since the last evaluation
+    eval_fn: Box<dyn Fn() -> bool>,
+    notify_fn: Box<dyn Fn()>,
+}
+
+impl ChangeTrackerInner {
+    fn process(&self) {
+        // Safety: the tracker is in a Rc and is never moved
+        let tracker = unsafe { Pin::new_unchecked(&self.tracker) };
+        if tracker.evaluate_as_dependency_root(|| (self.eval_fn)()) {
+            (self.notify_fn)();
+        }
+    }
+}
+
+/// A ChangeTracker calls a handler when the value of an expression changes.
+///
+/// The handler is not called directly when one of the dependencies changes. Instead, the
+/// tracker is scheduled, and [`ChangeTracker::run_change_handlers`] re-evaluates the
+/// expression and calls the handler if the value is different from the previous one.
+#[derive(Default)]
+pub struct ChangeTracker {
+    inner: Cell<Option<Rc<ChangeTrackerInner>>>,
+}
+
+impl ChangeTracker {
+    /// Initialize the tracker with the function that computes the value, and the handler that
+    /// must be called when that value changes.
+    ///
+    /// The value is evaluated right away, but the handler is only called for subsequent changes.
+    pub fn init<T: PartialEq + 'static>(
+        &self,
+        eval_fn: impl Fn() -> T + 'static,
+        notify_fn: impl Fn() + 'static,
+    ) {
+        let last_value = RefCell::new(None);
+        self.init_impl(
+            Box::new(move || {
+                let value = eval_fn();
+                let mut last_value = last_value.borrow_mut();
+                let changed = last_value.as_ref().map_or(false, |last| *last != value);
+                *last_value = Some(value);
+                changed
+            }),
+            Box::new(notify_fn),
+        )
+    }
+
+    fn init_impl(&self, eval_fn: Box<dyn Fn() -> bool>, notify_fn: Box<dyn Fn()>) {
+        let inner = Rc::new(ChangeTrackerInner {
+            tracker: PropertyTracker::new_with_change_handler(ChangeTrackerNotify(
+                Default::default(),
+            )),
+            eval_fn,
+            notify_fn,
+        });
+        *inner.tracker.holder.binding.0.borrow_mut() = Rc::downgrade(&inner);
+        inner.process();
+        self.inner.set(Some(inner));
+    }
+
+    /// Returns true if some change trackers were notified and their handler is pending.
+    pub fn has_pending_change_handlers() -> bool {
+        PENDING_CHANGE_TRACKERS.with(|pending| !pending.borrow().is_empty())
+    }
+
+    /// Run the handlers of all the change trackers whose value have changed.
+    ///
+    /// This is called by the event loop.
+    pub fn run_change_handlers() {
+        // A handler may change properties which makes other trackers dirty. Process these
+        // too, but give up after a few rounds in case the handlers change each other's properties
+        for _ in 0..10 {
+            let pending = PENDING_CHANGE_TRACKERS.with(|pending| pending.take());
+            if pending.is_empty() {
+                return;
+            }
+            for inner in pending.iter().filter_map(|weak| weak.upgrade()) {
+                inner.process();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_change_tracker() {
+    let prop = Rc::pin(Property::new(1));
+    let prop2 = Rc::pin(Property::<i32>::default());
+    prop2.as_ref().set_binding({
+        let prop = prop.clone();
+        move || prop.as_ref().get() * 2
+    });
+    let count = Rc::new(Cell::new(0));
+    let tracker = ChangeTracker::default();
+    tracker.init(
+        {
+            let prop2 = prop2.clone();
+            move || prop2.as_ref().get()
+        },
+        {
+            let count = count.clone();
+            move || count.set(count.get() + 1)
+        },
+    );
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 0);
+    prop.as_ref().set(2);
+    // The handler is not called until the change handlers are run
+    assert_eq!(count.get(), 0);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 1);
+    prop.as_ref().set(3);
+    prop.as_ref().set(2);
+    // The value is the same as before
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 1);
+    prop.as_ref().set(5);
+    drop(tracker);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(count.get(), 1);
+}
+
 #[test]
 fn test_property_listener_scope() {
     let scope = Box::pin(PropertyTracker::default());
@@ -2747,4 +2929,56 @@ pub struct PropertyTrackerOpaque {
     pub unsafe extern "C" fn slint_property_tracker_drop(handle: *mut PropertyTrackerOpaque) {
         core::ptr::drop_in_place(handle as *mut PropertyTracker);
     }
+
+    #[repr(C)]
+    /// Opaque type representing the ChangeTracker
+    pub struct ChangeTrackerOpaque {
+        inner: *const c_void,
+    }
+
+    static_assertions::assert_eq_align!(ChangeTrackerOpaque, ChangeTracker);
+    static_assertions::assert_eq_size!(ChangeTrackerOpaque, ChangeTracker);
+
+    /// Initialize the ChangeTracker.
+    /// `out` is assumed to be uninitialized
+    /// slint_change_tracker_drop need to be called after that
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_change_tracker_construct(out: *mut ChangeTrackerOpaque) {
+        core::ptr::write(out as *mut ChangeTracker, ChangeTracker::default());
+    }
+
+    /// Set the functions of the ChangeTracker. `eval_fn` must evaluate the value and return
+    /// true if it has changed. `notify_fn` is then called from the event loop.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_change_tracker_init(
+        handle: *const ChangeTrackerOpaque,
+        user_data: *mut c_void,
+        drop_user_data: Option<extern "C" fn(*mut c_void)>,
+        eval_fn: extern "C" fn(user_data: *mut c_void) -> bool,
+        notify_fn: extern "C" fn(user_data: *mut c_void),
+    ) {
+        struct UserData {
+            user_data: *mut c_void,
+            drop_user_data: Option<extern "C" fn(*mut c_void)>,
+        }
+        impl Drop for UserData {
+            fn drop(&mut self) {
+                if let Some(x) = self.drop_user_data {
+                    x(self.user_data)
+                }
+            }
+        }
+        let user_data = Rc::new(UserData { user_data, drop_user_data });
+        let user_data2 = user_data.clone();
+        (*(handle as *const ChangeTracker)).init_impl(
+            Box::new(move || eval_fn(user_data.user_data)),
+            Box::new(move || notify_fn(user_data2.user_data)),
+        )
+    }
+
+    /// Destroy the ChangeTracker
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_change_tracker_drop(handle: *mut ChangeTrackerOpaque) {
+        core::ptr::drop_in_place(handle as *mut ChangeTracker);
+    }
 }
@@ -49,6 +49,8 @@ pub trait ValueType: 'static + Default + Clone $(+ TryInto<$ty> + TryFrom<$ty>)*
     crate::items::PointerEvent,
     crate::items::PointerEventButton,
     crate::items::PointerEventKind,
+    crate::items::AccessibleRole,
+    crate::input::DropEvent,
 ];
 
 /// What kind of animation is on a binding
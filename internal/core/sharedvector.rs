@@ -211,6 +211,30 @@ pub fn push(&mut self, value: T) {
         }
     }
 
+    /// Removes the last element of the array and returns it, or `None` if the array is empty.
+    /// If the array was shared, this will make a copy of the array.
+    ///
+    /// ```
+    /// use i_slint_core::SharedVector;
+    /// let mut shared_vector = SharedVector::<u32>::from_slice(&[1, 2]);
+    /// assert_eq!(shared_vector.pop(), Some(2));
+    /// assert_eq!(shared_vector.pop(), Some(1));
+    /// assert_eq!(shared_vector.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.detach(self.len());
+        // Safety: detach ensured that the array is not shared, and it is not empty so there is
+        // an element at the last position. Decreasing the size first transfers its ownership.
+        unsafe {
+            let inner = self.inner.as_mut();
+            inner.header.size -= 1;
+            Some(core::ptr::read(inner.data.as_mut_ptr().add(inner.header.size)))
+        }
+    }
+
     /// Resize the array to the given size.
     /// If the array was smaller new elements will be initialized with the value.
     /// If the array was bigger, extra elements will be discarded
@@ -5,21 +5,26 @@
 #![warn(missing_docs)]
 #![allow(unsafe_code)]
 
+use crate::graphics::{Point, Rect};
 use crate::input::{KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
+use crate::items::{ItemRc, ItemRef};
 use crate::window::WindowRc;
 use crate::SharedString;
+use alloc::vec::Vec;
 
 /// Slint animations do not use real time, but use a mocked time.
 /// Normally, the event loop update the time of the animation using
 /// real time, but in tests, it is more convenient to use the fake time.
-/// This function will add some milliseconds to the fake time
+/// This function will add some milliseconds to the fake time, and run the pending
+/// change handlers.
 #[no_mangle]
 pub extern "C" fn slint_mock_elapsed_time(time_in_ms: u64) {
     crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
         let mut tick = driver.current_tick();
         tick += core::time::Duration::from_millis(time_in_ms);
         driver.update_animations(tick)
-    })
+    });
+    crate::properties::ChangeTracker::run_change_handlers();
 }
 
 /// Simulate a click on a position within the component.
@@ -82,6 +87,185 @@ pub extern "C" fn send_keyboard_string_sequence(
     }
 }
 
+/// Writes the textual representation of the accessibility tree of the component into `result`.
+#[no_mangle]
+pub extern "C" fn slint_testing_dump_accessibility_tree(
+    component: &crate::component::ComponentRc,
+    result: &mut SharedString,
+) {
+    let tree = crate::accessibility::accessibility_tree(component);
+    *result = crate::accessibility::dump_accessibility_tree(&tree).into();
+}
+
+/// Criteria to find elements in the item tree of a component, with [`find_elements`].
+///
+/// An item matches the query if it matches all the criteria that were set.
+#[derive(Default, Clone, Debug)]
+pub struct ElementQuery {
+    element_id: Option<SharedString>,
+    element_type: Option<SharedString>,
+    text: Option<SharedString>,
+}
+
+impl ElementQuery {
+    /// Creates a query that matches all the items
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches the items of the element that has the given id in the .slint file
+    pub fn element_id(mut self, id: &str) -> Self {
+        self.element_id = Some(id.replace('_', "-").into());
+        self
+    }
+
+    /// Matches the items of the given builtin element, for example `TouchArea` or `TextInput`
+    pub fn element_type(mut self, type_name: &str) -> Self {
+        self.element_type = Some(type_name.into());
+        self
+    }
+
+    /// Matches the `Text` and `TextInput` items that have exactly the given text
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    fn matches(&self, element: &ElementHandle) -> bool {
+        self.element_id.as_ref().map_or(true, |id| element.element_id() == *id)
+            && self
+                .element_type
+                .as_ref()
+                .map_or(true, |ty| element.element_type() == Some(ty.as_str()))
+            && self.text.as_ref().map_or(true, |text| element.text().as_ref() == Some(text))
+    }
+}
+
+/// A handle to an item in the item tree of a window, to inspect it and interact with it in tests.
+#[derive(Clone)]
+pub struct ElementHandle {
+    item: ItemRc,
+    window: WindowRc,
+}
+
+impl ElementHandle {
+    /// Returns the item
+    pub fn item(&self) -> &ItemRc {
+        &self.item
+    }
+
+    /// Returns the id of the element in the .slint file, or an empty string if the element
+    /// has no id
+    pub fn element_id(&self) -> SharedString {
+        let mut result = SharedString::default();
+        let component = self.item.component();
+        vtable::VRc::borrow_pin(&component)
+            .as_ref()
+            .item_element_id(self.item.index(), &mut result);
+        result
+    }
+
+    /// Returns the name of the builtin element of this item, for example `Rectangle`, or None
+    /// if the item is not one of the builtin elements of the core library
+    pub fn element_type(&self) -> Option<&'static str> {
+        let item = self.item.borrow();
+        macro_rules! element_type {
+            ($($ty:ident => $name:literal,)*) => {
+                $(if ItemRef::downcast_pin::<crate::items::$ty>(item).is_some() {
+                    return Some($name);
+                })*
+            }
+        }
+        element_type!(
+            Rectangle => "Rectangle",
+            BorderRectangle => "Rectangle",
+            ImageItem => "Image",
+            ClippedImage => "Image",
+            TouchArea => "TouchArea",
+            PinchArea => "PinchArea",
+            DragArea => "DragArea",
+            DropArea => "DropArea",
+            FocusScope => "FocusScope",
+            Flickable => "Flickable",
+            Text => "Text",
+            TextInput => "TextInput",
+            Path => "Path",
+            WindowItem => "Window",
+            Clip => "Clip",
+            BoxShadow => "BoxShadow",
+            Rotate => "Rotate",
+            Scale => "Scale",
+            Opacity => "Opacity",
+        );
+        None
+    }
+
+    /// Returns the text of `Text` and `TextInput` items, None for other items
+    pub fn text(&self) -> Option<SharedString> {
+        let item = self.item.borrow();
+        if let Some(text) = ItemRef::downcast_pin::<crate::items::Text>(item) {
+            Some(text.text())
+        } else {
+            ItemRef::downcast_pin::<crate::items::TextInput>(item)
+                .map(|text_input| text_input.text())
+        }
+    }
+
+    /// Returns the geometry of the item, relative to the window
+    pub fn absolute_geometry(&self) -> Rect {
+        let size = self.item.borrow().as_ref().geometry().size;
+        Rect::new(self.item.map_to_window(Point::default()), size)
+    }
+
+    /// Simulates a click with the left button in the center of the item
+    pub fn click(&self) {
+        let center = self.absolute_geometry().center();
+        slint_send_mouse_click(&self.window.component(), center.x, center.y, &self.window);
+    }
+
+    /// Gives the keyboard focus to the item and simulates typing the given text
+    pub fn type_text(&self, text: &str) {
+        self.window.clone().set_focus_item(&self.item);
+        send_keyboard_string_sequence(&text.into(), Default::default(), &self.window);
+    }
+
+    /// Simulates a mouse wheel event in the center of the item, scrolling by the given amount of pixels
+    pub fn scroll(&self, delta_x: f32, delta_y: f32) {
+        let pos = self.absolute_geometry().center();
+        self.window.clone().process_mouse_input(MouseEvent::MouseMoved { pos });
+        self.window.clone().process_mouse_input(MouseEvent::MouseWheel {
+            pos,
+            delta: crate::graphics::euclid::point2(delta_x, delta_y),
+        });
+    }
+}
+
+/// Returns the elements in the item tree of the component, including the items of repeated
+/// elements, that match the query. The parents come before their children in the result.
+pub fn find_elements(
+    component: &crate::component::ComponentRc,
+    window: &WindowRc,
+    query: &ElementQuery,
+) -> Vec<ElementHandle> {
+    let mut result = Vec::new();
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |component, _, index, _| {
+            let element = ElementHandle {
+                item: ItemRc::new(component.clone(), index),
+                window: window.clone(),
+            };
+            if query.matches(&element) {
+                result.push(element);
+            }
+            crate::item_tree::ItemVisitorResult::Continue(())
+        },
+        (),
+    );
+    result
+}
+
 cfg_if::cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
         use wasm_bindgen::prelude::*;
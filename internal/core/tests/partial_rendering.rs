@@ -0,0 +1,96 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use core::cell::RefCell;
+use i_slint_core::item_rendering::{DirtyRegion, PartialRenderer, PartialRenderingCache};
+use i_slint_core::window::WindowHandleAccess;
+use slint::ComponentHandle;
+
+slint::slint! {
+    TestCase := Window {
+        width: 100px;
+        height: 100px;
+        property <length> parent-x: 10px;
+        property <color> first-color: red;
+        Rectangle {
+            x: parent-x;
+            y: 10px;
+            width: 50px;
+            height: 50px;
+            background: green;
+            Rectangle {
+                x: 5px;
+                y: 5px;
+                width: 10px;
+                height: 10px;
+                background: first-color;
+            }
+            // Outside of the geometry of its parent
+            Rectangle {
+                x: 60px;
+                y: 5px;
+                width: 10px;
+                height: 10px;
+                background: blue;
+            }
+        }
+    }
+}
+
+/// Renders a frame with a `PartialRenderer` and returns the region that it had to redraw
+fn render_frame(instance: &TestCase, cache: &RefCell<PartialRenderingCache>) -> DirtyRegion {
+    let window = instance.window().window_handle().clone();
+    let actual_renderer = i_slint_backend_testing::SoftwareRenderer::new(
+        100,
+        100,
+        Default::default(),
+        1.,
+        window.clone(),
+    )
+    .unwrap();
+    let mut renderer = PartialRenderer::new(cache, DirtyRegion::default(), actual_renderer);
+    window.draw_contents(|components| {
+        for (component, origin) in components {
+            renderer.compute_dirty_regions(component, *origin);
+        }
+        for (component, origin) in components {
+            renderer.render_component_items(component, *origin);
+        }
+    });
+    renderer.dirty_region
+}
+
+#[test]
+fn dirty_region_of_changed_property() {
+    i_slint_backend_testing::init();
+    let instance = TestCase::new();
+    let cache = RefCell::new(PartialRenderingCache::default());
+
+    assert_eq!(render_frame(&instance, &cache), euclid::rect(0., 0., 100., 100.));
+    assert!(render_frame(&instance, &cache).is_empty());
+
+    instance.set_first_color(slint::Color::from_rgb_u8(0, 0, 0));
+    assert_eq!(render_frame(&instance, &cache), euclid::rect(15., 15., 10., 10.));
+    assert!(render_frame(&instance, &cache).is_empty());
+}
+
+#[test]
+fn dirty_region_of_moved_parent() {
+    i_slint_backend_testing::init();
+    let instance = TestCase::new();
+    let cache = RefCell::new(PartialRenderingCache::default());
+    render_frame(&instance, &cache);
+
+    instance.set_parent_x(20.);
+    let dirty_region = render_frame(&instance, &cache);
+    // The old and the new location of the parent and of its children
+    assert_eq!(dirty_region, euclid::rect(10., 10., 90., 50.));
+    for child_rect in [
+        euclid::rect(15., 15., 10., 10.),
+        euclid::rect(25., 15., 10., 10.),
+        euclid::rect(70., 15., 10., 10.),
+        euclid::rect(80., 15., 10., 10.),
+    ] {
+        assert!(dirty_region.contains_rect(&child_rect), "{:?}", child_rect);
+    }
+}
@@ -0,0 +1,731 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the runtime support for the `@tr(...)` macro of the `.slint` language.
+
+The strings are looked up in the [`TranslationCatalog`] set with [`set_translation_catalog`].
+The catalogs are gettext `.mo` or `.po` files. Setting a catalog re-evaluates all the bindings
+that contain a translated string.
+*/
+
+#![warn(missing_docs)]
+
+use crate::{Property, SharedString};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::Write;
+use core::pin::Pin;
+
+/// Error returned by [`TranslationCatalog::parse`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TranslationCatalogError {
+    /// The data starts with the magic number of a `.mo` file but is truncated or otherwise invalid
+    InvalidMoFile,
+    /// The data is not a valid `.po` file. The line number starts at 1.
+    InvalidPoFile {
+        /// The line with the error
+        line: usize,
+    },
+    /// The `Plural-Forms` header of the catalog cannot be parsed
+    InvalidPluralForms,
+}
+
+impl core::fmt::Display for TranslationCatalogError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidMoFile => f.write_str("Invalid .mo file"),
+            Self::InvalidPoFile { line } => write!(f, "Invalid .po file at line {}", line),
+            Self::InvalidPluralForms => f.write_str("Invalid Plural-Forms header"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TranslationCatalogError {}
+
+/// A set of translated strings, loaded from a gettext `.mo` or `.po` file.
+///
+/// ```
+/// # use i_slint_core::translations::TranslationCatalog;
+/// let catalog = TranslationCatalog::parse(br#"
+/// msgid ""
+/// msgstr "Plural-Forms: nplurals=2; plural=(n > 1);\n"
+///
+/// msgid "Hello {}"
+/// msgstr "Bonjour {}"
+/// "#).unwrap();
+/// assert_eq!(catalog.lookup("", "Hello {}", None), Some("Bonjour {}"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TranslationCatalog {
+    /// The key is the msgid, prefixed by the context and `\u{4}` if there is a context
+    messages: BTreeMap<String, Vec<String>>,
+    plural_rule: Option<PluralExpression>,
+}
+
+impl TranslationCatalog {
+    /// Parses the content of a `.mo` file, or of a `.po` file encoded in UTF-8.
+    pub fn parse(data: &[u8]) -> Result<Self, TranslationCatalogError> {
+        if data.len() >= 4
+            && matches!(&data[0..4], [0xde, 0x12, 0x04, 0x95] | [0x95, 0x04, 0x12, 0xde])
+        {
+            Self::parse_mo(data)
+        } else {
+            let text = core::str::from_utf8(data).map_err(|e| {
+                let line = data[..e.valid_up_to()].iter().filter(|c| **c == b'\n').count() + 1;
+                TranslationCatalogError::InvalidPoFile { line }
+            })?;
+            Self::parse_po(text)
+        }
+    }
+
+    fn parse_mo(data: &[u8]) -> Result<Self, TranslationCatalogError> {
+        let big_endian = data[0] == 0x95;
+        let read_u32 = |offset: usize| -> Result<usize, TranslationCatalogError> {
+            let bytes: [u8; 4] = offset
+                .checked_add(4)
+                .and_then(|end| data.get(offset..end))
+                .and_then(|b| b.try_into().ok())
+                .ok_or(TranslationCatalogError::InvalidMoFile)?;
+            Ok(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) }
+                as usize)
+        };
+        let read_string = |table: usize, index: usize| -> Result<&str, TranslationCatalogError> {
+            // The offsets come from the file, so an overflow means that the file is malformed
+            let entry = index
+                .checked_mul(8)
+                .and_then(|x| x.checked_add(table))
+                .ok_or(TranslationCatalogError::InvalidMoFile)?;
+            let len = read_u32(entry)?;
+            let offset =
+                read_u32(entry.checked_add(4).ok_or(TranslationCatalogError::InvalidMoFile)?)?;
+            offset
+                .checked_add(len)
+                .and_then(|end| data.get(offset..end))
+                .and_then(|s| core::str::from_utf8(s).ok())
+                .ok_or(TranslationCatalogError::InvalidMoFile)
+        };
+
+        let count = read_u32(8)?;
+        let originals = read_u32(12)?;
+        let translations = read_u32(16)?;
+        let mut catalog = Self::default();
+        for i in 0..count {
+            // The plural form is after the singular form, separated by a nul character
+            let original = read_string(originals, i)?.split('\0').next().unwrap_or_default();
+            let translation = read_string(translations, i)?;
+            catalog.add_message(original, translation.split('\0').map(String::from).collect())?;
+        }
+        Ok(catalog)
+    }
+
+    fn parse_po(text: &str) -> Result<Self, TranslationCatalogError> {
+        #[derive(Default)]
+        struct Entry {
+            context: Option<String>,
+            id: Option<String>,
+            translations: Vec<String>,
+            fuzzy: bool,
+        }
+        #[derive(Clone, Copy)]
+        enum Field {
+            Context,
+            Id,
+            IdPlural,
+            Translation(usize),
+        }
+
+        let mut catalog = Self::default();
+        let mut entry = Entry::default();
+        let mut field = None;
+
+        let mut finish_entry = |entry: &mut Entry| -> Result<(), TranslationCatalogError> {
+            let entry = core::mem::take(entry);
+            let id = match entry.id {
+                Some(id) => id,
+                None => return Ok(()),
+            };
+            // Fuzzy translations are not used, except for the header
+            if (entry.fuzzy && !id.is_empty()) || entry.translations.iter().all(String::is_empty) {
+                return Ok(());
+            }
+            let key = match entry.context {
+                Some(context) => alloc::format!("{}\u{4}{}", context, id),
+                None => id,
+            };
+            catalog.add_message(&key, entry.translations)
+        };
+
+        for (line_index, line) in text.lines().enumerate() {
+            let error = TranslationCatalogError::InvalidPoFile { line: line_index + 1 };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if !entry.translations.is_empty() {
+                    finish_entry(&mut entry)?;
+                }
+                if comment.starts_with(',') && comment.split(',').any(|flag| flag.trim() == "fuzzy")
+                {
+                    entry.fuzzy = true;
+                }
+                continue;
+            }
+
+            let (keyword, string) = match line.find(|c: char| c.is_whitespace()) {
+                _ if line.starts_with('"') => ("", line),
+                Some(pos) => (&line[..pos], line[pos..].trim_start()),
+                None => return Err(error),
+            };
+            let string = unescape_po_string(string).ok_or_else(|| error.clone())?;
+
+            if keyword.is_empty() {
+                // Continuation of the previous string
+                match field.ok_or_else(|| error.clone())? {
+                    Field::Context => entry.context.get_or_insert_with(Default::default),
+                    Field::Id => entry.id.get_or_insert_with(Default::default),
+                    Field::IdPlural => continue,
+                    Field::Translation(index) => &mut entry.translations[index],
+                }
+                .push_str(&string);
+                continue;
+            }
+
+            if matches!(keyword, "msgctxt" | "msgid") && !entry.translations.is_empty() {
+                finish_entry(&mut entry)?;
+            }
+            field = Some(match keyword {
+                "msgctxt" => {
+                    entry.context = Some(string);
+                    Field::Context
+                }
+                "msgid" => {
+                    entry.id = Some(string);
+                    Field::Id
+                }
+                "msgid_plural" => Field::IdPlural,
+                "msgstr" => {
+                    entry.translations = alloc::vec![string];
+                    Field::Translation(0)
+                }
+                _ => {
+                    let index = keyword
+                        .strip_prefix("msgstr[")
+                        .and_then(|k| k.strip_suffix(']'))
+                        .and_then(|k| k.parse::<usize>().ok())
+                        .filter(|index| *index == entry.translations.len())
+                        .ok_or(error)?;
+                    entry.translations.push(string);
+                    Field::Translation(index)
+                }
+            });
+        }
+        finish_entry(&mut entry)?;
+        Ok(catalog)
+    }
+
+    fn add_message(
+        &mut self,
+        key: &str,
+        translations: Vec<String>,
+    ) -> Result<(), TranslationCatalogError> {
+        if key.is_empty() {
+            // The header
+            let plural_forms = translations
+                .first()
+                .and_then(|header| {
+                    header.lines().find_map(|l| l.trim().strip_prefix("Plural-Forms:"))
+                })
+                .and_then(|plural_forms| {
+                    plural_forms.split(';').find_map(|p| p.trim().strip_prefix("plural="))
+                });
+            if let Some(plural_forms) = plural_forms {
+                self.plural_rule = Some(
+                    PluralExpression::parse(plural_forms)
+                        .ok_or(TranslationCatalogError::InvalidPluralForms)?,
+                );
+            }
+        } else {
+            self.messages.insert(key.into(), translations);
+        }
+        Ok(())
+    }
+
+    /// Returns the translation of `original` in the given context (or an empty string if there
+    /// is no context).
+    /// For a message with plural forms, `n` is the number that selects the form.
+    pub fn lookup(&self, context: &str, original: &str, n: Option<i32>) -> Option<&str> {
+        let translations = if context.is_empty() {
+            self.messages.get(original)
+        } else {
+            let mut key = String::with_capacity(context.len() + original.len() + 1);
+            write!(key, "{}\u{4}{}", context, original).unwrap();
+            self.messages.get(&key)
+        }?;
+        let index = match n {
+            Some(n) => match &self.plural_rule {
+                Some(rule) => rule.evaluate(n.unsigned_abs() as u64) as usize,
+                None => (n != 1) as usize,
+            },
+            None => 0,
+        };
+        translations.get(index).map(String::as_str).filter(|s| !s.is_empty())
+    }
+}
+
+/// A C expression from the `Plural-Forms` header, that computes the index of the plural form
+#[derive(Clone, Debug)]
+enum PluralExpression {
+    N,
+    Constant(u64),
+    Not(Box<PluralExpression>),
+    Binary(Box<PluralExpression>, &'static str, Box<PluralExpression>),
+    Conditional(Box<PluralExpression>, Box<PluralExpression>, Box<PluralExpression>),
+}
+
+impl PluralExpression {
+    fn parse(source: &str) -> Option<Self> {
+        let mut parser = PluralParser { rest: source };
+        let expression = parser.parse_conditional()?;
+        parser.rest.trim().is_empty().then(|| expression)
+    }
+
+    fn evaluate(&self, n: u64) -> u64 {
+        match self {
+            Self::N => n,
+            Self::Constant(c) => *c,
+            Self::Not(e) => (e.evaluate(n) == 0) as u64,
+            Self::Binary(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(n), rhs.evaluate(n));
+                match *op {
+                    "||" => (lhs != 0 || rhs != 0) as u64,
+                    "&&" => (lhs != 0 && rhs != 0) as u64,
+                    "==" => (lhs == rhs) as u64,
+                    "!=" => (lhs != rhs) as u64,
+                    "<" => (lhs < rhs) as u64,
+                    ">" => (lhs > rhs) as u64,
+                    "<=" => (lhs <= rhs) as u64,
+                    ">=" => (lhs >= rhs) as u64,
+                    "+" => lhs.wrapping_add(rhs),
+                    "-" => lhs.wrapping_sub(rhs),
+                    "*" => lhs.wrapping_mul(rhs),
+                    "/" => lhs.checked_div(rhs).unwrap_or(0),
+                    "%" => lhs.checked_rem(rhs).unwrap_or(0),
+                    _ => unreachable!(),
+                }
+            }
+            Self::Conditional(cond, true_expr, false_expr) => {
+                if cond.evaluate(n) != 0 {
+                    true_expr.evaluate(n)
+                } else {
+                    false_expr.evaluate(n)
+                }
+            }
+        }
+    }
+}
+
+/// Recursive descent parser for [`PluralExpression`], following the C operator precedence
+struct PluralParser<'a> {
+    rest: &'a str,
+}
+
+impl PluralParser<'_> {
+    /// Consumes one of the operators and returns it
+    fn operator(&mut self, operators: &[&'static str]) -> Option<&'static str> {
+        self.rest = self.rest.trim_start();
+        let op = operators.iter().find(|op| self.rest.starts_with(**op))?;
+        // Do not mistake `<=` for `<`, or `!=` for `!`
+        if op.len() == 1 && self.rest[1..].starts_with('=') && "<>!=".contains(*op) {
+            return None;
+        }
+        self.rest = &self.rest[op.len()..];
+        Some(op)
+    }
+
+    fn parse_conditional(&mut self) -> Option<PluralExpression> {
+        let cond = self.parse_binary(0)?;
+        if self.operator(&["?"]).is_none() {
+            return Some(cond);
+        }
+        let true_expr = self.parse_conditional()?;
+        self.operator(&[":"])?;
+        let false_expr = self.parse_conditional()?;
+        Some(PluralExpression::Conditional(cond.into(), true_expr.into(), false_expr.into()))
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Option<PluralExpression> {
+        const LEVELS: &[&[&str]] = &[
+            &["||"],
+            &["&&"],
+            &["==", "!="],
+            &["<=", ">=", "<", ">"],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+        let operators = match LEVELS.get(level) {
+            Some(operators) => operators,
+            None => return self.parse_unary(),
+        };
+        let mut lhs = self.parse_binary(level + 1)?;
+        while let Some(op) = self.operator(operators) {
+            let rhs = self.parse_binary(level + 1)?;
+            lhs = PluralExpression::Binary(lhs.into(), op, rhs.into());
+        }
+        Some(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Option<PluralExpression> {
+        if self.operator(&["!"]).is_some() {
+            return Some(PluralExpression::Not(self.parse_unary()?.into()));
+        }
+        if self.operator(&["("]).is_some() {
+            let e = self.parse_conditional()?;
+            self.operator(&[")"])?;
+            return Some(e);
+        }
+        if self.operator(&["n"]).is_some() {
+            return Some(PluralExpression::N);
+        }
+        let len = self.rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest.len());
+        let value = self.rest[..len].parse().ok()?;
+        self.rest = &self.rest[len..];
+        Some(PluralExpression::Constant(value))
+    }
+}
+
+/// Removes the quotes around a string of a `.po` file and replaces the escape sequences.
+/// These are the C escape sequences, including octal (`\ooo`) and hexadecimal (`\xhh`) bytes.
+fn unescape_po_string(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = Vec::with_capacity(s.len());
+    let mut bytes = s.bytes().peekable();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            result.push(b);
+            continue;
+        }
+        let b = bytes.next()?;
+        result.push(match b {
+            b'n' => b'\n',
+            b't' => b'\t',
+            b'r' => b'\r',
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b'f' => 0x0c,
+            b'v' => 0x0b,
+            b'"' | b'\\' | b'\'' | b'?' => b,
+            b'0'..=b'7' => {
+                let mut value = u32::from(b - b'0');
+                for _ in 0..2 {
+                    match bytes.peek() {
+                        Some(d @ b'0'..=b'7') => value = value * 8 + u32::from(d - b'0'),
+                        _ => break,
+                    }
+                    bytes.next();
+                }
+                u8::try_from(value).ok()?
+            }
+            b'x' => {
+                let mut value = (*bytes.peek()? as char).to_digit(16)?;
+                bytes.next();
+                while let Some(digit) = bytes.peek().and_then(|d| (*d as char).to_digit(16)) {
+                    value = value.checked_mul(16)? + digit;
+                    bytes.next();
+                }
+                u8::try_from(value).ok()?
+            }
+            _ => return None,
+        });
+    }
+    String::from_utf8(result).ok()
+}
+
+/// Replaces the placeholders of a translated string: `{}` with the next argument, `{0}`, `{1}`,...
+/// with the argument at that index, and `{n}` with `n`. `{{` and `}}` are replaced by `{` and `}`.
+pub fn format(format_str: &str, arguments: &[SharedString], n: i32) -> SharedString {
+    let mut result = String::with_capacity(format_str.len());
+    let mut next_argument = 0;
+    let mut rest = format_str;
+    while let Some(pos) = rest.find(|c: char| c == '{' || c == '}') {
+        result.push_str(&rest[..pos]);
+        let (c, after) = rest[pos..].split_at(1);
+        if after.starts_with(c) {
+            // `{{` or `}}`
+            result.push_str(c);
+            rest = &after[1..];
+            continue;
+        }
+        let end = match (c, after.find('}')) {
+            ("{", Some(end)) => end,
+            _ => {
+                result.push_str(c);
+                rest = after;
+                continue;
+            }
+        };
+        match &after[..end] {
+            "" => {
+                result.push_str(arguments.get(next_argument).map_or("", |a| a.as_str()));
+                next_argument += 1;
+            }
+            "n" => write!(result, "{}", n).unwrap(),
+            index => {
+                let argument = index.parse::<usize>().ok().and_then(|index| arguments.get(index));
+                result.push_str(argument.map_or("", |a| a.as_str()));
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    result.into()
+}
+
+struct TranslationState {
+    catalog: RefCell<Option<TranslationCatalog>>,
+    /// Incremented every time the catalog changes, so that the bindings that use `translate` get
+    /// re-evaluated.
+    generation: Pin<Box<Property<u32>>>,
+}
+
+#[cfg(all(not(feature = "std"), feature = "unsafe_single_core"))]
+use crate::unsafe_single_core::thread_local;
+
+thread_local!(static CURRENT_TRANSLATIONS : TranslationState = TranslationState {
+    catalog: Default::default(),
+    generation: Box::pin(Property::new(0)),
+});
+
+/// Sets the catalog used to translate the strings of the `@tr(...)` macro, or use the original
+/// strings if `None`. The bindings that depend on translated strings are re-evaluated.
+pub fn set_translation_catalog(catalog: Option<TranslationCatalog>) {
+    CURRENT_TRANSLATIONS.with(|translations| {
+        *translations.catalog.borrow_mut() = catalog;
+        let generation = translations.generation.as_ref();
+        generation.set(generation.get_untracked().wrapping_add(1));
+    })
+}
+
+/// Translates `original` with the current catalog, and formats the result with the arguments.
+///
+/// `plural` is the plural form of the original string, or an empty string if the string has no
+/// plural form. In that case `n` is ignored.
+/// This registers the current binding as a dependency of the catalog.
+pub fn translate(
+    original: &str,
+    context: &str,
+    arguments: &[SharedString],
+    n: i32,
+    plural: &str,
+) -> SharedString {
+    let n_opt = if plural.is_empty() { None } else { Some(n) };
+    CURRENT_TRANSLATIONS.with(|translations| {
+        translations.generation.as_ref().get();
+        let catalog = translations.catalog.borrow();
+        let translated = catalog.as_ref().and_then(|c| c.lookup(context, original, n_opt));
+        let format_str = match translated {
+            Some(translated) => translated,
+            None if n_opt.map_or(false, |n| n != 1) => plural,
+            None => original,
+        };
+        format(format_str, arguments, n)
+    })
+}
+
+#[cfg(feature = "ffi")]
+pub(crate) mod ffi {
+    #![allow(unsafe_code)]
+
+    use super::*;
+    use crate::slice::Slice;
+
+    /// Replaces `to_translate` with its translation, see [`translate`]
+    #[no_mangle]
+    pub extern "C" fn slint_translate(
+        to_translate: &mut SharedString,
+        context: &SharedString,
+        arguments: Slice<SharedString>,
+        n: i32,
+        plural: &SharedString,
+    ) {
+        *to_translate = translate(to_translate.as_str(), context, &arguments, n, plural);
+    }
+
+    /// Parses the catalog and sets it as the current catalog. Returns false if the catalog
+    /// is invalid, in which case the current catalog is not changed.
+    #[no_mangle]
+    pub extern "C" fn slint_set_translation_catalog(data: Slice<u8>) -> bool {
+        match TranslationCatalog::parse(&data) {
+            Ok(catalog) => {
+                set_translation_catalog(Some(catalog));
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Resets the translations to the original strings
+    #[no_mangle]
+    pub extern "C" fn slint_reset_translation_catalog() {
+        set_translation_catalog(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plural_expression() {
+        let russian = PluralExpression::parse(
+            "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        )
+        .unwrap();
+        let forms: Vec<_> =
+            [1, 2, 5, 11, 21, 22, 25, 112].iter().map(|n| russian.evaluate(*n)).collect();
+        assert_eq!(forms, [0, 1, 2, 2, 0, 1, 2, 2]);
+
+        let french = PluralExpression::parse("n > 1").unwrap();
+        assert_eq!(french.evaluate(0), 0);
+        assert_eq!(french.evaluate(2), 1);
+        assert_eq!(PluralExpression::parse("!(n==1)").unwrap().evaluate(1), 0);
+        assert!(PluralExpression::parse("n ==").is_none());
+        assert!(PluralExpression::parse("(n != 1").is_none());
+    }
+
+    #[test]
+    fn format_placeholders() {
+        let args = [SharedString::from("a"), SharedString::from("b")];
+        assert_eq!(format("{} and {}", &args, 0), "a and b");
+        assert_eq!(format("{1} and {0}", &args, 0), "b and a");
+        assert_eq!(format("{n} items {{}}", &args, 42), "42 items {}");
+        assert_eq!(format("{} {2} {", &args, 0), "a  {");
+    }
+
+    #[test]
+    fn parse_po() {
+        let catalog = TranslationCatalog::parse(
+            br#"
+# A comment
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=3; plural=(n==1 ? 0 : n==2 ? 1 : 2);\n"
+
+msgctxt "Menu"
+msgid "Open"
+msgstr "Ouvrir"
+
+msgid "Open"
+msgstr ""
+"Ouvrir "
+"le fichier"
+
+#, fuzzy
+msgid "Close"
+msgstr "Fermer"
+
+msgid "{n} file"
+msgid_plural "{n} files"
+msgstr[0] "un fichier"
+msgstr[1] "deux fichiers"
+msgstr[2] "{n} fichiers"
+"#,
+        )
+        .unwrap();
+        assert_eq!(catalog.lookup("Menu", "Open", None), Some("Ouvrir"));
+        assert_eq!(catalog.lookup("", "Open", None), Some("Ouvrir le fichier"));
+        assert_eq!(catalog.lookup("", "Close", None), None);
+        assert_eq!(catalog.lookup("", "{n} file", Some(1)), Some("un fichier"));
+        assert_eq!(catalog.lookup("", "{n} file", Some(2)), Some("deux fichiers"));
+        assert_eq!(catalog.lookup("", "{n} file", Some(7)), Some("{n} fichiers"));
+
+        assert_eq!(
+            unescape_po_string(r#""a\tb\\c\"d\'e\?f\a\b\f\v""#).as_deref(),
+            Some("a\tb\\c\"d'e?f\x07\x08\x0c\x0b")
+        );
+        assert_eq!(
+            unescape_po_string(r#""\101\0\x42\xc3\xa9\303\251""#).as_deref(),
+            Some("A\0Béé")
+        );
+        assert_eq!(unescape_po_string(r#""\x""#), None);
+        assert_eq!(unescape_po_string(r#""\400""#), None);
+        assert_eq!(unescape_po_string(r#""\q""#), None);
+
+        assert_eq!(
+            TranslationCatalog::parse(b"msgid \"a\"\nmsgstr[1] \"b\"\n").unwrap_err(),
+            TranslationCatalogError::InvalidPoFile { line: 2 }
+        );
+    }
+
+    #[test]
+    fn parse_mo() {
+        fn mo_file(messages: &[(&str, &str)]) -> Vec<u8> {
+            let mut data = Vec::new();
+            let header_len = 28 + messages.len() * 16;
+            let mut strings = Vec::new();
+            let mut tables = [Vec::new(), Vec::new()];
+            for (original, translation) in messages {
+                for (table, s) in tables.iter_mut().zip([original, translation]) {
+                    table.extend((s.len() as u32).to_le_bytes());
+                    table.extend(((header_len + strings.len()) as u32).to_le_bytes());
+                    strings.extend(s.as_bytes());
+                    strings.push(0);
+                }
+            }
+            for value in [0x950412de, 0, messages.len(), 28, 28 + messages.len() * 8, 0, 0] {
+                data.extend((value as u32).to_le_bytes());
+            }
+            data.extend(tables.concat());
+            data.extend(strings);
+            data
+        }
+
+        let catalog = TranslationCatalog::parse(&mo_file(&[
+            ("", "Plural-Forms: nplurals=2; plural=n != 1;\n"),
+            ("Hello", "Hallo"),
+            ("ctx\u{4}Hello", "Servus"),
+            ("{n} file\0{n} files", "{n} Datei\0{n} Dateien"),
+        ]))
+        .unwrap();
+        assert_eq!(catalog.lookup("", "Hello", None), Some("Hallo"));
+        assert_eq!(catalog.lookup("ctx", "Hello", None), Some("Servus"));
+        assert_eq!(catalog.lookup("", "{n} file", Some(3)), Some("{n} Dateien"));
+        assert_eq!(catalog.lookup("", "Bye", None), None);
+
+        let mut truncated = mo_file(&[("Hello", "Hallo")]);
+        truncated.truncate(30);
+        assert_eq!(
+            TranslationCatalog::parse(&truncated).unwrap_err(),
+            TranslationCatalogError::InvalidMoFile
+        );
+
+        // The length and the offset of the first original string are out of bounds
+        let mut out_of_bounds = mo_file(&[("Hello", "Hallo")]);
+        let originals = u32::from_le_bytes(out_of_bounds[12..16].try_into().unwrap()) as usize;
+        out_of_bounds[originals..originals + 8].fill(0xff);
+        assert_eq!(
+            TranslationCatalog::parse(&out_of_bounds).unwrap_err(),
+            TranslationCatalogError::InvalidMoFile
+        );
+    }
+
+    #[test]
+    fn translate_with_catalog() {
+        assert_eq!(translate("{n} file", "", &[], 1, "{n} files"), "1 file");
+        assert_eq!(translate("{n} file", "", &[], 3, "{n} files"), "3 files");
+        set_translation_catalog(Some(
+            TranslationCatalog::parse(b"msgid \"Hello {}\"\nmsgstr \"Bonjour {}\"\n").unwrap(),
+        ));
+        assert_eq!(translate("Hello {}", "", &["World".into()], 0, ""), "Bonjour World");
+        assert_eq!(translate("Hello {}", "other", &["World".into()], 0, ""), "Hello World");
+        set_translation_catalog(None);
+        assert_eq!(translate("Hello {}", "", &["World".into()], 0, ""), "Hello World");
+    }
+}
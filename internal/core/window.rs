@@ -5,10 +5,14 @@
 //! Exposed Window API
 
 use crate::component::{ComponentRc, ComponentWeak};
-use crate::graphics::{Point, Size};
-use crate::input::{KeyEvent, MouseEvent, MouseInputState, TextCursorBlinker};
-use crate::items::{ItemRc, ItemRef, ItemWeak, MouseCursor};
+use crate::graphics::{Point, Rect, Size};
+use crate::input::{
+    DropEvent, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent, MouseInputState,
+    TextCursorBlinker, TouchPhase,
+};
+use crate::items::{DropArea, ItemRc, ItemRef, ItemWeak, MouseCursor, PointerEventButton};
 use crate::properties::{Property, PropertyTracker};
+use crate::SharedString;
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
 use core::cell::{Cell, RefCell};
@@ -140,6 +144,8 @@ pub struct Window {
     platform_window: once_cell::unsync::OnceCell<Rc<dyn PlatformWindow>>,
     component: RefCell<ComponentWeak>,
     mouse_input_state: Cell<MouseInputState>,
+    /// The id of the touch point whose events are converted into mouse events
+    primary_touch_point: Cell<Option<u64>>,
     redraw_tracker: once_cell::unsync::OnceCell<Pin<Box<PropertyTracker<WindowRedrawTracker>>>>,
     window_properties_tracker:
         once_cell::unsync::OnceCell<Pin<Box<PropertyTracker<WindowPropertiesTracker>>>>,
@@ -147,7 +153,11 @@ pub struct Window {
     meta_properties_tracker: Pin<Rc<PropertyTracker>>,
 
     focus_item: RefCell<ItemWeak>,
+    /// The `DropArea` that currently accepts the drag over the window
+    drop_area: RefCell<ItemWeak>,
     cursor_blinker: RefCell<pin_weak::rc::PinWeak<crate::input::TextCursorBlinker>>,
+    /// The keyboard modifiers that are currently pressed, as reported by the backend
+    keyboard_modifiers: Cell<KeyboardModifiers>,
 
     scale_factor: Pin<Box<Property<f32>>>,
     active: Pin<Box<Property<bool>>>,
@@ -172,11 +182,14 @@ pub fn new(
             platform_window: Default::default(),
             component: Default::default(),
             mouse_input_state: Default::default(),
+            primary_touch_point: Default::default(),
             redraw_tracker: Default::default(),
             window_properties_tracker: Default::default(),
             meta_properties_tracker: Rc::pin(Default::default()),
             focus_item: Default::default(),
+            drop_area: Default::default(),
             cursor_blinker: Default::default(),
+            keyboard_modifiers: Default::default(),
             scale_factor: Box::pin(Property::new(1.)),
             active: Box::pin(Property::new(false)),
             active_popup: Default::default(),
@@ -241,6 +254,30 @@ pub fn try_component(&self) -> Option<ComponentRc> {
     pub fn process_mouse_input(self: Rc<Self>, mut event: MouseEvent) {
         crate::animations::update_animations();
 
+        // The first touch point behaves like the mouse
+        if let MouseEvent::Touch { pos, id, phase } = event {
+            let primary = self.primary_touch_point.get();
+            match phase {
+                TouchPhase::Pressed if primary.is_none() => {
+                    self.primary_touch_point.set(Some(id));
+                    event = MouseEvent::MousePressed { pos, button: PointerEventButton::left };
+                }
+                TouchPhase::Moved if primary == Some(id) => {
+                    event = MouseEvent::MouseMoved { pos };
+                }
+                TouchPhase::Released if primary == Some(id) => {
+                    self.primary_touch_point.set(None);
+                    self.clone().process_mouse_input(MouseEvent::MouseReleased {
+                        pos,
+                        button: PointerEventButton::left,
+                    });
+                    // There is no hover once the finger is lifted
+                    event = MouseEvent::MouseExit;
+                }
+                _ => {}
+            }
+        }
+
         let embedded_popup_component =
             self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
                 PopupWindowLocation::TopLevel(_) => None,
@@ -291,6 +328,55 @@ pub fn process_mouse_input(self: Rc<Self>, mut event: MouseEvent) {
             }
         }
     }
+
+    /// Receive a drag that moves over the window, either from a `DragArea` or from another
+    /// application, and update the `contains-drag` property of the `DropArea` below it.
+    ///
+    /// The position of the event is in window coordinates. Returns true if a `DropArea`
+    /// accepts the drag at that position.
+    pub fn process_drag_move(self: Rc<Self>, event: DropEvent) -> bool {
+        let drop_area =
+            self.try_component().and_then(|c| crate::input::locate_drop_area(&c, &event));
+        let accepted = drop_area.is_some();
+        self.set_drop_area(drop_area.map(|(item, _)| item));
+        accepted
+    }
+
+    /// Drop the data of the event on the `DropArea` below its position, which is in window
+    /// coordinates. Returns true if a `DropArea` accepted the drop.
+    pub fn process_drop(self: Rc<Self>, event: DropEvent) -> bool {
+        let drop_area =
+            self.try_component().and_then(|c| crate::input::locate_drop_area(&c, &event));
+        self.set_drop_area(None);
+        if let Some((item, event)) = drop_area {
+            if let Some(drop_area) = ItemRef::downcast_pin::<DropArea>(item.borrow()) {
+                drop_area.drop(event);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Cancel the drag in progress, because it left the window or was aborted.
+    pub fn cancel_drag(&self) {
+        self.set_drop_area(None);
+    }
+
+    fn set_drop_area(&self, item: Option<ItemRc>) {
+        let new = item.as_ref().map(|item| item.downgrade()).unwrap_or_default();
+        if let Some(old) = self.drop_area.replace(new).upgrade() {
+            if let Some(drop_area) = ItemRef::downcast_pin::<DropArea>(old.borrow()) {
+                drop_area.set_contains_drag(false);
+            }
+        }
+        if let Some(drop_area) =
+            item.as_ref().and_then(|item| ItemRef::downcast_pin::<DropArea>(item.borrow()))
+        {
+            drop_area.set_contains_drag(true);
+        }
+    }
+
     /// Receive a key event and pass it to the items of the component to
     /// change their state.
     ///
@@ -309,6 +395,37 @@ pub fn process_key_input(self: Rc<Self>, event: &KeyEvent) {
         }
     }
 
+    /// Sets the text that the input method is composing. It is shown at the cursor position of the
+    /// focused text input until it is committed with [`Self::commit_preedit_text`]. An empty text
+    /// cancels the composition.
+    pub fn set_preedit_text(self: Rc<Self>, text: SharedString) {
+        self.process_key_input(&KeyEvent {
+            text,
+            event_type: KeyEventType::UpdateComposition,
+            ..Default::default()
+        });
+    }
+
+    /// Ends the composition of the input method by inserting the given text in the focused
+    /// text input, in place of the pre-edit text.
+    pub fn commit_preedit_text(self: Rc<Self>, text: SharedString) {
+        self.process_key_input(&KeyEvent {
+            text,
+            event_type: KeyEventType::CommitComposition,
+            ..Default::default()
+        });
+    }
+
+    /// Returns the rectangle of the text cursor of the focused text input, in window coordinates,
+    /// or None if no text input has the focus. Backends report it to the platform so that the
+    /// candidate window of the input method is placed next to the text being composed.
+    pub fn text_input_cursor_rect(self: &Rc<Self>) -> Option<Rect> {
+        let focus_item = self.focus_item.borrow().upgrade()?;
+        let text_input = ItemRef::downcast_pin::<crate::items::TextInput>(focus_item.borrow())?;
+        let rect = text_input.cursor_rect(self);
+        Some(Rect::new(focus_item.map_to_window(rect.origin), rect.size))
+    }
+
     /// Installs a binding on the specified property that's toggled whenever the text cursor is supposed to be visible or not.
     pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
         let existing_blinker = self.cursor_blinker.borrow().clone();
@@ -336,6 +453,10 @@ pub fn set_focus_item(self: Rc<Self>, focus_item: &ItemRc) {
         *self.as_ref().focus_item.borrow_mut() = focus_item.downgrade();
 
         focus_item.borrow().as_ref().focus_event(&crate::input::FocusEvent::FocusIn, &self);
+
+        if ItemRef::downcast_pin::<crate::items::TextInput>(focus_item.borrow()).is_some() {
+            crate::flickable::ensure_item_visible(focus_item);
+        }
     }
 
     /// Sets the focus on the window to true or false, depending on the have_focus argument.
@@ -365,6 +486,18 @@ pub fn active(&self) -> bool {
         self.active.as_ref().get()
     }
 
+    /// Records the keyboard modifiers that are currently pressed. Backends call this when the
+    /// state of the modifiers changes, so that the items can take them into account when
+    /// processing mouse events, for example to extend the selection with shift+click.
+    pub fn set_current_keyboard_modifiers(&self, modifiers: KeyboardModifiers) {
+        self.keyboard_modifiers.set(modifiers);
+    }
+
+    /// Returns the keyboard modifiers that are currently pressed.
+    pub fn current_keyboard_modifiers(&self) -> KeyboardModifiers {
+        self.keyboard_modifiers.get()
+    }
+
     /// If the component's root item is a Window element, then this function synchronizes its properties, such as the title
     /// for example, with the properties known to the windowing system.
     pub fn update_window_properties(&self) {
@@ -262,6 +262,7 @@ fn try_into(self) -> Result<$name, ()> {
 declare_value_struct_conversion!(struct i_slint_core::layout::LayoutInfo { min, max, min_percent, max_percent, preferred, stretch });
 declare_value_struct_conversion!(struct i_slint_core::graphics::Point { x, y, ..Default::default()});
 declare_value_struct_conversion!(struct i_slint_core::items::PointerEvent { kind, button });
+declare_value_struct_conversion!(struct i_slint_core::input::DropEvent { mime_type, data, position });
 
 /// Implement From / TryInto for Value that convert an `enum` to/from `Value::EnumerationValue`
 ///
@@ -313,6 +314,7 @@ fn try_into(self) -> Result<$ty, ()> {
 declare_value_enum_conversion!(i_slint_core::items::PointerEventKind, PointerEventKind);
 declare_value_enum_conversion!(i_slint_core::items::PointerEventButton, PointerEventButton);
 declare_value_enum_conversion!(i_slint_core::items::DialogButtonRole, DialogButtonRole);
+declare_value_enum_conversion!(i_slint_core::items::AccessibleRole, AccessibleRole);
 declare_value_enum_conversion!(i_slint_core::graphics::PathEvent, PathEvent);
 
 impl From<i_slint_core::animations::Instant> for Value {
@@ -644,6 +646,22 @@ pub fn callbacks(&self) -> impl Iterator<Item = String> + '_ {
         })
     }
 
+    /// Returns the names of all publicly declared functions.
+    ///
+    /// They can be called with [`ComponentInstance::invoke_callback`].
+    pub fn functions(&self) -> impl Iterator<Item = String> + '_ {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).properties().filter_map(|(prop_name, prop_type)| {
+            if matches!(prop_type, LangType::Function { .. }) {
+                Some(prop_name)
+            } else {
+                None
+            }
+        })
+    }
+
     /// Returns the names of all exported global singletons
     ///
     /// **Note:** Only globals that are exported or re-exported from the main .slint file will
@@ -768,6 +786,52 @@ pub fn set_property(&self, name: &str, value: Value) -> Result<(), SetPropertyEr
         comp.description().set_property(comp.borrow(), &normalize_identifier(name), value)
     }
 
+    /// Set a handler that is called with the new value when the value of the property
+    /// with the given name changes. This replaces the handler previously set for that property.
+    ///
+    /// The handler is not called right away when the property changes, but later from the
+    /// event loop, and only once even if the property changed several times in between.
+    ///
+    /// Note: Since the [`ComponentInstance`] holds the handler, the handler itself should not
+    /// contain a strong reference to the instance.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value};
+    /// let code = r#"
+    ///     MyWin := Window {
+    ///         property <int> my_property: 42;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let last_value = std::rc::Rc::new(std::cell::RefCell::new(Value::Void));
+    /// let last_value_clone = last_value.clone();
+    /// instance.on_property_changed("my_property", move |value| {
+    ///     *last_value_clone.borrow_mut() = value;
+    /// }).unwrap();
+    /// instance.set_property("my_property", Value::from(12)).unwrap();
+    /// slint_interpreter::testing::mock_elapsed_time(0);
+    /// assert_eq!(*last_value.borrow(), Value::from(12));
+    /// ```
+    pub fn on_property_changed(
+        &self,
+        name: &str,
+        handler: impl FnMut(Value) + 'static,
+    ) -> Result<(), GetPropertyError> {
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        comp.description()
+            .set_property_change_handler(
+                comp.borrow(),
+                &normalize_identifier(name),
+                Box::new(handler),
+            )
+            .map_err(|()| GetPropertyError::NoSuchProperty)
+    }
+
     /// Set a handler for the callback with the given name. A callback with that
     /// name must be defined in the document otherwise an error will be returned.
     ///
@@ -815,6 +879,8 @@ pub fn set_callback(
 
     /// Call the given callback with the arguments
     ///
+    /// This can also be used to call public functions declared with the `function` keyword.
+    ///
     /// ## Examples
     /// See the documentation of [`Self::set_callback`] for an example
     pub fn invoke_callback(
@@ -1013,7 +1079,7 @@ fn from(value: ComponentInstance) -> Self {
     }
 }
 
-/// Error returned by [`ComponentInstance::get_property`]
+/// Error returned by [`ComponentInstance::get_property`] and [`ComponentInstance::on_property_changed`]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
 #[non_exhaustive]
 pub enum GetPropertyError {
@@ -1065,6 +1131,10 @@ pub mod testing {
     use super::ComponentHandle;
     use i_slint_core::window::WindowHandleAccess;
 
+    /// Wrapper around [`i_slint_core::tests::slint_mock_elapsed_time`]
+    pub fn mock_elapsed_time(time_in_ms: u64) {
+        i_slint_core::tests::slint_mock_elapsed_time(time_in_ms);
+    }
     /// Wrapper around [`i_slint_core::tests::slint_send_mouse_click`]
     pub fn send_mouse_click(comp: &super::ComponentInstance, x: f32, y: f32) {
         i_slint_core::tests::slint_send_mouse_click(
@@ -1085,6 +1155,33 @@ pub fn send_keyboard_string_sequence(
             comp.window().window_handle(),
         );
     }
+
+    pub use i_slint_core::tests::{ElementHandle, ElementQuery};
+
+    /// Wrapper around [`i_slint_core::tests::find_elements`]
+    pub fn find_elements(
+        comp: &super::ComponentInstance,
+        query: &ElementQuery,
+    ) -> Vec<ElementHandle> {
+        i_slint_core::tests::find_elements(
+            &vtable::VRc::into_dyn(comp.inner.clone()),
+            comp.window().window_handle(),
+            query,
+        )
+    }
+
+    pub use i_slint_core::accessibility::AccessibleNode;
+
+    /// Wrapper around [`i_slint_core::accessibility::accessibility_tree`]
+    pub fn accessibility_tree(comp: &super::ComponentInstance) -> Vec<AccessibleNode> {
+        i_slint_core::accessibility::accessibility_tree(&vtable::VRc::into_dyn(comp.inner.clone()))
+    }
+
+    /// Returns the accessibility tree of the component in the format of
+    /// [`i_slint_core::accessibility::dump_accessibility_tree`]
+    pub fn dump_accessibility_tree(comp: &super::ComponentInstance) -> String {
+        i_slint_core::accessibility::dump_accessibility_tree(&accessibility_tree(comp))
+    }
 }
 
 #[test]
@@ -1391,6 +1488,33 @@ fn check_model(val: Value, r: &[f64]) {
     check_model(instance.get_property("prop").unwrap(), &[]);
 }
 
+#[test]
+fn component_definition_adapter_model_properties() {
+    use crate::{Model, ModelExt, ModelRc, VecModel};
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let comp_def = spin_on::spin_on(compiler.build_from_source(
+        "export Dummy := Rectangle { property <[int]> prop; property <int> count: prop.length; }"
+            .into(),
+        "".into(),
+    ))
+    .unwrap();
+    let instance = comp_def.create();
+
+    let source = std::rc::Rc::new(VecModel::from(vec![Value::Number(3.), Value::Number(12.)]));
+    let filtered = source.clone().filter(|v| matches!(v, Value::Number(n) if *n > 10.));
+    instance.set_property("prop", Value::Model(ModelRc::new(filtered))).unwrap();
+    assert_eq!(instance.get_property("count").unwrap(), Value::Number(1.));
+
+    source.push(Value::Number(42.));
+    assert_eq!(instance.get_property("count").unwrap(), Value::Number(2.));
+    source.push(Value::Number(1.));
+    assert_eq!(instance.get_property("count").unwrap(), Value::Number(2.));
+    source.set_row_data(0, Value::Number(30.));
+    assert_eq!(instance.get_property("count").unwrap(), Value::Number(3.));
+}
+
 #[cfg(feature = "ffi")]
 #[allow(missing_docs)]
 #[path = "ffi.rs"]
@@ -11,16 +11,19 @@
 use i_slint_compiler::object_tree::ElementRc;
 use i_slint_compiler::*;
 use i_slint_compiler::{diagnostics::BuildDiagnostics, object_tree::PropertyDeclaration};
+use i_slint_core::accessibility::AccessibleStringProperty;
 use i_slint_core::api::Window;
 use i_slint_core::component::{Component, ComponentRef, ComponentRefPin, ComponentVTable};
 use i_slint_core::item_tree::{
     ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, TraversalOrder, VisitChildrenResult,
 };
-use i_slint_core::items::{Flickable, ItemRc, ItemRef, ItemVTable, ItemWeak, PropertyAnimation};
+use i_slint_core::items::{
+    AccessibleRole, Flickable, ItemRc, ItemRef, ItemVTable, ItemWeak, PropertyAnimation,
+};
 use i_slint_core::layout::{BoxLayoutCellData, LayoutInfo, Orientation};
 use i_slint_core::model::RepeatedComponent;
 use i_slint_core::model::Repeater;
-use i_slint_core::properties::InterpolatedPropertyValue;
+use i_slint_core::properties::{ChangeTracker, InterpolatedPropertyValue};
 use i_slint_core::rtti::{self, AnimatedBindingKind, FieldOffset, PropertyInfo};
 use i_slint_core::window::{WindowHandleAccess, WindowRc};
 use i_slint_core::{Brush, Color, Property, SharedString, SharedVector};
@@ -170,6 +173,23 @@ fn get_item_ref(self: Pin<&Self>, index: usize) -> Pin<ItemRef> {
     fn parent_item(self: Pin<&Self>, index: usize, result: &mut ItemWeak) {
         self.borrow().as_ref().parent_item(index, result)
     }
+    fn item_element_id(self: Pin<&Self>, index: usize, result: &mut SharedString) {
+        self.borrow().as_ref().item_element_id(index, result)
+    }
+    fn accessible_role(self: Pin<&Self>, index: usize) -> AccessibleRole {
+        self.borrow().as_ref().accessible_role(index)
+    }
+    fn accessible_checked(self: Pin<&Self>, index: usize) -> bool {
+        self.borrow().as_ref().accessible_checked(index)
+    }
+    fn accessible_string_property(
+        self: Pin<&Self>,
+        index: usize,
+        what: AccessibleStringProperty,
+        result: &mut SharedString,
+    ) {
+        self.borrow().as_ref().accessible_string_property(index, what, result)
+    }
 }
 
 i_slint_core::ComponentVTable_static!(static COMPONENT_BOX_VT for ErasedComponentBox);
@@ -181,6 +201,10 @@ pub(crate) struct ComponentExtraData {
         once_cell::unsync::OnceCell<vtable::VWeak<ComponentVTable, ErasedComponentBox>>,
     // resource id -> file path
     pub(crate) embedded_file_resources: HashMap<usize, String>,
+    /// The trackers for the `changed` callbacks
+    pub(crate) change_trackers: once_cell::unsync::OnceCell<Vec<ChangeTracker>>,
+    /// The trackers for the handlers set with `ComponentInstance::on_property_changed`
+    pub(crate) property_observers: core::cell::RefCell<HashMap<String, ChangeTracker>>,
 }
 
 struct ErasedRepeaterWithinComponent<'id>(RepeaterWithinComponent<'id, 'static>);
@@ -450,6 +474,51 @@ pub fn get_property(&self, component: ComponentRefPin, name: &str) -> Result<Val
         }
     }
 
+    /// Sets a handler that is called with the new value when the value of a property changes.
+    /// This replaces the previous handler for that property.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// or if the property with this name does not exist in this component
+    pub fn set_property_change_handler(
+        &self,
+        component: ComponentRefPin,
+        name: &str,
+        handler: Box<dyn FnMut(Value)>,
+    ) -> Result<(), ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        let decl = self.public_properties.get(name).ok_or(())?;
+        if !decl.property_type.is_property_type() {
+            return Err(());
+        }
+        let (element, prop_name) = match &decl.is_alias {
+            Some(alias) => (alias.element(), alias.name().to_owned()),
+            None => (self.original.root_element.clone(), name.to_owned()),
+        };
+        generativity::make_guard!(guard);
+        // Safety: we just verified that the component has the right vtable
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        // Safety: the tracker is owned by the component, so the component outlives it
+        let component = unsafe {
+            Pin::new_unchecked(vtable::VRef::from_raw(
+                NonNull::from(&self.ct).cast(),
+                NonNull::new_unchecked(c.as_ptr() as *mut u8),
+            ))
+        };
+        let load = move || {
+            generativity::make_guard!(guard);
+            let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+            eval::load_property(c, &element, &prop_name).unwrap()
+        };
+        let tracker = ChangeTracker::default();
+        let handler = core::cell::RefCell::new(handler);
+        tracker.init(load.clone(), move || (handler.borrow_mut())(load()));
+        let extra_data = c.component_type.extra_data_offset.apply(c.as_ref());
+        extra_data.property_observers.borrow_mut().insert(name.to_owned(), tracker);
+        Ok(())
+    }
+
     /// Sets an handler for a callback
     ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
@@ -525,14 +594,12 @@ pub fn invoke_callback(
         generativity::make_guard!(guard);
         // Safety: we just verified that the component has the right vtable
         let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
-        if let Some(alias) = self
-            .original
-            .root_element
-            .borrow()
-            .property_declarations
-            .get(name)
-            .and_then(|d| d.is_alias.as_ref())
-        {
+        let decl = self.original.root_element.borrow().property_declarations.get(name).cloned();
+        if decl.as_ref().map_or(false, |d| d.is_private) {
+            // private functions can't be called from the outside
+            return Err(());
+        }
+        if let Some(alias) = decl.as_ref().and_then(|d| d.is_alias.as_ref()) {
             eval::invoke_callback(
                 eval::ComponentInstance::InstanceRef(c),
                 &alias.element(),
@@ -724,6 +791,9 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<Rectangle>(),
                 rtti_for::<BorderRectangle>(),
                 rtti_for::<TouchArea>(),
+                rtti_for::<PinchArea>(),
+                rtti_for::<DragArea>(),
+                rtti_for::<DropArea>(),
                 rtti_for::<FocusScope>(),
                 rtti_for::<Path>(),
                 rtti_for::<Flickable>(),
@@ -732,6 +802,7 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<Clip>(),
                 rtti_for::<BoxShadow>(),
                 rtti_for::<Rotate>(),
+                rtti_for::<Scale>(),
                 rtti_for::<Opacity>(),
             ]
             .iter()
@@ -915,12 +986,15 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                     .insert(name.clone(), builder.type_builder.add_field_type::<Callback>());
                 continue;
             }
+            // Functions are evaluated from their body and don't need any storage
+            Type::Function { .. } => continue,
             Type::Struct { name: Some(name), .. } if name.ends_with("::StateInfo") => {
                 property_info::<i_slint_core::properties::StateInfo>()
             }
             Type::Struct { .. } => property_info::<Value>(),
             Type::Array(_) => property_info::<Value>(),
             Type::Percent => property_info::<f32>(),
+            Type::Enumeration(e) if e.node.is_some() => property_info::<Value>(),
             Type::Enumeration(e) => match e.name.as_ref() {
                 "LayoutAlignment" => property_info::<i_slint_core::layout::LayoutAlignment>(),
                 "TextHorizontalAlignment" => {
@@ -938,6 +1012,7 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                 "DialogButtonRole" => property_info::<i_slint_core::items::DialogButtonRole>(),
                 "PointerEventButton" => property_info::<i_slint_core::items::PointerEventButton>(),
                 "PointerEventKind" => property_info::<i_slint_core::items::PointerEventKind>(),
+                "AccessibleRole" => property_info::<i_slint_core::items::AccessibleRole>(),
                 _ => panic!("unknown enum"),
             },
             Type::LayoutCache => property_info::<SharedVector<f32>>(),
@@ -1014,6 +1089,10 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
         layout_info,
         get_item_ref,
         parent_item,
+        item_element_id,
+        accessible_role,
+        accessible_checked,
+        accessible_string_property,
         drop_in_place,
         dealloc,
     };
@@ -1142,9 +1221,12 @@ pub fn instantiate(
 
     // Some properties are generated as Value, but for which the default constructed Value must be initialized
     for (prop_name, decl) in &component_type.original.root_element.borrow().property_declarations {
-        if !matches!(decl.property_type, Type::Struct { .. } | Type::Array(_))
-            || decl.is_alias.is_some()
-        {
+        let is_value = match &decl.property_type {
+            Type::Struct { .. } | Type::Array(_) => true,
+            Type::Enumeration(e) => e.node.is_some(),
+            _ => false,
+        };
+        if !is_value || decl.is_alias.is_some() {
             continue;
         }
         if let Some(b) = component_type.original.root_element.borrow().bindings.get(prop_name) {
@@ -1326,7 +1408,7 @@ pub fn instantiate(
         },
     );
 
-    for rep_in_comp in &component_type.repeater {
+    for (index, rep_in_comp) in component_type.repeater.iter().enumerate() {
         generativity::make_guard!(guard);
         let rep_in_comp = rep_in_comp.unerase(guard);
 
@@ -1340,6 +1422,52 @@ pub fn instantiate(
                 instance.cast(),
             ))
         };
+        if let Some(lv) = &rep_in_comp
+            .component_to_repeat
+            .original
+            .parent_element
+            .upgrade()
+            .unwrap()
+            .borrow()
+            .repeated
+            .as_ref()
+            .unwrap()
+            .is_listview
+        {
+            if let Some(callback_offset) =
+                component_type.custom_callbacks.get(lv.ensure_visible.name())
+            {
+                let lv = lv.clone();
+                callback_offset.apply(instance_ref.as_ref()).set_handler(move |args| {
+                    generativity::make_guard!(guard);
+                    let instance_ref = unsafe { InstanceRef::from_pin_ref(c, guard) };
+                    // Safety: we do not mix the component with other component id in this function
+                    let rep_in_comp =
+                        unsafe { instance_ref.component_type.repeater[index].get_untagged() };
+                    let row: i32 = args[0].clone().try_into().unwrap();
+                    let viewport_y = unsafe {
+                        Pin::new_unchecked(
+                            &*(get_property_ptr(&lv.viewport_y, instance_ref)
+                                as *const Property<f32>),
+                        )
+                    };
+                    let listview_height: f32 = eval::load_property(
+                        instance_ref,
+                        &lv.listview_height.element(),
+                        lv.listview_height.name(),
+                    )
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+                    rep_in_comp.offset.apply_pin(instance_ref.instance).ensure_visible_listview(
+                        row.max(0) as usize,
+                        viewport_y,
+                        listview_height,
+                    );
+                    Value::Void
+                });
+            }
+        }
         repeater.set_model_binding(move || {
             generativity::make_guard!(guard);
             let m = eval::eval_expression(
@@ -1431,6 +1559,52 @@ pub fn run_setup_code(&self) {
                 &mut eval::EvalLocalContext::from_component_instance(instance_ref),
             );
         }
+
+        let mut change_trackers = Vec::new();
+        object_tree::recurse_elem(
+            &self.0.component_type.original.root_element,
+            &(),
+            &mut |elem, _| {
+                for (name, handlers) in &elem.borrow().change_callbacks {
+                    for handler in handlers.borrow().iter() {
+                        // Safety: the tracker is owned by the component, so the component outlives it
+                        let c = unsafe {
+                            Pin::new_unchecked(vtable::VRef::from_raw(
+                                NonNull::from(&compo_box.component_type.ct).cast(),
+                                compo_box.instance.as_ptr().cast(),
+                            ))
+                        };
+                        let tracker = ChangeTracker::default();
+                        let elem = elem.clone();
+                        let name = name.clone();
+                        let handler = handler.clone();
+                        tracker.init(
+                            move || {
+                                generativity::make_guard!(guard);
+                                let instance_ref = unsafe { InstanceRef::from_pin_ref(c, guard) };
+                                eval::load_property(instance_ref, &elem, &name).unwrap()
+                            },
+                            move || {
+                                generativity::make_guard!(guard);
+                                let instance_ref = unsafe { InstanceRef::from_pin_ref(c, guard) };
+                                eval::eval_expression(
+                                    &handler,
+                                    &mut eval::EvalLocalContext::from_component_instance(
+                                        instance_ref,
+                                    ),
+                                );
+                            },
+                        );
+                        change_trackers.push(tracker);
+                    }
+                }
+            },
+        );
+        if !change_trackers.is_empty() {
+            let extra_data =
+                instance_ref.component_type.extra_data_offset.apply(instance_ref.as_ref());
+            extra_data.change_trackers.set(change_trackers).ok().unwrap();
+        }
     }
 }
 impl<'id> From<ComponentBox<'id>> for ErasedComponentBox {
@@ -1502,6 +1676,77 @@ extern "C" fn layout_info(component: ComponentRefPin, orientation: Orientation)
     }
 }
 
+unsafe extern "C" fn item_element_id(
+    component: ComponentRefPin,
+    index: usize,
+    result: &mut SharedString,
+) {
+    generativity::make_guard!(guard);
+    let instance_ref = InstanceRef::from_pin_ref(component, guard);
+    if let Some(item) = instance_ref
+        .component_type
+        .items
+        .values()
+        .find(|item| item.elem.borrow().item_index.get() == Some(&index))
+    {
+        let elem = item.elem.borrow();
+        if !elem.original_id.is_empty() {
+            *result = elem.original_id.as_str().into();
+        }
+    }
+}
+
+/// Returns the value of the `accessible-*` property of the item at the given index, if it is set
+fn accessibility_property(instance_ref: InstanceRef, index: usize, name: &str) -> Option<Value> {
+    let nr = instance_ref
+        .component_type
+        .items
+        .values()
+        .find(|item| item.elem.borrow().item_index.get() == Some(&index))?
+        .elem
+        .borrow()
+        .accessibility_props
+        .get(name)?
+        .clone();
+    eval::load_property(instance_ref, &nr.element(), nr.name()).ok()
+}
+
+unsafe extern "C" fn accessible_role(component: ComponentRefPin, index: usize) -> AccessibleRole {
+    generativity::make_guard!(guard);
+    let instance_ref = InstanceRef::from_pin_ref(component, guard);
+    accessibility_property(instance_ref, index, "accessible-role")
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default()
+}
+
+unsafe extern "C" fn accessible_checked(component: ComponentRefPin, index: usize) -> bool {
+    generativity::make_guard!(guard);
+    let instance_ref = InstanceRef::from_pin_ref(component, guard);
+    accessibility_property(instance_ref, index, "accessible-checked")
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default()
+}
+
+unsafe extern "C" fn accessible_string_property(
+    component: ComponentRefPin,
+    index: usize,
+    what: AccessibleStringProperty,
+    result: &mut SharedString,
+) {
+    generativity::make_guard!(guard);
+    let instance_ref = InstanceRef::from_pin_ref(component, guard);
+    let name = match what {
+        AccessibleStringProperty::Label => "accessible-label",
+        AccessibleStringProperty::Description => "accessible-description",
+        AccessibleStringProperty::Value => "accessible-value",
+    };
+    if let Some(value) = accessibility_property(instance_ref, index, name) {
+        if let Ok(value) = value.try_into() {
+            *result = value;
+        }
+    }
+}
+
 unsafe extern "C" fn parent_item(component: ComponentRefPin, index: usize, result: &mut ItemWeak) {
     generativity::make_guard!(guard);
     let instance_ref = InstanceRef::from_pin_ref(component, guard);
@@ -5,7 +5,9 @@
 use crate::dynamic_component::InstanceRef;
 use core::convert::TryInto;
 use core::pin::Pin;
-use corelib::graphics::{GradientStop, LinearGradientBrush, PathElement};
+use corelib::graphics::{
+    ConicGradientBrush, GradientStop, LinearGradientBrush, PathElement, RadialGradientBrush,
+};
 use corelib::items::{ItemRef, PropertyAnimation};
 use corelib::model::{Model, ModelRc};
 use corelib::rtti::AnimatedBindingKind;
@@ -142,6 +144,7 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
         Expression::NumberLiteral(n, unit) => Value::Number(unit.normalize(*n)),
         Expression::BoolLiteral(b) => Value::Bool(*b),
         Expression::CallbackReference { .. } => panic!("callback in expression"),
+        Expression::FunctionReference { .. } => panic!("function in expression"),
         Expression::BuiltinFunctionReference(..) => panic!(
             "naked builtin function reference not allowed, should be handled by function call"
         ),
@@ -215,6 +218,10 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let args = arguments.iter().map(|e| eval_expression(e, local_context)).collect::<Vec<_>>();
                 invoke_callback(local_context.component_instance, &nr.element(), nr.name(), &args).unwrap()
             }
+            Expression::FunctionReference(nr) => {
+                let args = arguments.iter().map(|e| eval_expression(e, local_context)).collect::<Vec<_>>();
+                call_function(local_context.component_instance, &nr.element(), nr.name(), args).unwrap()
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowScaleFactor, _) => {
                 match local_context.component_instance {
                     ComponentInstance::InstanceRef(component) => Value::Number(window_ref(component).unwrap().scale_factor() as _),
@@ -345,6 +352,61 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::FlickableScrollTo, _) => {
+                if arguments.len() != 4 {
+                    panic!("internal error: incorrect argument count to FlickableScrollTo")
+                }
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot scroll a Flickable from a global component")
+                };
+                if let Expression::ElementReference(flickable) = &arguments[0] {
+                    let x: f32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                    let y: f32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                    let animated: bool = eval_expression(&arguments[3], local_context).try_into().unwrap();
+
+                    generativity::make_guard!(guard);
+                    let flickable = flickable.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&flickable, component, guard);
+                    let item_info = &enclosing_component.component_type.items[flickable.borrow().id.as_str()];
+                    let item = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+                    ItemRef::downcast_pin::<corelib::items::Flickable>(item)
+                        .expect("internal error: argument to FlickableScrollTo must be a Flickable")
+                        .scroll_to(corelib::graphics::Point::new(x, y), animated);
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to FlickableScrollTo must be an element")
+                }
+            }
+            Expression::BuiltinFunctionReference(function @ (BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo), _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to {:?}", function)
+                }
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot edit a TextInput from a global component")
+                };
+                if let Expression::ElementReference(text_input) = &arguments[0] {
+                    generativity::make_guard!(guard);
+                    let text_input = text_input.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&text_input, component, guard);
+                    let item_info = &enclosing_component.component_type.items[text_input.borrow().id.as_str()];
+                    let item = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+                    let text_input = ItemRef::downcast_pin::<corelib::items::TextInput>(item)
+                        .expect("internal error: argument to TextInputUndo/TextInputRedo must be a TextInput");
+                    let window = window_ref(component).unwrap();
+                    if matches!(function, BuiltinFunction::TextInputUndo) {
+                        text_input.undo(window);
+                    } else {
+                        text_input.redo(window);
+                    }
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to {:?} must be an element", function)
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::StringIsFloat, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to StringIsFloat")
@@ -355,6 +417,25 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("Argument not a string");
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::Translate, _) => {
+                let mut to_string = |e: &Expression| match eval_expression(e, local_context) {
+                    Value::String(s) => s,
+                    _ => panic!("Argument not a string"),
+                };
+                if let [original, context, Expression::Array { values, .. }, n, plural] =
+                    arguments.as_slice()
+                {
+                    let values = values.iter().map(&mut to_string).collect::<Vec<_>>();
+                    let (original, context, plural) =
+                        (to_string(original), to_string(context), to_string(plural));
+                    let n: f64 = eval_expression(n, local_context).try_into().unwrap();
+                    Value::String(corelib::translations::translate(
+                        &original, &context, &values, n as i32, &plural,
+                    ))
+                } else {
+                    panic!("internal error: invalid args to Translate {:?}", arguments)
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::StringToFloat, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to StringToFloat")
@@ -603,6 +684,20 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 GradientStop{ color, position }
             }))))
         }
+        Expression::RadialGradient{stops} => {
+            Value::Brush(Brush::RadialGradient(RadialGradientBrush::new_circle(stops.iter().map(|(color, stop)| {
+                let color = eval_expression(color, local_context).try_into().unwrap();
+                let position = eval_expression(stop, local_context).try_into().unwrap();
+                GradientStop{ color, position }
+            }))))
+        }
+        Expression::ConicGradient{stops} => {
+            Value::Brush(Brush::ConicGradient(ConicGradientBrush::new(stops.iter().map(|(color, stop)| {
+                let color = eval_expression(color, local_context).try_into().unwrap();
+                let position = eval_expression(stop, local_context).try_into().unwrap();
+                GradientStop{ color, position }
+            }))))
+        }
         Expression::EnumerationValue(value) => {
             Value::EnumerationValue(value.enumeration.name.clone(), value.to_string())
         }
@@ -913,6 +1008,9 @@ pub(crate) fn invoke_callback(
     callback_name: &str,
     args: &[Value],
 ) -> Option<Value> {
+    if element.borrow().is_function(callback_name) {
+        return call_function(component_instance, element, callback_name, args.to_vec());
+    }
     generativity::make_guard!(guard);
     match enclosing_component_instance_for_element(element, component_instance, guard) {
         ComponentInstance::InstanceRef(enclosing_component) => {
@@ -955,6 +1053,26 @@ pub(crate) fn invoke_callback(
     }
 }
 
+/// Call a function declared with the `function` keyword by evaluating its body
+pub(crate) fn call_function(
+    component_instance: ComponentInstance,
+    element: &ElementRc,
+    function_name: &str,
+    args: Vec<Value>,
+) -> Option<Value> {
+    generativity::make_guard!(guard);
+    let component_instance =
+        enclosing_component_instance_for_element(element, component_instance, guard);
+    let body = element.borrow().bindings.get(function_name)?.borrow().expression.clone();
+    let mut local_context = EvalLocalContext {
+        local_variables: Default::default(),
+        function_arguments: args,
+        component_instance,
+        return_value: None,
+    };
+    Some(eval_expression(&body, &mut local_context))
+}
+
 fn root_component_instance<'a, 'old_id, 'new_id>(
     component: InstanceRef<'a, 'old_id>,
     guard: generativity::Guard<'new_id>,
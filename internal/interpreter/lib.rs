@@ -60,6 +60,49 @@
 # return; // we don't want to call run in the tests
 instance.run();
 ```
+
+## Models
+
+Array properties hold a [`Value::Model`] with a [`ModelRc<Value>`](ModelRc). The model adapters
+[`FilterModel`], [`MapModel`] and [`SortModel`] can wrap any [`Model`] of [`Value`]s, and the
+[`ModelExt`] trait provides shortcuts to create them. The adapters are updated when the
+wrapped model changes.
+
+This example filters the rows of a [`VecModel`] before assigning them to a property:
+
+```rust
+use slint_interpreter::{ComponentCompiler, Model, ModelExt, ModelRc, Value, VecModel};
+use std::rc::Rc;
+
+let code = r#"
+    MyWin := Window {
+        property <[int]> numbers;
+        property <int> count: numbers.length;
+    }
+"#;
+
+let mut compiler = ComponentCompiler::default();
+let definition =
+    spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+let instance = definition.unwrap().create();
+
+let source = Rc::new(VecModel::from(vec![Value::Number(3.), Value::Number(12.)]));
+let large_numbers = source.clone().filter(|v| matches!(v, Value::Number(n) if *n > 10.));
+instance.set_property("numbers", Value::Model(ModelRc::new(large_numbers))).unwrap();
+assert_eq!(instance.get_property("count").unwrap(), Value::Number(1.));
+
+source.push(Value::Number(42.));
+assert_eq!(instance.get_property("count").unwrap(), Value::Number(2.));
+
+// A model read from a property can be wrapped as well
+if let Value::Model(numbers) = instance.get_property("numbers").unwrap() {
+    let sorted = numbers.sort_by(|a, b| match (a, b) {
+        (Value::Number(a), Value::Number(b)) => b.partial_cmp(a).unwrap(),
+        _ => std::cmp::Ordering::Equal,
+    });
+    assert_eq!(sorted.row_data(0), Some(Value::Number(42.)));
+}
+```
 */
 //! ## Feature flags
 #![cfg_attr(feature = "document-features", doc = document_features::document_features!())]
@@ -98,6 +141,13 @@ pub(crate) fn register_font_from_path<P: AsRef<std::path::Path>>(
 #[doc(inline)]
 pub use i_slint_core::{Brush, Color, SharedString, SharedVector};
 
+/// (Re-export from corelib.)
+#[doc(inline)]
+pub use i_slint_core::model::{
+    FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
+    SortModel, StandardListViewItem, VecModel,
+};
+
 /// One need to use at least one function in each module in order to get them
 /// exported in the final binary.
 /// This only use functions from modules which are not otherwise used.
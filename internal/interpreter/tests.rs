@@ -43,3 +43,159 @@ fn reuse_window() {
         instance
     };
 }
+
+#[test]
+fn screenshot() {
+    i_slint_backend_testing::init();
+    use crate::{ComponentCompiler, ComponentHandle};
+    use i_slint_core::window::WindowHandleAccess;
+    let code = r#"
+        MainWindow := Window {
+            width: 40px;
+            height: 30px;
+            background: #00f;
+            Rectangle {
+                x: 10px;
+                y: 10px;
+                width: 20px;
+                height: 10px;
+                background: #f00;
+            }
+        }
+    "#;
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    let instance = definition.unwrap().create();
+    let buffer = i_slint_backend_testing::screenshot(instance.window().window_handle());
+    assert_eq!((buffer.width(), buffer.height()), (40, 30));
+    let pixel = |x: usize, y: usize| buffer.as_slice()[y * buffer.stride() as usize + x];
+    assert_eq!(pixel(5, 5), i_slint_core::graphics::Rgba8Pixel::new(0, 0, 255, 255));
+    assert_eq!(pixel(15, 15), i_slint_core::graphics::Rgba8Pixel::new(255, 0, 0, 255));
+    assert_eq!(pixel(35, 25), i_slint_core::graphics::Rgba8Pixel::new(0, 0, 255, 255));
+}
+
+#[test]
+fn simulated_event_loop() {
+    i_slint_backend_testing::init();
+    use crate::{ComponentCompiler, ComponentHandle, Value};
+    use i_slint_core::timers::{Timer, TimerMode};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    let code = r#"
+        TestCase := Rectangle {
+            property <int> value: 0;
+            property <float> animated: value * 100;
+            animate animated { duration: 100ms; }
+        }
+    "#;
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    let instance = definition.unwrap().create();
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let instance_weak = instance.as_weak();
+    std::thread::spawn(move || {
+        instance_weak.upgrade_in_event_loop(move |_| sender.send("posted").unwrap())
+    })
+    .join()
+    .unwrap();
+    i_slint_backend_testing::run_until_idle();
+    assert_eq!(receiver.try_recv(), Ok("posted"));
+
+    let repeated = Timer::default();
+    repeated.start(TimerMode::Repeated, Duration::from_millis(30), {
+        let log = log.clone();
+        move || log.borrow_mut().push("repeated")
+    });
+    Timer::single_shot(Duration::from_millis(50), {
+        let log = log.clone();
+        move || log.borrow_mut().push("single shot")
+    });
+
+    i_slint_backend_testing::advance_time(Duration::from_millis(20));
+    assert!(log.borrow().is_empty());
+    i_slint_backend_testing::advance_time(Duration::from_millis(50));
+    assert_eq!(*log.borrow(), ["repeated", "single shot", "repeated"]);
+    repeated.stop();
+
+    assert_eq!(instance.get_property("animated"), Ok(Value::Number(0.)));
+    instance.set_property("value", Value::Number(1.)).unwrap();
+    // Reading the property starts the animation
+    assert_eq!(instance.get_property("animated"), Ok(Value::Number(0.)));
+    i_slint_backend_testing::advance_time(Duration::from_millis(50));
+    let animated: f64 = instance.get_property("animated").unwrap().try_into().unwrap();
+    assert!(animated > 0. && animated < 100., "{}", animated);
+    i_slint_backend_testing::advance_time(Duration::from_millis(60));
+    assert_eq!(instance.get_property("animated"), Ok(Value::Number(100.)));
+}
+
+#[test]
+fn find_elements() {
+    i_slint_backend_testing::init();
+    use crate::testing::{find_elements, ElementQuery};
+    use crate::{ComponentCompiler, SharedString, Value};
+
+    let code = r#"
+        TestCase := Window {
+            width: 200px;
+            height: 100px;
+            property <int> clicked;
+            property <string> typed <=> input.text;
+            Rectangle {
+                x: 20px;
+                y: 10px;
+                width: 100px;
+                height: 50px;
+                ok_area := TouchArea {
+                    x: 10px;
+                    width: 20px;
+                    height: 30px;
+                    clicked => { clicked += 1; }
+                }
+                Text { text: "Hello"; }
+            }
+            input := TextInput { y: 70px; height: 20px; }
+            for i in 2 : Text { text: "item " + i; }
+        }
+    "#;
+    let mut compiler = ComponentCompiler::default();
+    let definition = spin_on::spin_on(compiler.build_from_source(code.into(), Default::default()));
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    let instance = definition.unwrap().create();
+
+    let areas = find_elements(&instance, &ElementQuery::new().element_id("ok-area"));
+    assert_eq!(areas.len(), 1);
+    assert_eq!(areas[0].element_id(), "ok-area");
+    assert_eq!(areas[0].element_type(), Some("TouchArea"));
+    assert_eq!(
+        areas[0].absolute_geometry(),
+        i_slint_core::graphics::Rect::new([30., 10.].into(), [20., 30.].into())
+    );
+    areas[0].click();
+    areas[0].click();
+    assert_eq!(instance.get_property("clicked"), Ok(Value::from(2)));
+
+    let texts = find_elements(&instance, &ElementQuery::new().element_type("Text"));
+    assert_eq!(
+        texts.iter().map(|t| t.text().unwrap()).collect::<Vec<_>>(),
+        ["Hello", "item 0", "item 1"]
+    );
+    assert_eq!(texts[0].element_id(), "");
+    let items = find_elements(&instance, &ElementQuery::new().element_type("Text").text("item 1"));
+    assert_eq!(items.len(), 1);
+
+    let inputs = find_elements(&instance, &ElementQuery::new().element_id("input"));
+    assert_eq!(inputs.len(), 1);
+    assert_eq!(inputs[0].element_type(), Some("TextInput"));
+    inputs[0].type_text("abc");
+    assert_eq!(instance.get_property("typed"), Ok(Value::from(SharedString::from("abc"))));
+    assert_eq!(inputs[0].text(), Some("abc".into()));
+
+    assert!(find_elements(&instance, &ElementQuery::new().element_id("nothing")).is_empty());
+}
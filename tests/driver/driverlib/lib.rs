@@ -20,10 +20,16 @@ pub fn identifier(&self) -> String {
 
 /// Returns a list of all the `.slint` files in the `tests/cases` subfolders.
 pub fn collect_test_cases() -> std::io::Result<Vec<TestCase>> {
+    collect_test_cases_in("cases")
+}
+
+/// Returns a list of all the `.slint` files in the subfolders of the given directory,
+/// which is relative to the `tests` directory.
+pub fn collect_test_cases_in(directory: &str) -> std::io::Result<Vec<TestCase>> {
     let mut results = vec![];
 
     let case_root_dir: std::path::PathBuf =
-        [env!("CARGO_MANIFEST_DIR"), "..", "..", "cases"].iter().collect();
+        [env!("CARGO_MANIFEST_DIR"), "..", "..", directory].iter().collect();
 
     println!("cargo:rerun-if-env-changed=SLINT_TEST_FILTER");
     let filter = std::env::var("SLINT_TEST_FILTER").ok();
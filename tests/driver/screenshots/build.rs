@@ -0,0 +1,41 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use std::io::Write;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let tests_file_path =
+        std::path::Path::new(&std::env::var_os("OUT_DIR").unwrap()).join("test_functions.rs");
+
+    let mut tests_file = std::fs::File::create(&tests_file_path)?;
+
+    let references_root_dir: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "..", "..", "screenshots", "references"].iter().collect();
+
+    for testcase in test_driver_lib::collect_test_cases_in("screenshots/cases")? {
+        let test_function_name = testcase.identifier();
+        let reference_path =
+            references_root_dir.join(&testcase.relative_path).with_extension("png");
+
+        write!(
+            tests_file,
+            r##"
+            #[test]
+            fn test_screenshots_{function_name}() {{
+                screenshots::test(&test_driver_lib::TestCase{{
+                    absolute_path: std::path::PathBuf::from(r#"{absolute_path}"#),
+                    relative_path: std::path::PathBuf::from(r#"{relative_path}"#),
+                }}, std::path::Path::new(r#"{reference_path}"#)).unwrap();
+            }}
+        "##,
+            function_name = test_function_name,
+            absolute_path = testcase.absolute_path.to_string_lossy(),
+            relative_path = testcase.relative_path.to_string_lossy(),
+            reference_path = reference_path.to_string_lossy(),
+        )?;
+    }
+
+    println!("cargo:rustc-env=TEST_FUNCTIONS={}", tests_file_path.to_string_lossy());
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+#[cfg(test)]
+mod screenshots;
+
+include!(env!("TEST_FUNCTIONS"));
+
+fn main() {
+    println!("Nothing to see here, please run me through cargo test :)");
+}
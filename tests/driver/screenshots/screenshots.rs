@@ -0,0 +1,178 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use i_slint_core::graphics::{Rgba8Pixel, SharedPixelBuffer};
+use i_slint_core::window::WindowHandleAccess;
+use itertools::Itertools;
+use slint_interpreter::ComponentHandle;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// The maximum difference of each color channel that is accepted between a pixel of the
+/// screenshot and the reference, when the test case does not specify another one.
+const DEFAULT_TOLERANCE: u8 = 0;
+
+/// Renders the test case and compares the result with the reference image.
+///
+/// If the `SLINT_UPDATE_SCREENSHOTS` environment variable is set, the reference image is
+/// (re-)created instead.
+/// On mismatch, the screenshot and an image highlighting the different pixels in red are written
+/// to the directory in the `SLINT_SCREENSHOT_DIFF_DIR` environment variable, or to a directory in
+/// the system's temporary directory.
+pub fn test(
+    testcase: &test_driver_lib::TestCase,
+    reference_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    i_slint_backend_testing::init();
+
+    let source = std::fs::read_to_string(&testcase.absolute_path)?;
+    let include_paths = test_driver_lib::extract_include_paths(&source)
+        .map(std::path::PathBuf::from)
+        .collect::<Vec<_>>();
+    let tolerance = extract_tolerance(&source)?.unwrap_or(DEFAULT_TOLERANCE);
+
+    let mut compiler = slint_interpreter::ComponentCompiler::default();
+    compiler.set_include_paths(include_paths);
+    compiler.set_style(String::from("fluent"));
+
+    let component =
+        spin_on::spin_on(compiler.build_from_source(source, testcase.absolute_path.clone()));
+
+    let component = match component {
+        None => {
+            slint_interpreter::print_diagnostics(&compiler.diagnostics());
+            return Err(compiler.diagnostics().iter().map(|d| d.to_string()).join("\n").into());
+        }
+        Some(c) => c,
+    };
+
+    let instance = component.create();
+    let screenshot = i_slint_backend_testing::screenshot(instance.window().window_handle());
+
+    if std::env::var_os("SLINT_UPDATE_SCREENSHOTS").is_some() {
+        if let Some(parent) = reference_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        save_png(&screenshot, reference_path)?;
+        return Ok(());
+    }
+
+    let reference = image::open(reference_path)
+        .map_err(|err| {
+            format!(
+                "{}: could not load the reference image {}: {}. \
+                 Run the test with SLINT_UPDATE_SCREENSHOTS=1 to create it.",
+                testcase.relative_path.display(),
+                reference_path.display(),
+                err
+            )
+        })?
+        .into_rgba8();
+
+    if (reference.width(), reference.height()) != (screenshot.width(), screenshot.height()) {
+        let actual_path = save_failure_image(testcase, "actual", &screenshot)?;
+        return Err(format!(
+            "{}: the screenshot has the size {}x{} but the reference has the size {}x{}. \
+             Screenshot saved to {}",
+            testcase.relative_path.display(),
+            screenshot.width(),
+            screenshot.height(),
+            reference.width(),
+            reference.height(),
+            actual_path.display()
+        )
+        .into());
+    }
+
+    let mut diff = SharedPixelBuffer::<Rgba8Pixel>::new(screenshot.width(), screenshot.height());
+    let mut mismatch_count = 0;
+    for ((actual, expected), diff_pixel) in
+        screenshot.as_slice().iter().zip(reference.pixels()).zip(diff.make_mut_slice())
+    {
+        let expected = Rgba8Pixel::new(expected[0], expected[1], expected[2], expected[3]);
+        if pixel_matches(*actual, expected, tolerance) {
+            // Show the matching pixels in faded gray, so that the differences stand out
+            let gray = ((expected.r as u32 + expected.g as u32 + expected.b as u32) / 3) as u8;
+            let faded = 255 - (255 - gray) / 4;
+            *diff_pixel = Rgba8Pixel::new(faded, faded, faded, 255);
+        } else {
+            mismatch_count += 1;
+            *diff_pixel = Rgba8Pixel::new(255, 0, 0, 255);
+        }
+    }
+
+    if mismatch_count > 0 {
+        let actual_path = save_failure_image(testcase, "actual", &screenshot)?;
+        let diff_path = save_failure_image(testcase, "diff", &diff)?;
+        return Err(format!(
+            "{}: {} pixels differ from the reference {} by more than {}. \
+             Screenshot saved to {}, difference saved to {}",
+            testcase.relative_path.display(),
+            mismatch_count,
+            reference_path.display(),
+            tolerance,
+            actual_path.display(),
+            diff_path.display()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+fn pixel_matches(actual: Rgba8Pixel, expected: Rgba8Pixel, tolerance: u8) -> bool {
+    // Fully transparent pixels are equal regardless of their color
+    if actual.a == 0 && expected.a == 0 {
+        return true;
+    }
+    actual.iter().zip(expected.iter()).all(|(a, e)| (a as i16 - e as i16).abs() <= tolerance as i16)
+}
+
+/// Extract the tolerance from a comment in the source if present, like so:
+/// ```text
+/// //screenshot_tolerance: 2
+/// ```
+fn extract_tolerance(source: &str) -> Result<Option<u8>, Box<dyn Error>> {
+    const PREFIX: &str = "//screenshot_tolerance:";
+    match source.lines().find_map(|line| line.trim().strip_prefix(PREFIX)) {
+        Some(value) => Ok(Some(
+            value.trim().parse().map_err(|err| format!("invalid screenshot tolerance: {}", err))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+fn save_png(buffer: &SharedPixelBuffer<Rgba8Pixel>, path: &Path) -> Result<(), Box<dyn Error>> {
+    image::save_buffer(
+        path,
+        buffer.as_bytes(),
+        buffer.width(),
+        buffer.height(),
+        image::ColorType::Rgba8,
+    )?;
+    Ok(())
+}
+
+fn save_failure_image(
+    testcase: &test_driver_lib::TestCase,
+    kind: &str,
+    buffer: &SharedPixelBuffer<Rgba8Pixel>,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let directory = std::env::var_os("SLINT_SCREENSHOT_DIFF_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("slint-screenshot-failures"));
+    std::fs::create_dir_all(&directory)?;
+    let path = directory.join(format!("{}-{}.png", testcase.identifier(), kind));
+    save_png(buffer, &path)?;
+    Ok(path)
+}
+
+#[test]
+fn test_extract_tolerance() {
+    assert_eq!(extract_tolerance("Foo := Rectangle {}").unwrap(), None);
+    assert_eq!(
+        extract_tolerance("//screenshot_tolerance: 3\nFoo := Rectangle {}").unwrap(),
+        Some(3)
+    );
+    assert!(extract_tolerance("//screenshot_tolerance: many\n").is_err());
+}
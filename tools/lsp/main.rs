@@ -578,7 +578,7 @@ fn get_document_symbols(
     .unwrap();
 
     let inner_components = doc.inner_components.clone();
-    let inner_structs = doc.inner_structs.clone();
+    let inner_types = doc.inner_types.clone();
     let mut make_range = |node: &SyntaxNode| {
         let r = node.text_range();
         Some(Range::new(
@@ -602,13 +602,19 @@ fn get_document_symbols(
         })
         .collect::<Vec<_>>();
 
-    r.extend(inner_structs.iter().filter_map(|c| match c {
+    r.extend(inner_types.iter().filter_map(|c| match c {
         Type::Struct { name: Some(name), node: Some(node), .. } => Some(SymbolInformation {
             location: Location::new(uri.clone(), make_range(node.parent().as_ref()?)?),
             name: name.clone(),
             kind: lsp_types::SymbolKind::STRUCT,
             ..si.clone()
         }),
+        Type::Enumeration(enumeration) => Some(SymbolInformation {
+            location: Location::new(uri.clone(), make_range(enumeration.node.as_ref()?)?),
+            name: enumeration.name.clone(),
+            kind: lsp_types::SymbolKind::ENUM,
+            ..si.clone()
+        }),
         _ => None,
     }));
 
@@ -23,7 +23,7 @@ macro_rules! declare_legend {
     (@ [], $n:expr) => {};
 }
 // the id of the element
-declare_legend!(LEGEND_TYPES : SemanticTokenType = [TYPE PARAMETER VARIABLE PROPERTY FUNCTION MACRO KEYWORD COMMENT STRING NUMBER OPERATOR]);
+declare_legend!(LEGEND_TYPES : SemanticTokenType = [TYPE PARAMETER VARIABLE PROPERTY FUNCTION MACRO KEYWORD COMMENT STRING NUMBER OPERATOR ENUM ENUM_MEMBER]);
 declare_legend!(LEGEND_MODS: SemanticTokenModifier = [DEFINITION DECLARATION]);
 
 pub fn get_semantic_tokens(
@@ -53,6 +53,8 @@ pub fn get_semantic_tokens(
                 SyntaxKind::ConditionalElement => Some((self::KEYWORD, 0)),
                 SyntaxKind::CallbackDeclaration => Some((self::KEYWORD, 0)),
                 SyntaxKind::CallbackConnection => Some((self::FUNCTION, 0)),
+                SyntaxKind::Function => Some((self::KEYWORD, 0)),
+                SyntaxKind::PropertyChangedCallback => Some((self::KEYWORD, 0)),
                 SyntaxKind::PropertyDeclaration => Some((self::KEYWORD, 0)),
                 SyntaxKind::PropertyAnimation => Some((self::KEYWORD, 0)),
                 SyntaxKind::QualifiedName => match token.parent().parent()?.kind() {
@@ -77,6 +79,11 @@ pub fn get_semantic_tokens(
                         SyntaxKind::CallbackConnection => {
                             Some((self::PARAMETER, 1 << self::DEFINITION))
                         }
+                        SyntaxKind::Function => Some((self::FUNCTION, 1 << self::DEFINITION)),
+                        SyntaxKind::PropertyChangedCallback => Some((self::PROPERTY, 0)),
+                        SyntaxKind::ArgumentDeclaration => {
+                            Some((self::PARAMETER, 1 << self::DEFINITION))
+                        }
                         SyntaxKind::PropertyDeclaration => {
                             Some((self::PROPERTY, 1 << self::DEFINITION))
                         }
@@ -85,6 +92,7 @@ pub fn get_semantic_tokens(
                             None
                         }
                         SyntaxKind::StructDeclaration => Some((self::TYPE, 1 << self::DEFINITION)),
+                        SyntaxKind::EnumDeclaration => Some((self::ENUM, 1 << self::DEFINITION)),
                         _ => None,
                     }
                 }
@@ -93,6 +101,9 @@ pub fn get_semantic_tokens(
                 SyntaxKind::ReturnStatement => Some((self::KEYWORD, 0)),
                 SyntaxKind::AtImageUrl => Some((self::MACRO, 0)),
                 SyntaxKind::AtLinearGradient => Some((self::MACRO, 0)),
+                SyntaxKind::AtRadialGradient => Some((self::MACRO, 0)),
+                SyntaxKind::AtConicGradient => Some((self::MACRO, 0)),
+                SyntaxKind::AtTr => Some((self::MACRO, 0)),
                 SyntaxKind::ConditionalExpression => Some((self::KEYWORD, 0)),
                 SyntaxKind::ObjectMember => Some((self::PROPERTY, 1 << self::DECLARATION)),
                 SyntaxKind::States => Some((self::KEYWORD, 0)),
@@ -129,6 +140,8 @@ pub fn get_semantic_tokens(
                 SyntaxKind::InternalName => Some((self::TYPE, 1 << self::DECLARATION)),
                 SyntaxKind::ObjectTypeMember => Some((self::PROPERTY, 1 << self::DEFINITION)),
                 SyntaxKind::StructDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::EnumDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::EnumValue => Some((self::ENUM_MEMBER, 1 << self::DEFINITION)),
                 _ => None,
             },
             SyntaxKind::PlusEqual
@@ -148,7 +161,9 @@ pub fn get_semantic_tokens(
             | SyntaxKind::Minus
             | SyntaxKind::Star
             | SyntaxKind::Div
-            | SyntaxKind::Equal => Some((self::OPERATOR, 0)),
+            | SyntaxKind::Equal
+            | SyntaxKind::Pipe
+            | SyntaxKind::Percent => Some((self::OPERATOR, 0)),
             SyntaxKind::Question => Some((self::OPERATOR, 0)),
             SyntaxKind::At => Some((self::MACRO, 0)),
             _ => None,
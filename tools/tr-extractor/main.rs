@@ -0,0 +1,164 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Extracts the strings of the `@tr(...)` macros of .slint files to a gettext `.pot` file.
+//! ```sh
+//!     cargo run --bin slint-tr-extractor -- -o app.pot ui/*.slint
+//! ```
+//! The translators then create a `.po` file from the `.pot` file for each language.
+
+use clap::Parser;
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
+use std::io::Write;
+
+#[derive(clap::Parser)]
+struct Cli {
+    #[clap(name = "path to .slint file(s)", parse(from_os_str))]
+    paths: Vec<std::path::PathBuf>,
+
+    /// Sets the output file ('-' for stdout)
+    #[clap(name = "file to generate", short = 'o', default_value = "-", parse(from_os_str))]
+    output: std::path::PathBuf,
+
+    /// The package name written in the header of the .pot file
+    #[clap(long = "package-name", default_value = "PACKAGE")]
+    package_name: String,
+
+    /// The package version written in the header of the .pot file
+    #[clap(long = "package-version", default_value = "VERSION")]
+    package_version: String,
+
+    /// The `Plural-Forms` header of the .pot file. As many `msgstr[n]` as `nplurals` are
+    /// written for each plural message.
+    #[clap(long = "plural-forms", default_value = "nplurals=2; plural=(n != 1);")]
+    plural_forms: String,
+}
+
+/// A translatable string, with all the places where it is used
+struct Message {
+    context: String,
+    id: String,
+    plural: String,
+    locations: Vec<String>,
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Cli::parse();
+    let mut messages: Vec<Message> = Vec::new();
+
+    let mut diag = BuildDiagnostics::default();
+    for path in &args.paths {
+        let source = std::fs::read_to_string(path)?;
+        let line_offsets: Vec<usize> =
+            source.match_indices('\n').map(|(offset, _)| offset).collect();
+        let document = i_slint_compiler::parser::parse(source, Some(path.as_path()), &mut diag);
+        for node in document.descendants().filter(|n| n.kind() == SyntaxKind::AtTr) {
+            let offset = usize::from(node.text_range().start());
+            let line = line_offsets.partition_point(|o| *o < offset) + 1;
+            let node = syntax_nodes::AtTr::from(SyntaxNode {
+                node,
+                source_file: document.source_file.clone(),
+            });
+            if let Some(message) = extract_message(&node) {
+                let location = format!("{}:{}", path.display(), line);
+                match messages
+                    .iter_mut()
+                    .find(|m| m.context == message.context && m.id == message.id)
+                {
+                    Some(existing) => existing.locations.push(location),
+                    None => messages.push(Message { locations: vec![location], ..message }),
+                }
+            }
+        }
+    }
+    if diag.has_error() {
+        diag.print();
+        std::process::exit(-1);
+    }
+    if nplurals(&args.plural_forms).is_none() {
+        eprintln!("Invalid plural forms, 'nplurals=N' is missing: {}", args.plural_forms);
+        std::process::exit(-1);
+    }
+
+    if args.output == std::path::Path::new("-") {
+        write_pot(&mut std::io::stdout(), &args, &messages)
+    } else {
+        write_pot(&mut std::fs::File::create(&args.output)?, &args, &messages)
+    }
+}
+
+fn extract_message(node: &syntax_nodes::AtTr) -> Option<Message> {
+    let string_literal = |n: &SyntaxNode| {
+        n.child_text(SyntaxKind::StringLiteral)
+            .and_then(|s| i_slint_compiler::literals::unescape_string(&s))
+    };
+    Some(Message {
+        context: node.TrContext().map_or(Some(String::new()), |n| string_literal(&n))?,
+        id: string_literal(node)?,
+        plural: node.TrPlural().map_or(Some(String::new()), |n| string_literal(&n))?,
+        locations: vec![],
+    })
+}
+
+fn write_pot(out: &mut impl Write, args: &Cli, messages: &[Message]) -> std::io::Result<()> {
+    writeln!(out, "# Translations of {}", args.package_name)?;
+    writeln!(out, "msgid \"\"")?;
+    writeln!(out, "msgstr \"\"")?;
+    writeln!(out, "\"Project-Id-Version: {} {}\\n\"", args.package_name, args.package_version)?;
+    writeln!(out, "\"MIME-Version: 1.0\\n\"")?;
+    writeln!(out, "\"Content-Type: text/plain; charset=UTF-8\\n\"")?;
+    writeln!(out, "\"Content-Transfer-Encoding: 8bit\\n\"")?;
+    writeln!(out, "\"Plural-Forms: {}\\n\"", args.plural_forms)?;
+    let nplurals = nplurals(&args.plural_forms).unwrap_or(2);
+
+    for message in messages {
+        writeln!(out)?;
+        for location in &message.locations {
+            writeln!(out, "#: {}", location)?;
+        }
+        if !message.context.is_empty() {
+            writeln!(out, "msgctxt {}", escape(&message.context))?;
+        }
+        writeln!(out, "msgid {}", escape(&message.id))?;
+        if message.plural.is_empty() {
+            writeln!(out, "msgstr \"\"")?;
+        } else {
+            writeln!(out, "msgid_plural {}", escape(&message.plural))?;
+            for n in 0..nplurals {
+                writeln!(out, "msgstr[{}] \"\"", n)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the value of `nplurals` in the `Plural-Forms` header
+fn nplurals(plural_forms: &str) -> Option<usize> {
+    plural_forms.split(';').find_map(|part| {
+        let (key, value) = part.split_once('=')?;
+        if key.trim() == "nplurals" {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Quotes the string for the .pot file
+fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}